@@ -0,0 +1,34 @@
+//! Bakes the git commit the binary was built from into `GIT_SHA`, so the
+//! health check can report which build is deployed.
+//!
+//! A `GIT_SHA` environment variable takes precedence, for builds without a
+//! `.git` directory (e.g. Docker). If neither is available the variable is left
+//! unset.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+
+    let sha = std::env::var("GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .or_else(|| {
+            let output = Command::new("git")
+                .args(["rev-parse", "--short", "HEAD"])
+                .output()
+                .ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            String::from_utf8(output.stdout)
+                .ok()
+                .map(|sha| sha.trim().to_string())
+        });
+
+    if let Some(sha) = sha {
+        println!("cargo:rustc-env=GIT_SHA={}", sha);
+    }
+}
@@ -0,0 +1,42 @@
+-- Real storage for incident detail data that was previously synthesized in SQL.
+
+CREATE TABLE IF NOT EXISTS incident_timeline_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    event_date TIMESTAMP NOT NULL,
+    event_title VARCHAR(255) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS incident_lab_results (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    pathogen VARCHAR(255) NOT NULL,
+    test_date DATE NOT NULL,
+    confirmed_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS incident_affected_institutions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    institution_name VARCHAR(255) NOT NULL,
+    institution_type VARCHAR(50),
+    affected_count INTEGER,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS incident_corrective_actions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    action_description TEXT NOT NULL,
+    completed BOOLEAN DEFAULT FALSE,
+    completed_date TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_timeline_events_incident ON incident_timeline_events(incident_id);
+CREATE INDEX IF NOT EXISTS idx_incident_lab_results_incident ON incident_lab_results(incident_id);
+CREATE INDEX IF NOT EXISTS idx_incident_affected_institutions_incident ON incident_affected_institutions(incident_id);
+CREATE INDEX IF NOT EXISTS idx_incident_corrective_actions_incident ON incident_corrective_actions(incident_id);
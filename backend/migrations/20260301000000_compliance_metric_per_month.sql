@@ -0,0 +1,5 @@
+-- One compliance metric row per kitchen per calendar month, so recording
+-- metrics again in the same month updates the existing row.
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_compliance_kitchen_month
+    ON compliance_metrics (kitchen_id, (DATE_TRUNC('month', created_at)));
@@ -0,0 +1,3 @@
+-- Each YouTube video may only be added to the library once.
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_videos_youtube_id ON videos (youtube_id);
@@ -0,0 +1,25 @@
+-- Full-text search for kitchens and incidents.
+--
+-- The 'simple' configuration is used because the searched text is mostly
+-- Indonesian proper nouns (kitchen names, cities, provinces) that an English
+-- stemmer would mangle.
+
+ALTER TABLE kitchens ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector(
+        'simple',
+        COALESCE(name, '') || ' ' || COALESCE(address, '') || ' ' ||
+        COALESCE(city, '') || ' ' || COALESCE(province, '')
+    )
+) STORED;
+
+CREATE INDEX idx_kitchens_search_vector ON kitchens USING GIN (search_vector);
+
+ALTER TABLE incidents ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector(
+        'simple',
+        COALESCE(description, '') || ' ' || COALESCE(cause, '') || ' ' ||
+        COALESCE(location, '')
+    )
+) STORED;
+
+CREATE INDEX idx_incidents_search_vector ON incidents USING GIN (search_vector);
@@ -0,0 +1,28 @@
+-- Kitchen locations for "kitchens near me" queries.
+
+ALTER TABLE kitchens
+    ADD COLUMN latitude DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    ADD COLUMN longitude DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180);
+
+-- Backfill from the GPS coordinates of each kitchen's most recent incident.
+-- The CASEs guard the casts so malformed coordinates are skipped rather than
+-- failing the migration.
+UPDATE kitchens k
+SET latitude = src.lat, longitude = src.lng
+FROM (
+    SELECT DISTINCT ON (kitchen_id) kitchen_id, lat, lng
+    FROM (
+        SELECT
+            kitchen_id,
+            date,
+            CASE WHEN jsonb_typeof(gps_coordinates -> 'lat') = 'number'
+                THEN (gps_coordinates ->> 'lat')::DOUBLE PRECISION END AS lat,
+            CASE WHEN jsonb_typeof(gps_coordinates -> 'lng') = 'number'
+                THEN (gps_coordinates ->> 'lng')::DOUBLE PRECISION END AS lng
+        FROM incidents
+        WHERE jsonb_typeof(gps_coordinates) = 'object'
+    ) coords
+    WHERE lat BETWEEN -90 AND 90 AND lng BETWEEN -180 AND 180
+    ORDER BY kitchen_id, date DESC
+) src
+WHERE k.id = src.kitchen_id;
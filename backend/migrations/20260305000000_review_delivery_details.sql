@@ -0,0 +1,6 @@
+-- Store which meal delivery a review is about, so complaints can be matched
+-- to specific deliveries
+ALTER TABLE reviews
+    ADD COLUMN delivery_date DATE,
+    ADD COLUMN meal_type VARCHAR(20)
+        CHECK (meal_type IN ('breakfast', 'lunch', 'dinner', 'snack'));
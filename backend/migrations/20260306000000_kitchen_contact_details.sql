@@ -0,0 +1,7 @@
+-- Contact details shown on the kitchen profile page.
+
+ALTER TABLE kitchens
+    ADD COLUMN contact_phone VARCHAR(20),
+    ADD COLUMN contact_email VARCHAR(255),
+    ADD COLUMN operating_hours VARCHAR(100),
+    ADD COLUMN capacity INTEGER CHECK (capacity >= 0);
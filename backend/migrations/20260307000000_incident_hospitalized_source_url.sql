@@ -0,0 +1,5 @@
+-- Hospitalization count and public source link shown on incident reports.
+
+ALTER TABLE incidents
+    ADD COLUMN hospitalized INTEGER DEFAULT 0 CHECK (hospitalized >= 0),
+    ADD COLUMN source_url VARCHAR(2048);
@@ -0,0 +1,12 @@
+-- Content hashes of uploaded files, so identical uploads reuse the stored copy.
+
+CREATE TABLE IF NOT EXISTS file_hashes (
+    hash VARCHAR(64) PRIMARY KEY,
+    file_id VARCHAR(255) NOT NULL,
+    url TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    mime_type VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_file_hashes_file_id ON file_hashes (file_id);
@@ -0,0 +1,64 @@
+-- Tighten foreign keys: deleting a kitchen must not silently wipe its
+-- inspection, incident and review history, so kitchen references now RESTRICT.
+-- Records owned by a parent (evidence, comments, findings, incident details)
+-- keep cascading.
+
+ALTER TABLE compliance_metrics
+    DROP CONSTRAINT FK_compliance_metrics_kitchens,
+    ADD CONSTRAINT FK_compliance_metrics_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+ALTER TABLE incidents
+    DROP CONSTRAINT FK_incidents_kitchens,
+    ADD CONSTRAINT FK_incidents_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+ALTER TABLE inspections
+    DROP CONSTRAINT FK_inspections_kitchens,
+    ADD CONSTRAINT FK_inspections_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+ALTER TABLE complaints
+    DROP CONSTRAINT FK_complaints_kitchens,
+    ADD CONSTRAINT FK_complaints_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+ALTER TABLE reviews
+    DROP CONSTRAINT FK_reviews_kitchens,
+    ADD CONSTRAINT FK_reviews_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+ALTER TABLE performance_badges
+    DROP CONSTRAINT FK_performance_badges_kitchens,
+    ADD CONSTRAINT FK_performance_badges_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+ALTER TABLE alerts
+    DROP CONSTRAINT FK_alerts_kitchens,
+    ADD CONSTRAINT FK_alerts_kitchens FOREIGN KEY(kitchen_id)
+        REFERENCES kitchens(id) ON DELETE RESTRICT;
+
+-- Dispute history recorded the acting user without a constraint.
+UPDATE review_dispute_history h
+SET by_user_id = NULL
+WHERE by_user_id IS NOT NULL
+  AND NOT EXISTS (SELECT 1 FROM users u WHERE u.id = h.by_user_id);
+
+ALTER TABLE review_dispute_history
+    ADD CONSTRAINT FK_review_disputes_users FOREIGN KEY(by_user_id)
+        REFERENCES users(id) ON DELETE SET NULL;
+
+-- Give the incident detail constraints FK_<table>_<parent> names like the
+-- rest of the schema so violations can be reported by parent table.
+ALTER TABLE incident_timeline_events
+    RENAME CONSTRAINT incident_timeline_events_incident_id_fkey
+        TO FK_incident_timeline_events_incidents;
+ALTER TABLE incident_lab_results
+    RENAME CONSTRAINT incident_lab_results_incident_id_fkey
+        TO FK_incident_lab_results_incidents;
+ALTER TABLE incident_affected_institutions
+    RENAME CONSTRAINT incident_affected_institutions_incident_id_fkey
+        TO FK_incident_affected_institutions_incidents;
+ALTER TABLE incident_corrective_actions
+    RENAME CONSTRAINT incident_corrective_actions_incident_id_fkey
+        TO FK_incident_corrective_actions_incidents;
@@ -0,0 +1,23 @@
+-- Incident status changes go through a state machine; record when an incident
+-- was resolved and every transition it went through.
+
+ALTER TABLE incidents ADD COLUMN resolved_at TIMESTAMP;
+
+-- Best available guess for incidents resolved before this column existed.
+UPDATE incidents SET resolved_at = updated_at WHERE status = 'resolved';
+
+CREATE TABLE IF NOT EXISTS incident_status_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    incident_id UUID NOT NULL,
+    from_status incident_status_enum NOT NULL,
+    to_status incident_status_enum NOT NULL,
+    changed_by UUID,
+    note TEXT,
+    changed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT FK_incident_status_history_incidents FOREIGN KEY(incident_id)
+        REFERENCES incidents(id) ON DELETE CASCADE,
+    CONSTRAINT FK_incident_status_history_users FOREIGN KEY(changed_by)
+        REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_status_history_incident ON incident_status_history(incident_id);
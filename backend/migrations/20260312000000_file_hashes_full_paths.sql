@@ -0,0 +1,8 @@
+-- File IDs used to be bare UUIDs, with the date directory recomputed from the
+-- current time on every access. They are now the file's full path, so
+-- recover it for existing rows from the URL recorded at upload time.
+
+UPDATE file_hashes
+SET file_id = substring(url FROM '([0-9]{4}/[0-9]{2}/[0-9]{2}/' || file_id || ')$')
+WHERE file_id NOT LIKE '%/%'
+  AND url ~ ('[0-9]{4}/[0-9]{2}/[0-9]{2}/' || file_id || '$');
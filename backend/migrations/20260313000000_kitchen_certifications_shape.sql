@@ -0,0 +1,34 @@
+-- Kitchen certifications are a JSON array of names. Nothing enforced that, so
+-- readers silently showed no certifications for anything else. Rewrite other
+-- shapes into arrays of strings, keeping non-string values as their JSON
+-- text, then enforce the shape.
+
+UPDATE kitchens
+SET certifications = CASE jsonb_typeof(certifications)
+    WHEN 'null' THEN NULL
+    WHEN 'array' THEN (
+        SELECT COALESCE(
+            jsonb_agg(
+                CASE jsonb_typeof(element)
+                    WHEN 'string' THEN element
+                    ELSE to_jsonb(element::text)
+                END
+                ORDER BY position
+            ),
+            '[]'::jsonb
+        )
+        FROM jsonb_array_elements(certifications) WITH ORDINALITY AS e(element, position)
+    )
+    WHEN 'string' THEN jsonb_build_array(certifications)
+    ELSE jsonb_build_array(certifications::text)
+END
+WHERE certifications IS NOT NULL
+  AND (jsonb_typeof(certifications) <> 'array'
+       OR jsonb_path_exists(certifications, '$[*] ? (@.type() != "string")'));
+
+ALTER TABLE kitchens
+    ADD CONSTRAINT kitchens_certifications_string_array CHECK (
+        certifications IS NULL
+        OR (jsonb_typeof(certifications) = 'array'
+            AND NOT jsonb_path_exists(certifications, '$[*] ? (@.type() != "string")'))
+    );
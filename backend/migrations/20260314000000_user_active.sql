@@ -0,0 +1,3 @@
+-- Deactivated users can no longer sign in or use existing tokens, but keep
+-- their reviews and audit history.
+ALTER TABLE users ADD COLUMN active BOOLEAN DEFAULT TRUE;
@@ -0,0 +1,153 @@
+-- Store timestamps as TIMESTAMPTZ. Existing values were written in UTC, so
+-- they are reinterpreted as such. Monthly reports group by calendar month in
+-- Asia/Jakarta, since that is where the kitchens are.
+
+DROP VIEW IF EXISTS kitchen_compliance_trend;
+DROP INDEX IF EXISTS idx_compliance_kitchen_month;
+
+ALTER TABLE alerts
+    ALTER COLUMN "timestamp" TYPE TIMESTAMPTZ USING "timestamp" AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE audit_logs
+    ALTER COLUMN "timestamp" TYPE TIMESTAMPTZ USING "timestamp" AT TIME ZONE 'UTC';
+
+ALTER TABLE checklist_items
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE complaint_comments
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE complaint_evidence
+    ALTER COLUMN "timestamp" TYPE TIMESTAMPTZ USING "timestamp" AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE complaints
+    ALTER COLUMN reported_at TYPE TIMESTAMPTZ USING reported_at AT TIME ZONE 'UTC',
+    ALTER COLUMN sla_deadline TYPE TIMESTAMPTZ USING sla_deadline AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE compliance_metrics
+    ALTER COLUMN last_inspection_date TYPE TIMESTAMPTZ USING last_inspection_date AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE file_hashes
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE incident_affected_institutions
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE incident_corrective_actions
+    ALTER COLUMN completed_date TYPE TIMESTAMPTZ USING completed_date AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE incident_lab_results
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE incident_status_history
+    ALTER COLUMN changed_at TYPE TIMESTAMPTZ USING changed_at AT TIME ZONE 'UTC';
+
+ALTER TABLE incident_timeline_events
+    ALTER COLUMN event_date TYPE TIMESTAMPTZ USING event_date AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE incidents
+    ALTER COLUMN date TYPE TIMESTAMPTZ USING date AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN resolved_at TYPE TIMESTAMPTZ USING resolved_at AT TIME ZONE 'UTC';
+
+ALTER TABLE inspection_findings
+    ALTER COLUMN deadline TYPE TIMESTAMPTZ USING deadline AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE inspections
+    ALTER COLUMN date TYPE TIMESTAMPTZ USING date AT TIME ZONE 'UTC',
+    ALTER COLUMN next_inspection_date TYPE TIMESTAMPTZ USING next_inspection_date AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE institutions
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE kitchens
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE notification_audit_trail
+    ALTER COLUMN "timestamp" TYPE TIMESTAMPTZ USING "timestamp" AT TIME ZONE 'UTC';
+
+ALTER TABLE notifications
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE performance_badges
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE review_dispute_history
+    ALTER COLUMN "timestamp" TYPE TIMESTAMPTZ USING "timestamp" AT TIME ZONE 'UTC';
+
+ALTER TABLE reviews
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE users
+    ALTER COLUMN last_login TYPE TIMESTAMPTZ USING last_login AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE videos
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+ALTER TABLE webhook_dead_letters
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
+
+CREATE UNIQUE INDEX idx_compliance_kitchen_month
+    ON compliance_metrics (kitchen_id, (DATE_TRUNC('month', created_at AT TIME ZONE 'Asia/Jakarta')));
+
+CREATE VIEW kitchen_compliance_trend AS
+SELECT
+    k.id as kitchen_id,
+    TO_CHAR(DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta'), 'YYYY-MM') as month,
+    ROUND(AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating +
+               r.temperature_rating + r.packaging_rating + r.handling_rating) / 6.0)::NUMERIC, 1) as score,
+    COUNT(DISTINCT i.id) as incidents
+FROM kitchens k
+LEFT JOIN reviews r ON r.kitchen_id = k.id AND r.verified = TRUE
+LEFT JOIN incidents i ON i.kitchen_id = k.id
+    AND DATE_TRUNC('month', i.date AT TIME ZONE 'Asia/Jakarta')
+        = DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta')
+WHERE r.created_at >= (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta')::DATE - INTERVAL '6 months'
+GROUP BY k.id, DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta')
+ORDER BY month DESC;
+
+CREATE OR REPLACE FUNCTION get_kitchen_compliance_trend(
+    p_kitchen_id UUID,
+    p_months INTEGER DEFAULT 6
+)
+RETURNS TABLE(
+    month TEXT,
+    score DECIMAL,
+    incidents BIGINT
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        TO_CHAR(DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta'), 'YYYY-MM') as period,
+        ROUND(AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating +
+                   r.temperature_rating + r.packaging_rating + r.handling_rating) / 6.0)::NUMERIC, 1)::DECIMAL as avg_score,
+        COUNT(DISTINCT i.id) as incident_count
+    FROM reviews r
+    LEFT JOIN incidents i ON i.kitchen_id = r.kitchen_id
+        AND DATE_TRUNC('month', i.date AT TIME ZONE 'Asia/Jakarta')
+            = DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta')
+    WHERE r.kitchen_id = p_kitchen_id
+        AND r.verified = TRUE
+        AND r.created_at >= (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta')::DATE - INTERVAL '1 month' * p_months
+    GROUP BY DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta')
+    ORDER BY period DESC;
+END;
+$$ LANGUAGE plpgsql;
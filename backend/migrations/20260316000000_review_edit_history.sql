@@ -0,0 +1,22 @@
+-- Keep what a review said before each edit, so a changed rating or comment
+-- can be traced when a kitchen disputes it.
+
+CREATE TABLE IF NOT EXISTS review_edit_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    review_id UUID NOT NULL,
+    edited_by UUID,
+    taste_rating DECIMAL(2,1) NOT NULL,
+    hygiene_rating DECIMAL(2,1) NOT NULL,
+    freshness_rating DECIMAL(2,1) NOT NULL,
+    temperature_rating DECIMAL(2,1) NOT NULL,
+    packaging_rating DECIMAL(2,1) NOT NULL,
+    handling_rating DECIMAL(2,1) NOT NULL,
+    comment TEXT NOT NULL,
+    edited_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT FK_review_edit_history_reviews FOREIGN KEY(review_id)
+        REFERENCES reviews(id) ON DELETE CASCADE,
+    CONSTRAINT FK_review_edit_history_users FOREIGN KEY(edited_by)
+        REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_edit_history_review ON review_edit_history(review_id);
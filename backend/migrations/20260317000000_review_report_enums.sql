@@ -0,0 +1,13 @@
+-- Store review report sources and confidence levels as enums, matching the
+-- values the old CHECK constraints allowed.
+
+CREATE TYPE report_source_enum AS ENUM ('public', 'official_inspector', 'health_worker');
+CREATE TYPE confidence_level_enum AS ENUM ('low', 'medium', 'high');
+
+ALTER TABLE reviews
+    DROP CONSTRAINT IF EXISTS reviews_report_source_check,
+    DROP CONSTRAINT IF EXISTS reviews_confidence_level_check;
+
+ALTER TABLE reviews
+    ALTER COLUMN report_source TYPE report_source_enum USING report_source::report_source_enum,
+    ALTER COLUMN confidence_level TYPE confidence_level_enum USING confidence_level::confidence_level_enum;
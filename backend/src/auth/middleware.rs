@@ -8,21 +8,28 @@ use axum::http::StatusCode;
 use axum::http::header;
 use axum::middleware::Next;
 use axum::response::Response;
+use log::error;
 
+use crate::auth::utils::JwtKeys;
 use crate::auth::utils::validate_token;
-use crate::config::Config;
+use crate::database::Database;
 
 /// State for the authentication middleware.
 #[derive(Clone)]
 pub struct AuthState {
-    /// Application configuration, containing the JWT secret.
-    pub config: Arc<Config>,
+    /// Keys tokens are validated with, built once at startup.
+    pub keys: Arc<JwtKeys>,
+    /// Database used to reject tokens of deactivated users. Without it, any
+    /// validly signed token is accepted until it expires.
+    pub db: Option<Arc<Database>>,
 }
 
 /// Middleware that validates the JWT in the `Authorization` header.
 ///
-/// If the token is valid, the [`Claims`] are inserted into the request extensions.
-/// Otherwise, it returns `401 Unauthorized`.
+/// If the token is valid and its user hasn't been deactivated, the [`Claims`]
+/// are inserted into the request extensions, so handlers can read the user's
+/// id, role and institution via `Extension<Claims>`. Otherwise, it returns
+/// `401 Unauthorized`.
 pub async fn auth_middleware(
     State(state): State<AuthState>,
     mut req: Request,
@@ -44,12 +51,25 @@ pub async fn auth_middleware(
     }
 
     let token = &auth_header[7..];
+    let claims = validate_token(token, &state.keys).map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    match validate_token(token, &state.config.jwt_secret) {
-        Ok(claims) => {
-            req.extensions_mut().insert(claims);
-            Ok(next.run(req).await)
+    if let Some(db) = &state.db {
+        let deactivated = db
+            .user_table
+            .is_deactivated(claims.sub)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to check whether user {} is active: {}",
+                    claims.sub, e
+                );
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        if deactivated {
+            return Err(StatusCode::UNAUTHORIZED);
         }
-        Err(_) => Err(StatusCode::UNAUTHORIZED),
     }
+
+    req.extensions_mut().insert(claims);
+    Ok(next.run(req).await)
 }
@@ -1,9 +1,13 @@
 //! Authentication utilities for password hashing and JWT management.
 
+use std::fmt;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+use argon2::Algorithm;
 use argon2::Argon2;
+use argon2::Params;
+use argon2::Version;
 use argon2::password_hash::PasswordHash;
 use argon2::password_hash::PasswordHasher;
 use argon2::password_hash::PasswordVerifier;
@@ -19,9 +23,104 @@ use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::config::Config;
+use crate::config::PasswordHashConfig;
 use crate::database::model::UserRole;
 use crate::error::AppError;
 
+/// Current version of the [`Claims`] layout.
+///
+/// Bump this whenever claims are added or change meaning; tokens carrying a
+/// different version are rejected by [`validate_token`].
+pub const CLAIMS_VERSION: u32 = 3;
+
+/// Supported JWT signing algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    /// HMAC-SHA256 with a shared secret.
+    Hs256,
+    /// RSA-SHA256; verifying only needs the public key.
+    Rs256,
+}
+
+impl fmt::Display for JwtAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwtAlgorithm::Hs256 => write!(f, "HS256"),
+            JwtAlgorithm::Rs256 => write!(f, "RS256"),
+        }
+    }
+}
+
+impl std::str::FromStr for JwtAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::Hs256),
+            "RS256" => Ok(JwtAlgorithm::Rs256),
+            _ => Err(format!("Unknown JWT algorithm: {}", s)),
+        }
+    }
+}
+
+/// Keys used by [`generate_token`] and [`validate_token`].
+#[derive(Clone)]
+pub struct JwtKeys {
+    algorithm: jsonwebtoken::Algorithm,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+}
+
+impl JwtKeys {
+    /// HS256 keys derived from a shared secret.
+    pub fn hmac(secret: &str) -> Self {
+        Self {
+            algorithm: jsonwebtoken::Algorithm::HS256,
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+        }
+    }
+
+    /// RS256 keys from a PEM-encoded RSA key pair.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::InternalServerError`] if either key is not valid PEM.
+    pub fn rsa(private_key_pem: &str, public_key_pem: &str) -> Result<Self, AppError> {
+        let invalid = |e: jsonwebtoken::errors::Error| AppError::InternalServerError(e.to_string());
+        Ok(Self {
+            algorithm: jsonwebtoken::Algorithm::RS256,
+            encoding: EncodingKey::from_rsa_pem(private_key_pem.as_bytes()).map_err(invalid)?,
+            decoding: DecodingKey::from_rsa_pem(public_key_pem.as_bytes()).map_err(invalid)?,
+        })
+    }
+
+    /// Keys for the configured [`JwtAlgorithm`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::InternalServerError`] if the algorithm is unknown or
+    /// an RS256 key is missing or invalid; [`Config::validate`] rejects such
+    /// configurations at startup.
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        let algorithm = config
+            .jwt
+            .algorithm
+            .parse::<JwtAlgorithm>()
+            .map_err(AppError::InternalServerError)?;
+        match algorithm {
+            JwtAlgorithm::Hs256 => Ok(Self::hmac(&config.jwt_secret)),
+            JwtAlgorithm::Rs256 => match (&config.jwt.private_key, &config.jwt.public_key) {
+                (Some(private_key), Some(public_key)) => Self::rsa(private_key, public_key),
+                _ => Err(AppError::InternalServerError(
+                    "RS256 requires both a private and a public key".to_string(),
+                )),
+            },
+        }
+    }
+}
+
 /// JWT claims structure.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -29,20 +128,45 @@ pub struct Claims {
     pub sub: Uuid,
     /// User role.
     pub role: UserRole,
+    /// ID of the institution the user belongs to.
+    #[serde(default)]
+    pub institution_id: Option<Uuid>,
+    /// Name of the institution the user belongs to.
+    #[serde(default)]
+    pub institution_name: Option<String>,
+    /// Claims layout version (see [`CLAIMS_VERSION`]); absent in legacy tokens.
+    #[serde(default)]
+    pub ver: u32,
     /// Expiration timestamp.
     pub exp: usize,
     /// Issued-at timestamp.
     pub iat: usize,
 }
 
-/// Hashes a password using Argon2.
+/// Builds an Argon2id hasher from the configured parameters.
+///
+/// # Errors
+///
+/// Returns [`AppError::InternalServerError`] if the parameters are out of range.
+fn build_argon2(config: &PasswordHashConfig) -> Result<Argon2<'static>, AppError> {
+    let params = Params::new(
+        config.memory_cost_kib,
+        config.iterations,
+        config.parallelism,
+        None,
+    )
+    .map_err(|e| AppError::InternalServerError(format!("Invalid Argon2 parameters: {}", e)))?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+/// Hashes a password using Argon2 with the configured parameters.
 ///
 /// # Errors
 ///
 /// Returns [`AppError::InternalServerError`] if hashing fails.
-pub fn hash_password(password: &str) -> Result<String, AppError> {
+pub fn hash_password(password: &str, config: &PasswordHashConfig) -> Result<String, AppError> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
+    let argon2 = build_argon2(config)?;
     let password_hash = argon2
         .hash_password(password.as_bytes(), &salt)
         .map_err(|e| AppError::InternalServerError(e.to_string()))?
@@ -50,43 +174,94 @@ pub fn hash_password(password: &str) -> Result<String, AppError> {
     Ok(password_hash)
 }
 
-pub fn verify_password(password: &str, password_hash: &str) -> Result<bool, AppError> {
+/// Verifies a password against an encoded Argon2 hash.
+///
+/// The cost parameters embedded in `password_hash` take precedence, so hashes
+/// created before the parameters were tuned keep verifying.
+///
+/// # Errors
+///
+/// Returns [`AppError::InternalServerError`] if the hash cannot be parsed or the
+/// configured parameters are invalid.
+pub fn verify_password(
+    password: &str,
+    password_hash: &str,
+    config: &PasswordHashConfig,
+) -> Result<bool, AppError> {
     let parsed_hash = PasswordHash::new(password_hash)
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
-    Ok(Argon2::default()
+    Ok(build_argon2(config)?
         .verify_password(password.as_bytes(), &parsed_hash)
         .is_ok())
 }
 
-pub fn generate_token(user_id: Uuid, role: UserRole, secret: &str) -> Result<String, AppError> {
+/// Reports whether `password_hash` was made with other settings than the
+/// configured Argon2id parameters and should be replaced.
+///
+/// Hashes that cannot be parsed also count as outdated.
+pub fn needs_rehash(password_hash: &str, config: &PasswordHashConfig) -> bool {
+    let Ok(parsed_hash) = PasswordHash::new(password_hash) else {
+        return true;
+    };
+    let Ok(params) = Params::try_from(&parsed_hash) else {
+        return true;
+    };
+
+    parsed_hash.algorithm != Algorithm::Argon2id.ident()
+        || parsed_hash.version != Some(Version::V0x13.into())
+        || params.m_cost() != config.memory_cost_kib
+        || params.t_cost() != config.iterations
+        || params.p_cost() != config.parallelism
+}
+
+/// How long an issued token stays valid, in seconds.
+pub const TOKEN_TTL_SECONDS: u64 = 3600;
+
+/// Generates a signed JWT for a user, valid for [`TOKEN_TTL_SECONDS`].
+///
+/// # Errors
+///
+/// Returns [`AppError::InternalServerError`] if encoding fails.
+pub fn generate_token(
+    user_id: Uuid,
+    role: UserRole,
+    institution_id: Option<Uuid>,
+    institution_name: Option<String>,
+    keys: &JwtKeys,
+) -> Result<String, AppError> {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as usize;
-    let expiration = now + 3600; // 1 hour
+    let expiration = now + TOKEN_TTL_SECONDS as usize;
 
     let claims = Claims {
         sub: user_id,
         role,
+        institution_id,
+        institution_name,
+        ver: CLAIMS_VERSION,
         exp: expiration,
         iat: now,
     };
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
-    )
-    .map_err(|e| AppError::InternalServerError(e.to_string()))
+    encode(&Header::new(keys.algorithm), &claims, &keys.encoding)
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
 }
 
-pub fn validate_token(token: &str, secret: &str) -> Result<Claims, AppError> {
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
-    )
-    .map_err(|_| AppError::Unauthorized("Invalid token".to_string()))?;
+/// Validates a JWT and returns its claims.
+///
+/// # Errors
+///
+/// Returns [`AppError::Unauthorized`] if the token is invalid, expired, or was
+/// issued with a different [`CLAIMS_VERSION`].
+pub fn validate_token(token: &str, keys: &JwtKeys) -> Result<Claims, AppError> {
+    let token_data = decode::<Claims>(token, &keys.decoding, &Validation::new(keys.algorithm))
+        .map_err(|_| AppError::Unauthorized("Invalid token".to_string()))?;
+
+    if token_data.claims.ver != CLAIMS_VERSION {
+        return Err(AppError::Unauthorized("Outdated token".to_string()));
+    }
 
     Ok(token_data.claims)
 }
@@ -103,57 +278,115 @@ mod tests {
     #[test]
     fn test_password_hashing_success() {
         let password = "password123";
-        let hash = hash_password(password).unwrap();
+        let hash = hash_password(password, &PasswordHashConfig::default()).unwrap();
 
         // Verify the hash is not empty and different from original password
         assert!(!hash.is_empty());
         assert_ne!(hash, password);
 
         // Verify correct password matches
-        assert!(verify_password(password, &hash).unwrap());
+        assert!(verify_password(password, &hash, &PasswordHashConfig::default()).unwrap());
     }
 
     #[test]
     fn test_password_hashing_wrong_password() {
         let password = "password123";
-        let hash = hash_password(password).unwrap();
+        let hash = hash_password(password, &PasswordHashConfig::default()).unwrap();
 
         // Verify wrong password doesn't match
-        assert!(!verify_password("wrongpassword", &hash).unwrap());
-        assert!(!verify_password("", &hash).unwrap());
-        assert!(!verify_password("password1234", &hash).unwrap());
+        assert!(!verify_password("wrongpassword", &hash, &PasswordHashConfig::default()).unwrap());
+        assert!(!verify_password("", &hash, &PasswordHashConfig::default()).unwrap());
+        assert!(!verify_password("password1234", &hash, &PasswordHashConfig::default()).unwrap());
     }
 
     #[test]
     fn test_password_hashing_different_salts() {
         let password = "password123";
-        let hash1 = hash_password(password).unwrap();
-        let hash2 = hash_password(password).unwrap();
+        let hash1 = hash_password(password, &PasswordHashConfig::default()).unwrap();
+        let hash2 = hash_password(password, &PasswordHashConfig::default()).unwrap();
 
         // Same password should produce different hashes due to different salts
         assert_ne!(hash1, hash2);
 
         // But both should verify correctly
-        assert!(verify_password(password, &hash1).unwrap());
-        assert!(verify_password(password, &hash2).unwrap());
+        assert!(verify_password(password, &hash1, &PasswordHashConfig::default()).unwrap());
+        assert!(verify_password(password, &hash2, &PasswordHashConfig::default()).unwrap());
     }
 
     #[test]
     fn test_password_hashing_empty_password() {
         let password = "";
-        let hash = hash_password(password).unwrap();
+        let hash = hash_password(password, &PasswordHashConfig::default()).unwrap();
 
         // Empty password should still hash and verify
-        assert!(verify_password(password, &hash).unwrap());
+        assert!(verify_password(password, &hash, &PasswordHashConfig::default()).unwrap());
     }
 
     #[test]
     fn test_password_hashing_long_password() {
         let password = "a".repeat(1000);
-        let hash = hash_password(&password).unwrap();
+        let hash = hash_password(&password, &PasswordHashConfig::default()).unwrap();
 
         // Long password should still work
-        assert!(verify_password(&password, &hash).unwrap());
+        assert!(verify_password(&password, &hash, &PasswordHashConfig::default()).unwrap());
+    }
+
+    #[test]
+    fn test_password_hashing_custom_params() {
+        let password = "password123";
+        let config = PasswordHashConfig {
+            memory_cost_kib: 8192,
+            iterations: 3,
+            parallelism: 2,
+        };
+
+        let hash = hash_password(password, &config).unwrap();
+        assert!(hash.contains("$m=8192,t=3,p=2$"));
+        assert!(verify_password(password, &hash, &config).unwrap());
+
+        // Hashes carry their own parameters, so they verify under the defaults too
+        assert!(verify_password(password, &hash, &PasswordHashConfig::default()).unwrap());
+
+        let default_hash = hash_password(password, &PasswordHashConfig::default()).unwrap();
+        assert!(default_hash.contains("$m=19456,t=2,p=1$"));
+    }
+
+    #[test]
+    fn test_password_hashing_invalid_params() {
+        let config = PasswordHashConfig {
+            memory_cost_kib: 1,
+            iterations: 0,
+            parallelism: 1,
+        };
+        assert!(matches!(
+            hash_password("password123", &config),
+            Err(AppError::InternalServerError(_))
+        ));
+    }
+
+    #[test]
+    fn test_needs_rehash() {
+        let old_config = PasswordHashConfig {
+            memory_cost_kib: 8192,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let hash = hash_password("password123", &old_config).unwrap();
+
+        assert!(!needs_rehash(&hash, &old_config));
+        assert!(needs_rehash(&hash, &PasswordHashConfig::default()));
+        assert!(needs_rehash("not-a-phc-string", &old_config));
+
+        // Other Argon2 variants are replaced by Argon2id
+        let argon2i = Argon2::new(
+            Algorithm::Argon2i,
+            Version::V0x13,
+            Params::new(8192, 1, 1, None).unwrap(),
+        )
+        .hash_password(b"password123", &SaltString::generate(&mut OsRng))
+        .unwrap()
+        .to_string();
+        assert!(needs_rehash(&argon2i, &old_config));
     }
 
     #[test]
@@ -162,8 +395,8 @@ mod tests {
         let role = UserRole::Kitchen;
         let secret = "test_secret";
 
-        let token = generate_token(user_id, role, secret).unwrap();
-        let claims = validate_token(&token, secret).unwrap();
+        let token = generate_token(user_id, role, None, None, &JwtKeys::hmac(secret)).unwrap();
+        let claims = validate_token(&token, &JwtKeys::hmac(secret)).unwrap();
 
         assert_eq!(claims.sub, user_id);
         assert_eq!(claims.role, role);
@@ -181,8 +414,8 @@ mod tests {
         let secret = "test_secret";
 
         for role in [UserRole::Admin, UserRole::Kitchen, UserRole::School] {
-            let token = generate_token(user_id, role, secret).unwrap();
-            let claims = validate_token(&token, secret).unwrap();
+            let token = generate_token(user_id, role, None, None, &JwtKeys::hmac(secret)).unwrap();
+            let claims = validate_token(&token, &JwtKeys::hmac(secret)).unwrap();
             assert_eq!(claims.role, role);
         }
     }
@@ -194,8 +427,8 @@ mod tests {
         let secret = "test_secret";
         let wrong_secret = "wrong_secret";
 
-        let token = generate_token(user_id, role, secret).unwrap();
-        let result = validate_token(&token, wrong_secret);
+        let token = generate_token(user_id, role, None, None, &JwtKeys::hmac(secret)).unwrap();
+        let result = validate_token(&token, &JwtKeys::hmac(wrong_secret));
 
         assert!(result.is_err());
         match result {
@@ -208,13 +441,13 @@ mod tests {
     fn test_jwt_token_malformed_token() {
         let secret = "test_secret";
 
-        let result = validate_token("not.a.valid.token", secret);
+        let result = validate_token("not.a.valid.token", &JwtKeys::hmac(secret));
         assert!(result.is_err());
 
-        let result = validate_token("", secret);
+        let result = validate_token("", &JwtKeys::hmac(secret));
         assert!(result.is_err());
 
-        let result = validate_token("invalid", secret);
+        let result = validate_token("invalid", &JwtKeys::hmac(secret));
         assert!(result.is_err());
     }
 
@@ -224,7 +457,7 @@ mod tests {
         let role = UserRole::Kitchen;
         let secret = "test_secret";
 
-        let token = generate_token(user_id, role, secret).unwrap();
+        let token = generate_token(user_id, role, None, None, &JwtKeys::hmac(secret)).unwrap();
 
         // Tamper with the token by changing a character
         let mut tampered = token.clone();
@@ -232,7 +465,7 @@ mod tests {
             tampered.push(if last == 'a' { 'b' } else { 'a' });
         }
 
-        let result = validate_token(&tampered, secret);
+        let result = validate_token(&tampered, &JwtKeys::hmac(secret));
         assert!(result.is_err());
     }
 
@@ -246,6 +479,9 @@ mod tests {
         let claims = Claims {
             sub: Uuid::new_v4(),
             role: UserRole::Admin,
+            institution_id: Some(Uuid::new_v4()),
+            institution_name: Some("SD Negeri 01".to_string()),
+            ver: CLAIMS_VERSION,
             exp: now + 3600,
             iat: now,
         };
@@ -263,5 +499,113 @@ mod tests {
         assert_eq!(decoded.role, claims.role);
         assert_eq!(decoded.exp, claims.exp);
         assert_eq!(decoded.iat, claims.iat);
+        assert_eq!(decoded.institution_id, claims.institution_id);
+        assert_eq!(decoded.institution_name, claims.institution_name);
+        assert_eq!(decoded.ver, CLAIMS_VERSION);
+    }
+
+    #[test]
+    fn test_jwt_token_carries_institution() {
+        let institution_id = Uuid::new_v4();
+        let token = generate_token(
+            Uuid::new_v4(),
+            UserRole::School,
+            Some(institution_id),
+            Some("SD Negeri 01".to_string()),
+            &JwtKeys::hmac("test_secret"),
+        )
+        .unwrap();
+
+        let claims = validate_token(&token, &JwtKeys::hmac("test_secret")).unwrap();
+        assert_eq!(claims.institution_id, Some(institution_id));
+        assert_eq!(claims.institution_name.as_deref(), Some("SD Negeri 01"));
+        assert_eq!(claims.ver, CLAIMS_VERSION);
+    }
+
+    #[test]
+    fn test_jwt_token_legacy_version_rejected() {
+        #[derive(Serialize)]
+        struct LegacyClaims {
+            sub: Uuid,
+            role: UserRole,
+            exp: usize,
+            iat: usize,
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as usize;
+        let token = encode(
+            &Header::default(),
+            &LegacyClaims {
+                sub: Uuid::new_v4(),
+                role: UserRole::Admin,
+                exp: now + 3600,
+                iat: now,
+            },
+            &EncodingKey::from_secret(b"test_secret"),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            validate_token(&token, &JwtKeys::hmac("test_secret")),
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    const RS256_PRIVATE_KEY: &str = include_str!("../../tests/fixtures/jwt_rs256_private.pem");
+    const RS256_PUBLIC_KEY: &str = include_str!("../../tests/fixtures/jwt_rs256_public.pem");
+
+    #[test]
+    fn test_jwt_token_rs256() {
+        let keys = JwtKeys::rsa(RS256_PRIVATE_KEY, RS256_PUBLIC_KEY).unwrap();
+        let user_id = Uuid::new_v4();
+
+        let token = generate_token(user_id, UserRole::Admin, None, None, &keys).unwrap();
+        let header = jsonwebtoken::decode_header(&token).unwrap();
+        assert_eq!(header.alg, jsonwebtoken::Algorithm::RS256);
+
+        let claims = validate_token(&token, &keys).unwrap();
+        assert_eq!(claims.sub, user_id);
+        assert_eq!(claims.role, UserRole::Admin);
+
+        // An HS256 verifier must not accept RS256 tokens, nor the reverse
+        assert!(validate_token(&token, &JwtKeys::hmac("test_secret")).is_err());
+        let hs256_token = generate_token(
+            user_id,
+            UserRole::Admin,
+            None,
+            None,
+            &JwtKeys::hmac("test_secret"),
+        )
+        .unwrap();
+        assert!(validate_token(&hs256_token, &keys).is_err());
+    }
+
+    #[test]
+    fn test_jwt_keys_from_config() {
+        let mut config = Config {
+            jwt_secret: "test_secret".to_string(),
+            ..Default::default()
+        };
+        let token = generate_token(
+            Uuid::new_v4(),
+            UserRole::School,
+            None,
+            None,
+            &JwtKeys::hmac("test_secret"),
+        )
+        .unwrap();
+        assert!(validate_token(&token, &JwtKeys::from_config(&config).unwrap()).is_ok());
+
+        config.jwt.algorithm = "RS256".to_string();
+        assert!(JwtKeys::from_config(&config).is_err());
+
+        config.jwt.private_key = Some(RS256_PRIVATE_KEY.to_string());
+        config.jwt.public_key = Some(RS256_PUBLIC_KEY.to_string());
+        let keys = JwtKeys::from_config(&config).unwrap();
+        let token = generate_token(Uuid::new_v4(), UserRole::School, None, None, &keys).unwrap();
+        assert!(validate_token(&token, &keys).is_ok());
     }
 }
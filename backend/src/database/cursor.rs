@@ -0,0 +1,88 @@
+//! Opaque cursors for keyset pagination.
+//!
+//! A [`Cursor`] identifies the last row of a page by its `(created_at, id)` pair.
+//! Lists ordered by `created_at DESC, id DESC` can then fetch the next page with
+//! `WHERE (created_at, id) < ($ts, $id)`, which stays fast however deep the client
+//! pages, unlike `OFFSET`.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::DateTime;
+use chrono::NaiveDateTime;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// UTC timestamp format used inside encoded cursors (microsecond precision,
+/// matching Postgres).
+const CURSOR_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+/// Position of the last row returned in a keyset-paginated page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// `created_at` of the last row.
+    pub created_at: DateTime<Utc>,
+    /// `id` of the last row, used as a tie-breaker for equal timestamps.
+    pub id: Uuid,
+}
+
+impl Cursor {
+    /// Creates a cursor pointing at the given row.
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Encodes the cursor as an opaque, URL-safe base64 string.
+    pub fn encode(&self) -> String {
+        let raw = format!("{}|{}", self.created_at.format(CURSOR_TIME_FORMAT), self.id);
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// Decodes a cursor previously produced by [`Cursor::encode`].
+    ///
+    /// Returns `None` if the string is not a valid cursor.
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(encoded.trim()).ok()?;
+        let raw = String::from_utf8(bytes).ok()?;
+        let (ts, id) = raw.split_once('|')?;
+        let created_at = NaiveDateTime::parse_from_str(ts, CURSOR_TIME_FORMAT)
+            .ok()?
+            .and_utc();
+        let id = Uuid::parse_str(id).ok()?;
+        Some(Self { created_at, id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let created_at = NaiveDate::from_ymd_opt(2026, 1, 15)
+            .unwrap()
+            .and_hms_micro_opt(10, 30, 0, 123456)
+            .unwrap()
+            .and_utc();
+        let cursor = Cursor::new(created_at, Uuid::new_v4());
+
+        let encoded = cursor.encode();
+        assert!(!encoded.contains('|'));
+        assert_eq!(Cursor::decode(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn test_cursor_decode_rejects_garbage() {
+        assert_eq!(Cursor::decode(""), None);
+        assert_eq!(Cursor::decode("not base64!"), None);
+        assert_eq!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode("no-separator")),
+            None
+        );
+        assert_eq!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode("2026-01-15T10:30:00.000000|nope")),
+            None
+        );
+    }
+}
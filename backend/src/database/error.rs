@@ -5,8 +5,16 @@
 #[non_exhaustive]
 pub enum DatabaseError {
     /// Error from the underlying database backend (sqlx)
-    #[error("Database error: {0}")]
-    BackendError(#[from] sqlx::Error),
+    #[error(
+        "Database error{}: {source}",
+        .context.as_deref().map(|c| format!(" ({c})")).unwrap_or_default()
+    )]
+    BackendError {
+        source: sqlx::Error,
+        /// The table and operation that failed, e.g. `insert on reviews`,
+        /// when known.
+        context: Option<String>,
+    },
 
     /// Internal database error not originating from the backend
     #[error("Application database error: {message}")]
@@ -15,4 +23,116 @@ pub enum DatabaseError {
     /// Failed to parse or extract data from a model field
     #[error("Data parse error: {message}")]
     ParseError { message: String },
+
+    /// A conditional write matched no rows because the record changed concurrently
+    #[error("Conflict: {message}")]
+    Conflict { message: String },
+
+    /// A write violated a unique constraint (SQLSTATE `23505`)
+    #[error("Unique constraint violated: {constraint}")]
+    UniqueViolation { constraint: String },
+}
+
+/// SQLSTATE of a serialization failure.
+const SERIALIZATION_FAILURE: &str = "40001";
+
+/// SQLSTATE of a detected deadlock.
+const DEADLOCK_DETECTED: &str = "40P01";
+
+impl DatabaseError {
+    /// Whether the error is a serialization failure or deadlock, i.e. one
+    /// that re-running the whole transaction may resolve.
+    pub fn is_transaction_conflict(&self) -> bool {
+        match self {
+            DatabaseError::BackendError {
+                source: sqlx::Error::Database(db_err),
+                ..
+            } => matches!(
+                db_err.code().as_deref(),
+                Some(SERIALIZATION_FAILURE | DEADLOCK_DETECTED)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Records that the error came from `operation` on `table`.
+    ///
+    /// Only [`DatabaseError::BackendError`] carries context; other variants
+    /// already describe what failed and are returned unchanged. Context
+    /// that was already set is kept.
+    pub fn with_context(self, table: &str, operation: &str) -> Self {
+        match self {
+            DatabaseError::BackendError {
+                source,
+                context: None,
+            } => DatabaseError::BackendError {
+                source,
+                context: Some(format!("{} on {}", operation, table)),
+            },
+            err => err,
+        }
+    }
+}
+
+impl From<sqlx::Error> for DatabaseError {
+    fn from(err: sqlx::Error) -> Self {
+        if let sqlx::Error::Database(db_err) = &err
+            && db_err.is_unique_violation()
+        {
+            return DatabaseError::UniqueViolation {
+                constraint: db_err.constraint().unwrap_or_default().to_string(),
+            };
+        }
+        DatabaseError::BackendError {
+            source: err,
+            context: None,
+        }
+    }
+}
+
+/// Attaches table and operation context to a failed database call.
+pub(crate) trait ResultExt<T> {
+    /// Converts the error into a [`DatabaseError`] and applies
+    /// [`DatabaseError::with_context`].
+    fn context(self, table: &str, operation: &str) -> Result<T, DatabaseError>;
+}
+
+impl<T, E: Into<DatabaseError>> ResultExt<T> for Result<T, E> {
+    fn context(self, table: &str, operation: &str) -> Result<T, DatabaseError> {
+        self.map_err(|e| e.into().with_context(table, operation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_context_names_table_and_operation() {
+        let err = DatabaseError::from(sqlx::Error::RowNotFound).with_context("reviews", "insert");
+        assert!(matches!(
+            &err,
+            DatabaseError::BackendError { context: Some(c), .. } if c == "insert on reviews"
+        ));
+        assert!(
+            err.to_string()
+                .starts_with("Database error (insert on reviews): ")
+        );
+
+        // The innermost context wins
+        let err = err.with_context("kitchens", "update");
+        assert!(err.to_string().contains("(insert on reviews)"));
+
+        let err = DatabaseError::from(sqlx::Error::RowNotFound);
+        assert!(err.to_string().starts_with("Database error: "));
+    }
+
+    #[test]
+    fn test_with_context_keeps_other_variants() {
+        let err = DatabaseError::Conflict {
+            message: "stale".to_string(),
+        }
+        .with_context("reviews", "update");
+        assert!(matches!(err, DatabaseError::Conflict { ref message } if message == "stale"));
+    }
 }
@@ -4,27 +4,52 @@
 //! and all table-specific data access objects.
 
 use std::str::FromStr;
+use std::time::Duration;
 
+use futures::future::BoxFuture;
 use log::debug;
 use log::info;
+use log::warn;
+use sqlx::Transaction;
 use sqlx::migrate::MigrateDatabase;
 use sqlx::postgres::PgConnectOptions as ConnOpt;
 use sqlx::postgres::PgPool as Pool;
 use sqlx::postgres::Postgres;
 
+pub mod cursor;
 pub mod error;
 pub mod model;
+pub mod seed;
 pub mod table;
 
 pub use table::StatsQueries;
 use table::*;
 
+use crate::database::error::DatabaseError;
+use crate::database::error::ResultExt;
+
+/// Default number of times [`Database::transaction_with_retry`] re-runs a
+/// transaction that hit a serialization failure or deadlock.
+pub const DEFAULT_TRANSACTION_RETRIES: u32 = 3;
+
+/// An embedded migration, identified by the version and description taken
+/// from its file name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationInfo {
+    /// Version prefix of the migration file.
+    pub version: i64,
+    /// Rest of the file name, with underscores replaced by spaces.
+    pub description: String,
+}
+
 /// Central database manager.
 ///
 /// Holds the connection pool and all table-specific data access objects.
 pub struct Database {
     /// The underlying SQLx connection pool.
     pool: Pool,
+    /// Retries allowed by [`Database::transaction_with_retry`].
+    transaction_retries: u32,
     /// Table for institution data.
     pub institution_table: InstitutionTable,
     /// Table for user data.
@@ -37,6 +62,16 @@ pub struct Database {
     pub checklist_item_table: ChecklistItemTable,
     /// Table for incident reports.
     pub incident_table: IncidentTable,
+    /// Table for incident status changes.
+    pub incident_status_history_table: IncidentStatusHistoryTable,
+    /// Table for incident timeline events.
+    pub incident_timeline_event_table: IncidentTimelineEventTable,
+    /// Table for incident laboratory results.
+    pub incident_lab_result_table: IncidentLabResultTable,
+    /// Table for institutions affected by incidents.
+    pub affected_institution_table: AffectedInstitutionTable,
+    /// Table for incident corrective actions.
+    pub corrective_action_table: CorrectiveActionTable,
     /// Table for inspection records.
     pub inspection_table: InspectionTable,
     /// Table for inspection findings.
@@ -51,6 +86,8 @@ pub struct Database {
     pub review_table: ReviewTable,
     /// Table for review dispute history.
     pub review_dispute_history_table: ReviewDisputeHistoryTable,
+    /// Table for review contents before each edit.
+    pub review_edit_history_table: ReviewEditHistoryTable,
     /// Table for performance badges.
     pub performance_badge_table: PerformanceBadgeTable,
     /// Table for audit logs.
@@ -63,6 +100,10 @@ pub struct Database {
     pub notification_audit_trail_table: NotificationAuditTrailTable,
     /// Table for video data.
     pub video_table: VideoTable,
+    /// Table for failed outbound webhook deliveries.
+    pub webhook_dead_letter_table: WebhookDeadLetterTable,
+    /// Table mapping uploaded file contents to stored files.
+    pub file_hash_table: FileHashTable,
     /// Statistics queries
     pub stats_queries: StatsQueries,
 }
@@ -73,6 +114,111 @@ impl Database {
     pub async fn close(&self) {
         self.pool.close().await;
     }
+
+    /// Checks database connectivity by running `SELECT 1`.
+    ///
+    /// Cheap enough to call from health checks regardless of table sizes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails (e.g. the database is unreachable).
+    pub async fn ping(&self) -> Result<(), DatabaseError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Number of connections currently open in the pool, idle or in use.
+    pub fn pool_size(&self) -> u32 {
+        self.pool.size()
+    }
+
+    /// Number of open connections not currently in use.
+    pub fn pool_idle(&self) -> usize {
+        self.pool.num_idle()
+    }
+
+    /// Embedded migrations that have not been applied yet, in order.
+    ///
+    /// Every migration is pending on a database that was never migrated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the applied migrations cannot be read.
+    pub async fn pending_migrations(&self) -> Result<Vec<MigrationInfo>, DatabaseError> {
+        let tracked: bool =
+            sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+                .fetch_one(&self.pool)
+                .await
+                .context("_sqlx_migrations", "pending_migrations")?;
+        let applied: Vec<i64> = if tracked {
+            sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool)
+                .await
+                .context("_sqlx_migrations", "pending_migrations")?
+        } else {
+            Vec::new()
+        };
+
+        Ok(sqlx::migrate!("./migrations")
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+            .map(|m| MigrationInfo {
+                version: m.version,
+                description: m.description.to_string(),
+            })
+            .collect())
+    }
+
+    /// Sets how many times [`Database::transaction_with_retry`] re-runs a
+    /// transaction (default: [`DEFAULT_TRANSACTION_RETRIES`]).
+    pub fn with_transaction_retries(mut self, retries: u32) -> Self {
+        self.transaction_retries = retries;
+        self
+    }
+
+    /// Runs `f` on a new transaction and commits it, re-running the whole
+    /// transaction when it fails with a serialization failure (`40001`) or a
+    /// deadlock (`40P01`).
+    ///
+    /// `f` is called once per attempt and must be idempotent: everything it
+    /// wrote in a failed attempt is rolled back, but side effects outside the
+    /// database are not.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that isn't a transaction conflict right away,
+    /// and the last conflict once the retries are used up.
+    pub async fn transaction_with_retry<'a, T, F>(&self, mut f: F) -> Result<T, DatabaseError>
+    where
+        F: for<'c> FnMut(
+            &'c mut Transaction<'a, Postgres>,
+        ) -> BoxFuture<'c, Result<T, DatabaseError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result: Result<T, DatabaseError> = async {
+                // Naming the transaction's lifetime `'a` lets `f` borrow
+                // anything that outlives the call
+                let mut tx: Transaction<'a, Postgres> = self.pool.begin().await?;
+                let value = f(&mut tx).await?;
+                tx.commit().await?;
+                Ok(value)
+            }
+            .await;
+
+            match result {
+                Err(e) if e.is_transaction_conflict() && attempt < self.transaction_retries => {
+                    attempt += 1;
+                    warn!(
+                        "Retrying transaction ({}/{}): {}",
+                        attempt, self.transaction_retries, e
+                    );
+                    tokio::time::sleep(Duration::from_millis(10 * u64::from(attempt))).await;
+                }
+                result => return result,
+            }
+        }
+    }
 }
 
 impl Database {
@@ -95,6 +241,11 @@ impl Database {
         let compliance_metric_table = ComplianceMetricTable::new(pool.clone());
         let checklist_item_table = ChecklistItemTable::new(pool.clone());
         let incident_table = IncidentTable::new(pool.clone());
+        let incident_status_history_table = IncidentStatusHistoryTable::new(pool.clone());
+        let incident_timeline_event_table = IncidentTimelineEventTable::new(pool.clone());
+        let incident_lab_result_table = IncidentLabResultTable::new(pool.clone());
+        let affected_institution_table = AffectedInstitutionTable::new(pool.clone());
+        let corrective_action_table = CorrectiveActionTable::new(pool.clone());
         let inspection_table = InspectionTable::new(pool.clone());
         let inspection_finding_table = InspectionFindingTable::new(pool.clone());
         let complaint_table = ComplaintTable::new(pool.clone());
@@ -102,22 +253,31 @@ impl Database {
         let complaint_comment_table = ComplaintCommentTable::new(pool.clone());
         let review_table = ReviewTable::new(pool.clone());
         let review_dispute_history_table = ReviewDisputeHistoryTable::new(pool.clone());
+        let review_edit_history_table = ReviewEditHistoryTable::new(pool.clone());
         let performance_badge_table = PerformanceBadgeTable::new(pool.clone());
         let audit_log_table = AuditLogTable::new(pool.clone());
         let alert_table = AlertTable::new(pool.clone());
         let notification_table = NotificationTable::new(pool.clone());
         let notification_audit_trail_table = NotificationAuditTrailTable::new(pool.clone());
         let video_table = VideoTable::new(pool.clone());
+        let webhook_dead_letter_table = WebhookDeadLetterTable::new(pool.clone());
+        let file_hash_table = FileHashTable::new(pool.clone());
         let stats_queries = StatsQueries::new(pool.clone());
 
         Ok(Self {
             pool,
+            transaction_retries: DEFAULT_TRANSACTION_RETRIES,
             institution_table,
             user_table,
             kitchen_table,
             compliance_metric_table,
             checklist_item_table,
             incident_table,
+            incident_status_history_table,
+            incident_timeline_event_table,
+            incident_lab_result_table,
+            affected_institution_table,
+            corrective_action_table,
             inspection_table,
             inspection_finding_table,
             complaint_table,
@@ -125,24 +285,28 @@ impl Database {
             complaint_comment_table,
             review_table,
             review_dispute_history_table,
+            review_edit_history_table,
             performance_badge_table,
             audit_log_table,
             alert_table,
             notification_table,
             notification_audit_trail_table,
             video_table,
+            webhook_dead_letter_table,
+            file_hash_table,
             stats_queries,
         })
     }
 
-    /// Runs database migrations.
+    /// Runs pending database migrations and returns the ones applied.
     ///
     /// # Errors
     ///
     /// Returns an error if migrations fail.
-    pub async fn run_migrations(&self) -> anyhow::Result<()> {
+    pub async fn run_migrations(&self) -> anyhow::Result<Vec<MigrationInfo>> {
+        let pending = self.pending_migrations().await?;
         sqlx::migrate!("./migrations").run(&self.pool).await?;
-        Ok(())
+        Ok(pending)
     }
 
     pub async fn drop_all_tables(&self) -> anyhow::Result<()> {
@@ -152,6 +316,7 @@ impl Database {
         self.alert_table.drop_table().await?;
         self.audit_log_table.drop_table().await?;
         self.performance_badge_table.drop_table().await?;
+        self.review_edit_history_table.drop_table().await?;
         self.review_dispute_history_table.drop_table().await?;
         self.review_table.drop_table().await?;
         self.complaint_comment_table.drop_table().await?;
@@ -159,6 +324,11 @@ impl Database {
         self.complaint_table.drop_table().await?;
         self.inspection_finding_table.drop_table().await?;
         self.inspection_table.drop_table().await?;
+        self.corrective_action_table.drop_table().await?;
+        self.affected_institution_table.drop_table().await?;
+        self.incident_lab_result_table.drop_table().await?;
+        self.incident_timeline_event_table.drop_table().await?;
+        self.incident_status_history_table.drop_table().await?;
         self.incident_table.drop_table().await?;
         self.checklist_item_table.drop_table().await?;
         self.compliance_metric_table.drop_table().await?;
@@ -166,6 +336,8 @@ impl Database {
         self.user_table.drop_table().await?;
         self.institution_table.drop_table().await?;
         self.video_table.drop_table().await?;
+        self.webhook_dead_letter_table.drop_table().await?;
+        self.file_hash_table.drop_table().await?;
         Ok(())
     }
 
@@ -175,6 +347,7 @@ impl Database {
         self.alert_table.delete_all().await?;
         self.audit_log_table.delete_all().await?;
         self.performance_badge_table.delete_all().await?;
+        self.review_edit_history_table.delete_all().await?;
         self.review_dispute_history_table.delete_all().await?;
         self.review_table.delete_all().await?;
         self.complaint_comment_table.delete_all().await?;
@@ -182,6 +355,11 @@ impl Database {
         self.complaint_table.delete_all().await?;
         self.inspection_finding_table.delete_all().await?;
         self.inspection_table.delete_all().await?;
+        self.corrective_action_table.delete_all().await?;
+        self.affected_institution_table.delete_all().await?;
+        self.incident_lab_result_table.delete_all().await?;
+        self.incident_timeline_event_table.delete_all().await?;
+        self.incident_status_history_table.delete_all().await?;
         self.incident_table.delete_all().await?;
         self.checklist_item_table.delete_all().await?;
         self.compliance_metric_table.delete_all().await?;
@@ -189,6 +367,8 @@ impl Database {
         self.user_table.delete_all().await?;
         self.institution_table.delete_all().await?;
         self.video_table.delete_all().await?;
+        self.webhook_dead_letter_table.delete_all().await?;
+        self.file_hash_table.delete_all().await?;
         Ok(())
     }
 
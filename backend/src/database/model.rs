@@ -2,19 +2,24 @@
 //!
 //! This module contains structs representing database tables and enums for various fields.
 
+use chrono::DateTime;
 use chrono::NaiveDate;
-use chrono::NaiveDateTime;
+use chrono::Utc;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use serde::Serialize;
 use sqlx::FromRow;
+use sqlx::types::Json;
 use uuid::Uuid;
 
 // Enums
 
 /// Roles for users in the system.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, utoipa::ToSchema,
+)]
 #[sqlx(type_name = "user_role_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum UserRole {
     /// Kitchen staff or manager.
     Kitchen,
@@ -26,8 +31,44 @@ pub enum UserRole {
     Admin,
 }
 
+impl UserRole {
+    /// Whether the role may edit and delete other users' content.
+    pub fn can_moderate(self) -> bool {
+        matches!(self, UserRole::Admin)
+    }
+}
+
+/// Who a review's report came from.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "report_source_enum", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSource {
+    /// Submitted by a school or member of the public.
+    #[default]
+    Public,
+    OfficialInspector,
+    HealthWorker,
+}
+
+/// How much weight a review's report carries.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "confidence_level_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ConfidenceLevel {
+    Low,
+    /// Given to newly submitted reviews.
+    #[default]
+    Medium,
+    High,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "compliance_trend_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum ComplianceTrend {
     Improving,
     Stable,
@@ -36,6 +77,7 @@ pub enum ComplianceTrend {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "checklist_status_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum ChecklistStatus {
     Pass,
     Fail,
@@ -44,6 +86,7 @@ pub enum ChecklistStatus {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "incident_type_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum IncidentType {
     Poisoning,
     Nutrition,
@@ -53,6 +96,7 @@ pub enum IncidentType {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "incident_source_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum IncidentSource {
     Consumer,
     Inspector,
@@ -61,6 +105,7 @@ pub enum IncidentSource {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "incident_severity_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum IncidentSeverity {
     Minor,
     Major,
@@ -69,6 +114,7 @@ pub enum IncidentSeverity {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "incident_status_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum IncidentStatus {
     Investigating,
     Resolved,
@@ -80,15 +126,16 @@ pub enum IncidentStatus {
     type_name = "inspection_follow_up_status_enum",
     rename_all = "kebab-case"
 )] // 'in-progress'
+#[serde(rename_all = "kebab-case")]
 pub enum InspectionFollowUpStatus {
     Pending,
-    #[serde(rename = "in-progress")]
     InProgress,
     Completed,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "finding_category_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum FindingCategory {
     Major,
     Minor,
@@ -97,6 +144,7 @@ pub enum FindingCategory {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "complaint_category_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum ComplaintCategory {
     Hygiene,
     Taste,
@@ -108,15 +156,16 @@ pub enum ComplaintCategory {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "complaint_status_enum", rename_all = "kebab-case")] // 'in-progress'
+#[serde(rename_all = "kebab-case")]
 pub enum ComplaintStatus {
     Pending,
-    #[serde(rename = "in-progress")]
     InProgress,
     Resolved,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "evidence_metadata_status_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum EvidenceMetadataStatus {
     Verified,
     Mismatch,
@@ -125,6 +174,7 @@ pub enum EvidenceMetadataStatus {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "evidence_capture_method_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum EvidenceCaptureMethod {
     Camera,
     Fallback,
@@ -132,6 +182,7 @@ pub enum EvidenceCaptureMethod {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "alert_type_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum AlertType {
     Compliance,
     Incident,
@@ -141,6 +192,7 @@ pub enum AlertType {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "alert_severity_enum", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum AlertSeverity {
     Low,
     Medium,
@@ -148,7 +200,9 @@ pub enum AlertSeverity {
     Critical,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, utoipa::ToSchema,
+)]
 #[sqlx(type_name = "kitchen_type_enum")] // Values have spaces and Title Case in DB definition
 pub enum KitchenType {
     #[sqlx(rename = "Central Kitchen")]
@@ -177,8 +231,8 @@ pub struct Institution {
     pub email: Option<String>,
     pub registration_number: Option<String>,
     pub verified: Option<bool>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Institution {
@@ -201,7 +255,7 @@ impl Default for Institution {
 }
 
 /// Represents a user in the system.
-#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone, utoipa::ToSchema)]
 pub struct User {
     /// Unique identifier for the user.
     #[serde(default)]
@@ -216,20 +270,23 @@ pub struct User {
     pub phone: Option<String>,
     /// Whether the user's account is verified.
     pub verified: Option<bool>,
+    /// Whether the user may sign in; deactivated users keep their history.
+    pub active: Option<bool>,
     /// Name of the institution the user belongs to.
     pub institution_name: Option<String>,
     /// ID of the institution the user belongs to.
     pub institution_id: Option<Uuid>,
     /// Hashed password for authentication.
+    #[schema(ignore)]
     pub password_hash: Option<String>,
     /// Hash of the user's KTP (ID card) photo.
     pub ktp_photo_hash: Option<String>,
     /// Timestamp of the last login.
-    pub last_login: Option<NaiveDateTime>,
+    pub last_login: Option<DateTime<Utc>>,
     /// Timestamp when the user was created.
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
     /// Timestamp when the user was last updated.
-    pub updated_at: Option<NaiveDateTime>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for User {
@@ -241,6 +298,7 @@ impl Default for User {
             unique_code: String::default(),
             phone: None,
             verified: None,
+            active: Some(true),
             institution_name: None,
             institution_id: None,
             password_hash: None,
@@ -264,11 +322,18 @@ pub struct Kitchen {
     pub location: Option<String>,
     pub r#type: Option<KitchenType>,
     pub meals_served: Option<i32>,
-    pub certifications: Option<serde_json::Value>,
+    /// Certification names; the column only accepts a JSON array of strings.
+    pub certifications: Option<Json<Vec<String>>>,
     pub image_url: Option<String>,
     pub owner_id: Option<Uuid>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub contact_phone: Option<String>,
+    pub contact_email: Option<String>,
+    pub operating_hours: Option<String>,
+    pub capacity: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Kitchen {
@@ -285,6 +350,12 @@ impl Default for Kitchen {
             certifications: None,
             image_url: None,
             owner_id: None,
+            latitude: None,
+            longitude: None,
+            contact_phone: None,
+            contact_email: None,
+            operating_hours: None,
+            capacity: None,
             created_at: None,
             updated_at: None,
         }
@@ -301,10 +372,10 @@ pub struct ComplianceMetric {
     pub nutrition_compliance: Option<Decimal>,
     pub temperature_control: Option<Decimal>,
     pub sla_performance: Option<serde_json::Value>,
-    pub last_inspection_date: Option<NaiveDateTime>,
+    pub last_inspection_date: Option<DateTime<Utc>>,
     pub trend: Option<ComplianceTrend>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for ComplianceMetric {
@@ -334,7 +405,7 @@ pub struct ChecklistItem {
     pub item: Option<String>,
     pub status: Option<ChecklistStatus>,
     pub notes: Option<String>,
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for ChecklistItem {
@@ -358,7 +429,7 @@ pub struct Incident {
     pub kitchen_id: Uuid,
     pub r#type: IncidentType,
     pub source: IncidentSource,
-    pub date: NaiveDateTime,
+    pub date: DateTime<Utc>,
     pub location: Option<String>,
     pub province: Option<String>,
     pub food_type: Option<String>,
@@ -371,8 +442,14 @@ pub struct Incident {
     pub reported_by: Option<String>,
     pub map_coordinates: Option<serde_json::Value>,
     pub gps_coordinates: Option<serde_json::Value>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub hospitalized: Option<i32>,
+    pub source_url: Option<String>,
+    /// Set by [`IncidentTable::transition_status`] when the incident is resolved.
+    ///
+    /// [`IncidentTable::transition_status`]: crate::database::table::IncidentTable::transition_status
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Incident {
@@ -382,7 +459,7 @@ impl Default for Incident {
             kitchen_id: Uuid::default(),
             r#type: IncidentType::Other,
             source: IncidentSource::Public,
-            date: NaiveDateTime::default(),
+            date: DateTime::<Utc>::default(),
             location: None,
             province: None,
             food_type: None,
@@ -395,26 +472,156 @@ impl Default for Incident {
             reported_by: None,
             map_coordinates: None,
             gps_coordinates: None,
+            hospitalized: None,
+            source_url: None,
+            resolved_at: None,
             created_at: None,
             updated_at: None,
         }
     }
 }
 
+/// A change of an incident's status.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct IncidentStatusChange {
+    #[serde(default)]
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub from_status: IncidentStatus,
+    pub to_status: IncidentStatus,
+    pub changed_by: Option<Uuid>,
+    pub note: Option<String>,
+    pub changed_at: Option<DateTime<Utc>>,
+}
+
+impl Default for IncidentStatusChange {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::default(),
+            from_status: IncidentStatus::Investigating,
+            to_status: IncidentStatus::Investigating,
+            changed_by: None,
+            note: None,
+            changed_at: None,
+        }
+    }
+}
+
+/// Event on an incident's investigation timeline.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct IncidentTimelineEvent {
+    #[serde(default)]
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub event_date: DateTime<Utc>,
+    pub event_title: String,
+    pub description: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Default for IncidentTimelineEvent {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::default(),
+            event_date: DateTime::<Utc>::default(),
+            event_title: String::default(),
+            description: None,
+            created_at: None,
+        }
+    }
+}
+
+/// Laboratory result confirming the cause of an incident.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct IncidentLabResult {
+    #[serde(default)]
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub pathogen: String,
+    pub test_date: NaiveDate,
+    pub confirmed_by: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Default for IncidentLabResult {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::default(),
+            pathogen: String::default(),
+            test_date: NaiveDate::default(),
+            confirmed_by: String::default(),
+            created_at: None,
+        }
+    }
+}
+
+/// Institution (e.g. a school) affected by an incident.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct AffectedInstitution {
+    #[serde(default)]
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub institution_name: String,
+    pub institution_type: Option<String>,
+    pub affected_count: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Default for AffectedInstitution {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::default(),
+            institution_name: String::default(),
+            institution_type: None,
+            affected_count: None,
+            created_at: None,
+        }
+    }
+}
+
+/// Corrective action taken in response to an incident.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct CorrectiveAction {
+    #[serde(default)]
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub action_description: String,
+    pub completed: Option<bool>,
+    pub completed_date: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Default for CorrectiveAction {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::default(),
+            action_description: String::default(),
+            completed: None,
+            completed_date: None,
+            created_at: None,
+        }
+    }
+}
+
 #[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
 pub struct Inspection {
     #[serde(default)]
     pub id: Uuid,
     pub kitchen_id: Uuid,
     pub inspector_name: Option<String>,
-    pub date: NaiveDateTime,
+    pub date: DateTime<Utc>,
     pub overall_score: Option<Decimal>,
     pub recommendations: Option<serde_json::Value>,
     pub follow_up_status: Option<InspectionFollowUpStatus>,
     pub attachments: Option<serde_json::Value>,
-    pub next_inspection_date: Option<NaiveDateTime>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub next_inspection_date: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Inspection {
@@ -423,7 +630,7 @@ impl Default for Inspection {
             id: Uuid::new_v4(),
             kitchen_id: Uuid::default(),
             inspector_name: None,
-            date: NaiveDateTime::default(),
+            date: DateTime::<Utc>::default(),
             overall_score: None,
             recommendations: None,
             follow_up_status: None,
@@ -444,8 +651,8 @@ pub struct InspectionFinding {
     pub description: String,
     pub evidence: Option<String>,
     pub correction_required: Option<bool>,
-    pub deadline: Option<NaiveDateTime>,
-    pub created_at: Option<NaiveDateTime>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for InspectionFinding {
@@ -472,13 +679,13 @@ pub struct Complaint {
     pub description: String,
     pub status: Option<ComplaintStatus>,
     pub reported_by: Option<String>,
-    pub reported_at: NaiveDateTime,
-    pub sla_deadline: Option<NaiveDateTime>,
+    pub reported_at: DateTime<Utc>,
+    pub sla_deadline: Option<DateTime<Utc>>,
     pub assigned_to: Option<Uuid>,
     pub resolution: Option<String>,
     pub satisfaction_rating: Option<i32>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Complaint {
@@ -490,7 +697,7 @@ impl Default for Complaint {
             description: String::default(),
             status: None,
             reported_by: None,
-            reported_at: NaiveDateTime::default(),
+            reported_at: DateTime::<Utc>::default(),
             sla_deadline: None,
             assigned_to: None,
             resolution: None,
@@ -507,10 +714,10 @@ pub struct ComplaintEvidence {
     pub id: Uuid,
     pub complaint_id: Uuid,
     pub url: String,
-    pub timestamp: NaiveDateTime,
+    pub timestamp: DateTime<Utc>,
     pub metadata_status: Option<EvidenceMetadataStatus>,
     pub capture_method: Option<EvidenceCaptureMethod>,
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for ComplaintEvidence {
@@ -519,7 +726,7 @@ impl Default for ComplaintEvidence {
             id: Uuid::new_v4(),
             complaint_id: Uuid::default(),
             url: String::default(),
-            timestamp: NaiveDateTime::default(),
+            timestamp: DateTime::<Utc>::default(),
             metadata_status: None,
             capture_method: None,
             created_at: None,
@@ -536,7 +743,7 @@ pub struct ComplaintComment {
     pub author_name: Option<String>,
     pub role: Option<String>,
     pub message: String,
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for ComplaintComment {
@@ -570,15 +777,17 @@ pub struct Review {
     pub comment: String,
     pub photos: Option<serde_json::Value>,
     pub verification_status: Option<String>,
-    pub report_source: String,
-    pub confidence_level: String,
+    pub report_source: ReportSource,
+    pub confidence_level: ConfidenceLevel,
     pub root_causes: Option<serde_json::Value>,
     pub evidence: Option<serde_json::Value>,
     pub dispute_status: Option<String>,
     pub verified: Option<bool>,
     pub is_draft: Option<bool>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub delivery_date: Option<NaiveDate>,
+    pub meal_type: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Review {
@@ -598,13 +807,15 @@ impl Default for Review {
             comment: String::default(),
             photos: None,
             verification_status: Some("unverified".to_string()),
-            report_source: "public".to_string(),
-            confidence_level: "low".to_string(),
+            report_source: ReportSource::default(),
+            confidence_level: ConfidenceLevel::default(),
             root_causes: None,
             evidence: None,
             dispute_status: Some("none".to_string()),
             verified: None,
             is_draft: None,
+            delivery_date: None,
+            meal_type: None,
             created_at: None,
             updated_at: None,
         }
@@ -616,7 +827,7 @@ pub struct ReviewDisputeHistory {
     #[serde(default)]
     pub id: Uuid,
     pub review_id: Uuid,
-    pub timestamp: Option<NaiveDateTime>,
+    pub timestamp: Option<DateTime<Utc>>,
     pub action: String,
     pub by_user_id: Option<Uuid>,
     pub by_user_code: Option<String>,
@@ -637,6 +848,41 @@ impl Default for ReviewDisputeHistory {
     }
 }
 
+/// A review's ratings and comment as they were before an edit.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct ReviewEdit {
+    #[serde(default)]
+    pub id: Uuid,
+    pub review_id: Uuid,
+    pub edited_by: Option<Uuid>,
+    pub taste_rating: Decimal,
+    pub hygiene_rating: Decimal,
+    pub freshness_rating: Decimal,
+    pub temperature_rating: Decimal,
+    pub packaging_rating: Decimal,
+    pub handling_rating: Decimal,
+    pub comment: String,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+impl Default for ReviewEdit {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            review_id: Uuid::default(),
+            edited_by: None,
+            taste_rating: Decimal::default(),
+            hygiene_rating: Decimal::default(),
+            freshness_rating: Decimal::default(),
+            temperature_rating: Decimal::default(),
+            packaging_rating: Decimal::default(),
+            handling_rating: Decimal::default(),
+            comment: String::default(),
+            edited_at: None,
+        }
+    }
+}
+
 #[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
 pub struct PerformanceBadge {
     #[serde(default)]
@@ -646,7 +892,7 @@ pub struct PerformanceBadge {
     pub title: String,
     pub description: String,
     pub earned_date: NaiveDate,
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for PerformanceBadge {
@@ -672,7 +918,7 @@ pub struct AuditLog {
     pub action: String,
     pub entity_type: String,
     pub entity_id: String,
-    pub timestamp: Option<NaiveDateTime>,
+    pub timestamp: Option<DateTime<Utc>>,
     pub ip_address: Option<String>,
     pub metadata: Option<serde_json::Value>,
 }
@@ -702,9 +948,9 @@ pub struct Alert {
     pub severity: AlertSeverity,
     pub title: String,
     pub message: String,
-    pub timestamp: Option<NaiveDateTime>,
+    pub timestamp: Option<DateTime<Utc>>,
     pub acknowledged: Option<bool>,
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Alert {
@@ -737,8 +983,8 @@ pub struct Notification {
     pub status: Option<String>,
     pub target_role: String,
     pub created_by: String,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Notification {
@@ -766,7 +1012,7 @@ pub struct NotificationAuditTrail {
     #[serde(default)]
     pub id: Uuid,
     pub notification_id: Uuid,
-    pub timestamp: Option<NaiveDateTime>,
+    pub timestamp: Option<DateTime<Utc>>,
     pub action: String,
     pub user_code: String,
 }
@@ -783,6 +1029,45 @@ impl Default for NotificationAuditTrail {
     }
 }
 
+/// Stored file keyed by the SHA-256 of its contents.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FileHash {
+    /// Lowercase hex SHA-256 of the file contents.
+    pub hash: String,
+    pub file_id: String,
+    pub url: String,
+    pub size: i64,
+    pub mime_type: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Outbound webhook delivery that failed after all retries.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct WebhookDeadLetter {
+    #[serde(default)]
+    pub id: Uuid,
+    pub url: String,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub error: String,
+    pub attempts: i32,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Default for WebhookDeadLetter {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            url: String::default(),
+            event: String::default(),
+            payload: serde_json::Value::Null,
+            error: String::default(),
+            attempts: 0,
+            created_at: None,
+        }
+    }
+}
+
 #[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
 pub struct Video {
     #[serde(default)]
@@ -795,7 +1080,7 @@ pub struct Video {
     pub upload_date: Option<NaiveDate>,
     pub thumbnail: Option<String>,
     pub haccp_relevance: Option<String>,
-    pub created_at: Option<NaiveDateTime>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Default for Video {
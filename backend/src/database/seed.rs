@@ -0,0 +1,322 @@
+//! Demo data for local development.
+//!
+//! [`Database::seed_demo_data`] fills an empty database with a small but
+//! realistic set of institutions, users, kitchens, reviews and incidents so the
+//! dashboard has something to show on first run. Every row has a fixed UUID, so
+//! seeding twice inserts nothing new and links to demo rows stay stable.
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::database::error::DatabaseError;
+use crate::database::model::ConfidenceLevel;
+use crate::database::model::Incident;
+use crate::database::model::IncidentSeverity;
+use crate::database::model::IncidentSource;
+use crate::database::model::IncidentStatus;
+use crate::database::model::IncidentType;
+use crate::database::model::Institution;
+use crate::database::model::Kitchen;
+use crate::database::model::KitchenType;
+use crate::database::model::Review;
+use crate::database::model::User;
+use crate::database::model::UserRole;
+use crate::database::table::Table;
+
+/// Unique code of the seeded admin account.
+pub const DEMO_ADMIN_CODE: &str = "DEMO-ADMIN";
+
+/// Password of every seeded account.
+pub const DEMO_PASSWORD: &str = "demo-password";
+
+/// Rows inserted by [`Database::seed_demo_data`], per table.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SeedSummary {
+    pub institutions: usize,
+    pub users: usize,
+    pub kitchens: usize,
+    pub reviews: usize,
+    pub incidents: usize,
+}
+
+/// Builds the fixed UUID of the `n`th demo row of `table`, e.g.
+/// `00000000-0000-4000-8000-000300000002` for the second kitchen.
+fn demo_id(table: u16, n: u16) -> Uuid {
+    Uuid::from_u128(0x4000_8000_0000_0000_0000 | (u128::from(table) << 32) | u128::from(n))
+}
+
+const INSTITUTION: u16 = 1;
+const USER: u16 = 2;
+const KITCHEN: u16 = 3;
+const REVIEW: u16 = 4;
+const INCIDENT: u16 = 5;
+
+struct DemoKitchen {
+    name: &'static str,
+    city: &'static str,
+    province: &'static str,
+    r#type: KitchenType,
+    meals_served: i32,
+    lat: f64,
+    lng: f64,
+}
+
+const KITCHENS: [DemoKitchen; 4] = [
+    DemoKitchen {
+        name: "SPPG Menteng",
+        city: "Jakarta Pusat",
+        province: "DKI Jakarta",
+        r#type: KitchenType::CentralKitchen,
+        meals_served: 3200,
+        lat: -6.1959,
+        lng: 106.8312,
+    },
+    DemoKitchen {
+        name: "SPPG Cakung",
+        city: "Jakarta Timur",
+        province: "DKI Jakarta",
+        r#type: KitchenType::RegionalKitchen,
+        meals_served: 2500,
+        lat: -6.1823,
+        lng: 106.9481,
+    },
+    DemoKitchen {
+        name: "SPPG Coblong",
+        city: "Bandung",
+        province: "Jawa Barat",
+        r#type: KitchenType::CentralKitchen,
+        meals_served: 2800,
+        lat: -6.8915,
+        lng: 107.6107,
+    },
+    DemoKitchen {
+        name: "SPPG Gubeng",
+        city: "Surabaya",
+        province: "Jawa Timur",
+        r#type: KitchenType::SatelliteKitchen,
+        meals_served: 1200,
+        lat: -7.2655,
+        lng: 112.7521,
+    },
+];
+
+impl Database {
+    /// Inserts the demo data set, skipping rows that already exist.
+    ///
+    /// Every seeded account uses `password_hash`, the hash of
+    /// [`DEMO_PASSWORD`]; it's passed in so this module doesn't depend on the
+    /// configured hashing parameters. Never run this against production.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any insert fails.
+    pub async fn seed_demo_data(&self, password_hash: &str) -> Result<SeedSummary, DatabaseError> {
+        let mut summary = SeedSummary::default();
+        let now = Utc::now();
+
+        let institutions = [
+            (
+                "SD Negeri 01 Menteng",
+                "school",
+                "Jakarta Pusat",
+                "DKI Jakarta",
+            ),
+            ("SMP Negeri 5 Bandung", "school", "Bandung", "Jawa Barat"),
+            (
+                "Dinas Kesehatan DKI Jakarta",
+                "government",
+                "Jakarta Pusat",
+                "DKI Jakarta",
+            ),
+        ];
+        for (n, (name, r#type, city, province)) in (1..).zip(institutions) {
+            let institution = Institution {
+                id: demo_id(INSTITUTION, n),
+                name: name.to_string(),
+                r#type: r#type.to_string(),
+                city: Some(city.to_string()),
+                province: Some(province.to_string()),
+                verified: Some(true),
+                ..Default::default()
+            };
+            if self.institution_table.insert_with_id(&institution).await? {
+                summary.institutions += 1;
+            }
+        }
+
+        let users = [
+            (DEMO_ADMIN_CODE, "Demo Admin", UserRole::Admin, None),
+            ("DEMO-SCHOOL", "Ibu Sari", UserRole::School, Some(1)),
+            ("DEMO-KITCHEN", "Pak Budi", UserRole::Kitchen, None),
+            ("DEMO-SUPPLIER", "CV Sayur Segar", UserRole::Supplier, None),
+        ];
+        for (n, (code, name, role, institution)) in (1..).zip(users) {
+            let user = User {
+                id: demo_id(USER, n),
+                name: name.to_string(),
+                role,
+                unique_code: code.to_string(),
+                verified: Some(true),
+                institution_id: institution.map(|i| demo_id(INSTITUTION, i)),
+                institution_name: institution.map(|i| institutions[usize::from(i) - 1].0.into()),
+                password_hash: Some(password_hash.to_string()),
+                ..Default::default()
+            };
+            if self.user_table.insert_with_id(&user).await? {
+                summary.users += 1;
+            }
+        }
+
+        for (n, k) in (1..).zip(&KITCHENS) {
+            let kitchen = Kitchen {
+                id: demo_id(KITCHEN, n),
+                name: k.name.to_string(),
+                address: Some(format!("Jl. Demo No. {}, {}", n, k.city)),
+                city: Some(k.city.to_string()),
+                province: Some(k.province.to_string()),
+                r#type: Some(k.r#type),
+                meals_served: Some(k.meals_served),
+                certifications: Some(Json(vec![
+                    "HACCP Certified".to_string(),
+                    "Halal MUI".to_string(),
+                ])),
+                // The demo kitchen user manages the first kitchen
+                owner_id: (n == 1).then(|| demo_id(USER, 3)),
+                latitude: Some(k.lat),
+                longitude: Some(k.lng),
+                contact_phone: Some(format!("021-555-010{}", n)),
+                operating_hours: Some("Mon-Sat 05:00-14:00".to_string()),
+                capacity: Some(k.meals_served + 500),
+                ..Default::default()
+            };
+            if self.kitchen_table.insert_with_id(&kitchen).await? {
+                summary.kitchens += 1;
+            }
+        }
+
+        // Ratings per kitchen: taste, hygiene, freshness, temperature,
+        // packaging, handling
+        let reviews: [(u16, [i64; 6], &str); 6] = [
+            (
+                1,
+                [45, 48, 46, 47, 44, 48],
+                "Makanan hangat dan porsinya pas untuk anak-anak.",
+            ),
+            (
+                1,
+                [42, 45, 44, 40, 43, 46],
+                "Sayurnya segar, tapi nasi sedikit dingin saat tiba.",
+            ),
+            (
+                2,
+                [38, 35, 40, 32, 36, 34],
+                "Pengiriman terlambat dan suhu makanan kurang terjaga.",
+            ),
+            (
+                2,
+                [41, 40, 42, 39, 40, 41],
+                "Menu bervariasi, kebersihan wadah cukup baik.",
+            ),
+            (
+                3,
+                [47, 49, 48, 46, 47, 48],
+                "Sangat rapi dan higienis, anak-anak menyukai lauknya.",
+            ),
+            (
+                4,
+                [30, 28, 33, 31, 35, 29],
+                "Beberapa kotak makanan bocor dan baunya kurang sedap.",
+            ),
+        ];
+        for (n, (kitchen, ratings, comment)) in (1..).zip(reviews) {
+            let [taste, hygiene, freshness, temperature, packaging, handling] =
+                ratings.map(|r| Decimal::new(r, 1));
+            let review = Review {
+                id: demo_id(REVIEW, n),
+                kitchen_id: demo_id(KITCHEN, kitchen),
+                reviewer_id: demo_id(USER, 2),
+                reviewer_name: institutions[0].0.to_string(),
+                reviewer_type: UserRole::School,
+                taste_rating: taste,
+                hygiene_rating: hygiene,
+                freshness_rating: freshness,
+                temperature_rating: temperature,
+                packaging_rating: packaging,
+                handling_rating: handling,
+                comment: comment.to_string(),
+                verification_status: Some(
+                    if n % 2 == 1 { "verified" } else { "unverified" }.into(),
+                ),
+                verified: Some(n % 2 == 1),
+                confidence_level: ConfidenceLevel::Medium,
+                delivery_date: Some(days_ago(now, i64::from(n) * 3).date_naive()),
+                meal_type: Some("lunch".to_string()),
+                ..Default::default()
+            };
+            if self.review_table.insert_with_id(&review).await? {
+                summary.reviews += 1;
+            }
+        }
+
+        let incidents = [
+            Incident {
+                kitchen_id: demo_id(KITCHEN, 2),
+                r#type: IncidentType::Poisoning,
+                source: IncidentSource::Inspector,
+                date: days_ago(now, 20),
+                location: Some("Jakarta Timur".to_string()),
+                province: Some("DKI Jakarta".to_string()),
+                food_type: Some("Nasi ayam".to_string()),
+                affected_count: Some(18),
+                deaths: Some(0),
+                hospitalized: Some(4),
+                cause: Some("Bacterial contamination (Salmonella)".to_string()),
+                severity: IncidentSeverity::Major,
+                status: Some(IncidentStatus::Resolved),
+                resolved_at: Some(days_ago(now, 12)),
+                description: Some("Siswa mengalami mual setelah makan siang.".to_string()),
+                reported_by: Some("Dinas Kesehatan DKI Jakarta".to_string()),
+                source_url: Some("https://dinkes.jakarta.go.id/".to_string()),
+                gps_coordinates: Some(json!({ "lat": -6.1823, "lng": 106.9481 })),
+                ..Default::default()
+            },
+            Incident {
+                kitchen_id: demo_id(KITCHEN, 4),
+                r#type: IncidentType::Sanitation,
+                source: IncidentSource::Consumer,
+                date: days_ago(now, 5),
+                location: Some("Surabaya".to_string()),
+                province: Some("Jawa Timur".to_string()),
+                affected_count: Some(0),
+                deaths: Some(0),
+                cause: Some("Kemasan rusak saat pengiriman".to_string()),
+                severity: IncidentSeverity::Minor,
+                status: Some(IncidentStatus::Investigating),
+                description: Some("Kotak makanan bocor di beberapa kelas.".to_string()),
+                gps_coordinates: Some(json!({ "lat": -7.2655, "lng": 112.7521 })),
+                ..Default::default()
+            },
+        ];
+        for (n, incident) in (1..).zip(incidents) {
+            let incident = Incident {
+                id: demo_id(INCIDENT, n),
+                ..incident
+            };
+            if self.incident_table.insert_with_id(&incident).await? {
+                summary.incidents += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+fn days_ago(now: DateTime<Utc>, days: i64) -> DateTime<Utc> {
+    now - Duration::days(days)
+}
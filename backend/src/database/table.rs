@@ -3,19 +3,126 @@
 //! This module defines the [`Table`] trait and provides a macro for implementing
 //! standard CRUD operations for database tables.
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use futures::StreamExt;
+use futures::stream::BoxStream;
 use rust_decimal::prelude::FromPrimitive;
+use sqlx::PgConnection;
 use sqlx::PgPool;
 use sqlx::Postgres as Db;
+use sqlx::QueryBuilder;
 use sqlx::postgres::PgArguments as Arguments;
+use sqlx::types::Json;
 use uuid::Uuid;
 
+use crate::config::HaccpWeights;
+use crate::config::LeaderboardConfig;
+use crate::database::cursor::Cursor;
 use crate::database::error::DatabaseError;
+use crate::database::error::ResultExt;
 use crate::database::model::*;
 
 type QA<'q, O> = sqlx::query::QueryAs<'q, Db, O, Arguments>;
 type Q<'q> = sqlx::query::Query<'q, Db, Arguments>;
 
+/// Maximum number of bind parameters Postgres accepts in a single statement.
+const MAX_BIND_PARAMS: usize = 65535;
+
+/// SQL expression for the weighted HACCP average of a single review.
+///
+/// `prefix` qualifies the rating columns (e.g. `"r."`). The weighted sum is
+/// divided by the weight total rather than assuming it is exactly 1, so equal
+/// weights give the same result as the plain six-way mean.
+fn weighted_rating_sql(weights: &HaccpWeights, prefix: &str) -> String {
+    let [taste, hygiene, freshness, temperature, packaging, handling] = weights.as_array();
+    format!(
+        "(({p}taste_rating * {taste} + {p}hygiene_rating * {hygiene} + {p}freshness_rating * {freshness} \
+         + {p}temperature_rating * {temperature} + {p}packaging_rating * {packaging} \
+         + {p}handling_rating * {handling}) \
+         / ({taste} + {hygiene} + {freshness} + {temperature} + {packaging} + {handling}))",
+        p = prefix,
+    )
+}
+
+/// Days a kitchen stays active after its latest review or inspection.
+pub const ACTIVE_KITCHEN_DAYS: i32 = 90;
+
+/// SQL condition that kitchen `alias` is active: it had a review or an
+/// inspection in the last [`ACTIVE_KITCHEN_DAYS`] days.
+fn active_kitchen_sql(alias: &str) -> String {
+    format!(
+        "(EXISTS (SELECT 1 FROM reviews ar WHERE ar.kitchen_id = {a}.id \
+         AND ar.created_at >= CURRENT_TIMESTAMP - INTERVAL '{days} days') \
+         OR EXISTS (SELECT 1 FROM inspections ai WHERE ai.kitchen_id = {a}.id \
+         AND ai.date >= CURRENT_TIMESTAMP - INTERVAL '{days} days'))",
+        a = alias,
+        days = ACTIVE_KITCHEN_DAYS,
+    )
+}
+
+/// SQL condition that kitchen `alias` lists at least one certification.
+///
+/// Uncertified kitchens store `[]`, so a non-null column isn't enough.
+fn certified_kitchen_sql(alias: &str) -> String {
+    format!(
+        "(CASE WHEN jsonb_typeof({a}.certifications) = 'array' \
+         THEN jsonb_array_length({a}.certifications) > 0 ELSE FALSE END)",
+        a = alias,
+    )
+}
+
+/// How a free-text `q` filter is matched.
+///
+/// Single words keep the substring match, which also catches partial words
+/// (e.g. "dapur" in "Dapurku"). Multi-word queries use the `search_vector`
+/// full-text column instead, which matches the words anywhere in the row and
+/// can be ranked.
+enum TextSearch<'a> {
+    Contains(&'a str),
+    FullText(&'a str),
+}
+
+impl<'a> TextSearch<'a> {
+    fn new(query: &'a str) -> Self {
+        let query = query.trim();
+        if query.split_whitespace().count() > 1 {
+            Self::FullText(query)
+        } else {
+            Self::Contains(query)
+        }
+    }
+
+    /// Pushes the `WHERE` condition, matching `column` by substring or
+    /// `vector` by full text.
+    fn push_condition(&self, builder: &mut QueryBuilder<'_, Db>, column: &str, vector: &str) {
+        match self {
+            Self::Contains(q) => builder
+                .push(format!("{} ILIKE ", column))
+                .push_bind(format!("%{}%", q)),
+            Self::FullText(q) => builder
+                .push(format!("{} @@ plainto_tsquery('simple', ", vector))
+                .push_bind(q.to_string())
+                .push(")"),
+        };
+    }
+
+    /// Pushes an `ORDER BY` term ranking full-text matches, best first,
+    /// followed by `", "`. Substring matches aren't ranked, so nothing is
+    /// pushed for them.
+    fn push_rank(&self, builder: &mut QueryBuilder<'_, Db>, vector: &str) {
+        if let Self::FullText(q) = self {
+            builder
+                .push(format!("ts_rank({}, plainto_tsquery('simple', ", vector))
+                .push_bind(q.to_string())
+                .push(")) DESC, ");
+        }
+    }
+}
+
 pub struct BaseTable {
     pub pool: PgPool,
 }
@@ -40,12 +147,46 @@ pub trait TableBase {
 /// Generic trait for CRUD operations on a database table.
 #[async_trait]
 pub trait Table<T, ID>: TableBase {
-    /// Selects all records from the table.
+    /// Selects all records from the table, newest first.
+    ///
+    /// Tables without a `created_at` column are ordered by primary key instead,
+    /// so repeated calls always return rows in the same order.
     async fn select_all(&self) -> Result<Vec<T>, DatabaseError>;
+    /// Selects all records ordered by `column`, with the primary key breaking
+    /// ties.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::InternalError`] if `column` isn't a plain
+    /// lowercase identifier.
+    async fn select_all_ordered_by(
+        &self,
+        column: &str,
+        descending: bool,
+    ) -> Result<Vec<T>, DatabaseError>;
     /// Inserts a new record into the table.
     async fn insert(&self, model: &T) -> Result<ID, DatabaseError>;
+    /// [`insert`](Self::insert) on `conn`, so it can share a transaction with
+    /// other writes.
+    async fn insert_in(&self, conn: &mut PgConnection, model: &T) -> Result<ID, DatabaseError>;
+    /// Inserts many records using multi-row `INSERT` statements.
+    ///
+    /// Rows are sent in chunks that stay under Postgres's bind parameter limit,
+    /// all within a single transaction. Returns the new IDs in input order.
+    async fn insert_many(&self, models: &[T]) -> Result<Vec<ID>, DatabaseError>;
+    /// Inserts a record keeping the model's own ID instead of generating one.
+    ///
+    /// Does nothing if a record with that ID already exists. Returns whether a
+    /// record was inserted.
+    async fn insert_with_id(&self, model: &T) -> Result<bool, DatabaseError>;
     /// Selects a record by its ID.
     async fn select(&self, id: &ID) -> Result<Option<T>, DatabaseError>;
+    /// Whether a record with the given ID exists, without fetching it.
+    async fn exists(&self, id: &ID) -> Result<bool, DatabaseError>;
+    /// Selects all records whose ID is in `ids` with a single `= ANY($1)` query.
+    ///
+    /// Results follow the order of `ids`; unknown IDs are skipped.
+    async fn select_by_ids(&self, ids: &[ID]) -> Result<Vec<T>, DatabaseError>;
     /// Updates an existing record in the table.
     async fn update(&self, model: &T) -> Result<(), DatabaseError>;
     /// Deletes a record by its ID.
@@ -54,6 +195,34 @@ pub trait Table<T, ID>: TableBase {
     async fn replace(&self, model: &T) -> Result<ID, DatabaseError>;
 }
 
+/// Optimistic concurrency control for tables with an `updated_at` column.
+#[async_trait]
+pub trait VersionedTable<T>: TableBase {
+    /// Updates a record only if its `updated_at` still equals `expected_updated_at`
+    /// (compared NULL-safely, so callers can pass the `updated_at` they read).
+    ///
+    /// Bumps `updated_at` to the current time and returns the new value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::Conflict`] if no row matched, i.e. the record was
+    /// modified (or deleted) since the caller read it.
+    async fn update_if_unchanged(
+        &self,
+        model: &T,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> Result<DateTime<Utc>, DatabaseError>;
+
+    /// [`update_if_unchanged`](Self::update_if_unchanged) on `conn`, so it can
+    /// share a transaction with other writes.
+    async fn update_if_unchanged_in(
+        &self,
+        conn: &mut PgConnection,
+        model: &T,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> Result<DateTime<Utc>, DatabaseError>;
+}
+
 // Helper trait to handle binding parameters
 pub trait BindParam<'q> {
     fn bind_param<O>(self, query: QA<'q, O>) -> QA<'q, O>;
@@ -73,6 +242,50 @@ where
     }
 }
 
+/// Whether `create_sql` declares a column named `column`.
+const fn declares_column(create_sql: &str, column: &str) -> bool {
+    let sql = create_sql.as_bytes();
+    let name = column.as_bytes();
+    let mut i = 0;
+    while i + name.len() <= sql.len() {
+        let mut j = 0;
+        while j < name.len() && sql[i + j] == name[j] {
+            j += 1;
+        }
+        if j == name.len()
+            && (i == 0 || !is_ident(sql[i - 1]))
+            && (i + j == sql.len() || !is_ident(sql[i + j]))
+        {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Rejects anything but a plain lowercase column name, since `ORDER BY` can't
+/// take a bind parameter.
+fn check_order_column(column: &str) -> Result<(), DatabaseError> {
+    let valid = column
+        .bytes()
+        .next()
+        .is_some_and(|b| b.is_ascii_lowercase() || b == b'_')
+        && column
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    if valid {
+        Ok(())
+    } else {
+        Err(DatabaseError::InternalError {
+            message: format!("Invalid ORDER BY column: {:?}", column),
+        })
+    }
+}
+
 // Special handling for Option<T> where T implements Encode
 // Actually the generic impl above covers Option<T> because Option<T> implements Encode if T does.
 // But we need to make sure strict types like &str vs String don't conflict.
@@ -97,11 +310,33 @@ macro_rules! impl_table {
         }
 
         impl $struct_name {
+            /// Name of the table, as reported in [`DatabaseError`] context.
+            const TABLE: &'static str = $table;
+            /// Number of columns written by `insert`/`update`.
+            const COLUMN_COUNT: usize = [$( stringify!($field) ),+].len();
+            /// `UPDATE` statement ending in `WHERE <pk>=$<COLUMN_COUNT + 1>`.
+            const UPDATE_SQL: &'static str = concat!("UPDATE ", $table, " SET ", $update_set);
+            /// `select_all` query: newest first when the table has `created_at`,
+            /// with the primary key as a tiebreaker.
+            const SELECT_ALL_SQL: &'static str = if declares_column($create_sql, "created_at") {
+                concat!("SELECT * FROM ", $table, " ORDER BY created_at DESC NULLS LAST, ", stringify!($pk))
+            } else {
+                concat!("SELECT * FROM ", $table, " ORDER BY ", stringify!($pk))
+            };
+
             pub fn new(pool: PgPool) -> Self {
                 Self {
                     base: BaseTable::new(pool),
                 }
             }
+
+            /// Binds the update columns followed by the primary key.
+            fn bind_update<'q>(model: &'q $model, mut query: Q<'q>) -> Q<'q> {
+                $(
+                    query = BindParam::bind_param_q(&model.$field, query);
+                )+
+                BindParam::bind_param_q(&model.$pk, query)
+            }
         }
 
         #[async_trait]
@@ -109,21 +344,21 @@ macro_rules! impl_table {
             async fn create_table(&self) -> Result<(), DatabaseError> {
                 sqlx::query($create_sql)
                     .execute(&self.base.pool)
-                    .await?;
+                    .await.context(Self::TABLE, "create_table")?;
                 Ok(())
             }
 
             async fn drop_table(&self) -> Result<(), DatabaseError> {
                 sqlx::query(concat!("DROP TABLE IF EXISTS ", $table))
                     .execute(&self.base.pool)
-                    .await?;
+                    .await.context(Self::TABLE, "drop_table")?;
                 Ok(())
             }
 
             async fn delete_all(&self) -> Result<(), DatabaseError> {
                 sqlx::query(concat!("DELETE FROM ", $table))
                     .execute(&self.base.pool)
-                    .await?;
+                    .await.context(Self::TABLE, "delete_all")?;
                 Ok(())
             }
         }
@@ -131,9 +366,25 @@ macro_rules! impl_table {
         #[async_trait]
         impl Table<$model, $id_type> for $struct_name {
             async fn select_all(&self) -> Result<Vec<$model>, DatabaseError> {
-                Ok(sqlx::query_as::<_, $model>(concat!("SELECT * FROM ", $table))
+                Ok(sqlx::query_as::<_, $model>(Self::SELECT_ALL_SQL)
+                    .fetch_all(&self.base.pool)
+                    .await.context(Self::TABLE, "select_all")?)
+            }
+
+            async fn select_all_ordered_by(
+                &self,
+                column: &str,
+                descending: bool,
+            ) -> Result<Vec<$model>, DatabaseError> {
+                check_order_column(column)?;
+                let sql = format!(
+                    concat!("SELECT * FROM ", $table, " ORDER BY {} {} NULLS LAST, ", stringify!($pk)),
+                    column,
+                    if descending { "DESC" } else { "ASC" }
+                );
+                Ok(sqlx::query_as::<_, $model>(&sql)
                     .fetch_all(&self.base.pool)
-                    .await?)
+                    .await.context(Self::TABLE, "select_all_ordered_by")?)
             }
 
             async fn select(&self, id: &$id_type) -> Result<Option<$model>, DatabaseError> {
@@ -142,10 +393,36 @@ macro_rules! impl_table {
                 Ok(
                     query
                         .fetch_optional(&self.base.pool)
-                        .await?,
+                        .await.context(Self::TABLE, "select")?,
                 )
             }
 
+            async fn exists(&self, id: &$id_type) -> Result<bool, DatabaseError> {
+                let query = sqlx::query_as::<_, (bool,)>(concat!(
+                    "SELECT EXISTS (SELECT 1 FROM ", $table, " WHERE ", stringify!($pk), " = $1)"
+                ));
+                let query = BindParam::bind_param(id, query);
+                let row = query
+                    .fetch_one(&self.base.pool)
+                    .await
+                    .context(Self::TABLE, "exists")?;
+                Ok(row.0)
+            }
+
+            async fn select_by_ids(&self, ids: &[$id_type]) -> Result<Vec<$model>, DatabaseError> {
+                if ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                Ok(sqlx::query_as::<_, $model>(concat!(
+                        "SELECT * FROM ", $table, " WHERE ", stringify!($pk), " = ANY($1) ",
+                        "ORDER BY array_position($1, ", stringify!($pk), ")"
+                    ))
+                    .bind(ids)
+                    .fetch_all(&self.base.pool)
+                    .await.context(Self::TABLE, "select_by_ids")?)
+            }
+
             async fn insert(&self, model: &$model) -> Result<$id_type, DatabaseError> {
                 let mut query = sqlx::query_as(concat!(
                         "INSERT INTO ", $table, " (", $cols, ") VALUES (", $vals, ") RETURNING ", stringify!($pk)
@@ -155,28 +432,96 @@ macro_rules! impl_table {
                     query = BindParam::bind_param(&model.$field, query);
                 )+
 
-                let row: ($db_id_type,) = query.fetch_one(&self.base.pool).await?;
+                let row: ($db_id_type,) = query
+                    .fetch_one(&self.base.pool)
+                    .await
+                    .context(Self::TABLE, "insert")?;
                 Ok(row.0 as $id_type)
             }
 
-            async fn update(&self, model: &$model) -> Result<(), DatabaseError> {
-                let mut query = sqlx::query(concat!(
-                        "UPDATE ", $table, " SET ", $update_set
+            async fn insert_in(&self, conn: &mut PgConnection, model: &$model) -> Result<$id_type, DatabaseError> {
+                let mut query = sqlx::query_as(concat!(
+                        "INSERT INTO ", $table, " (", $cols, ") VALUES (", $vals, ") RETURNING ", stringify!($pk)
                     ));
 
                 $(
-                    query = BindParam::bind_param_q(&model.$field, query);
+                    query = BindParam::bind_param(&model.$field, query);
                 )+
-                query = BindParam::bind_param_q(&model.$pk, query);
 
-                query.execute(&self.base.pool).await?;
+                let row: ($db_id_type,) = query
+                    .fetch_one(&mut *conn)
+                    .await
+                    .context(Self::TABLE, "insert")?;
+                Ok(row.0 as $id_type)
+            }
+
+            async fn insert_with_id(&self, model: &$model) -> Result<bool, DatabaseError> {
+                let sql = format!(
+                    concat!(
+                        "INSERT INTO ", $table, " (", $cols, ", ", stringify!($pk), ") ",
+                        "VALUES (", $vals, ", ${}) ",
+                        "ON CONFLICT (", stringify!($pk), ") DO NOTHING"
+                    ),
+                    Self::COLUMN_COUNT + 1
+                );
+                let query = Self::bind_update(model, sqlx::query(&sql));
+                let result = query
+                    .execute(&self.base.pool)
+                    .await
+                    .context(Self::TABLE, "insert_with_id")?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn insert_many(&self, models: &[$model]) -> Result<Vec<$id_type>, DatabaseError> {
+                if models.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let chunk_size = (MAX_BIND_PARAMS / Self::COLUMN_COUNT).max(1);
+
+                let mut tx = self.base.pool.begin().await.context(Self::TABLE, "insert_many")?;
+                let mut ids = Vec::with_capacity(models.len());
+
+                for chunk in models.chunks(chunk_size) {
+                    let mut builder = sqlx::QueryBuilder::<Db>::new(concat!(
+                        "INSERT INTO ", $table, " (", $cols, ") "
+                    ));
+                    builder.push_values(chunk, |mut row, model| {
+                        $(
+                            row.push_bind(&model.$field);
+                        )+
+                    });
+                    builder.push(concat!(" RETURNING ", stringify!($pk)));
+
+                    let rows: Vec<($db_id_type,)> =
+                        builder
+                            .build_query_as()
+                            .fetch_all(&mut *tx)
+                            .await
+                            .context(Self::TABLE, "insert_many")?;
+                    ids.extend(rows.into_iter().map(|row| row.0 as $id_type));
+                }
+
+                tx.commit().await.context(Self::TABLE, "insert_many")?;
+                Ok(ids)
+            }
+
+            async fn update(&self, model: &$model) -> Result<(), DatabaseError> {
+                let query = Self::bind_update(model, sqlx::query(Self::UPDATE_SQL));
+                query
+                    .execute(&self.base.pool)
+                    .await
+                    .context(Self::TABLE, "update")?;
                 Ok(())
             }
 
             async fn delete(&self, id: &$id_type) -> Result<(), DatabaseError> {
                 let query = sqlx::query(concat!("DELETE FROM ", $table, " WHERE ", stringify!($pk), " = $1"));
                 let query = BindParam::bind_param_q(id, query);
-                query.execute(&self.base.pool).await?;
+                query
+                    .execute(&self.base.pool)
+                    .await
+                    .context(Self::TABLE, "delete")?;
                 Ok(())
             }
 
@@ -187,6 +532,65 @@ macro_rules! impl_table {
     };
 }
 
+/// Implements [`VersionedTable`] for a table generated by `impl_table!` whose
+/// schema has an `updated_at` column.
+macro_rules! impl_versioned_table {
+    ($struct_name:ident, $model:ty) => {
+        #[async_trait]
+        impl VersionedTable<$model> for $struct_name {
+            async fn update_if_unchanged(
+                &self,
+                model: &$model,
+                expected_updated_at: Option<DateTime<Utc>>,
+            ) -> Result<DateTime<Utc>, DatabaseError> {
+                let mut conn = self
+                    .base
+                    .pool
+                    .acquire()
+                    .await
+                    .context(Self::TABLE, "update_if_unchanged")?;
+                self.update_if_unchanged_in(&mut conn, model, expected_updated_at)
+                    .await
+            }
+
+            async fn update_if_unchanged_in(
+                &self,
+                conn: &mut PgConnection,
+                model: &$model,
+                expected_updated_at: Option<DateTime<Utc>>,
+            ) -> Result<DateTime<Utc>, DatabaseError> {
+                let sql = format!(
+                    "{} AND updated_at IS NOT DISTINCT FROM ${} RETURNING updated_at",
+                    Self::UPDATE_SQL.replacen(
+                        " WHERE ",
+                        ", updated_at = CURRENT_TIMESTAMP WHERE ",
+                        1
+                    ),
+                    Self::COLUMN_COUNT + 2
+                );
+                let query = Self::bind_update(model, sqlx::query(&sql)).bind(expected_updated_at);
+
+                let row = query
+                    .fetch_optional(&mut *conn)
+                    .await
+                    .context(Self::TABLE, "update_if_unchanged")?;
+                match row {
+                    Some(row) => sqlx::Row::try_get(&row, "updated_at")
+                        .context(Self::TABLE, "update_if_unchanged"),
+                    None => Err(DatabaseError::Conflict {
+                        message: concat!(
+                            "Record in ",
+                            stringify!($struct_name),
+                            " was modified or removed since it was read"
+                        )
+                        .into(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
 impl UserTable {
     pub async fn find_by_unique_code(
         &self,
@@ -195,7 +599,7 @@ impl UserTable {
         Ok(sqlx::query_as::<_, User>(
             r#"
             SELECT 
-                id, name, role, unique_code, phone, verified, 
+                id, name, role, unique_code, phone, verified, active,
                 institution_name, institution_id, ktp_photo_hash, last_login, 
                 created_at, updated_at, password_hash
             FROM users 
@@ -206,6 +610,64 @@ impl UserTable {
         .fetch_optional(&self.base.pool)
         .await?)
     }
+
+    /// Replaces only the password hash of a user, leaving other columns as
+    /// they are.
+    pub async fn update_password_hash(
+        &self,
+        id: Uuid,
+        password_hash: &str,
+    ) -> Result<(), DatabaseError> {
+        sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+            .bind(password_hash)
+            .bind(id)
+            .execute(&self.base.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Sets `last_login` to now, returning the stored timestamp.
+    pub async fn record_login(&self, id: Uuid) -> Result<Option<DateTime<Utc>>, DatabaseError> {
+        Ok(sqlx::query_scalar(
+            "UPDATE users SET last_login = CURRENT_TIMESTAMP WHERE id = $1 RETURNING last_login",
+        )
+        .bind(id)
+        .fetch_optional(&self.base.pool)
+        .await?
+        .flatten())
+    }
+
+    /// Marks a user as verified. Returns `false` if no user has `id`.
+    pub async fn set_verified(&self, id: Uuid) -> Result<bool, DatabaseError> {
+        let result = sqlx::query(
+            "UPDATE users SET verified = TRUE, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.base.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Marks a user as deactivated. Returns `false` if no user has `id`.
+    pub async fn deactivate(&self, id: Uuid) -> Result<bool, DatabaseError> {
+        let result = sqlx::query(
+            "UPDATE users SET active = FALSE, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.base.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether the user with `id` exists and has been deactivated.
+    pub async fn is_deactivated(&self, id: Uuid) -> Result<bool, DatabaseError> {
+        Ok(sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND active = FALSE)",
+        )
+        .bind(id)
+        .fetch_one(&self.base.pool)
+        .await?)
+    }
 }
 
 /// Kitchen statistics computed from reviews
@@ -250,27 +712,258 @@ pub struct KitchenWithStats {
     pub province: Option<String>,
     pub r#type: Option<KitchenType>,
     pub meals_served: Option<i32>,
-    pub certifications: Option<serde_json::Value>,
+    pub certifications: Option<Json<Vec<String>>>,
     pub image_url: Option<String>,
-    pub created_at: Option<chrono::NaiveDateTime>,
-    pub updated_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub total_reviews: i64,
     pub average_rating: Option<rust_decimal::Decimal>,
+    /// Distance from the [`GeoRadius`] centre, when filtering by one.
+    pub distance_km: Option<f64>,
+}
+
+/// A circle to filter kitchens by: within `radius_km` of (`lat`, `lng`).
+#[derive(Debug, Clone, Copy)]
+pub struct GeoRadius {
+    pub lat: f64,
+    pub lng: f64,
+    pub radius_km: f64,
+}
+
+/// Column the kitchen list can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KitchenSortColumn {
+    Rating,
+    Name,
+    MealsServed,
+    TotalReviews,
+    CreatedAt,
+}
+
+impl KitchenSortColumn {
+    /// Direction used when the client picks the column but not the order:
+    /// A–Z for names, largest/newest first for everything else.
+    pub fn default_descending(self) -> bool {
+        self != Self::Name
+    }
+}
+
+/// Explicit ordering for [`KitchenTable::list_kitchens_with_stats`].
+#[derive(Debug, Clone, Copy)]
+pub struct KitchenSort {
+    pub column: KitchenSortColumn,
+    pub descending: bool,
+}
+
+impl KitchenSort {
+    /// `ORDER BY` term for this sort. Kitchens without a value (no reviews,
+    /// unknown meal count) come last in either direction.
+    fn sql(self) -> String {
+        let dir = if self.descending { "DESC" } else { "ASC" };
+        match self.column {
+            KitchenSortColumn::Rating => format!("stats.average_rating {} NULLS LAST", dir),
+            KitchenSortColumn::Name => format!("LOWER(k.name) {}", dir),
+            KitchenSortColumn::MealsServed => format!("k.meals_served {} NULLS LAST", dir),
+            KitchenSortColumn::TotalReviews => format!("total_reviews {}", dir),
+            KitchenSortColumn::CreatedAt => format!("k.created_at {} NULLS LAST", dir),
+        }
+    }
+}
+
+/// Filters for [`KitchenTable::list_kitchens_with_stats`]; `None` fields
+/// don't filter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KitchenFilter<'a> {
+    /// Matches the kitchen name (see [`TextSearch`]).
+    pub query: Option<&'a str>,
+    /// Substring of the city or province.
+    pub location: Option<&'a str>,
+    pub kitchen_type: Option<&'a str>,
+    /// Minimum average rating; unrated kitchens count as 0.
+    pub min_rating: Option<f64>,
+    /// Only kitchens with known coordinates inside the circle.
+    pub near: Option<GeoRadius>,
+}
+
+impl<'a> KitchenFilter<'a> {
+    fn search(&self) -> Option<TextSearch<'a>> {
+        self.query
+            .filter(|q| !q.trim().is_empty())
+            .map(TextSearch::new)
+    }
+
+    /// Pushes the `WHERE` clause over `kitchens k` joined with the review
+    /// `stats` subquery.
+    fn push_where(&self, builder: &mut QueryBuilder<'_, Db>) {
+        builder.push(" WHERE 1=1");
+        if let Some(search) = self.search() {
+            builder.push(" AND ");
+            search.push_condition(builder, "k.name", "k.search_vector");
+        }
+        if let Some(location) = self.location {
+            let pattern = format!("%{}%", location);
+            builder
+                .push(" AND (k.city ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR k.province ILIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+        if let Some(kitchen_type) = self.kitchen_type {
+            builder
+                .push(" AND k.type::text = ")
+                .push_bind(kitchen_type.to_string());
+        }
+        if let Some(min_rating) = self.min_rating {
+            builder
+                .push(" AND COALESCE(stats.average_rating, 0) >= ")
+                .push_bind(rust_decimal::Decimal::from_f64(min_rating).unwrap_or_default());
+        }
+        if let Some(near) = self.near {
+            builder.push(" AND k.latitude IS NOT NULL AND k.longitude IS NOT NULL AND ");
+            push_distance_km(builder, near);
+            builder.push(" <= ").push_bind(near.radius_km);
+        }
+    }
+}
+
+/// Pushes the great-circle distance in km between a kitchen and the centre
+/// of `near` (haversine formula).
+fn push_distance_km(builder: &mut QueryBuilder<'_, Db>, near: GeoRadius) {
+    builder
+        .push("(6371.0 * 2 * ASIN(LEAST(1.0, SQRT(POWER(SIN(RADIANS(k.latitude - ")
+        .push_bind(near.lat)
+        .push(") / 2), 2) + COS(RADIANS(")
+        .push_bind(near.lat)
+        .push(")) * COS(RADIANS(k.latitude)) * POWER(SIN(RADIANS(k.longitude - ")
+        .push_bind(near.lng)
+        .push(") / 2), 2)))))");
 }
 
+/// Advisory lock key serializing [`KitchenTable::upsert_by_name_and_city`]
+/// calls, so concurrent imports can't both insert the same kitchen.
+const KITCHEN_IMPORT_LOCK: i64 = 0x6B69_7463_6865_6E73; // "kitchens"
+
 impl KitchenTable {
+    /// Inserts or updates kitchens matched by name and city, compared
+    /// case-insensitively (a missing city matches only a missing city).
+    ///
+    /// A matched kitchen gets the model's name and each of its other fields
+    /// that is set; unset fields are left unchanged. When several kitchens
+    /// match, the oldest is updated. Runs on `conn`, which
+    /// should be a transaction from
+    /// [`Database::transaction_with_retry`](crate::database::Database::transaction_with_retry).
+    /// Returns each kitchen's ID and whether it was created, in input order.
+    pub async fn upsert_by_name_and_city(
+        &self,
+        conn: &mut PgConnection,
+        kitchens: &[Kitchen],
+    ) -> Result<Vec<(Uuid, bool)>, DatabaseError> {
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(KITCHEN_IMPORT_LOCK)
+            .execute(&mut *conn)
+            .await
+            .context(Self::TABLE, "upsert_by_name_and_city")?;
+
+        let mut results = Vec::with_capacity(kitchens.len());
+        for kitchen in kitchens {
+            let updated: Option<(Uuid,)> = sqlx::query_as(
+                r#"
+                UPDATE kitchens SET
+                    name = $1,
+                    address = COALESCE($2, address),
+                    city = COALESCE($3, city),
+                    province = COALESCE($4, province),
+                    type = COALESCE($5, type),
+                    meals_served = COALESCE($6, meals_served),
+                    certifications = COALESCE($7, certifications),
+                    image_url = COALESCE($8, image_url),
+                    latitude = COALESCE($9, latitude),
+                    longitude = COALESCE($10, longitude),
+                    contact_phone = COALESCE($11, contact_phone),
+                    contact_email = COALESCE($12, contact_email),
+                    operating_hours = COALESCE($13, operating_hours),
+                    capacity = COALESCE($14, capacity),
+                    owner_id = COALESCE($15, owner_id)
+                WHERE id = (
+                    SELECT id FROM kitchens
+                    WHERE LOWER(name) = LOWER($1)
+                      AND LOWER(COALESCE(city, '')) = LOWER(COALESCE($3, ''))
+                    ORDER BY created_at, id
+                    LIMIT 1
+                )
+                RETURNING id
+                "#,
+            )
+            .bind(&kitchen.name)
+            .bind(&kitchen.address)
+            .bind(&kitchen.city)
+            .bind(&kitchen.province)
+            .bind(kitchen.r#type)
+            .bind(kitchen.meals_served)
+            .bind(&kitchen.certifications)
+            .bind(&kitchen.image_url)
+            .bind(kitchen.latitude)
+            .bind(kitchen.longitude)
+            .bind(&kitchen.contact_phone)
+            .bind(&kitchen.contact_email)
+            .bind(&kitchen.operating_hours)
+            .bind(kitchen.capacity)
+            .bind(kitchen.owner_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .context(Self::TABLE, "upsert_by_name_and_city")?;
+
+            let result = match updated {
+                Some((id,)) => (id, false),
+                None => {
+                    let (id,): (Uuid,) = sqlx::query_as(
+                        r#"
+                        INSERT INTO kitchens (name, address, city, province, type, meals_served, certifications, image_url, owner_id, latitude, longitude, contact_phone, contact_email, operating_hours, capacity)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(&kitchen.name)
+                    .bind(&kitchen.address)
+                    .bind(&kitchen.city)
+                    .bind(&kitchen.province)
+                    .bind(kitchen.r#type)
+                    .bind(kitchen.meals_served)
+                    .bind(&kitchen.certifications)
+                    .bind(&kitchen.image_url)
+                    .bind(kitchen.owner_id)
+                    .bind(kitchen.latitude)
+                    .bind(kitchen.longitude)
+                    .bind(&kitchen.contact_phone)
+                    .bind(&kitchen.contact_email)
+                    .bind(&kitchen.operating_hours)
+                    .bind(kitchen.capacity)
+                    .fetch_one(&mut *conn)
+                    .await
+                    .context(Self::TABLE, "upsert_by_name_and_city")?;
+                    (id, true)
+                }
+            };
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
     /// Get statistics for a specific kitchen computed from reviews
     pub async fn get_kitchen_stats(
         &self,
         kitchen_id: &Uuid,
+        weights: &HaccpWeights,
     ) -> Result<Option<KitchenStats>, DatabaseError> {
-        let stats = sqlx::query_as::<_, KitchenStats>(
+        let stats = sqlx::query_as::<_, KitchenStats>(&format!(
             r#"
             SELECT 
                 kitchen_id,
                 COUNT(*) as total_reviews,
                 COUNT(*) FILTER (WHERE verified = true) as verified_reviews,
-                AVG((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) as average_rating,
+                AVG({rating}) as average_rating,
                 AVG(taste_rating) as taste_avg,
                 AVG(hygiene_rating) as hygiene_avg,
                 AVG(freshness_rating) as freshness_avg,
@@ -281,7 +974,8 @@ impl KitchenTable {
             WHERE kitchen_id = $1
             GROUP BY kitchen_id
             "#,
-        )
+            rating = weighted_rating_sql(weights, ""),
+        ))
         .bind(kitchen_id)
         .fetch_optional(&self.base.pool)
         .await?;
@@ -289,22 +983,62 @@ impl KitchenTable {
         Ok(stats)
     }
 
+    /// Get review statistics for several kitchens in one query, keyed by kitchen ID.
+    ///
+    /// Kitchens without reviews are absent from the map.
+    pub async fn get_kitchen_stats_for(
+        &self,
+        kitchen_ids: &[Uuid],
+        weights: &HaccpWeights,
+    ) -> Result<HashMap<Uuid, KitchenStats>, DatabaseError> {
+        if kitchen_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let stats = sqlx::query_as::<_, KitchenStats>(&format!(
+            r#"
+            SELECT 
+                kitchen_id,
+                COUNT(*) as total_reviews,
+                COUNT(*) FILTER (WHERE verified = true) as verified_reviews,
+                AVG({rating}) as average_rating,
+                AVG(taste_rating) as taste_avg,
+                AVG(hygiene_rating) as hygiene_avg,
+                AVG(freshness_rating) as freshness_avg,
+                AVG(temperature_rating) as temperature_avg,
+                AVG(packaging_rating) as packaging_avg,
+                AVG(handling_rating) as handling_avg
+            FROM reviews 
+            WHERE kitchen_id = ANY($1)
+            GROUP BY kitchen_id
+            "#,
+            rating = weighted_rating_sql(weights, ""),
+        ))
+        .bind(kitchen_ids)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(stats.into_iter().map(|s| (s.kitchen_id, s)).collect())
+    }
+
     /// Get review distribution (count of reviews per rating bucket)
     pub async fn get_review_distribution(
         &self,
         kitchen_id: &Uuid,
+        weights: &HaccpWeights,
     ) -> Result<Vec<ReviewDistribution>, DatabaseError> {
-        let distribution = sqlx::query_as::<_, ReviewDistribution>(
+        let distribution = sqlx::query_as::<_, ReviewDistribution>(&format!(
             r#"
             SELECT 
-                FLOOR((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) as rating_bucket,
+                FLOOR({rating})::INT as rating_bucket,
                 COUNT(*) as count
             FROM reviews 
             WHERE kitchen_id = $1
             GROUP BY rating_bucket
             ORDER BY rating_bucket DESC
             "#,
-        )
+            rating = weighted_rating_sql(weights, ""),
+        ))
         .bind(kitchen_id)
         .fetch_all(&self.base.pool)
         .await?;
@@ -312,27 +1046,65 @@ impl KitchenTable {
         Ok(distribution)
     }
 
-    /// Get compliance trend for a kitchen over the last N months
+    /// Get a kitchen's compliance trend over the last N months, most recent
+    /// first.
+    ///
+    /// Each month with recorded compliance metrics gets their overall score
+    /// (the mean of the recorded hygiene, portion, nutrition and temperature
+    /// scores), that month's incident count, and its review count and
+    /// average rating. Months without metrics are left out. Months are
+    /// calendar months in Asia/Jakarta.
     pub async fn get_compliance_trend(
         &self,
         kitchen_id: &Uuid,
         months: i32,
+        weights: &HaccpWeights,
     ) -> Result<Vec<ComplianceTrendData>, DatabaseError> {
-        let trend = sqlx::query_as::<_, ComplianceTrendData>(
+        let trend = sqlx::query_as::<_, ComplianceTrendData>(&format!(
             r#"
-            SELECT 
-                TO_CHAR(DATE_TRUNC('month', created_at), 'YYYY-MM') as month,
-                AVG((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) as average_score,
-                0 as incidents,
-                COUNT(*) as reviews,
-                AVG((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) as average_rating
-            FROM reviews 
-            WHERE kitchen_id = $1 
-                AND created_at >= DATE_TRUNC('month', CURRENT_DATE - INTERVAL '1 month' * $2)
-            GROUP BY DATE_TRUNC('month', created_at)
-            ORDER BY month DESC
+            WITH metrics AS (
+                SELECT
+                    DATE_TRUNC('month', created_at AT TIME ZONE 'Asia/Jakarta') AS month_start,
+                    ROUND(AVG(
+                        (COALESCE(hygiene_score, 0) + COALESCE(portion_compliance, 0)
+                            + COALESCE(nutrition_compliance, 0) + COALESCE(temperature_control, 0))
+                        / NULLIF(
+                            (hygiene_score IS NOT NULL)::INT + (portion_compliance IS NOT NULL)::INT
+                                + (nutrition_compliance IS NOT NULL)::INT
+                                + (temperature_control IS NOT NULL)::INT,
+                            0
+                        )
+                    ), 2) AS average_score
+                FROM compliance_metrics
+                WHERE kitchen_id = $1
+                    AND created_at >= DATE_TRUNC(
+                        'month',
+                        (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta') - INTERVAL '1 month' * $2
+                    ) AT TIME ZONE 'Asia/Jakarta'
+                GROUP BY DATE_TRUNC('month', created_at AT TIME ZONE 'Asia/Jakarta')
+            )
+            SELECT
+                TO_CHAR(m.month_start, 'YYYY-MM') AS month,
+                m.average_score,
+                (
+                    SELECT COUNT(*)
+                    FROM incidents i
+                    WHERE i.kitchen_id = $1
+                        AND i.date >= m.month_start AT TIME ZONE 'Asia/Jakarta'
+                        AND i.date < (m.month_start + INTERVAL '1 month') AT TIME ZONE 'Asia/Jakarta'
+                ) AS incidents,
+                COUNT(r.id) AS reviews,
+                AVG({rating}) AS average_rating
+            FROM metrics m
+            LEFT JOIN reviews r ON r.kitchen_id = $1
+                AND r.created_at >= m.month_start AT TIME ZONE 'Asia/Jakarta'
+                AND r.created_at < (m.month_start + INTERVAL '1 month') AT TIME ZONE 'Asia/Jakarta'
+            WHERE m.average_score IS NOT NULL
+            GROUP BY m.month_start, m.average_score
+            ORDER BY m.month_start DESC
             "#,
-        )
+            rating = weighted_rating_sql(weights, "r."),
+        ))
         .bind(kitchen_id)
         .bind(months)
         .fetch_all(&self.base.pool)
@@ -342,82 +1114,42 @@ impl KitchenTable {
     }
 
     /// List kitchens with computed statistics and filtering
+    ///
+    /// With `filter.near`, only kitchens with known coordinates inside the
+    /// circle are returned, nearest first. Without `sort`, search matches are
+    /// ranked by relevance, then average rating.
     pub async fn list_kitchens_with_stats(
         &self,
-        query: Option<&str>,
-        location: Option<&str>,
-        kitchen_type: Option<&str>,
-        min_rating: Option<f64>,
+        filter: &KitchenFilter<'_>,
+        sort: Option<KitchenSort>,
         limit: i64,
         offset: i64,
+        weights: &HaccpWeights,
     ) -> Result<(Vec<KitchenWithStats>, i64), DatabaseError> {
-        // Build the query dynamically
-        let mut conditions: Vec<String> = vec!["1=1".to_string()];
-        let mut param_idx = 1;
-
-        if query.is_some() {
-            conditions.push(format!("k.name ILIKE ${}", param_idx));
-            param_idx += 1;
-        }
-        if location.is_some() {
-            conditions.push(format!(
-                "(k.city ILIKE ${} OR k.province ILIKE ${})",
-                param_idx, param_idx
-            ));
-            param_idx += 1;
-        }
-        if kitchen_type.is_some() {
-            conditions.push(format!("k.type::text = ${}", param_idx));
-            param_idx += 1;
-        }
-        if min_rating.is_some() {
-            conditions.push(format!(
-                "COALESCE(stats.average_rating, 0) >= ${}",
-                param_idx
-            ));
-            param_idx += 1;
-        }
-
-        let where_clause = conditions.join(" AND ");
+        let rating = weighted_rating_sql(weights, "");
 
-        // Count query
-        let count_sql = format!(
+        let mut count_query = QueryBuilder::<Db>::new(format!(
             r#"
-            SELECT COUNT(*) 
+            SELECT COUNT(*)
             FROM kitchens k
             LEFT JOIN (
-                SELECT 
+                SELECT
                     kitchen_id,
-                    AVG((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) as average_rating
+                    AVG({rating}) as average_rating
                 FROM reviews
                 GROUP BY kitchen_id
             ) stats ON k.id = stats.kitchen_id
-            WHERE {}
             "#,
-            where_clause
-        );
-
-        let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
-
-        if let Some(q) = query {
-            count_query = count_query.bind(format!("%{}%", q));
-        }
-        if let Some(loc) = location {
-            count_query = count_query.bind(format!("%{}%", loc));
-        }
-        if let Some(t) = kitchen_type {
-            count_query = count_query.bind(t);
-        }
-        if let Some(r) = min_rating {
-            count_query = count_query.bind(rust_decimal::Decimal::from_f64(r).unwrap_or_default());
-        }
-
-        let total = count_query.fetch_one(&self.base.pool).await?;
+        ));
+        filter.push_where(&mut count_query);
+        let total: i64 = count_query
+            .build_query_scalar()
+            .fetch_one(&self.base.pool)
+            .await?;
 
-        // Data query - use sequential parameter indices
-        let data_sql = format!(
+        let mut data_query = QueryBuilder::<Db>::new(
             r#"
-            SELECT 
+            SELECT
                 k.id,
                 k.name,
                 k.address,
@@ -430,43 +1162,54 @@ impl KitchenTable {
                 k.created_at,
                 k.updated_at,
                 COALESCE(stats.total_reviews, 0) as total_reviews,
-                stats.average_rating
+                stats.average_rating,
+            "#,
+        );
+        match filter.near {
+            Some(near) => push_distance_km(&mut data_query, near),
+            None => {
+                data_query.push("NULL::DOUBLE PRECISION");
+            }
+        }
+        data_query.push(format!(
+            r#" as distance_km
             FROM kitchens k
             LEFT JOIN (
-                SELECT 
+                SELECT
                     kitchen_id,
                     COUNT(*) as total_reviews,
-                    AVG((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) as average_rating
+                    AVG({rating}) as average_rating
                 FROM reviews
                 GROUP BY kitchen_id
             ) stats ON k.id = stats.kitchen_id
-            WHERE {}
-            ORDER BY stats.average_rating DESC NULLS LAST
-            LIMIT ${} OFFSET ${}
             "#,
-            where_clause,
-            param_idx,
-            param_idx + 1
-        );
-
-        let mut data_query = sqlx::query_as::<_, KitchenWithStats>(&data_sql);
-
-        if let Some(q) = query {
-            data_query = data_query.bind(format!("%{}%", q));
-        }
-        if let Some(loc) = location {
-            data_query = data_query.bind(format!("%{}%", loc));
-        }
-        if let Some(t) = kitchen_type {
-            data_query = data_query.bind(t);
-        }
-        if let Some(r) = min_rating {
-            data_query = data_query.bind(rust_decimal::Decimal::from_f64(r).unwrap_or_default());
+        ));
+        filter.push_where(&mut data_query);
+
+        // An explicit sort replaces distance, relevance and rating ordering
+        data_query.push(" ORDER BY ");
+        match sort {
+            Some(sort) => {
+                data_query.push(sort.sql()).push(", k.id");
+            }
+            None => {
+                if filter.near.is_some() {
+                    data_query.push("distance_km ASC, ");
+                }
+                if let Some(search) = filter.search() {
+                    search.push_rank(&mut data_query, "k.search_vector");
+                }
+                data_query.push("stats.average_rating DESC NULLS LAST");
+            }
         }
+        data_query
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
 
         let kitchens = data_query
-            .bind(limit)
-            .bind(offset)
+            .build_query_as::<KitchenWithStats>()
             .fetch_all(&self.base.pool)
             .await?;
 
@@ -487,50 +1230,6 @@ impl KitchenTable {
     }
 }
 
-/// Incident timeline event
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct IncidentTimelineEvent {
-    pub id: Uuid,
-    pub incident_id: Uuid,
-    pub event_date: chrono::NaiveDateTime,
-    pub event_title: String,
-    pub description: Option<String>,
-    pub created_at: Option<chrono::NaiveDateTime>,
-}
-
-/// Laboratory results for an incident
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct IncidentLabResult {
-    pub id: Uuid,
-    pub incident_id: Uuid,
-    pub pathogen: String,
-    pub test_date: chrono::NaiveDate,
-    pub confirmed_by: String,
-    pub created_at: Option<chrono::NaiveDateTime>,
-}
-
-/// Affected institution in an incident
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct AffectedInstitution {
-    pub id: Uuid,
-    pub incident_id: Uuid,
-    pub institution_name: String,
-    pub institution_type: Option<String>,
-    pub affected_count: Option<i32>,
-    pub created_at: Option<chrono::NaiveDateTime>,
-}
-
-/// Corrective action for an incident
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct CorrectiveAction {
-    pub id: Uuid,
-    pub incident_id: Uuid,
-    pub action_description: String,
-    pub completed: Option<bool>,
-    pub completed_date: Option<chrono::NaiveDateTime>,
-    pub created_at: Option<chrono::NaiveDateTime>,
-}
-
 /// Incident with extended details
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct IncidentWithDetails {
@@ -539,7 +1238,7 @@ pub struct IncidentWithDetails {
     pub kitchen_name: Option<String>,
     pub r#type: IncidentType,
     pub source: IncidentSource,
-    pub date: chrono::NaiveDateTime,
+    pub date: chrono::DateTime<chrono::Utc>,
     pub location: Option<String>,
     pub address: Option<String>,
     pub province: Option<String>,
@@ -554,24 +1253,149 @@ pub struct IncidentWithDetails {
     pub description: Option<String>,
     pub reported_by: Option<String>,
     pub source_url: Option<String>,
-    pub resolved_at: Option<chrono::NaiveDateTime>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
     pub map_coordinates: Option<serde_json::Value>,
-    pub created_at: Option<chrono::NaiveDateTime>,
-    pub updated_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Filters for [`IncidentTable::list_incidents`] and
+/// [`IncidentTable::list_after_cursor`]; `None` fields don't filter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IncidentFilter<'a> {
+    /// Matches the description (see [`TextSearch`]).
+    pub query: Option<&'a str>,
+    pub status: Option<&'a str>,
+    /// Substring of the province.
+    pub province: Option<&'a str>,
+    /// Minimum number of affected people; unknown counts as 0.
+    pub min_victims: Option<i32>,
+}
+
+impl<'a> IncidentFilter<'a> {
+    fn search(&self) -> Option<TextSearch<'a>> {
+        self.query
+            .filter(|q| !q.trim().is_empty())
+            .map(TextSearch::new)
+    }
+
+    /// Pushes the `WHERE` clause over `incidents`.
+    fn push_where(&self, builder: &mut QueryBuilder<'_, Db>) {
+        builder.push(" WHERE 1=1");
+        if let Some(search) = self.search() {
+            builder.push(" AND ");
+            search.push_condition(builder, "description", "search_vector");
+        }
+        if let Some(status) = self.status {
+            builder
+                .push(" AND status::text = ")
+                .push_bind(status.to_string());
+        }
+        if let Some(province) = self.province {
+            builder
+                .push(" AND province ILIKE ")
+                .push_bind(format!("%{}%", province));
+        }
+        if let Some(min_victims) = self.min_victims {
+            builder
+                .push(" AND COALESCE(affected_count, 0) >= ")
+                .push_bind(min_victims);
+        }
+    }
 }
 
 impl IncidentTable {
-    /// Get incident with extended details including kitchen name
-    pub async fn get_incident_with_details(
+    /// Deletes an incident after recording it in `audit_logs` as `entry`.
+    /// Its timeline, lab results, affected institutions, corrective actions
+    /// and status history go with it through `ON DELETE CASCADE`.
+    ///
+    /// Runs on `conn`, which should be a transaction from
+    /// [`Database::transaction_with_retry`](crate::database::Database::transaction_with_retry)
+    /// so the audit entry and the deletion land together. Returns the deleted
+    /// incident, or `None` if it doesn't exist.
+    pub async fn delete_with_audit(
         &self,
-        incident_id: &Uuid,
-    ) -> Result<Option<IncidentWithDetails>, DatabaseError> {
-        let incident = sqlx::query_as::<_, IncidentWithDetails>(
+        conn: &mut PgConnection,
+        id: &Uuid,
+        entry: &AuditLog,
+    ) -> Result<Option<Incident>, DatabaseError> {
+        let incident =
+            sqlx::query_as::<_, Incident>("SELECT * FROM incidents WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *conn)
+                .await?;
+        let Some(incident) = incident else {
+            return Ok(None);
+        };
+
+        audit_deletion(conn, &incident, entry).await?;
+        sqlx::query("DELETE FROM incidents WHERE id = $1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .context(Self::TABLE, "delete")?;
+
+        Ok(Some(incident))
+    }
+
+    /// Finds the incident for `kitchen_id` of type `incident_type` whose date
+    /// is closest to `date`, if any lies between `from` and `to` inclusive.
+    pub async fn find_similar(
+        &self,
+        kitchen_id: &Uuid,
+        incident_type: IncidentType,
+        date: DateTime<Utc>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<Incident>, DatabaseError> {
+        let incident = sqlx::query_as::<_, Incident>(
             r#"
-            SELECT 
-                i.id,
-                i.kitchen_id,
-                k.name as kitchen_name,
+            SELECT * FROM incidents
+            WHERE kitchen_id = $1 AND type = $2 AND date BETWEEN $3 AND $4
+            ORDER BY ABS(EXTRACT(EPOCH FROM (date - $5))), created_at
+            LIMIT 1
+            "#,
+        )
+        .bind(kitchen_id)
+        .bind(incident_type)
+        .bind(from)
+        .bind(to)
+        .bind(date)
+        .fetch_optional(&self.base.pool)
+        .await
+        .context(Self::TABLE, "find_similar")?;
+
+        Ok(incident)
+    }
+
+    /// Lists a kitchen's most recent incidents, newest first
+    pub async fn list_by_kitchen(
+        &self,
+        kitchen_id: &Uuid,
+        limit: i64,
+    ) -> Result<Vec<Incident>, DatabaseError> {
+        let incidents = sqlx::query_as::<_, Incident>(
+            "SELECT * FROM incidents WHERE kitchen_id = $1 ORDER BY date DESC LIMIT $2",
+        )
+        .bind(kitchen_id)
+        .bind(limit)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(incidents)
+    }
+
+    /// Get incident with extended details including kitchen name
+    pub async fn get_incident_with_details(
+        &self,
+        incident_id: &Uuid,
+    ) -> Result<Option<IncidentWithDetails>, DatabaseError> {
+        let incident = sqlx::query_as::<_, IncidentWithDetails>(
+            r#"
+            SELECT 
+                i.id,
+                i.kitchen_id,
+                k.name as kitchen_name,
                 i.type,
                 i.source,
                 i.date,
@@ -582,13 +1406,13 @@ impl IncidentTable {
                 i.food_type,
                 i.affected_count,
                 i.deaths,
-                0 as hospitalized,
+                i.hospitalized,
                 i.cause,
                 i.severity,
                 i.status,
                 i.description,
                 i.reported_by,
-                NULL as source_url,
+                i.source_url,
                 CASE WHEN i.status = 'resolved' THEN i.updated_at ELSE NULL END as resolved_at,
                 i.map_coordinates,
                 i.created_at,
@@ -605,38 +1429,17 @@ impl IncidentTable {
         Ok(incident)
     }
 
-    /// Get timeline events for an incident
+    /// Get timeline events for an incident, oldest first
     pub async fn get_timeline_events(
         &self,
         incident_id: &Uuid,
     ) -> Result<Vec<IncidentTimelineEvent>, DatabaseError> {
-        // For now, generate timeline from incident data
-        // In a real implementation, this would query a separate timeline table
         let events = sqlx::query_as::<_, IncidentTimelineEvent>(
             r#"
-            SELECT 
-                uuid_generate_v4() as id,
-                id as incident_id,
-                date as event_date,
-                'Incident reported' as event_title,
-                description,
-                created_at
-            FROM incidents
-            WHERE id = $1
-            UNION ALL
-            SELECT 
-                uuid_generate_v4() as id,
-                id as incident_id,
-                updated_at as event_date,
-                CASE 
-                    WHEN status = 'resolved' THEN 'Incident resolved'
-                    ELSE 'Status updated'
-                END as event_title,
-                NULL as description,
-                updated_at as created_at
-            FROM incidents
-            WHERE id = $1 AND updated_at != date
-            ORDER BY event_date ASC
+            SELECT id, incident_id, event_date, event_title, description, created_at
+            FROM incident_timeline_events
+            WHERE incident_id = $1
+            ORDER BY event_date ASC, created_at ASC
             "#,
         )
         .bind(incident_id)
@@ -646,154 +1449,1115 @@ impl IncidentTable {
         Ok(events)
     }
 
-    /// Get lab results for an incident
-    pub async fn get_lab_results(
-        &self,
-        incident_id: &Uuid,
-    ) -> Result<Option<IncidentLabResult>, DatabaseError> {
-        // Mock lab result based on incident cause
-        let result = sqlx::query_as::<_, IncidentLabResult>(
+    /// Get the most recent lab result for an incident
+    pub async fn get_lab_results(
+        &self,
+        incident_id: &Uuid,
+    ) -> Result<Option<IncidentLabResult>, DatabaseError> {
+        let result = sqlx::query_as::<_, IncidentLabResult>(
+            r#"
+            SELECT id, incident_id, pathogen, test_date, confirmed_by, created_at
+            FROM incident_lab_results
+            WHERE incident_id = $1
+            ORDER BY test_date DESC, created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_optional(&self.base.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Get the names of institutions affected by an incident
+    pub async fn get_affected_institutions(
+        &self,
+        incident_id: &Uuid,
+    ) -> Result<Vec<String>, DatabaseError> {
+        let institutions = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT institution_name
+            FROM incident_affected_institutions
+            WHERE incident_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(institutions)
+    }
+
+    /// Get corrective action descriptions for an incident
+    pub async fn get_corrective_actions(
+        &self,
+        incident_id: &Uuid,
+    ) -> Result<Vec<String>, DatabaseError> {
+        let actions = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT action_description
+            FROM incident_corrective_actions
+            WHERE incident_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(actions)
+    }
+
+    /// Changes an incident's status from `from` to `to` and records the change
+    /// in `incident_status_history`.
+    ///
+    /// Runs on `conn`, which should be a transaction from
+    /// [`Database::transaction_with_retry`](crate::database::Database::transaction_with_retry)
+    /// so both writes land together.
+    ///
+    /// `resolved_at` is stamped when moving to `resolved` and cleared when
+    /// moving away from it. Returns the updated incident, or `None` if it
+    /// doesn't exist or its status is no longer `from`.
+    pub async fn transition_status(
+        &self,
+        conn: &mut PgConnection,
+        id: &Uuid,
+        from: IncidentStatus,
+        to: IncidentStatus,
+        changed_by: Option<Uuid>,
+        note: Option<&str>,
+    ) -> Result<Option<Incident>, DatabaseError> {
+        let incident = sqlx::query_as::<_, Incident>(
+            r#"
+            UPDATE incidents
+            SET status = $3,
+                resolved_at = CASE WHEN $3 = 'resolved'::incident_status_enum
+                    THEN CURRENT_TIMESTAMP ELSE NULL END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND COALESCE(status, 'investigating') = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let Some(incident) = incident else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO incident_status_history (incident_id, from_status, to_status, changed_by, note)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .bind(changed_by)
+        .bind(note)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(Some(incident))
+    }
+
+    /// Gets an incident's status changes, oldest first
+    pub async fn get_status_history(
+        &self,
+        incident_id: &Uuid,
+    ) -> Result<Vec<IncidentStatusChange>, DatabaseError> {
+        let history = sqlx::query_as::<_, IncidentStatusChange>(
+            r#"
+            SELECT *
+            FROM incident_status_history
+            WHERE incident_id = $1
+            ORDER BY changed_at ASC, id
+            "#,
+        )
+        .bind(incident_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// List incidents with filtering
+    ///
+    /// `filter.query` matches the description: multi-word queries use
+    /// full-text search (over description, cause and location) and rank
+    /// results by relevance.
+    pub async fn list_incidents(
+        &self,
+        filter: &IncidentFilter<'_>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Incident>, i64), DatabaseError> {
+        let total = self.count_filtered(filter).await?;
+
+        let mut data_query = QueryBuilder::<Db>::new("SELECT * FROM incidents");
+        filter.push_where(&mut data_query);
+        data_query.push(" ORDER BY ");
+        if let Some(search) = filter.search() {
+            search.push_rank(&mut data_query, "search_vector");
+        }
+        data_query
+            .push("date DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let incidents = data_query
+            .build_query_as::<Incident>()
+            .fetch_all(&self.base.pool)
+            .await?;
+
+        Ok((incidents, total))
+    }
+
+    /// List incidents with filtering using keyset pagination.
+    ///
+    /// Returns up to `limit` incidents ordered by `created_at DESC, id DESC`
+    /// strictly after `cursor` (or from the start if `None`), plus the total
+    /// number of incidents matching the filter. `filter.query` filters like in
+    /// [`IncidentTable::list_incidents`], but results are not ranked since the
+    /// cursor relies on the creation order.
+    pub async fn list_after_cursor(
+        &self,
+        filter: &IncidentFilter<'_>,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<(Vec<Incident>, i64), DatabaseError> {
+        let total = self.count_filtered(filter).await?;
+
+        let mut data_query = QueryBuilder::<Db>::new("SELECT * FROM incidents");
+        filter.push_where(&mut data_query);
+        if let Some(c) = cursor {
+            data_query
+                .push(" AND (created_at, id) < (")
+                .push_bind(c.created_at)
+                .push(", ")
+                .push_bind(c.id)
+                .push(")");
+        }
+        data_query
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(limit);
+
+        let incidents = data_query
+            .build_query_as::<Incident>()
+            .fetch_all(&self.base.pool)
+            .await?;
+
+        Ok((incidents, total))
+    }
+
+    /// Counts the incidents matching `filter`.
+    async fn count_filtered(&self, filter: &IncidentFilter<'_>) -> Result<i64, DatabaseError> {
+        let mut count_query = QueryBuilder::<Db>::new("SELECT COUNT(*) FROM incidents");
+        filter.push_where(&mut count_query);
+        let total = count_query
+            .build_query_scalar()
+            .fetch_one(&self.base.pool)
+            .await?;
+        Ok(total)
+    }
+}
+
+impl ReviewTable {
+    /// Records `review`'s ratings and comment in `review_edit_history` before
+    /// `edited_by` changes them.
+    ///
+    /// Runs on `conn`, which should be the transaction that applies the edit,
+    /// so history is only kept for edits that land.
+    pub async fn record_edit(
+        &self,
+        conn: &mut PgConnection,
+        review: &Review,
+        edited_by: Option<Uuid>,
+    ) -> Result<(), DatabaseError> {
+        sqlx::query(
+            r#"
+            INSERT INTO review_edit_history (
+                review_id, edited_by, taste_rating, hygiene_rating, freshness_rating,
+                temperature_rating, packaging_rating, handling_rating, comment
+            )
+            -- `edited_by` references `users`, so it's only set for users still on record
+            VALUES ($1, (SELECT id FROM users WHERE id = $2), $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(review.id)
+        .bind(edited_by)
+        .bind(review.taste_rating)
+        .bind(review.hygiene_rating)
+        .bind(review.freshness_rating)
+        .bind(review.temperature_rating)
+        .bind(review.packaging_rating)
+        .bind(review.handling_rating)
+        .bind(&review.comment)
+        .execute(&mut *conn)
+        .await
+        .context("review_edit_history", "record_edit")?;
+
+        Ok(())
+    }
+
+    /// Gets a review's prior versions, oldest first
+    pub async fn get_edit_history(
+        &self,
+        review_id: &Uuid,
+    ) -> Result<Vec<ReviewEdit>, DatabaseError> {
+        let history = sqlx::query_as::<_, ReviewEdit>(
+            r#"
+            SELECT *
+            FROM review_edit_history
+            WHERE review_id = $1
+            ORDER BY edited_at ASC, id
+            "#,
+        )
+        .bind(review_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// Returns the newest non-draft review `reviewer_id` wrote for
+    /// `kitchen_id` after `since`, if any.
+    pub async fn find_recent_by_user_kitchen(
+        &self,
+        reviewer_id: &Uuid,
+        kitchen_id: &Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Review>, DatabaseError> {
+        let mut conn = self
+            .base
+            .pool
+            .acquire()
+            .await
+            .context(Self::TABLE, "find_recent_by_user_kitchen")?;
+        self.find_recent_by_user_kitchen_in(&mut conn, reviewer_id, kitchen_id, since)
+            .await
+    }
+
+    /// [`find_recent_by_user_kitchen`](Self::find_recent_by_user_kitchen) on
+    /// `conn`.
+    ///
+    /// Holds a transaction-scoped advisory lock on the reviewer and kitchen
+    /// until `conn`'s transaction ends, so a review inserted in that
+    /// transaction can't race another submission past the cooldown check.
+    /// `conn` should be a transaction from
+    /// [`Database::transaction_with_retry`](crate::database::Database::transaction_with_retry).
+    pub async fn find_recent_by_user_kitchen_in(
+        &self,
+        conn: &mut PgConnection,
+        reviewer_id: &Uuid,
+        kitchen_id: &Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Review>, DatabaseError> {
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::text || $2::text, 0))")
+            .bind(reviewer_id)
+            .bind(kitchen_id)
+            .execute(&mut *conn)
+            .await
+            .context(Self::TABLE, "find_recent_by_user_kitchen")?;
+
+        Ok(sqlx::query_as::<_, Review>(
+            r#"
+            SELECT * FROM reviews
+            WHERE reviewer_id = $1
+              AND kitchen_id = $2
+              AND is_draft IS NOT TRUE
+              AND created_at > $3
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(reviewer_id)
+        .bind(kitchen_id)
+        .bind(since)
+        .fetch_optional(&mut *conn)
+        .await?)
+    }
+
+    /// Lists non-draft reviews newest first using offset pagination.
+    ///
+    /// Optionally restricted to one kitchen and/or verified reviews. Returns up
+    /// to `limit` reviews after skipping `offset`, plus the total number of
+    /// matching reviews.
+    pub async fn list_page(
+        &self,
+        kitchen_id: Option<&Uuid>,
+        verified_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Review>, i64), DatabaseError> {
+        const FILTER: &str = r#"
+            WHERE is_draft IS NOT TRUE
+              AND ($1::UUID IS NULL OR kitchen_id = $1)
+              AND (NOT $2 OR verified = TRUE)
+        "#;
+
+        let total =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM reviews {}", FILTER))
+                .bind(kitchen_id)
+                .bind(verified_only)
+                .fetch_one(&self.base.pool)
+                .await?;
+
+        let reviews = sqlx::query_as::<_, Review>(&format!(
+            "SELECT * FROM reviews {} ORDER BY created_at DESC NULLS LAST, id LIMIT $3 OFFSET $4",
+            FILTER
+        ))
+        .bind(kitchen_id)
+        .bind(verified_only)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok((reviews, total))
+    }
+
+    /// Streams every non-draft review of `kitchen_id`, oldest first.
+    ///
+    /// Reviews are fetched `batch_size` at a time using keyset pagination on
+    /// `(created_at, id)`, so only one batch is held in memory at once.
+    pub fn stream_by_kitchen(
+        &self,
+        kitchen_id: Uuid,
+        batch_size: i64,
+    ) -> BoxStream<'static, Result<Vec<Review>, DatabaseError>> {
+        let pool = self.base.pool.clone();
+        futures::stream::try_unfold(Some(None), move |after| {
+            let pool = pool.clone();
+            async move {
+                let Some(after) = after else {
+                    return Ok(None);
+                };
+                let (after_created_at, after_id): (Option<DateTime<Utc>>, Option<Uuid>) =
+                    match after {
+                        Some((created_at, id)) => (Some(created_at), Some(id)),
+                        None => (None, None),
+                    };
+                let reviews = sqlx::query_as::<_, Review>(
+                    r#"
+                    SELECT * FROM reviews
+                    WHERE kitchen_id = $1
+                      AND is_draft IS NOT TRUE
+                      AND ($2::TIMESTAMPTZ IS NULL
+                           OR (COALESCE(created_at, 'epoch'), id) > ($2, $3))
+                    ORDER BY COALESCE(created_at, 'epoch'), id
+                    LIMIT $4
+                    "#,
+                )
+                .bind(kitchen_id)
+                .bind(after_created_at)
+                .bind(after_id)
+                .bind(batch_size)
+                .fetch_all(&pool)
+                .await?;
+
+                if reviews.is_empty() {
+                    return Ok(None);
+                }
+                let next = (reviews.len() as i64 == batch_size).then(|| {
+                    let last = reviews.last().expect("batch is not empty");
+                    Some((last.created_at.unwrap_or_default(), last.id))
+                });
+                Ok(Some((reviews, next)))
+            }
+        })
+        .boxed()
+    }
+
+    /// Lists `reviewer_id`'s drafts, most recently edited first, plus their
+    /// total number.
+    pub async fn list_drafts(
+        &self,
+        reviewer_id: &Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Review>, i64), DatabaseError> {
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM reviews WHERE reviewer_id = $1 AND is_draft = TRUE",
+        )
+        .bind(reviewer_id)
+        .fetch_one(&self.base.pool)
+        .await?;
+
+        let reviews = sqlx::query_as::<_, Review>(
+            r#"
+            SELECT * FROM reviews
+            WHERE reviewer_id = $1 AND is_draft = TRUE
+            ORDER BY updated_at DESC NULLS LAST, id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(reviewer_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok((reviews, total))
+    }
+
+    /// List non-draft reviews using keyset pagination.
+    ///
+    /// Optionally restricted to one kitchen and/or verified reviews. Returns up to
+    /// `limit` reviews ordered by `created_at DESC, id DESC` strictly after
+    /// `cursor` (or from the start if `None`), plus the total number of matching
+    /// reviews.
+    pub async fn list_after_cursor(
+        &self,
+        kitchen_id: Option<&Uuid>,
+        verified_only: bool,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<(Vec<Review>, i64), DatabaseError> {
+        let mut conditions: Vec<String> = vec!["is_draft IS NOT TRUE".to_string()];
+        let mut param_idx = 1;
+
+        if kitchen_id.is_some() {
+            conditions.push(format!("kitchen_id = ${}", param_idx));
+            param_idx += 1;
+        }
+        if verified_only {
+            conditions.push("verified = TRUE".to_string());
+        }
+
+        let where_clause = conditions.join(" AND ");
+
+        let count_sql = format!(r#"SELECT COUNT(*) FROM reviews WHERE {}"#, where_clause);
+        let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+        if let Some(k) = kitchen_id {
+            count_query = count_query.bind(k);
+        }
+        let total = count_query.fetch_one(&self.base.pool).await?;
+
+        let mut keyset_clause = String::new();
+        if cursor.is_some() {
+            keyset_clause = format!(
+                " AND (created_at, id) < (${}, ${})",
+                param_idx,
+                param_idx + 1
+            );
+            param_idx += 2;
+        }
+
+        let data_sql = format!(
+            r#"
+            SELECT * FROM reviews
+            WHERE {}{}
+            ORDER BY created_at DESC, id DESC
+            LIMIT ${}
+            "#,
+            where_clause, keyset_clause, param_idx
+        );
+
+        let mut data_query = sqlx::query_as::<_, Review>(&data_sql);
+        if let Some(k) = kitchen_id {
+            data_query = data_query.bind(k);
+        }
+        if let Some(c) = cursor {
+            data_query = data_query.bind(c.created_at).bind(c.id);
+        }
+
+        let reviews = data_query.bind(limit).fetch_all(&self.base.pool).await?;
+
+        Ok((reviews, total))
+    }
+}
+
+impl InspectionTable {
+    /// Inserts an inspection and its findings on `conn`.
+    ///
+    /// Each finding's `inspection_id` is set to the new inspection's ID, so
+    /// callers can leave it unset. Run it in
+    /// [`Database::transaction_with_retry`](crate::database::Database::transaction_with_retry)
+    /// so nothing is written if any insert fails.
+    pub async fn insert_with_findings(
+        &self,
+        conn: &mut PgConnection,
+        inspection: &Inspection,
+        findings: &[InspectionFinding],
+    ) -> Result<Uuid, DatabaseError> {
+        let (inspection_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO inspections (kitchen_id, inspector_name, date, overall_score, recommendations, follow_up_status, attachments, next_inspection_date)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(inspection.kitchen_id)
+        .bind(&inspection.inspector_name)
+        .bind(inspection.date)
+        .bind(inspection.overall_score)
+        .bind(&inspection.recommendations)
+        .bind(inspection.follow_up_status)
+        .bind(&inspection.attachments)
+        .bind(inspection.next_inspection_date)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        for finding in findings {
+            sqlx::query(
+                r#"
+                INSERT INTO inspection_findings (inspection_id, category, description, evidence, correction_required, deadline)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(inspection_id)
+            .bind(finding.category)
+            .bind(&finding.description)
+            .bind(&finding.evidence)
+            .bind(finding.correction_required)
+            .bind(finding.deadline)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(inspection_id)
+    }
+
+    /// Lists a kitchen's inspections, most recent first
+    pub async fn list_by_kitchen(
+        &self,
+        kitchen_id: &Uuid,
+    ) -> Result<Vec<Inspection>, DatabaseError> {
+        let inspections = sqlx::query_as::<_, Inspection>(
+            "SELECT * FROM inspections WHERE kitchen_id = $1 ORDER BY date DESC",
+        )
+        .bind(kitchen_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(inspections)
+    }
+}
+
+impl InspectionFindingTable {
+    /// Get findings recorded for an inspection, oldest first
+    pub async fn select_by_inspection(
+        &self,
+        inspection_id: &Uuid,
+    ) -> Result<Vec<InspectionFinding>, DatabaseError> {
+        let findings = sqlx::query_as::<_, InspectionFinding>(
+            "SELECT * FROM inspection_findings WHERE inspection_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(inspection_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(findings)
+    }
+}
+
+impl ComplianceMetricTable {
+    /// Records this month's metrics for a kitchen together with their checklist.
+    ///
+    /// A kitchen has at most one metric row per calendar month (in
+    /// Asia/Jakarta): recording again in the same month overwrites that row
+    /// and replaces its checklist items.
+    /// Runs on `conn`, which should be a transaction from
+    /// [`Database::transaction_with_retry`](crate::database::Database::transaction_with_retry).
+    /// Returns the metric row's ID.
+    pub async fn upsert_with_items(
+        &self,
+        conn: &mut PgConnection,
+        metric: &ComplianceMetric,
+        items: &[ChecklistItem],
+    ) -> Result<Uuid, DatabaseError> {
+        let (metric_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO compliance_metrics (kitchen_id, hygiene_score, portion_compliance, nutrition_compliance, temperature_control, sla_performance, last_inspection_date, trend)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (kitchen_id, (DATE_TRUNC('month', created_at AT TIME ZONE 'Asia/Jakarta'))) DO UPDATE SET
+                hygiene_score = EXCLUDED.hygiene_score,
+                portion_compliance = EXCLUDED.portion_compliance,
+                nutrition_compliance = EXCLUDED.nutrition_compliance,
+                temperature_control = EXCLUDED.temperature_control,
+                sla_performance = EXCLUDED.sla_performance,
+                last_inspection_date = EXCLUDED.last_inspection_date,
+                trend = EXCLUDED.trend
+            RETURNING id
+            "#,
+        )
+        .bind(metric.kitchen_id)
+        .bind(metric.hygiene_score)
+        .bind(metric.portion_compliance)
+        .bind(metric.nutrition_compliance)
+        .bind(metric.temperature_control)
+        .bind(&metric.sla_performance)
+        .bind(metric.last_inspection_date)
+        .bind(metric.trend)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        sqlx::query("DELETE FROM checklist_items WHERE compliance_metric_id = $1")
+            .bind(metric_id)
+            .execute(&mut *conn)
+            .await?;
+
+        for item in items {
+            sqlx::query(
+                r#"
+                INSERT INTO checklist_items (compliance_metric_id, category, item, status, notes)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(metric_id)
+            .bind(&item.category)
+            .bind(&item.item)
+            .bind(item.status)
+            .bind(&item.notes)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(metric_id)
+    }
+
+    /// Get the most recently recorded metric row for a kitchen
+    pub async fn select_latest(
+        &self,
+        kitchen_id: &Uuid,
+    ) -> Result<Option<ComplianceMetric>, DatabaseError> {
+        let metric = sqlx::query_as::<_, ComplianceMetric>(
+            "SELECT * FROM compliance_metrics WHERE kitchen_id = $1 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(kitchen_id)
+        .fetch_optional(&self.base.pool)
+        .await?;
+
+        Ok(metric)
+    }
+
+    /// Get a kitchen's metric row for the calendar month before the current one
+    pub async fn select_previous_month(
+        &self,
+        kitchen_id: &Uuid,
+    ) -> Result<Option<ComplianceMetric>, DatabaseError> {
+        let metric = sqlx::query_as::<_, ComplianceMetric>(
+            r#"
+            SELECT * FROM compliance_metrics
+            WHERE kitchen_id = $1
+                AND DATE_TRUNC('month', created_at AT TIME ZONE 'Asia/Jakarta')
+                    = DATE_TRUNC('month', CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta') - INTERVAL '1 month'
+            "#,
+        )
+        .bind(kitchen_id)
+        .fetch_optional(&self.base.pool)
+        .await?;
+
+        Ok(metric)
+    }
+
+    /// Get a kitchen's metric rows over the last N months, most recent first
+    pub async fn select_recent(
+        &self,
+        kitchen_id: &Uuid,
+        months: i32,
+    ) -> Result<Vec<ComplianceMetric>, DatabaseError> {
+        let metrics = sqlx::query_as::<_, ComplianceMetric>(
+            r#"
+            SELECT * FROM compliance_metrics
+            WHERE kitchen_id = $1
+                AND created_at >= DATE_TRUNC(
+                    'month',
+                    (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta') - INTERVAL '1 month' * $2
+                ) AT TIME ZONE 'Asia/Jakarta'
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(kitchen_id)
+        .bind(months)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(metrics)
+    }
+}
+
+impl ChecklistItemTable {
+    /// Get checklist items recorded for a compliance metric row
+    pub async fn select_by_metric(
+        &self,
+        compliance_metric_id: &Uuid,
+    ) -> Result<Vec<ChecklistItem>, DatabaseError> {
+        let items = sqlx::query_as::<_, ChecklistItem>(
+            "SELECT * FROM checklist_items WHERE compliance_metric_id = $1 ORDER BY category, item",
+        )
+        .bind(compliance_metric_id)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(items)
+    }
+}
+
+/// Filters for [`ComplaintTable::list`]; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ComplaintFilter {
+    pub kitchen_id: Option<Uuid>,
+    pub category: Option<ComplaintCategory>,
+    /// A missing status counts as `pending`.
+    pub status: Option<ComplaintStatus>,
+    pub assigned_to: Option<Uuid>,
+    /// Inclusive lower bound on `reported_at`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `reported_at`.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ComplaintTable {
+    /// Deletes a complaint after recording it in `audit_logs` as `entry`.
+    /// Its evidence and comments go with it through `ON DELETE CASCADE`.
+    ///
+    /// Runs on `conn` like [`IncidentTable::delete_with_audit`]. Returns the
+    /// deleted complaint, or `None` if it doesn't exist.
+    pub async fn delete_with_audit(
+        &self,
+        conn: &mut PgConnection,
+        id: &Uuid,
+        entry: &AuditLog,
+    ) -> Result<Option<Complaint>, DatabaseError> {
+        let complaint =
+            sqlx::query_as::<_, Complaint>("SELECT * FROM complaints WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *conn)
+                .await?;
+        let Some(complaint) = complaint else {
+            return Ok(None);
+        };
+
+        audit_deletion(conn, &complaint, entry).await?;
+        sqlx::query("DELETE FROM complaints WHERE id = $1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .context(Self::TABLE, "delete")?;
+
+        Ok(Some(complaint))
+    }
+
+    /// Lists complaints matching `filter`, nearest SLA deadline first, plus
+    /// the total number of matches.
+    pub async fn list(
+        &self,
+        filter: &ComplaintFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Complaint>, i64), DatabaseError> {
+        const FILTER: &str = r#"
+            WHERE ($1::UUID IS NULL OR kitchen_id = $1)
+                AND ($2::complaint_category_enum IS NULL OR category = $2)
+                AND ($3::complaint_status_enum IS NULL OR COALESCE(status, 'pending') = $3)
+                AND ($4::UUID IS NULL OR assigned_to = $4)
+                AND ($5::TIMESTAMPTZ IS NULL OR reported_at >= $5)
+                AND ($6::TIMESTAMPTZ IS NULL OR reported_at < $6)
+        "#;
+
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM complaints {}", FILTER))
+            .bind(filter.kitchen_id)
+            .bind(filter.category)
+            .bind(filter.status)
+            .bind(filter.assigned_to)
+            .bind(filter.from)
+            .bind(filter.until)
+            .fetch_one(&self.base.pool)
+            .await?;
+
+        let complaints = sqlx::query_as::<_, Complaint>(&format!(
+            "SELECT * FROM complaints {} ORDER BY sla_deadline ASC NULLS LAST, reported_at, id LIMIT $7 OFFSET $8",
+            FILTER
+        ))
+        .bind(filter.kitchen_id)
+        .bind(filter.category)
+        .bind(filter.status)
+        .bind(filter.assigned_to)
+        .bind(filter.from)
+        .bind(filter.until)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok((complaints, total))
+    }
+
+    /// Moves a complaint from status `from` to `to`, optionally setting its
+    /// resolution. A missing status counts as `pending`.
+    ///
+    /// Returns `false` if the complaint doesn't exist or is no longer in `from`,
+    /// e.g. because a concurrent request already moved it.
+    pub async fn transition_status(
+        &self,
+        id: &Uuid,
+        from: ComplaintStatus,
+        to: ComplaintStatus,
+        resolution: Option<&str>,
+    ) -> Result<bool, DatabaseError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE complaints
+            SET status = $3, resolution = COALESCE($4, resolution)
+            WHERE id = $1 AND COALESCE(status, 'pending') = $2
+            "#,
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .bind(resolution)
+        .execute(&self.base.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get unresolved complaints whose SLA deadline is before `now`, most
+    /// overdue first
+    pub async fn select_sla_breached(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Complaint>, DatabaseError> {
+        let complaints = sqlx::query_as::<_, Complaint>(
+            r#"
+            SELECT * FROM complaints
+            WHERE sla_deadline < $1 AND status IS DISTINCT FROM 'resolved'
+            ORDER BY sla_deadline ASC
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok(complaints)
+    }
+}
+
+impl VideoTable {
+    /// Inserts a video unless one with the same `youtube_id` already exists.
+    ///
+    /// Returns the new ID, or `None` if the video is already in the library.
+    pub async fn insert_if_new(&self, video: &Video) -> Result<Option<Uuid>, DatabaseError> {
+        let id = sqlx::query_scalar::<_, Uuid>(
             r#"
-            SELECT 
-                uuid_generate_v4() as id,
-                id as incident_id,
-                COALESCE(cause, 'Unknown pathogen') as pathogen,
-                CURRENT_DATE - INTERVAL '3 days' as test_date,
-                'Balai Laboratorium Kesehatan' as confirmed_by,
-                created_at
-            FROM incidents
-            WHERE id = $1 AND cause IS NOT NULL
+            INSERT INTO videos (youtube_id, title, description, category, duration, upload_date, thumbnail, haccp_relevance)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (youtube_id) DO NOTHING
+            RETURNING id
             "#,
         )
-        .bind(incident_id)
+        .bind(&video.youtube_id)
+        .bind(&video.title)
+        .bind(&video.description)
+        .bind(&video.category)
+        .bind(&video.duration)
+        .bind(video.upload_date)
+        .bind(&video.thumbnail)
+        .bind(&video.haccp_relevance)
         .fetch_optional(&self.base.pool)
         .await?;
 
-        Ok(result)
+        Ok(id)
     }
 
-    /// Get affected institutions for an incident
-    pub async fn get_affected_institutions(
+    /// List videos with optional filtering, newest first, and the total count
+    /// of matching videos
+    ///
+    /// `category` matches exactly (ignoring case); `haccp_relevance` matches any
+    /// video whose relevance text contains it.
+    pub async fn list_filtered(
         &self,
-        _incident_id: &Uuid,
-    ) -> Result<Vec<String>, DatabaseError> {
-        // For now, return empty list - would query a separate table in production
-        let institutions: Vec<String> = vec![];
-        Ok(institutions)
+        category: Option<&str>,
+        haccp_relevance: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Video>, i64), DatabaseError> {
+        const FILTER: &str = r#"
+            WHERE ($1::TEXT IS NULL OR LOWER(category) = LOWER($1))
+                AND ($2::TEXT IS NULL OR haccp_relevance ILIKE '%' || $2 || '%')
+        "#;
+
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM videos {}", FILTER))
+            .bind(category)
+            .bind(haccp_relevance)
+            .fetch_one(&self.base.pool)
+            .await?;
+
+        let videos = sqlx::query_as::<_, Video>(&format!(
+            "SELECT * FROM videos {} ORDER BY upload_date DESC NULLS LAST, created_at DESC LIMIT $3 OFFSET $4",
+            FILTER
+        ))
+        .bind(category)
+        .bind(haccp_relevance)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.base.pool)
+        .await?;
+
+        Ok((videos, total))
     }
+}
 
-    /// Get corrective actions for an incident
-    pub async fn get_corrective_actions(
+impl AlertTable {
+    /// Marks the alerts in `ids` as acknowledged with a single `UPDATE` and
+    /// returns the IDs that were updated.
+    ///
+    /// With `owner_id`, only alerts for kitchens owned by that user are
+    /// touched. Alerts that were already acknowledged are included.
+    pub async fn acknowledge(
         &self,
-        incident_id: &Uuid,
-    ) -> Result<Vec<String>, DatabaseError> {
-        // Generate corrective actions based on incident type and severity
-        let actions = sqlx::query_scalar::<_, String>(
+        ids: &[Uuid],
+        owner_id: Option<Uuid>,
+    ) -> Result<Vec<Uuid>, DatabaseError> {
+        let acknowledged = sqlx::query_scalar::<_, Uuid>(
             r#"
-            SELECT 
-                CASE 
-                    WHEN severity = 'critical' THEN 'Kitchen suspended operations pending investigation'
-                    WHEN severity = 'major' THEN 'Enhanced monitoring implemented'
-                    ELSE 'Standard corrective measures applied'
-                END as action
-            FROM incidents
-            WHERE id = $1
-            UNION ALL
-            SELECT 'Staff retraining on food safety protocols' as action
-            FROM incidents
-            WHERE id = $1 AND severity IN ('major', 'critical')
-            UNION ALL
-            SELECT 'Equipment sanitization and maintenance' as action
-            FROM incidents
-            WHERE id = $1
+            UPDATE alerts
+            SET acknowledged = TRUE
+            WHERE id = ANY($1)
+                AND ($2::UUID IS NULL OR kitchen_id IN (
+                    SELECT id FROM kitchens WHERE owner_id = $2
+                ))
+            RETURNING id
             "#,
         )
-        .bind(incident_id)
+        .bind(ids)
+        .bind(owner_id)
         .fetch_all(&self.base.pool)
         .await?;
 
-        Ok(actions)
+        Ok(acknowledged)
     }
+}
 
-    /// List incidents with filtering
-    pub async fn list_incidents(
+/// Filters for [`AuditLogTable::list`]; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    pub user_id: Option<Uuid>,
+    pub entity_type: Option<String>,
+    /// Matches actions starting with this text, case-insensitively, so
+    /// `PATCH` finds every `PATCH /...` request.
+    pub action: Option<String>,
+    /// Inclusive lower bound on `timestamp`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `timestamp`.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditLogTable {
+    /// Lists audit log entries matching `filter`, newest first, plus the
+    /// total number of matches.
+    pub async fn list(
         &self,
-        status: Option<&str>,
-        province: Option<&str>,
-        min_victims: Option<i32>,
+        filter: &AuditLogFilter,
         limit: i64,
         offset: i64,
-    ) -> Result<(Vec<Incident>, i64), DatabaseError> {
-        let mut conditions: Vec<String> = vec!["1=1".to_string()];
-        let mut param_idx = 1;
-
-        if status.is_some() {
-            conditions.push(format!("status::text = ${}", param_idx));
-            param_idx += 1;
-        }
-        if province.is_some() {
-            conditions.push(format!("province ILIKE ${}", param_idx));
-            param_idx += 1;
-        }
-        if min_victims.is_some() {
-            conditions.push(format!("COALESCE(affected_count, 0) >= ${}", param_idx));
-            param_idx += 1;
-        }
-
-        let where_clause = conditions.join(" AND ");
-
-        // Count query
-        let count_sql = format!(r#"SELECT COUNT(*) FROM incidents WHERE {}"#, where_clause);
+    ) -> Result<(Vec<AuditLog>, i64), DatabaseError> {
+        const FILTER: &str = r#"
+            WHERE ($1::UUID IS NULL OR user_id = $1)
+                AND ($2::TEXT IS NULL OR entity_type = $2)
+                AND ($3::TEXT IS NULL OR action ILIKE REPLACE(REPLACE(REPLACE($3, '\', '\\'), '%', '\%'), '_', '\_') || '%')
+                AND ($4::TIMESTAMPTZ IS NULL OR timestamp >= $4)
+                AND ($5::TIMESTAMPTZ IS NULL OR timestamp < $5)
+        "#;
+
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM audit_logs {}", FILTER))
+            .bind(filter.user_id)
+            .bind(&filter.entity_type)
+            .bind(&filter.action)
+            .bind(filter.from)
+            .bind(filter.until)
+            .fetch_one(&self.base.pool)
+            .await?;
 
-        let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+        let logs = sqlx::query_as::<_, AuditLog>(&format!(
+            "SELECT * FROM audit_logs {} ORDER BY timestamp DESC NULLS LAST, id LIMIT $6 OFFSET $7",
+            FILTER
+        ))
+        .bind(filter.user_id)
+        .bind(&filter.entity_type)
+        .bind(&filter.action)
+        .bind(filter.from)
+        .bind(filter.until)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.base.pool)
+        .await?;
 
-        if let Some(s) = status {
-            count_query = count_query.bind(s);
-        }
-        if let Some(p) = province {
-            count_query = count_query.bind(format!("%{}%", p));
-        }
-        if let Some(mv) = min_victims {
-            count_query = count_query.bind(mv);
-        }
+        Ok((logs, total))
+    }
 
-        let total = count_query.fetch_one(&self.base.pool).await?;
+    /// Deletes entries recorded before `before`, returning how many were
+    /// removed.
+    pub async fn delete_before(&self, before: DateTime<Utc>) -> Result<u64, DatabaseError> {
+        let result = sqlx::query("DELETE FROM audit_logs WHERE timestamp < $1")
+            .bind(before)
+            .execute(&self.base.pool)
+            .await
+            .context(Self::TABLE, "delete")?;
+        Ok(result.rows_affected())
+    }
+}
 
-        // Data query - use sequential parameter indices
-        let data_sql = format!(
+impl NotificationTable {
+    /// Deletes notifications that were viewed or resolved before `before`,
+    /// returning how many were removed. New notifications are never touched,
+    /// however old.
+    pub async fn delete_read_before(&self, before: DateTime<Utc>) -> Result<u64, DatabaseError> {
+        let result = sqlx::query(
             r#"
-            SELECT * FROM incidents
-            WHERE {}
-            ORDER BY date DESC
-            LIMIT ${} OFFSET ${}
+            DELETE FROM notifications
+            WHERE status IN ('viewed', 'resolved')
+                AND COALESCE(updated_at, created_at) < $1
             "#,
-            where_clause,
-            param_idx,
-            param_idx + 1
-        );
-
-        let mut data_query = sqlx::query_as::<_, Incident>(&data_sql);
-
-        if let Some(s) = status {
-            data_query = data_query.bind(s);
-        }
-        if let Some(p) = province {
-            data_query = data_query.bind(format!("%{}%", p));
-        }
-        if let Some(mv) = min_victims {
-            data_query = data_query.bind(mv);
-        }
-
-        let incidents = data_query
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(&self.base.pool)
-            .await?;
-
-        Ok((incidents, total))
+        )
+        .bind(before)
+        .execute(&self.base.pool)
+        .await
+        .context(Self::TABLE, "delete")?;
+        Ok(result.rows_affected())
     }
 }
 
+/// Writes `entry` on `conn` with `row` under `metadata.deleted`, so a row
+/// about to be deleted stays on record once it's gone.
+async fn audit_deletion<T: serde::Serialize>(
+    conn: &mut PgConnection,
+    row: &T,
+    entry: &AuditLog,
+) -> Result<(), DatabaseError> {
+    let deleted = serde_json::to_value(row).map_err(|e| DatabaseError::InternalError {
+        message: format!("Failed to serialize deleted {}: {}", entry.entity_type, e),
+    })?;
+    sqlx::query(
+        r#"
+        INSERT INTO audit_logs (id, user_id, user_name, action, entity_type, entity_id, timestamp, ip_address, metadata)
+        VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, CURRENT_TIMESTAMP), $8, $9)
+        "#,
+    )
+    .bind(entry.id)
+    .bind(entry.user_id)
+    .bind(&entry.user_name)
+    .bind(&entry.action)
+    .bind(&entry.entity_type)
+    .bind(&entry.entity_id)
+    .bind(entry.timestamp)
+    .bind(&entry.ip_address)
+    .bind(serde_json::json!({ "deleted": deleted }))
+    .execute(&mut *conn)
+    .await
+    .context(AuditLogTable::TABLE, "insert")?;
+    Ok(())
+}
+
 /// National statistics
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct NationalStats {
@@ -840,6 +2604,10 @@ pub struct TopKitchen {
     pub name: String,
     pub rating: Option<rust_decimal::Decimal>,
     pub compliance_score: Option<rust_decimal::Decimal>,
+    pub review_count: i64,
+    /// Rating pulled towards the prior mean while a kitchen has few reviews;
+    /// see [`StatsQueries::get_top_kitchens`].
+    pub weighted_score: Option<rust_decimal::Decimal>,
 }
 
 /// Monthly incident trend
@@ -852,6 +2620,20 @@ pub struct IncidentTrend {
     pub top_cause: Option<String>,
 }
 
+/// National statistics for one calendar month
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MonthlyNationalStats {
+    /// `YYYY-MM`, in Asia/Jakarta
+    pub month: String,
+    pub total_reviews: i64,
+    pub verified_reviews: i64,
+    pub average_rating: Option<rust_decimal::Decimal>,
+    pub total_incidents: i64,
+    pub critical_incidents: i64,
+    pub total_victims: i64,
+    pub total_deaths: i64,
+}
+
 /// Statistics query methods
 pub struct StatsQueries {
     pool: PgPool,
@@ -863,16 +2645,23 @@ impl StatsQueries {
     }
 
     /// Get national statistics
-    pub async fn get_national_stats(&self) -> Result<NationalStats, DatabaseError> {
-        let stats = sqlx::query_as::<_, NationalStats>(
+    ///
+    /// Active kitchens had a review or inspection in the last
+    /// [`ACTIVE_KITCHEN_DAYS`] days; certified kitchens list at least one
+    /// certification.
+    pub async fn get_national_stats(
+        &self,
+        weights: &HaccpWeights,
+    ) -> Result<NationalStats, DatabaseError> {
+        let stats = sqlx::query_as::<_, NationalStats>(&format!(
             r#"
             SELECT 
                 (SELECT COUNT(*) FROM kitchens) as total_kitchens,
-                (SELECT COUNT(*) FROM kitchens) as active_kitchens,
-                (SELECT COUNT(*) FROM kitchens WHERE certifications IS NOT NULL) as certified_kitchens,
+                (SELECT COUNT(*) FROM kitchens k WHERE {active}) as active_kitchens,
+                (SELECT COUNT(*) FROM kitchens k WHERE {certified}) as certified_kitchens,
                 (SELECT COUNT(*) FROM reviews) as total_reviews,
                 (SELECT COUNT(*) FROM reviews WHERE verified = true) as verified_reviews,
-                (SELECT AVG((taste_rating + hygiene_rating + freshness_rating + temperature_rating + packaging_rating + handling_rating) / 6) FROM reviews) as average_rating,
+                (SELECT AVG({rating}) FROM reviews) as average_rating,
                 (SELECT COUNT(*) FROM incidents) as total_incidents,
                 (SELECT COUNT(*) FROM incidents WHERE status != 'resolved') as active_incidents,
                 (SELECT COUNT(*) FROM incidents WHERE status = 'resolved') as resolved_incidents,
@@ -880,21 +2669,98 @@ impl StatsQueries {
                 (SELECT COALESCE(SUM(affected_count), 0) FROM incidents) as total_victims,
                 (SELECT COALESCE(SUM(deaths), 0) FROM incidents) as total_deaths
             "#,
-        )
+            rating = weighted_rating_sql(weights, ""),
+            active = active_kitchen_sql("k"),
+            certified = certified_kitchen_sql("k"),
+        ))
         .fetch_one(&self.pool)
         .await?;
 
         Ok(stats)
     }
 
+    /// Get national statistics for each of the last `months` calendar months
+    /// in Asia/Jakarta, the current one included, oldest first
+    ///
+    /// Reviews are grouped by when they were written and incidents by when
+    /// they happened. Months without either are still returned, with zero
+    /// counts. `province` limits both to kitchens in that province.
+    pub async fn get_national_timeseries(
+        &self,
+        months: i32,
+        province: Option<&str>,
+        weights: &HaccpWeights,
+    ) -> Result<Vec<MonthlyNationalStats>, DatabaseError> {
+        let stats = sqlx::query_as::<_, MonthlyNationalStats>(&format!(
+            r#"
+            WITH months AS (
+                SELECT generate_series(
+                    DATE_TRUNC('month', CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta')
+                        - INTERVAL '1 month' * ($1 - 1),
+                    DATE_TRUNC('month', CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta'),
+                    INTERVAL '1 month'
+                ) AS month_start
+            ),
+            review_months AS (
+                SELECT
+                    DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta') AS month_start,
+                    COUNT(*) AS total_reviews,
+                    COUNT(*) FILTER (WHERE r.verified) AS verified_reviews,
+                    AVG({rating}) AS average_rating
+                FROM reviews r
+                JOIN kitchens k ON k.id = r.kitchen_id
+                WHERE r.created_at >= (SELECT MIN(month_start) FROM months) AT TIME ZONE 'Asia/Jakarta'
+                  AND ($2::TEXT IS NULL OR k.province = $2)
+                GROUP BY 1
+            ),
+            incident_months AS (
+                SELECT
+                    DATE_TRUNC('month', i.date AT TIME ZONE 'Asia/Jakarta') AS month_start,
+                    COUNT(*) AS total_incidents,
+                    COUNT(*) FILTER (WHERE i.severity = 'critical') AS critical_incidents,
+                    COALESCE(SUM(i.affected_count), 0) AS total_victims,
+                    COALESCE(SUM(i.deaths), 0) AS total_deaths
+                FROM incidents i
+                JOIN kitchens k ON k.id = i.kitchen_id
+                WHERE i.date >= (SELECT MIN(month_start) FROM months) AT TIME ZONE 'Asia/Jakarta'
+                  AND ($2::TEXT IS NULL OR k.province = $2)
+                GROUP BY 1
+            )
+            SELECT
+                TO_CHAR(m.month_start, 'YYYY-MM') AS month,
+                COALESCE(rm.total_reviews, 0) AS total_reviews,
+                COALESCE(rm.verified_reviews, 0) AS verified_reviews,
+                rm.average_rating,
+                COALESCE(im.total_incidents, 0) AS total_incidents,
+                COALESCE(im.critical_incidents, 0) AS critical_incidents,
+                COALESCE(im.total_victims, 0)::BIGINT AS total_victims,
+                COALESCE(im.total_deaths, 0)::BIGINT AS total_deaths
+            FROM months m
+            LEFT JOIN review_months rm ON rm.month_start = m.month_start
+            LEFT JOIN incident_months im ON im.month_start = m.month_start
+            ORDER BY m.month_start
+            "#,
+            rating = weighted_rating_sql(weights, "r."),
+        ))
+        .bind(months)
+        .bind(province)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stats)
+    }
+
     /// Get province statistics
-    pub async fn get_province_stats(&self) -> Result<Vec<ProvinceStat>, DatabaseError> {
-        let stats = sqlx::query_as::<_, ProvinceStat>(
+    pub async fn get_province_stats(
+        &self,
+        weights: &HaccpWeights,
+    ) -> Result<Vec<ProvinceStat>, DatabaseError> {
+        let stats = sqlx::query_as::<_, ProvinceStat>(&format!(
             r#"
             SELECT 
                 COALESCE(k.province, 'Unknown') as province,
                 COUNT(DISTINCT k.id) as total_kitchens,
-                AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating + r.temperature_rating + r.packaging_rating + r.handling_rating) / 6) as avg_rating,
+                AVG({rating}) as avg_rating,
                 COUNT(DISTINCT i.id) as incidents
             FROM kitchens k
             LEFT JOIN reviews r ON k.id = r.kitchen_id
@@ -902,7 +2768,8 @@ impl StatsQueries {
             GROUP BY k.province
             ORDER BY total_kitchens DESC
             "#,
-        )
+            rating = weighted_rating_sql(weights, "r."),
+        ))
         .fetch_all(&self.pool)
         .await?;
 
@@ -910,18 +2777,22 @@ impl StatsQueries {
     }
 
     /// Get regional statistics
+    ///
+    /// Active and certified kitchens are counted as in
+    /// [`StatsQueries::get_national_stats`].
     pub async fn get_regional_stats(
         &self,
         province: Option<&str>,
         kabupaten: Option<&str>,
+        weights: &HaccpWeights,
     ) -> Result<RegionalStats, DatabaseError> {
         let mut conditions = vec!["1=1"];
 
         if province.is_some() {
-            conditions.push("province = $1");
+            conditions.push("k.province = $1");
         }
         if kabupaten.is_some() {
-            conditions.push("city = $2");
+            conditions.push("k.city = $2");
         }
 
         let where_clause = conditions.join(" AND ");
@@ -930,19 +2801,22 @@ impl StatsQueries {
             r#"
             SELECT 
                 COUNT(DISTINCT k.id) as total_kitchens,
-                COUNT(DISTINCT k.id) as active_kitchens,
-                COUNT(DISTINCT CASE WHEN k.certifications IS NOT NULL THEN k.id END) as certified_kitchens,
+                COUNT(DISTINCT CASE WHEN {active} THEN k.id END) as active_kitchens,
+                COUNT(DISTINCT CASE WHEN {certified} THEN k.id END) as certified_kitchens,
                 COUNT(DISTINCT r.id) as total_reviews,
                 COUNT(DISTINCT i.id) as total_incidents,
                 COUNT(DISTINCT CASE WHEN i.status = 'resolved' THEN i.id END) as resolved_incidents,
                 COUNT(DISTINCT CASE WHEN i.status != 'resolved' THEN i.id END) as active_incidents,
-                AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating + r.temperature_rating + r.packaging_rating + r.handling_rating) / 6) as average_rating
+                AVG({rating}) as average_rating
             FROM kitchens k
             LEFT JOIN reviews r ON k.id = r.kitchen_id
             LEFT JOIN incidents i ON k.id = i.kitchen_id
             WHERE {}
             "#,
-            where_clause
+            where_clause,
+            rating = weighted_rating_sql(weights, "r."),
+            active = active_kitchen_sql("k"),
+            certified = certified_kitchen_sql("k"),
         );
 
         let mut query = sqlx::query_as::<_, RegionalStats>(&sql);
@@ -959,66 +2833,111 @@ impl StatsQueries {
         Ok(stats)
     }
 
-    /// Get top performing kitchens
+    /// Get top performing kitchens, best first.
+    ///
+    /// Kitchens are ranked by the Bayesian average
+    /// `(v / (v + m)) * R + (m / (v + m)) * C` of their `v` reviews averaging
+    /// `R`, where `m` is `leaderboard.min_reviews` and `C` is
+    /// `leaderboard.prior_rating` or, if unset, the mean of all reviews. A
+    /// kitchen with a handful of perfect reviews thus ranks below one with
+    /// hundreds of slightly lower ones. Ties go to the kitchen with more
+    /// reviews, then the higher raw rating, then by name. Kitchens without
+    /// reviews are left out.
     pub async fn get_top_kitchens(
         &self,
         province: Option<&str>,
         kabupaten: Option<&str>,
         limit: i64,
+        offset: i64,
+        weights: &HaccpWeights,
+        leaderboard: &LeaderboardConfig,
     ) -> Result<Vec<TopKitchen>, DatabaseError> {
-        let mut conditions = vec!["1=1"];
-
-        if province.is_some() {
-            conditions.push("k.province = $1");
-        }
-        if kabupaten.is_some() {
-            conditions.push("k.city = $2");
-        }
-
-        let where_clause = conditions.join(" AND ");
-
         let sql = format!(
             r#"
-            SELECT 
-                k.id,
-                k.name,
-                AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating + r.temperature_rating + r.packaging_rating + r.handling_rating) / 6) as rating,
-                AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating + r.temperature_rating + r.packaging_rating + r.handling_rating) / 6) as compliance_score
-            FROM kitchens k
-            LEFT JOIN reviews r ON k.id = r.kitchen_id
-            WHERE {}
-            GROUP BY k.id, k.name
-            HAVING COUNT(r.id) > 0
-            ORDER BY rating DESC NULLS LAST
-            LIMIT $3
+            WITH kitchen_ratings AS (
+                SELECT
+                    k.id,
+                    k.name,
+                    AVG({rating}) AS rating,
+                    COUNT(r.id) AS review_count
+                FROM kitchens k
+                JOIN reviews r ON k.id = r.kitchen_id
+                WHERE ($1::TEXT IS NULL OR k.province = $1)
+                  AND ($2::TEXT IS NULL OR k.city = $2)
+                GROUP BY k.id, k.name
+            ),
+            prior AS (
+                SELECT COALESCE(
+                    $4::FLOAT8::NUMERIC,
+                    (SELECT AVG({rating}) FROM reviews r)
+                ) AS mean
+            )
+            SELECT
+                kr.id,
+                kr.name,
+                kr.rating,
+                kr.rating AS compliance_score,
+                kr.review_count,
+                (kr.review_count * kr.rating + $3::BIGINT * p.mean)
+                    / (kr.review_count + $3::BIGINT) AS weighted_score
+            FROM kitchen_ratings kr
+            CROSS JOIN prior p
+            ORDER BY weighted_score DESC NULLS LAST, kr.review_count DESC,
+                kr.rating DESC, kr.name, kr.id
+            LIMIT $5 OFFSET $6
             "#,
-            where_clause
+            rating = weighted_rating_sql(weights, "r.")
         );
 
-        let mut query = sqlx::query_as::<_, TopKitchen>(&sql);
+        let kitchens = sqlx::query_as::<_, TopKitchen>(&sql)
+            .bind(province)
+            .bind(kabupaten)
+            .bind(i64::from(leaderboard.min_reviews))
+            .bind(leaderboard.prior_rating)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
 
-        if let Some(p) = province {
-            query = query.bind(p);
-        }
-        if let Some(k) = kabupaten {
-            query = query.bind(k);
-        }
+        Ok(kitchens)
+    }
 
-        let kitchens = query.bind(limit).fetch_all(&self.pool).await?;
+    /// Counts the kitchens [`get_top_kitchens`](Self::get_top_kitchens)
+    /// ranks for a region, i.e. those with at least one review.
+    pub async fn count_ranked_kitchens(
+        &self,
+        province: Option<&str>,
+        kabupaten: Option<&str>,
+    ) -> Result<i64, DatabaseError> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(DISTINCT k.id)
+            FROM kitchens k
+            JOIN reviews r ON k.id = r.kitchen_id
+            WHERE ($1::TEXT IS NULL OR k.province = $1)
+              AND ($2::TEXT IS NULL OR k.city = $2)
+            "#,
+        )
+        .bind(province)
+        .bind(kabupaten)
+        .fetch_one(&self.pool)
+        .await?;
 
-        Ok(kitchens)
+        Ok(count)
     }
 
-    /// Get compliance trends
+    /// Get compliance trends, by calendar month in Asia/Jakarta
     pub async fn get_compliance_trends(
         &self,
         province: Option<&str>,
         kabupaten: Option<&str>,
         kitchen_id: Option<Uuid>,
         months: i32,
+        weights: &HaccpWeights,
     ) -> Result<Vec<ComplianceTrendData>, DatabaseError> {
-        let mut conditions =
-            vec!["r.created_at >= DATE_TRUNC('month', CURRENT_DATE - INTERVAL '1 month' * $1)"];
+        let mut conditions = vec![
+            "r.created_at >= DATE_TRUNC('month', (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta') - INTERVAL '1 month' * $1) AT TIME ZONE 'Asia/Jakarta'",
+        ];
 
         if province.is_some() {
             conditions.push("k.province = $2");
@@ -1035,21 +2954,22 @@ impl StatsQueries {
         let sql = format!(
             r#"
             SELECT 
-                TO_CHAR(DATE_TRUNC('month', r.created_at), 'YYYY-MM') as month,
-                AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating + r.temperature_rating + r.packaging_rating + r.handling_rating) / 6) as average_score,
+                TO_CHAR(DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta'), 'YYYY-MM') as month,
+                AVG({rating}) as average_score,
                 COUNT(DISTINCT i.id) as incidents,
                 COUNT(*) as reviews,
-                AVG((r.taste_rating + r.hygiene_rating + r.freshness_rating + r.temperature_rating + r.packaging_rating + r.handling_rating) / 6) as average_rating
+                AVG({rating}) as average_rating
             FROM reviews r
             LEFT JOIN kitchens k ON r.kitchen_id = k.id
             LEFT JOIN incidents i ON k.id = i.kitchen_id 
-                AND i.date >= DATE_TRUNC('month', r.created_at)
-                AND i.date < DATE_TRUNC('month', r.created_at) + INTERVAL '1 month'
+                AND DATE_TRUNC('month', i.date AT TIME ZONE 'Asia/Jakarta')
+                    = DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta')
             WHERE {}
-            GROUP BY DATE_TRUNC('month', r.created_at)
+            GROUP BY DATE_TRUNC('month', r.created_at AT TIME ZONE 'Asia/Jakarta')
             ORDER BY month DESC
             "#,
-            where_clause
+            where_clause,
+            rating = weighted_rating_sql(weights, "r.")
         );
 
         let mut query = sqlx::query_as::<_, ComplianceTrendData>(&sql);
@@ -1079,14 +2999,15 @@ impl StatsQueries {
         Ok(trends)
     }
 
-    /// Get incident trends
+    /// Get incident trends, by calendar month in Asia/Jakarta
     pub async fn get_incident_trends(
         &self,
         province: Option<&str>,
         months: i32,
     ) -> Result<Vec<IncidentTrend>, DatabaseError> {
-        let mut conditions =
-            vec!["date >= DATE_TRUNC('month', CURRENT_DATE - INTERVAL '1 month' * $1)"];
+        let mut conditions = vec![
+            "date >= DATE_TRUNC('month', (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jakarta') - INTERVAL '1 month' * $1) AT TIME ZONE 'Asia/Jakarta'",
+        ];
 
         if province.is_some() {
             conditions.push("province = $2");
@@ -1097,14 +3018,14 @@ impl StatsQueries {
         let sql = format!(
             r#"
             SELECT 
-                TO_CHAR(DATE_TRUNC('month', date), 'YYYY-MM') as month,
+                TO_CHAR(DATE_TRUNC('month', date AT TIME ZONE 'Asia/Jakarta'), 'YYYY-MM') as month,
                 COUNT(*) as total_incidents,
                 COALESCE(SUM(affected_count), 0) as total_victims,
                 COALESCE(SUM(deaths), 0) as deaths,
                 MODE() WITHIN GROUP (ORDER BY cause) as top_cause
             FROM incidents
             WHERE {}
-            GROUP BY DATE_TRUNC('month', date)
+            GROUP BY DATE_TRUNC('month', date AT TIME ZONE 'Asia/Jakarta')
             ORDER BY month DESC
             "#,
             where_clause
@@ -1142,8 +3063,8 @@ impl_table!(
         email VARCHAR(255),
         registration_number VARCHAR(100) UNIQUE,
         verified BOOLEAN DEFAULT FALSE,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "name, type, address, city, province, phone, email, registration_number, verified",
     "$1, $2, $3, $4, $5, $6, $7, $8, $9",
@@ -1175,23 +3096,25 @@ impl_table!(
         unique_code VARCHAR(50) UNIQUE NOT NULL,
         phone VARCHAR(20),
         verified BOOLEAN DEFAULT FALSE,
+        active BOOLEAN DEFAULT TRUE,
         institution_name VARCHAR(255),
         institution_id UUID,
         password_hash VARCHAR(255),
         ktp_photo_hash VARCHAR(255),
-        last_login TIMESTAMP,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        last_login TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
-    "name, role, unique_code, phone, verified, institution_name, institution_id, password_hash, ktp_photo_hash, last_login",
-    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10",
-    "name=$1, role=$2, unique_code=$3, phone=$4, verified=$5, institution_name=$6, institution_id=$7, password_hash=$8, ktp_photo_hash=$9, last_login=$10 WHERE id=$11",
+    "name, role, unique_code, phone, verified, active, institution_name, institution_id, password_hash, ktp_photo_hash, last_login",
+    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11",
+    "name=$1, role=$2, unique_code=$3, phone=$4, verified=$5, active=$6, institution_name=$7, institution_id=$8, password_hash=$9, ktp_photo_hash=$10, last_login=$11 WHERE id=$12",
     [
         name,
         role,
         unique_code,
         phone,
         verified,
+        active,
         institution_name,
         institution_id,
         password_hash,
@@ -1219,12 +3142,18 @@ impl_table!(
         certifications JSONB,
         image_url VARCHAR(500),
         owner_id UUID,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        latitude DOUBLE PRECISION,
+        longitude DOUBLE PRECISION,
+        contact_phone VARCHAR(20),
+        contact_email VARCHAR(255),
+        operating_hours VARCHAR(100),
+        capacity INTEGER,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
-    "name, address, city, province, type, meals_served, certifications, image_url, owner_id",
-    "$1, $2, $3, $4, $5, $6, $7, $8, $9",
-    "name=$1, address=$2, city=$3, province=$4, type=$5, meals_served=$6, certifications=$7, image_url=$8, owner_id=$9 WHERE id=$10",
+    "name, address, city, province, type, meals_served, certifications, image_url, owner_id, latitude, longitude, contact_phone, contact_email, operating_hours, capacity",
+    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15",
+    "name=$1, address=$2, city=$3, province=$4, type=$5, meals_served=$6, certifications=$7, image_url=$8, owner_id=$9, latitude=$10, longitude=$11, contact_phone=$12, contact_email=$13, operating_hours=$14, capacity=$15 WHERE id=$16",
     [
         name,
         address,
@@ -1234,7 +3163,13 @@ impl_table!(
         meals_served,
         certifications,
         image_url,
-        owner_id
+        owner_id,
+        latitude,
+        longitude,
+        contact_phone,
+        contact_email,
+        operating_hours,
+        capacity
     ]
 );
 
@@ -1254,10 +3189,10 @@ impl_table!(
         nutrition_compliance DECIMAL(5, 2),
         temperature_control DECIMAL(5, 2),
         sla_performance JSONB,
-        last_inspection_date TIMESTAMP,
+        last_inspection_date TIMESTAMPTZ,
         trend compliance_trend_enum,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "kitchen_id, hygiene_score, portion_compliance, nutrition_compliance, temperature_control, sla_performance, last_inspection_date, trend",
     "$1, $2, $3, $4, $5, $6, $7, $8",
@@ -1289,7 +3224,7 @@ impl_table!(
         item VARCHAR(255),
         status checklist_status_enum,
         notes TEXT,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "compliance_metric_id, category, item, status, notes",
     "$1, $2, $3, $4, $5",
@@ -1310,7 +3245,7 @@ impl_table!(
         kitchen_id UUID NOT NULL,
         type incident_type_enum NOT NULL,
         source incident_source_enum NOT NULL,
-        date TIMESTAMP NOT NULL,
+        date TIMESTAMPTZ NOT NULL,
         location VARCHAR(255),
         province VARCHAR(100),
         food_type VARCHAR(100),
@@ -1323,12 +3258,15 @@ impl_table!(
         reported_by VARCHAR(255),
         map_coordinates JSONB,
         gps_coordinates JSONB,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        hospitalized INTEGER DEFAULT 0,
+        source_url VARCHAR(2048),
+        resolved_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
-    "kitchen_id, type, source, date, location, province, food_type, affected_count, deaths, cause, severity, status, description, reported_by, map_coordinates, gps_coordinates",
-    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16",
-    "kitchen_id=$1, type=$2, source=$3, date=$4, location=$5, province=$6, food_type=$7, affected_count=$8, deaths=$9, cause=$10, severity=$11, status=$12, description=$13, reported_by=$14, map_coordinates=$15, gps_coordinates=$16 WHERE id=$17",
+    "kitchen_id, type, source, date, location, province, food_type, affected_count, deaths, cause, severity, status, description, reported_by, map_coordinates, gps_coordinates, hospitalized, source_url, resolved_at",
+    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19",
+    "kitchen_id=$1, type=$2, source=$3, date=$4, location=$5, province=$6, food_type=$7, affected_count=$8, deaths=$9, cause=$10, severity=$11, status=$12, description=$13, reported_by=$14, map_coordinates=$15, gps_coordinates=$16, hospitalized=$17, source_url=$18, resolved_at=$19 WHERE id=$20",
     [
         kitchen_id,
         r#type,
@@ -1345,10 +3283,129 @@ impl_table!(
         description,
         reported_by,
         map_coordinates,
-        gps_coordinates
+        gps_coordinates,
+        hospitalized,
+        source_url,
+        resolved_at
+    ]
+);
+
+// IncidentStatusChange Table
+impl_table!(
+    IncidentStatusHistoryTable,
+    IncidentStatusChange,
+    "incident_status_history",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS incident_status_history (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+        from_status incident_status_enum NOT NULL,
+        to_status incident_status_enum NOT NULL,
+        changed_by UUID,
+        note TEXT,
+        changed_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "incident_id, from_status, to_status, changed_by, note",
+    "$1, $2, $3, $4, $5",
+    "incident_id=$1, from_status=$2, to_status=$3, changed_by=$4, note=$5 WHERE id=$6",
+    [incident_id, from_status, to_status, changed_by, note]
+);
+
+// IncidentTimelineEvent Table
+impl_table!(
+    IncidentTimelineEventTable,
+    IncidentTimelineEvent,
+    "incident_timeline_events",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS incident_timeline_events (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+        event_date TIMESTAMPTZ NOT NULL,
+        event_title VARCHAR(255) NOT NULL,
+        description TEXT,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "incident_id, event_date, event_title, description",
+    "$1, $2, $3, $4",
+    "incident_id=$1, event_date=$2, event_title=$3, description=$4 WHERE id=$5",
+    [incident_id, event_date, event_title, description]
+);
+
+// IncidentLabResult Table
+impl_table!(
+    IncidentLabResultTable,
+    IncidentLabResult,
+    "incident_lab_results",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS incident_lab_results (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+        pathogen VARCHAR(255) NOT NULL,
+        test_date DATE NOT NULL,
+        confirmed_by VARCHAR(255) NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "incident_id, pathogen, test_date, confirmed_by",
+    "$1, $2, $3, $4",
+    "incident_id=$1, pathogen=$2, test_date=$3, confirmed_by=$4 WHERE id=$5",
+    [incident_id, pathogen, test_date, confirmed_by]
+);
+
+// AffectedInstitution Table
+impl_table!(
+    AffectedInstitutionTable,
+    AffectedInstitution,
+    "incident_affected_institutions",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS incident_affected_institutions (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+        institution_name VARCHAR(255) NOT NULL,
+        institution_type VARCHAR(50),
+        affected_count INTEGER,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "incident_id, institution_name, institution_type, affected_count",
+    "$1, $2, $3, $4",
+    "incident_id=$1, institution_name=$2, institution_type=$3, affected_count=$4 WHERE id=$5",
+    [
+        incident_id,
+        institution_name,
+        institution_type,
+        affected_count
     ]
 );
 
+// CorrectiveAction Table
+impl_table!(
+    CorrectiveActionTable,
+    CorrectiveAction,
+    "incident_corrective_actions",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS incident_corrective_actions (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+        action_description TEXT NOT NULL,
+        completed BOOLEAN DEFAULT FALSE,
+        completed_date TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "incident_id, action_description, completed, completed_date",
+    "$1, $2, $3, $4",
+    "incident_id=$1, action_description=$2, completed=$3, completed_date=$4 WHERE id=$5",
+    [incident_id, action_description, completed, completed_date]
+);
+
 // Inspection Table
 impl_table!(
     InspectionTable,
@@ -1361,14 +3418,14 @@ impl_table!(
         id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
         kitchen_id UUID NOT NULL,
         inspector_name VARCHAR(255),
-        date TIMESTAMP NOT NULL,
+        date TIMESTAMPTZ NOT NULL,
         overall_score DECIMAL(5, 2),
         recommendations JSONB,
         follow_up_status inspection_follow_up_status_enum,
         attachments JSONB,
-        next_inspection_date TIMESTAMP,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        next_inspection_date TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "kitchen_id, inspector_name, date, overall_score, recommendations, follow_up_status, attachments, next_inspection_date",
     "$1, $2, $3, $4, $5, $6, $7, $8",
@@ -1400,8 +3457,8 @@ impl_table!(
         description TEXT NOT NULL,
         evidence VARCHAR(255),
         correction_required BOOLEAN DEFAULT FALSE,
-        deadline TIMESTAMP,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        deadline TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "inspection_id, category, description, evidence, correction_required, deadline",
     "$1, $2, $3, $4, $5, $6",
@@ -1431,13 +3488,13 @@ impl_table!(
         description TEXT NOT NULL,
         status complaint_status_enum DEFAULT 'pending',
         reported_by VARCHAR(255),
-        reported_at TIMESTAMP NOT NULL,
-        sla_deadline TIMESTAMP,
+        reported_at TIMESTAMPTZ NOT NULL,
+        sla_deadline TIMESTAMPTZ,
         assigned_to UUID,
         resolution TEXT,
         satisfaction_rating INTEGER,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "kitchen_id, category, description, status, reported_by, reported_at, sla_deadline, assigned_to, resolution, satisfaction_rating",
     "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10",
@@ -1468,10 +3525,10 @@ impl_table!(
         id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
         complaint_id UUID NOT NULL,
         url VARCHAR(255) NOT NULL,
-        timestamp TIMESTAMP NOT NULL,
+        timestamp TIMESTAMPTZ NOT NULL,
         metadata_status evidence_metadata_status_enum,
         capture_method evidence_capture_method_enum,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "complaint_id, url, timestamp, metadata_status, capture_method",
     "$1, $2, $3, $4, $5",
@@ -1500,7 +3557,7 @@ impl_table!(
         author_name VARCHAR(255),
         role VARCHAR(50),
         message TEXT NOT NULL,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "complaint_id, author_id, author_name, role, message",
     "$1, $2, $3, $4, $5",
@@ -1531,19 +3588,21 @@ impl_table!(
         comment TEXT NOT NULL,
         photos JSONB,
         verification_status VARCHAR(20) DEFAULT 'unverified',
-        report_source VARCHAR(20) NOT NULL,
-        confidence_level VARCHAR(10) NOT NULL,
+        report_source report_source_enum NOT NULL,
+        confidence_level confidence_level_enum NOT NULL,
         root_causes JSONB,
         evidence JSONB,
         dispute_status VARCHAR(20) DEFAULT 'none',
         verified BOOLEAN DEFAULT FALSE,
         is_draft BOOLEAN DEFAULT FALSE,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        delivery_date DATE,
+        meal_type VARCHAR(20),
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
-    "kitchen_id, reviewer_id, reviewer_name, reviewer_type, taste_rating, hygiene_rating, freshness_rating, temperature_rating, packaging_rating, handling_rating, comment, photos, verification_status, report_source, confidence_level, root_causes, evidence, dispute_status, verified, is_draft",
-    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20",
-    "kitchen_id=$1, reviewer_id=$2, reviewer_name=$3, reviewer_type=$4, taste_rating=$5, hygiene_rating=$6, freshness_rating=$7, temperature_rating=$8, packaging_rating=$9, handling_rating=$10, comment=$11, photos=$12, verification_status=$13, report_source=$14, confidence_level=$15, root_causes=$16, evidence=$17, dispute_status=$18, verified=$19, is_draft=$20 WHERE id=$21",
+    "kitchen_id, reviewer_id, reviewer_name, reviewer_type, taste_rating, hygiene_rating, freshness_rating, temperature_rating, packaging_rating, handling_rating, comment, photos, verification_status, report_source, confidence_level, root_causes, evidence, dispute_status, verified, is_draft, delivery_date, meal_type",
+    "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22",
+    "kitchen_id=$1, reviewer_id=$2, reviewer_name=$3, reviewer_type=$4, taste_rating=$5, hygiene_rating=$6, freshness_rating=$7, temperature_rating=$8, packaging_rating=$9, handling_rating=$10, comment=$11, photos=$12, verification_status=$13, report_source=$14, confidence_level=$15, root_causes=$16, evidence=$17, dispute_status=$18, verified=$19, is_draft=$20, delivery_date=$21, meal_type=$22 WHERE id=$23",
     [
         kitchen_id,
         reviewer_id,
@@ -1564,7 +3623,9 @@ impl_table!(
         evidence,
         dispute_status,
         verified,
-        is_draft
+        is_draft,
+        delivery_date,
+        meal_type
     ]
 );
 
@@ -1579,7 +3640,7 @@ impl_table!(
     r#"CREATE TABLE IF NOT EXISTS review_dispute_history (
         id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
         review_id UUID NOT NULL,
-        timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        timestamp TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
         action VARCHAR(50) NOT NULL,
         by_user_id UUID,
         by_user_code VARCHAR(50),
@@ -1598,6 +3659,43 @@ impl_table!(
     ]
 );
 
+// ReviewEdit Table
+impl_table!(
+    ReviewEditHistoryTable,
+    ReviewEdit,
+    "review_edit_history",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS review_edit_history (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        review_id UUID NOT NULL REFERENCES reviews(id) ON DELETE CASCADE,
+        edited_by UUID,
+        taste_rating DECIMAL(2,1) NOT NULL,
+        hygiene_rating DECIMAL(2,1) NOT NULL,
+        freshness_rating DECIMAL(2,1) NOT NULL,
+        temperature_rating DECIMAL(2,1) NOT NULL,
+        packaging_rating DECIMAL(2,1) NOT NULL,
+        handling_rating DECIMAL(2,1) NOT NULL,
+        comment TEXT NOT NULL,
+        edited_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "review_id, edited_by, taste_rating, hygiene_rating, freshness_rating, temperature_rating, packaging_rating, handling_rating, comment",
+    "$1, $2, $3, $4, $5, $6, $7, $8, $9",
+    "review_id=$1, edited_by=$2, taste_rating=$3, hygiene_rating=$4, freshness_rating=$5, temperature_rating=$6, packaging_rating=$7, handling_rating=$8, comment=$9 WHERE id=$10",
+    [
+        review_id,
+        edited_by,
+        taste_rating,
+        hygiene_rating,
+        freshness_rating,
+        temperature_rating,
+        packaging_rating,
+        handling_rating,
+        comment
+    ]
+);
+
 // PerformanceBadge Table
 impl_table!(
     PerformanceBadgeTable,
@@ -1613,7 +3711,7 @@ impl_table!(
         title VARCHAR(255) NOT NULL,
         description TEXT NOT NULL,
         earned_date DATE NOT NULL,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "kitchen_id, type, title, description, earned_date",
     "$1, $2, $3, $4, $5",
@@ -1636,7 +3734,7 @@ impl_table!(
         action VARCHAR(100) NOT NULL,
         entity_type VARCHAR(100) NOT NULL,
         entity_id VARCHAR(100) NOT NULL,
-        timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        timestamp TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
         ip_address VARCHAR(45),
         metadata JSONB
     )"#,
@@ -1670,9 +3768,9 @@ impl_table!(
         severity alert_severity_enum NOT NULL,
         title VARCHAR(255) NOT NULL,
         message TEXT NOT NULL,
-        timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        timestamp TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
         acknowledged BOOLEAN DEFAULT FALSE,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "kitchen_id, type, severity, title, message, timestamp, acknowledged",
     "$1, $2, $3, $4, $5, $6, $7",
@@ -1708,8 +3806,8 @@ impl_table!(
         status VARCHAR(20) DEFAULT 'new',
         target_role VARCHAR(20) NOT NULL,
         created_by VARCHAR(50) NOT NULL,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "title, description, category, priority, kitchen_code, school_code, review_id, status, target_role, created_by",
     "$1, $2, $3, $4, $5, $6, $7, $8, $9, $10",
@@ -1739,7 +3837,7 @@ impl_table!(
     r#"CREATE TABLE IF NOT EXISTS notification_audit_trail (
         id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
         notification_id UUID NOT NULL,
-        timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        timestamp TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
         action VARCHAR(50) NOT NULL,
         user_code VARCHAR(50) NOT NULL
     )"#,
@@ -1767,7 +3865,7 @@ impl_table!(
         upload_date DATE,
         thumbnail VARCHAR(255),
         haccp_relevance VARCHAR(255),
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
     )"#,
     "youtube_id, title, description, category, duration, upload_date, thumbnail, haccp_relevance",
     "$1, $2, $3, $4, $5, $6, $7, $8",
@@ -1783,3 +3881,82 @@ impl_table!(
         haccp_relevance
     ]
 );
+
+// WebhookDeadLetter Table
+impl_table!(
+    WebhookDeadLetterTable,
+    WebhookDeadLetter,
+    "webhook_dead_letters",
+    id,
+    Uuid,
+    Uuid,
+    r#"CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+        id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+        url TEXT NOT NULL,
+        event VARCHAR(100) NOT NULL,
+        payload JSONB NOT NULL,
+        error TEXT NOT NULL,
+        attempts INTEGER NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "url, event, payload, error, attempts",
+    "$1, $2, $3, $4, $5",
+    "url=$1, event=$2, payload=$3, error=$4, attempts=$5 WHERE id=$6",
+    [url, event, payload, error, attempts]
+);
+
+// FileHash Table
+impl_table!(
+    FileHashTable,
+    FileHash,
+    "file_hashes",
+    hash,
+    String,
+    String,
+    r#"CREATE TABLE IF NOT EXISTS file_hashes (
+        hash VARCHAR(64) PRIMARY KEY,
+        file_id VARCHAR(255) NOT NULL,
+        url TEXT NOT NULL,
+        size BIGINT NOT NULL,
+        mime_type VARCHAR(255) NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    )"#,
+    "file_id, url, size, mime_type",
+    "$1, $2, $3, $4",
+    "file_id=$1, url=$2, size=$3, mime_type=$4 WHERE hash=$5",
+    [file_id, url, size, mime_type]
+);
+
+impl FileHashTable {
+    /// Finds the recorded hash of a stored file.
+    pub async fn select_by_file_id(
+        &self,
+        file_id: &str,
+    ) -> Result<Option<FileHash>, DatabaseError> {
+        Ok(
+            sqlx::query_as::<_, FileHash>("SELECT * FROM file_hashes WHERE file_id = $1 LIMIT 1")
+                .bind(file_id)
+                .fetch_optional(&self.base.pool)
+                .await?,
+        )
+    }
+
+    /// Forgets the hash of a stored file, e.g. after the file is deleted.
+    pub async fn delete_by_file_id(&self, file_id: &str) -> Result<(), DatabaseError> {
+        sqlx::query("DELETE FROM file_hashes WHERE file_id = $1")
+            .bind(file_id)
+            .execute(&self.base.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+impl_versioned_table!(InstitutionTable, Institution);
+impl_versioned_table!(UserTable, User);
+impl_versioned_table!(KitchenTable, Kitchen);
+impl_versioned_table!(ComplianceMetricTable, ComplianceMetric);
+impl_versioned_table!(IncidentTable, Incident);
+impl_versioned_table!(InspectionTable, Inspection);
+impl_versioned_table!(ComplaintTable, Complaint);
+impl_versioned_table!(ReviewTable, Review);
+impl_versioned_table!(NotificationTable, Notification);
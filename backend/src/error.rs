@@ -2,6 +2,8 @@
 //!
 //! This module defines the central [`AppError`] enum used throughout the backend.
 
+use log::debug;
+
 use crate::database::error::DatabaseError;
 
 /// Central error type for the application.
@@ -36,6 +38,21 @@ pub enum AppError {
     #[error("Bad Request: {0}")]
     BadRequest(String),
 
+    /// The request body could not be parsed into the expected type.
+    ///
+    /// Responds with `400` and a `details` object holding the offending
+    /// `field` path and the `expected` type, when known.
+    #[error("Bad Request: {message}")]
+    InvalidBody {
+        message: String,
+        field: Option<String>,
+        expected: Option<String>,
+    },
+
+    /// The request body exceeds the route's size limit.
+    #[error("Payload Too Large: {0}")]
+    PayloadTooLarge(String),
+
     /// The requested resource was not found.
     #[error("Not Found: {0}")]
     NotFound(String),
@@ -44,20 +61,47 @@ pub enum AppError {
     #[error("Service Unavailable: {0}")]
     ServiceUnavailable(String),
 
+    /// The request took too long to complete.
+    #[error("Gateway Timeout: {0}")]
+    GatewayTimeout(String),
+
     /// Too many requests (rate limiting).
     #[error("Too Many Requests: {0}")]
     TooManyRequests(String),
+
+    /// The request conflicts with the current state of the resource.
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
 impl axum::response::IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
+        if let AppError::InvalidBody {
+            message,
+            field,
+            expected,
+        } = self
+        {
+            let body = axum::Json(serde_json::json!({
+                "error": message,
+                "details": {
+                    "field": field,
+                    "expected": expected,
+                },
+            }));
+            return (axum::http::StatusCode::BAD_REQUEST, body).into_response();
+        }
+
         let (status, error_message) = match self {
             AppError::Unauthorized(msg) => (axum::http::StatusCode::UNAUTHORIZED, msg),
             AppError::Forbidden(msg) => (axum::http::StatusCode::FORBIDDEN, msg),
             AppError::BadRequest(msg) => (axum::http::StatusCode::BAD_REQUEST, msg),
             AppError::NotFound(msg) => (axum::http::StatusCode::NOT_FOUND, msg),
+            AppError::PayloadTooLarge(msg) => (axum::http::StatusCode::PAYLOAD_TOO_LARGE, msg),
             AppError::ServiceUnavailable(msg) => (axum::http::StatusCode::SERVICE_UNAVAILABLE, msg),
+            AppError::GatewayTimeout(msg) => (axum::http::StatusCode::GATEWAY_TIMEOUT, msg),
             AppError::TooManyRequests(msg) => (axum::http::StatusCode::TOO_MANY_REQUESTS, msg),
+            AppError::Conflict(msg) => (axum::http::StatusCode::CONFLICT, msg),
             AppError::InternalServerError(msg) => {
                 (axum::http::StatusCode::INTERNAL_SERVER_ERROR, msg)
             }
@@ -77,7 +121,83 @@ impl axum::response::IntoResponse for AppError {
 
 impl From<DatabaseError> for AppError {
     fn from(err: DatabaseError) -> Self {
-        AppError::InternalServerError(err.to_string())
+        match err {
+            DatabaseError::Conflict { message } => AppError::Conflict(message),
+            DatabaseError::UniqueViolation { constraint } => unique_violation_error(&constraint),
+            DatabaseError::BackendError {
+                source: sqlx::Error::Database(e),
+                ..
+            } if e.is_foreign_key_violation() => foreign_key_error(e.message(), e.constraint()),
+            err => AppError::InternalServerError(err.to_string()),
+        }
+    }
+}
+
+/// Unique constraints and indexes, with the message shown when a write
+/// violates them. Constraint names are schema details and never reach the
+/// client.
+const UNIQUE_CONSTRAINTS: &[(&str, &str)] = &[
+    (
+        "users_unique_code_key",
+        "A user with this unique code already exists",
+    ),
+    (
+        "institutions_registration_number_key",
+        "An institution with this registration number already exists",
+    ),
+    ("idx_videos_youtube_id", "This video has already been added"),
+    (
+        "idx_compliance_kitchen_month",
+        "Compliance metrics for this kitchen and month already exist",
+    ),
+];
+
+/// Maps a unique violation (SQLSTATE `23505`) to a `409` whose message names
+/// what already exists, falling back to a generic one for unlisted
+/// constraints.
+fn unique_violation_error(constraint: &str) -> AppError {
+    debug!("Unique constraint {} violated", constraint);
+    let message = UNIQUE_CONSTRAINTS
+        .iter()
+        .find(|(name, _)| *name == constraint)
+        .map(|(_, message)| *message)
+        .unwrap_or("Record already exists");
+    AppError::Conflict(message.to_string())
+}
+
+/// Parent tables as they appear at the end of `FK_<table>_<parent>`
+/// constraint names, with the entity name used in error messages.
+const FOREIGN_KEY_PARENTS: &[(&str, &str)] = &[
+    ("_kitchens", "kitchen"),
+    ("_users", "user"),
+    ("_institutions", "institution"),
+    ("_incidents", "incident"),
+    ("_inspections", "inspection"),
+    ("_complaints", "complaint"),
+    ("_reviews", "review"),
+    ("_notifications", "notification"),
+    ("_compliance_metrics", "compliance metric"),
+];
+
+/// Maps a foreign-key violation (SQLSTATE `23503`) to a client error.
+///
+/// Writes pointing at a missing parent become `400`; deleting a parent that
+/// is still referenced under `ON DELETE RESTRICT` becomes `409`.
+fn foreign_key_error(message: &str, constraint: Option<&str>) -> AppError {
+    let entity = constraint
+        .map(str::to_lowercase)
+        .and_then(|name| {
+            FOREIGN_KEY_PARENTS
+                .iter()
+                .find(|(suffix, _)| name.ends_with(suffix))
+                .map(|(_, entity)| *entity)
+        })
+        .unwrap_or("record");
+
+    if message.starts_with("update or delete") {
+        AppError::Conflict(format!("{} is still referenced by other records", entity))
+    } else {
+        AppError::BadRequest(format!("referenced {} does not exist", entity))
     }
 }
 
@@ -130,6 +250,83 @@ mod tests {
         assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_invalid_body_response_has_details() {
+        let err = AppError::InvalidBody {
+            message: "Invalid value for `ratings.taste`".to_string(),
+            field: Some("ratings.taste".to_string()),
+            expected: Some("f64".to_string()),
+        };
+        let response = err.into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "Invalid value for `ratings.taste`");
+        assert_eq!(json["details"]["field"], "ratings.taste");
+        assert_eq!(json["details"]["expected"], "f64");
+    }
+
+    #[test]
+    fn test_database_conflict_maps_to_409() {
+        let err: AppError = DatabaseError::Conflict {
+            message: "stale".to_string(),
+        }
+        .into();
+        assert!(matches!(err, AppError::Conflict(_)));
+        assert_eq!(
+            err.into_response().status(),
+            axum::http::StatusCode::CONFLICT
+        );
+    }
+
+    #[test]
+    fn test_database_unique_violation_maps_to_409() {
+        let err: AppError = DatabaseError::UniqueViolation {
+            constraint: "users_unique_code_key".to_string(),
+        }
+        .into();
+        assert!(
+            matches!(err, AppError::Conflict(ref m) if m == "A user with this unique code already exists")
+        );
+        assert_eq!(
+            err.into_response().status(),
+            axum::http::StatusCode::CONFLICT
+        );
+
+        // Unlisted constraints don't leak their name
+        let err: AppError = DatabaseError::UniqueViolation {
+            constraint: "file_hashes_pkey".to_string(),
+        }
+        .into();
+        assert!(matches!(err, AppError::Conflict(ref m) if m == "Record already exists"));
+    }
+
+    #[test]
+    fn test_foreign_key_error_names_parent() {
+        let err = foreign_key_error(
+            "insert or update on table \"reviews\" violates foreign key constraint \"fk_reviews_kitchens\"",
+            Some("fk_reviews_kitchens"),
+        );
+        assert!(
+            matches!(err, AppError::BadRequest(ref m) if m == "referenced kitchen does not exist")
+        );
+
+        let err = foreign_key_error(
+            "update or delete on table \"kitchens\" violates foreign key constraint \"fk_incidents_kitchens\" on table \"incidents\"",
+            Some("fk_incidents_kitchens"),
+        );
+        assert!(
+            matches!(err, AppError::Conflict(ref m) if m == "kitchen is still referenced by other records")
+        );
+
+        let err = foreign_key_error("insert or update on table \"x\"", None);
+        assert!(
+            matches!(err, AppError::BadRequest(ref m) if m == "referenced record does not exist")
+        );
+    }
+
     #[test]
     fn test_error_into_response_forbidden() {
         let err = AppError::Forbidden("No permission".to_string());
@@ -3,40 +3,81 @@
 //! This is the entry point for the MonitorMBG backend server.
 //! It initializes configuration, logging, database connections, and starts the Axum server.
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
+use axum::Extension;
 use axum::Router;
+use axum::extract::DefaultBodyLimit;
 use backend::auth::middleware::AuthState as MiddlewareAuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::hash_password;
 use backend::config::Config;
 use backend::database::Database;
+use backend::database::seed::DEMO_ADMIN_CODE;
+use backend::database::seed::DEMO_PASSWORD;
 use backend::logging::setup_logging;
+use backend::middleware::audit::AuditState;
+use backend::middleware::metrics::install_recorder;
+use backend::middleware::metrics::track_metrics;
 use backend::middleware::rate_limit::RateLimitMiddleware;
 use backend::middleware::rate_limit::rate_limit;
+use backend::middleware::timeout::RequestTimeout;
+use backend::middleware::timeout::request_timeout;
+use backend::routes::alert::AlertState;
+use backend::routes::alert::alert_routes;
+use backend::routes::audit::AuditLogState;
+use backend::routes::audit::audit_log_routes;
 use backend::routes::auth::AuthState;
 use backend::routes::auth::auth_routes;
+use backend::routes::complaint::ComplaintState;
+use backend::routes::complaint::complaint_routes;
+use backend::routes::compliance::ComplianceState;
+use backend::routes::compliance::kitchen_compliance_routes;
 use backend::routes::incident::IncidentState;
 use backend::routes::incident::incident_routes;
+use backend::routes::inspection::InspectionState;
+use backend::routes::inspection::inspection_routes;
+use backend::routes::inspection::kitchen_inspection_routes;
 use backend::routes::kitchen::KitchenState;
 use backend::routes::kitchen::kitchen_routes;
+use backend::routes::metrics::MetricsState;
+use backend::routes::metrics::metrics_routes;
+use backend::routes::openapi::openapi_routes;
 use backend::routes::review::ReviewState;
 use backend::routes::review::review_routes;
 use backend::routes::stats::StatsState;
 use backend::routes::stats::stats_routes;
 use backend::routes::utility::UtilityState;
 use backend::routes::utility::utility_routes;
+use backend::routes::video::VideoState;
+use backend::routes::video::video_routes;
+use backend::routes::webhook::WebhookState;
+use backend::routes::webhook::webhook_routes;
+use backend::service::alert::AlertService;
+use backend::service::audit::AuditService;
 use backend::service::auth::AuthService;
+use backend::service::complaint::ComplaintService;
+use backend::service::compliance::ComplianceService;
 use backend::service::incident::IncidentService;
+use backend::service::inspection::InspectionService;
 use backend::service::kitchen::KitchenService;
 use backend::service::otp::OtpService;
 use backend::service::review::ReviewService;
 use backend::service::stats::StatsService;
 use backend::service::storage::StorageService;
 use backend::service::utility::UtilityService;
+use backend::service::video::VideoService;
+use backend::service::webhook::WebhookDispatcher;
+use backend::service::whatsapp::WhatsAppClient;
 use dotenv::dotenv;
 use log::debug;
+use log::error;
 use log::info;
+use log::warn;
 use tokio::net::TcpListener;
+use tower_http::compression::CompressionLayer;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -44,33 +85,102 @@ async fn main() -> anyhow::Result<()> {
     dotenv().ok();
 
     let mut config = Config::new();
-    config.load()?;
+    config.load();
+    config.validate()?;
     let config = Arc::new(config);
+    let jwt_keys = Arc::new(JwtKeys::from_config(&config)?);
+
+    // `backend seed` inserts demo data and exits instead of serving
+    let seed = match std::env::args().nth(1).as_deref() {
+        None => false,
+        Some("seed") if config.allow_demo_seed => true,
+        Some("seed") => anyhow::bail!(
+            "Refusing to seed demo data: set ALLOW_DEMO_SEED=true (never in production)"
+        ),
+        Some(command) => anyhow::bail!("Unknown command \"{}\", expected \"seed\"", command),
+    };
 
     setup_logging(&config)?;
+    let metrics_handle = install_recorder()?;
 
     info!("Starting MonitorMBG (backend)...");
+    if config.otp_debug_return_code {
+        warn!("OTP_DEBUG_RETURN_CODE is enabled: OTP codes are returned to clients");
+    }
 
     // Setup database
     debug!("Setting up Database...");
-    let db = Arc::new(Database::new(&config.db_url).await?);
-    info!("Running database migrations...");
-    db.run_migrations().await?;
+    let db = Arc::new(
+        Database::new(&config.db_url)
+            .await?
+            .with_transaction_retries(config.db_transaction_retries),
+    );
+    if config.skip_migrations {
+        let pending = db.pending_migrations().await?;
+        if !pending.is_empty() {
+            warn!(
+                "SKIP_MIGRATIONS is enabled and {} migrations are pending",
+                pending.len()
+            );
+        }
+    } else {
+        info!("Running database migrations...");
+        let applied = db.run_migrations().await?;
+        info!("Applied {} migrations.", applied.len());
+    }
     info!(
         "Database setup complete ({:.2}s).",
         init_start.elapsed().as_secs_f64()
     );
 
+    if seed {
+        return seed_demo_data(&config, &db).await;
+    }
+
     // Setup routes
-    let api_routes = setup_routes(config.clone(), db.clone());
+    let api_routes = setup_routes(config.clone(), db.clone(), jwt_keys, init_start);
 
     // Setup Rate Limit
-    let rl_state = RateLimitMiddleware::new(100);
+    let rl_state = RateLimitMiddleware::from_config(&config.rate_limit)
+        .await?
+        .with_trusted_proxies(&config.trusted_proxies);
+    info!("Rate limiting with the {} store", config.rate_limit.store);
+
+    // Setup Request Timeout
+    let timeout_state = RequestTimeout::new(&config.request_timeout);
 
     let app = Router::new()
         .nest("/api", api_routes)
+        .merge(openapi_routes())
+        .layer(axum::middleware::from_fn(track_metrics))
+        // Honors Accept-Encoding; the default predicate skips images and tiny bodies
+        .layer(CompressionLayer::new())
+        .layer(axum::middleware::from_fn_with_state(
+            timeout_state,
+            request_timeout,
+        ))
         .layer(axum::middleware::from_fn_with_state(rl_state, rate_limit));
 
+    // Setup Metrics; added after the layers so scrapes aren't rate limited or
+    // counted as API traffic
+    let metrics_router = metrics_routes(MetricsState {
+        handle: metrics_handle,
+        db: db.clone(),
+    });
+    let app = match config.metrics_port {
+        Some(port) => {
+            let listener = TcpListener::bind(format!("{}:{}", config.host, port)).await?;
+            info!("Serving metrics on {}", listener.local_addr()?);
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(listener, metrics_router).await {
+                    error!("Metrics server stopped: {}", e);
+                }
+            });
+            app
+        }
+        None => app.merge(metrics_router),
+    };
+
     // Start Server
     let addr = format!("{}:{}", config.host, config.port);
     let listener = TcpListener::bind(&addr).await?;
@@ -82,27 +192,98 @@ async fn main() -> anyhow::Result<()> {
         init_done.as_secs_f64()
     );
 
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
 
-fn setup_routes(config: Arc<Config>, db: Arc<Database>) -> Router {
+async fn seed_demo_data(config: &Config, db: &Database) -> anyhow::Result<()> {
+    info!("Seeding demo data...");
+    let password_hash = hash_password(DEMO_PASSWORD, &config.password_hash)?;
+    let summary = db.seed_demo_data(&password_hash).await?;
+    info!(
+        "Seeded {} institutions, {} users, {} kitchens, {} reviews and {} incidents.",
+        summary.institutions, summary.users, summary.kitchens, summary.reviews, summary.incidents
+    );
+    info!(
+        "Log in as {} with password \"{}\".",
+        DEMO_ADMIN_CODE, DEMO_PASSWORD
+    );
+    Ok(())
+}
+
+fn setup_routes(
+    config: Arc<Config>,
+    db: Arc<Database>,
+    jwt_keys: Arc<JwtKeys>,
+    started_at: Instant,
+) -> Router {
     // Setup Storage Service
     let storage_service = Arc::new(
-        StorageService::new(&config.storage).expect("Failed to initialize storage service"),
+        StorageService::new(&config.storage)
+            .expect("Failed to initialize storage service")
+            .with_file_hashes(db.clone()),
     );
 
     // Setup Services
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        jwt_keys.clone(),
+    ));
     let otp_service = Arc::new(OtpService::new(config.clone()));
-    let kitchen_service = Arc::new(KitchenService::new(db.clone()));
-    let review_service = Arc::new(ReviewService::new(db.clone()));
-    let incident_service = Arc::new(IncidentService::new(db.clone()));
-    let stats_service = Arc::new(StatsService::new(db.clone()));
-    let utility_service = Arc::new(UtilityService::new(db.clone(), storage_service));
+    let kitchen_service = Arc::new(
+        KitchenService::with_rating_weights(db.clone(), config.haccp_weights.clone())
+            .with_rating_config(config.kitchen_rating.clone()),
+    );
+    let review_service = Arc::new(
+        ReviewService::with_rating_weights(db.clone(), config.haccp_weights.clone())
+            .with_photo_storage(storage_service.clone(), config.review_photos.clone())
+            .with_cooldown(config.review_cooldown.clone()),
+    );
+    let incident_service = Arc::new(
+        IncidentService::new(db.clone())
+            .with_dedup(config.incident_dedup.clone())
+            .with_webhooks(WebhookDispatcher::new(
+                db.clone(),
+                config.incident_webhooks.clone(),
+            )),
+    );
+    let inspection_service = Arc::new(
+        InspectionService::new(db.clone()).with_attachment_storage(storage_service.clone()),
+    );
+    let compliance_service = Arc::new(ComplianceService::new(db.clone()));
+    let complaint_service = Arc::new(ComplaintService::with_sla(
+        db.clone(),
+        config.complaint_sla.clone(),
+    ));
+    let stats_service = Arc::new(
+        StatsService::with_rating_weights(db.clone(), config.haccp_weights.clone())
+            .with_leaderboard(config.leaderboard.clone()),
+    );
+    let video_service = Arc::new(VideoService::new(db.clone()));
+    let alert_service = Arc::new(AlertService::new(db.clone()));
+    let audit_service = Arc::new(AuditService::new(db.clone()));
+    let utility_service = Arc::new(
+        UtilityService::with_started_at(
+            db.clone(),
+            storage_service,
+            WhatsAppClient::new(config.clone()),
+            started_at,
+        )
+        .with_otp_service(otp_service.clone())
+        .with_retention(config.retention.clone()),
+    );
 
     // Setup States
+    let webhook_state = WebhookState {
+        whatsapp: WhatsAppClient::new(config.clone()),
+        otp_service: otp_service.clone(),
+    };
     let auth_state = AuthState {
         service: auth_service,
         otp_service,
@@ -116,26 +297,88 @@ fn setup_routes(config: Arc<Config>, db: Arc<Database>) -> Router {
     let incident_state = IncidentState {
         service: incident_service,
     };
+    let inspection_state = InspectionState {
+        service: inspection_service,
+    };
+    let compliance_state = ComplianceState {
+        service: compliance_service,
+    };
+    let complaint_state = ComplaintState {
+        service: complaint_service,
+    };
     let stats_state = StatsState {
         service: stats_service,
     };
+    let video_state = VideoState {
+        service: video_service,
+    };
+    let alert_state = AlertState {
+        service: alert_service,
+    };
+    let audit_log_state = AuditLogState {
+        service: audit_service,
+    };
     let utility_state = UtilityState {
         service: utility_service,
     };
 
     let middleware_auth_state = MiddlewareAuthState {
-        config: config.clone(),
+        keys: jwt_keys,
+        db: Some(db.clone()),
     };
 
     // Setup Router
     Router::new()
-        .nest("/auth", auth_routes(auth_state))
-        .nest("/kitchens", kitchen_routes(kitchen_state))
+        .nest(
+            "/auth",
+            auth_routes(auth_state, middleware_auth_state.clone()),
+        )
+        .nest(
+            "/kitchens",
+            kitchen_routes(kitchen_state, middleware_auth_state.clone())
+                .merge(kitchen_inspection_routes(inspection_state.clone()))
+                .merge(kitchen_compliance_routes(
+                    compliance_state,
+                    middleware_auth_state.clone(),
+                )),
+        )
         .nest(
             "/reviews",
-            review_routes(review_state, middleware_auth_state.clone()),
+            review_routes(
+                review_state,
+                middleware_auth_state.clone(),
+                AuditState::new(db.clone(), "review").with_trusted_proxies(&config.trusted_proxies),
+            ),
+        )
+        .nest(
+            "/incidents",
+            incident_routes(incident_state, middleware_auth_state.clone()),
+        )
+        .nest(
+            "/inspections",
+            inspection_routes(inspection_state, middleware_auth_state.clone()),
+        )
+        .nest(
+            "/complaints",
+            complaint_routes(complaint_state, middleware_auth_state.clone()),
         )
-        .nest("/incidents", incident_routes(incident_state))
         .nest("/stats", stats_routes(stats_state))
+        .nest(
+            "/videos",
+            video_routes(video_state, middleware_auth_state.clone()),
+        )
+        .nest(
+            "/alerts",
+            alert_routes(alert_state, middleware_auth_state.clone()),
+        )
+        .nest(
+            "/audit-logs",
+            audit_log_routes(audit_log_state, middleware_auth_state.clone()),
+        )
+        .nest("/webhooks", webhook_routes(webhook_state))
         .nest("/", utility_routes(utility_state, middleware_auth_state))
+        // Read by the PaginationParams extractor
+        .layer(Extension(config.pagination.clone()))
+        // Upload routes override this with their own, larger limits
+        .layer(DefaultBodyLimit::max(config.request_body.max_json_bytes))
 }
@@ -0,0 +1,216 @@
+//! Audit logging middleware.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::ConnectInfo;
+use axum::extract::OriginalUri;
+use axum::extract::Query;
+use axum::extract::Request;
+use axum::extract::State;
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+use log::warn;
+use uuid::Uuid;
+
+use crate::auth::utils::Claims;
+use crate::database::Database;
+use crate::database::model::AuditLog;
+use crate::database::table::Table;
+
+/// State for the audit logging middleware.
+///
+/// The middleware is opt-in: apply it only to routers whose mutations should be
+/// recorded, *inside* the authentication layer so that [`Claims`] are available.
+#[derive(Clone)]
+pub struct AuditState {
+    /// Database used to persist audit log entries.
+    pub db: Arc<Database>,
+    /// Entity type recorded for requests passing through this router (e.g. `"review"`).
+    pub entity_type: &'static str,
+    /// Proxies trusted to report the client IP, see [`client_ip`].
+    pub trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl AuditState {
+    /// Creates a new audit state for the given entity type.
+    pub fn new(db: Arc<Database>, entity_type: &'static str) -> Self {
+        Self {
+            db,
+            entity_type,
+            trusted_proxies: Arc::new([]),
+        }
+    }
+
+    /// Reads the client IP from `X-Forwarded-For` for requests coming from
+    /// one of `proxies`.
+    pub fn with_trusted_proxies(mut self, proxies: &[IpAddr]) -> Self {
+        self.trusted_proxies = proxies.into();
+        self
+    }
+}
+
+/// Middleware that records an [`AuditLog`] row for every authenticated,
+/// mutating (non-`GET`/`HEAD`/`OPTIONS`) request.
+///
+/// The entry is written after the handler has run so the response status can be
+/// stored alongside the query parameters in `metadata`. Failures to write the
+/// audit entry are logged and never affect the response.
+pub async fn audit_middleware(
+    State(state): State<AuditState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    if matches!(method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(req).await;
+    }
+
+    let Some(claims) = req.extensions().get::<Claims>().cloned() else {
+        return next.run(req).await;
+    };
+
+    let uri = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|original| original.0.clone())
+        .unwrap_or_else(|| req.uri().clone());
+    let path = uri.path().to_string();
+    let query = Query::<HashMap<String, String>>::try_from_uri(&uri)
+        .map(|Query(params)| params)
+        .unwrap_or_default();
+    let ip_address = client_ip(&req, &state.trusted_proxies);
+
+    let response = next.run(req).await;
+
+    let user_name = match state.db.user_table.select(&claims.sub).await {
+        Ok(user) => user.map(|u| u.name),
+        Err(e) => {
+            warn!("Failed to look up user {} for audit log: {}", claims.sub, e);
+            None
+        }
+    };
+
+    let entry = AuditLog {
+        user_id: Some(claims.sub),
+        user_name,
+        action: format!("{} {}", method, path),
+        entity_type: state.entity_type.to_string(),
+        entity_id: entity_id_from_path(&path),
+        timestamp: Some(Utc::now()),
+        ip_address,
+        metadata: Some(serde_json::json!({
+            "query": query,
+            "status": response.status().as_u16(),
+        })),
+        ..Default::default()
+    };
+
+    if let Err(e) = state.db.audit_log_table.insert(&entry).await {
+        warn!("Failed to write audit log for {}: {}", entry.action, e);
+    }
+
+    response
+}
+
+/// Extracts the entity id from a request path.
+///
+/// Returns the last path segment that parses as a UUID, or an empty string for
+/// collection-level requests (e.g. `POST /reviews`).
+fn entity_id_from_path(path: &str) -> String {
+    path.rsplit('/')
+        .find(|segment| Uuid::parse_str(segment).is_ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Determines the client IP of a request.
+///
+/// This is the socket peer address, unless the peer is one of
+/// `trusted_proxies`: then `X-Forwarded-For` is read from the right, skipping
+/// the entries added by trusted proxies, and the first other address is the
+/// client. Entries to its left were sent by the client and are never trusted.
+/// Returns `None` if the server was not started with
+/// [`ConnectInfo`](axum::extract::ConnectInfo).
+pub(crate) fn client_ip(req: &Request, trusted_proxies: &[IpAddr]) -> Option<String> {
+    let mut client = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())?;
+    let forwarded = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect::<Vec<_>>();
+    for entry in forwarded.iter().rev() {
+        if !trusted_proxies.contains(&client) {
+            break;
+        }
+        match entry.trim().parse() {
+            Ok(ip) => client = ip,
+            Err(_) => break,
+        }
+    }
+    Some(client.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_id_from_path() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            entity_id_from_path(&format!("/api/reviews/{}", id)),
+            id.to_string()
+        );
+        assert_eq!(
+            entity_id_from_path(&format!("/api/reviews/{}/photos", id)),
+            id.to_string()
+        );
+        assert_eq!(entity_id_from_path("/api/reviews/batch"), "");
+    }
+
+    fn request(peer: &str, forwarded_for: Option<&str>) -> Request {
+        let mut builder = Request::builder();
+        if let Some(forwarded_for) = forwarded_for {
+            builder = builder.header("x-forwarded-for", forwarded_for);
+        }
+        let mut req = builder.body(axum::body::Body::empty()).unwrap();
+        let peer: SocketAddr = peer.parse().unwrap();
+        req.extensions_mut().insert(ConnectInfo(peer));
+        req
+    }
+
+    #[test]
+    fn test_client_ip_trusts_forwarded_for_only_from_trusted_proxies() {
+        let proxies: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+
+        // Direct requests can't choose their own address
+        let req = request("203.0.113.7:5000", Some("198.51.100.1"));
+        assert_eq!(client_ip(&req, &proxies).as_deref(), Some("203.0.113.7"));
+
+        // The entry appended by the proxy wins over ones sent by the client
+        let req = request("10.0.0.1:5000", Some("198.51.100.1, 203.0.113.7"));
+        assert_eq!(client_ip(&req, &proxies).as_deref(), Some("203.0.113.7"));
+
+        // Chained trusted proxies are skipped
+        let req = request("10.0.0.1:5000", Some("198.51.100.1, 203.0.113.7, 10.0.0.2"));
+        assert_eq!(client_ip(&req, &proxies).as_deref(), Some("203.0.113.7"));
+
+        let req = request("10.0.0.1:5000", None);
+        assert_eq!(client_ip(&req, &proxies).as_deref(), Some("10.0.0.1"));
+        let req = request("10.0.0.1:5000", Some("198.51.100.1"));
+        assert_eq!(client_ip(&req, &[]).as_deref(), Some("10.0.0.1"));
+
+        let req = Request::builder().body(axum::body::Body::empty()).unwrap();
+        assert_eq!(client_ip(&req, &proxies), None);
+    }
+}
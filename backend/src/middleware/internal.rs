@@ -0,0 +1,71 @@
+//! Restricts routes to requests made from the server itself.
+
+use std::net::SocketAddr;
+
+use axum::extract::ConnectInfo;
+use axum::extract::Request;
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::Response;
+use log::warn;
+
+use crate::error::AppError;
+
+/// Headers a reverse proxy adds when forwarding a request.
+const FORWARDING_HEADERS: [&str; 3] = ["forwarded", "x-forwarded-for", "x-real-ip"];
+
+/// Middleware rejecting requests that don't come straight from a loopback
+/// address, such as an operator's shell on the host or a `kubectl
+/// port-forward`.
+///
+/// Unlike [`client_ip`](crate::middleware::audit::client_ip), forwarding
+/// headers are never trusted: a request carrying one came through the reverse
+/// proxy and is rejected with [`AppError::Forbidden`] even if the proxy itself
+/// runs on loopback.
+pub async fn internal_only(req: Request, next: Next) -> Result<Response, AppError> {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    if !is_internal(peer, req.headers()) {
+        warn!(
+            "Rejected {} {} from non-internal peer {:?}",
+            req.method(),
+            req.uri().path(),
+            peer
+        );
+        return Err(AppError::Forbidden(
+            "This endpoint is only available from the server itself".into(),
+        ));
+    }
+    Ok(next.run(req).await)
+}
+
+fn is_internal(peer: Option<SocketAddr>, headers: &HeaderMap) -> bool {
+    peer.is_some_and(|addr| addr.ip().is_loopback())
+        && FORWARDING_HEADERS
+            .iter()
+            .all(|name| !headers.contains_key(*name))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_is_internal() {
+        let loopback: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+        let remote: SocketAddr = "203.0.113.7:5000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+
+        assert!(is_internal(Some(loopback), &headers));
+        assert!(is_internal(Some("[::1]:5000".parse().unwrap()), &headers));
+        assert!(!is_internal(Some(remote), &headers));
+        assert!(!is_internal(None, &headers));
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("127.0.0.1"));
+        assert!(!is_internal(Some(loopback), &headers));
+    }
+}
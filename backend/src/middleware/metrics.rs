@@ -0,0 +1,68 @@
+//! Prometheus metrics recording.
+//!
+//! The recorder is installed once at startup with [`install_recorder`]; after
+//! that the `metrics` macros anywhere in the crate feed into it and
+//! [`crate::routes::metrics`] renders the result for scraping.
+
+use std::time::Instant;
+
+use axum::extract::MatchedPath;
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use metrics_exporter_prometheus::BuildError;
+use metrics_exporter_prometheus::Matcher;
+use metrics_exporter_prometheus::PrometheusBuilder;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+/// Total HTTP requests, labelled by `method`, `path` and `status`.
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+/// HTTP request latency in seconds, labelled like [`HTTP_REQUESTS_TOTAL`].
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+
+/// Latency buckets in seconds, from fast reads up to the upload timeout.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 120.0,
+];
+
+/// Installs the global Prometheus recorder.
+///
+/// # Errors
+///
+/// Returns an error if a recorder is already installed.
+pub fn install_recorder() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+            LATENCY_BUCKETS,
+        )?
+        .install_recorder()
+}
+
+/// Middleware that records a request count and latency histogram per route.
+///
+/// Requests are labelled with the matched route template (e.g.
+/// `/api/kitchens/{id}`) rather than the raw path, so IDs don't create a new
+/// series per request.
+pub async fn track_metrics(req: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = req.method().to_string();
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+
+    let labels = [
+        ("method", method),
+        ("path", path),
+        ("status", response.status().as_u16().to_string()),
+    ];
+    metrics::counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels)
+        .record(start.elapsed().as_secs_f64());
+
+    response
+}
@@ -1 +1,5 @@
+pub mod audit;
+pub mod internal;
+pub mod metrics;
 pub mod rate_limit;
+pub mod timeout;
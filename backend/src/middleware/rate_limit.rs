@@ -1,41 +1,673 @@
 //! Rate limiting middleware.
+//!
+//! Each request is matched to a quota by its path: the configured rule with
+//! the longest matching prefix, or the default quota. Requests are counted per
+//! quota and client IP in fixed windows kept in a [`RateLimitStore`]; the
+//! client IP comes from `X-Forwarded-For` only for requests sent by a trusted
+//! proxy, see [`client_ip`]. The
+//! in-memory store only sees the requests of its own process, so deployments
+//! running several replicas should use the Redis store, which every replica
+//! shares.
 
-use std::num::NonZeroU32;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
 
 use axum::extract::Request;
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::HeaderMap;
+use axum::http::HeaderValue;
 use axum::middleware::Next;
+use axum::response::IntoResponse;
 use axum::response::Response;
-use governor::Quota;
-use governor::RateLimiter;
-use governor::clock::DefaultClock;
-use governor::state::InMemoryState;
-use governor::state::NotKeyed;
+use chrono::Utc;
+use log::warn;
+use redis::Script;
+use redis::aio::ConnectionManager;
+
+use crate::config::RateLimitConfig;
+use crate::config::RateLimitQuota;
+use crate::error::AppError;
+use crate::middleware::audit::client_ip;
+
+/// Name of the quota applied to requests no rule matches.
+const DEFAULT_RULE: &str = "default";
+
+/// Prefix of the Redis keys holding request counts.
+const REDIS_KEY_PREFIX: &str = "rate_limit";
+
+/// Keys the in-memory store holds at most.
+const MAX_IN_MEMORY_KEYS: usize = 10_000;
+
+/// How often the in-memory store drops expired windows.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Increments a counter, starting its window's expiry on the first hit, and
+/// returns the new count and the window's remaining milliseconds. Runs
+/// atomically, so replicas never lose a hit or leave a counter without an
+/// expiry.
+const INCR_SCRIPT: &str = r"
+local count = redis.call('INCR', KEYS[1])
+if count == 1 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[1])
+end
+return {count, redis.call('PTTL', KEYS[1])}
+";
+
+/// Errors that can occur while checking a rate limit.
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitError {
+    /// The store holding request counts could not be reached.
+    #[error("Rate limit store error: {0}")]
+    StoreError(String),
+
+    /// The rate limiter is not configured properly.
+    #[error("Rate limit configuration error: {0}")]
+    ConfigurationError(String),
+}
+
+impl From<redis::RedisError> for RateLimitError {
+    fn from(err: redis::RedisError) -> Self {
+        RateLimitError::StoreError(err.to_string())
+    }
+}
+
+/// Supported rate limit stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitStoreType {
+    /// Counts kept in this process.
+    Memory,
+    /// Counts kept in Redis and shared between replicas.
+    Redis,
+}
+
+impl fmt::Display for RateLimitStoreType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitStoreType::Memory => write!(f, "memory"),
+            RateLimitStoreType::Redis => write!(f, "redis"),
+        }
+    }
+}
+
+impl std::str::FromStr for RateLimitStoreType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "memory" => Ok(RateLimitStoreType::Memory),
+            "redis" => Ok(RateLimitStoreType::Redis),
+            _ => Err(format!("Unknown rate limit store: {}", s)),
+        }
+    }
+}
+
+/// Requests counted in the current window of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowCount {
+    /// Requests in the window, including the one just counted.
+    pub count: u64,
+    /// Time until the window ends and the count resets.
+    pub reset_after: Duration,
+}
+
+/// Where request counts are kept.
+#[async_trait::async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Counts a request against `key`, starting a new window of length
+    /// `window` if the key has none.
+    async fn hit(&self, key: &str, window: Duration) -> Result<WindowCount, RateLimitError>;
+}
+
+/// Store keeping request counts in this process.
+///
+/// Holds at most a fixed number of keys. When full, a new key evicts the
+/// window closest to ending, so memory stays bounded however many clients
+/// there are. Expired windows are dropped by [`InMemoryStore::sweep`], which
+/// [`RateLimitMiddleware::from_config`] runs periodically.
+pub struct InMemoryStore {
+    max_keys: usize,
+    windows: Mutex<Windows>,
+}
+
+#[derive(Default)]
+struct Windows {
+    /// End of each key's window and the requests counted in it.
+    counts: HashMap<String, (Instant, u64)>,
+    /// The same windows ordered by end, soonest first.
+    ends: BTreeSet<(Instant, String)>,
+}
+
+impl InMemoryStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::with_max_keys(MAX_IN_MEMORY_KEYS)
+    }
+
+    /// Creates an empty store holding at most `max_keys` keys.
+    pub fn with_max_keys(max_keys: usize) -> Self {
+        Self {
+            max_keys: max_keys.max(1),
+            windows: Mutex::new(Windows::default()),
+        }
+    }
+
+    /// Drops the windows that have ended, returning how many were dropped.
+    pub fn sweep(&self) -> usize {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let Windows { counts, ends } = &mut *windows;
+        let mut dropped = 0;
+        while let Some((ends_at, _)) = ends.first()
+            && *ends_at <= now
+        {
+            let (_, key) = ends.pop_first().unwrap();
+            counts.remove(&key);
+            dropped += 1;
+        }
+        dropped
+    }
+
+    /// Runs [`sweep`](Self::sweep) every `interval` until the store is
+    /// dropped.
+    pub fn spawn_sweeper(self: &Arc<Self>, interval: Duration) {
+        let store = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(store) = Weak::upgrade(&store) else {
+                    break;
+                };
+                store.sweep();
+            }
+        });
+    }
+
+    /// Number of keys currently held.
+    pub fn len(&self) -> usize {
+        self.windows.lock().unwrap().counts.len()
+    }
+
+    /// Whether the store holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for InMemoryStore {
+    async fn hit(&self, key: &str, window: Duration) -> Result<WindowCount, RateLimitError> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let Windows { counts, ends } = &mut *windows;
+
+        let (ends_at, count) = match counts.get_mut(key) {
+            Some((ends_at, count)) if *ends_at > now => {
+                *count += 1;
+                (*ends_at, *count)
+            }
+            Some((ends_at, count)) => {
+                ends.remove(&(*ends_at, key.to_string()));
+                *ends_at = now + window;
+                *count = 1;
+                ends.insert((*ends_at, key.to_string()));
+                (*ends_at, *count)
+            }
+            None => {
+                if counts.len() >= self.max_keys
+                    && let Some((_, evicted)) = ends.pop_first()
+                {
+                    counts.remove(&evicted);
+                }
+                counts.insert(key.to_string(), (now + window, 1));
+                ends.insert((now + window, key.to_string()));
+                (now + window, 1)
+            }
+        };
+        Ok(WindowCount {
+            count,
+            reset_after: ends_at - now,
+        })
+    }
+}
+
+/// Store keeping request counts in Redis, so every replica enforces the same
+/// limit.
+pub struct RedisStore {
+    conn: ConnectionManager,
+    script: Script,
+}
+
+impl RedisStore {
+    /// Connects to the Redis server at `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RateLimitError::StoreError`] if the server is unreachable.
+    pub async fn connect(url: &str) -> Result<Self, RateLimitError> {
+        let client = redis::Client::open(url)?;
+        let conn = ConnectionManager::new(client).await?;
+        Ok(Self {
+            conn,
+            script: Script::new(INCR_SCRIPT),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for RedisStore {
+    async fn hit(&self, key: &str, window: Duration) -> Result<WindowCount, RateLimitError> {
+        let (count, ttl_ms): (u64, i64) = self
+            .script
+            .key(format!("{}:{}", REDIS_KEY_PREFIX, key))
+            .arg(window.as_millis() as u64)
+            .invoke_async(&mut self.conn.clone())
+            .await?;
+        Ok(WindowCount {
+            count,
+            // Negative if the key expired between the two calls
+            reset_after: Duration::from_millis(ttl_ms.max(0) as u64),
+        })
+    }
+}
 
 /// State for the rate limiting middleware.
 #[derive(Clone)]
 pub struct RateLimitMiddleware {
-    limiter: Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
+    store: Arc<dyn RateLimitStore>,
+    default: RateLimitQuota,
+    /// Path prefixes and their quotas, longest prefix first.
+    rules: Vec<(String, RateLimitQuota)>,
+    /// Proxies trusted to report the client IP.
+    trusted_proxies: Arc<[IpAddr]>,
 }
 
 impl RateLimitMiddleware {
-    /// Creates a new rate limiter with the specified requests per second.
-    pub fn new(requests_per_second: u32) -> Self {
-        let quota = Quota::per_second(NonZeroU32::new(requests_per_second).unwrap());
-        let limiter = Arc::new(RateLimiter::direct(quota));
-        Self { limiter }
+    /// Creates a rate limiter counting requests in `store` and applying
+    /// `default` to every path.
+    pub fn new(store: Arc<dyn RateLimitStore>, default: RateLimitQuota) -> Self {
+        Self {
+            store,
+            default,
+            rules: Vec::new(),
+            trusted_proxies: Arc::new([]),
+        }
+    }
+
+    /// Counts requests coming from one of `proxies` against the client named
+    /// in their `X-Forwarded-For` header instead of the proxy.
+    pub fn with_trusted_proxies(mut self, proxies: &[IpAddr]) -> Self {
+        self.trusted_proxies = proxies.into();
+        self
+    }
+
+    /// Applies `quota` to requests whose path is `path_prefix` or below it.
+    pub fn with_rule(mut self, path_prefix: &str, quota: RateLimitQuota) -> Self {
+        self.rules
+            .push((path_prefix.trim_end_matches('/').to_string(), quota));
+        self.rules
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    /// Creates the rate limiter selected by configuration, connecting to
+    /// Redis if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid or Redis is
+    /// unreachable.
+    pub async fn from_config(config: &RateLimitConfig) -> Result<Self, RateLimitError> {
+        let store_type = config
+            .store
+            .parse::<RateLimitStoreType>()
+            .map_err(RateLimitError::ConfigurationError)?;
+        if let Some(quota) = std::iter::once(&config.default)
+            .chain(config.rules.iter().map(|rule| &rule.quota))
+            .find(|quota| !quota.is_valid())
+        {
+            return Err(RateLimitError::ConfigurationError(format!(
+                "Invalid quota: {}/{}",
+                quota.limit, quota.window_seconds
+            )));
+        }
+
+        let store: Arc<dyn RateLimitStore> = match store_type {
+            RateLimitStoreType::Memory => {
+                let store = Arc::new(InMemoryStore::new());
+                store.spawn_sweeper(SWEEP_INTERVAL);
+                store
+            }
+            RateLimitStoreType::Redis => {
+                let url = config.redis_url.as_deref().ok_or_else(|| {
+                    RateLimitError::ConfigurationError("Redis URL is required".to_string())
+                })?;
+                Arc::new(RedisStore::connect(url).await?)
+            }
+        };
+        Ok(config
+            .rules
+            .iter()
+            .fold(Self::new(store, config.default), |limiter, rule| {
+                limiter.with_rule(&rule.path_prefix, rule.quota)
+            }))
+    }
+
+    /// Returns the name and quota of the rule applying to `path`.
+    fn rule_for<'a>(&'a self, path: &str) -> (&'a str, RateLimitQuota) {
+        self.rules
+            .iter()
+            .find(|(prefix, _)| {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map(|(prefix, quota)| (prefix.as_str(), *quota))
+            .unwrap_or((DEFAULT_RULE, self.default))
     }
 }
 
+/// Middleware that counts each request against the quota for its path and
+/// client.
+///
+/// Responses carry `X-RateLimit-Limit`, `X-RateLimit-Remaining` and
+/// `X-RateLimit-Reset` (Unix seconds) headers; requests over the quota get
+/// [`AppError::TooManyRequests`] with a `Retry-After` header instead. If the
+/// store cannot be reached the request is let through without headers, so a
+/// Redis outage degrades rate limiting instead of taking the API down.
 pub async fn rate_limit(
     State(state): State<RateLimitMiddleware>,
     req: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    if state.limiter.check().is_err() {
-        return Err(StatusCode::TOO_MANY_REQUESTS);
+) -> Response {
+    let (rule, quota) = state.rule_for(req.uri().path());
+    let client = client_ip(&req, &state.trusted_proxies).unwrap_or_default();
+    let window = Duration::from_secs(quota.window_seconds);
+
+    let hit = match state
+        .store
+        .hit(&format!("{}:{}", rule, client), window)
+        .await
+    {
+        Ok(hit) => hit,
+        Err(e) => {
+            warn!("Allowing request without rate limiting: {}", e);
+            return next.run(req).await;
+        }
+    };
+
+    let limit = u64::from(quota.limit);
+    let reset_seconds = hit.reset_after.as_secs_f64().ceil() as u64;
+    let mut response = if hit.count > limit {
+        let mut response = AppError::TooManyRequests(format!(
+            "Too many requests, retry in {} seconds",
+            reset_seconds
+        ))
+        .into_response();
+        response
+            .headers_mut()
+            .insert("retry-after", HeaderValue::from(reset_seconds));
+        response
+    } else {
+        next.run(req).await
+    };
+    insert_headers(
+        response.headers_mut(),
+        limit,
+        limit.saturating_sub(hit.count),
+        Utc::now().timestamp().max(0) as u64 + reset_seconds,
+    );
+    response
+}
+
+fn insert_headers(headers: &mut HeaderMap, limit: u64, remaining: u64, reset: u64) {
+    headers.insert("x-ratelimit-limit", HeaderValue::from(limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(reset));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::Router;
+    use axum::body::Body;
+    use axum::extract::ConnectInfo;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    /// Store whose backend is always down.
+    struct UnavailableStore;
+
+    #[async_trait::async_trait]
+    impl RateLimitStore for UnavailableStore {
+        async fn hit(&self, _key: &str, _window: Duration) -> Result<WindowCount, RateLimitError> {
+            Err(RateLimitError::StoreError("connection refused".to_string()))
+        }
+    }
+
+    fn quota(limit: u32, window_seconds: u64) -> RateLimitQuota {
+        RateLimitQuota {
+            limit,
+            window_seconds,
+        }
+    }
+
+    async fn get_path(state: &RateLimitMiddleware, uri: &str, ip: &str) -> Response {
+        get_forwarded(state, uri, ip, None).await
+    }
+
+    async fn get_forwarded(
+        state: &RateLimitMiddleware,
+        uri: &str,
+        ip: &str,
+        forwarded_for: Option<&str>,
+    ) -> Response {
+        let peer = SocketAddr::new(ip.parse().unwrap(), 5000);
+        let mut request = Request::builder().uri(uri).extension(ConnectInfo(peer));
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.header("x-forwarded-for", forwarded_for);
+        }
+        Router::new()
+            .route("/{*path}", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                rate_limit,
+            ))
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_counts_each_key_per_window() {
+        let store = InMemoryStore::new();
+        let window = Duration::from_millis(50);
+        assert_eq!(store.hit("a", window).await.unwrap().count, 1);
+        assert_eq!(store.hit("a", window).await.unwrap().count, 2);
+        assert_eq!(store.hit("b", window).await.unwrap().count, 1);
+
+        tokio::time::sleep(window).await;
+        let hit = store.hit("a", window).await.unwrap();
+        assert_eq!(hit.count, 1);
+        assert!(hit.reset_after <= window);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_evicts_soonest_ending_window_when_full() {
+        let store = InMemoryStore::with_max_keys(2);
+        store.hit("a", Duration::from_secs(10)).await.unwrap();
+        store.hit("b", Duration::from_secs(60)).await.unwrap();
+        store.hit("c", Duration::from_secs(60)).await.unwrap();
+        assert_eq!(store.len(), 2);
+
+        // "a" was evicted, "b" kept its count
+        assert_eq!(
+            store.hit("b", Duration::from_secs(60)).await.unwrap().count,
+            2
+        );
+        assert_eq!(store.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_sweep_drops_ended_windows() {
+        let store = InMemoryStore::new();
+        store.hit("a", Duration::from_millis(20)).await.unwrap();
+        store.hit("b", Duration::from_secs(60)).await.unwrap();
+        assert_eq!(store.sweep(), 0);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(store.sweep(), 1);
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            store.hit("b", Duration::from_secs(60)).await.unwrap().count,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_ignores_spoofed_forwarded_for() {
+        let state = RateLimitMiddleware::new(Arc::new(InMemoryStore::new()), quota(1, 60))
+            .with_trusted_proxies(&["10.0.0.1".parse().unwrap()]);
+
+        // A direct client can't reset its count by inventing addresses
+        let response = get_forwarded(&state, "/api/auth", "203.0.113.7", Some("1.1.1.1")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = get_forwarded(&state, "/api/auth", "203.0.113.7", Some("2.2.2.2")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Behind the proxy, only the entry it appended counts
+        let response = get_forwarded(
+            &state,
+            "/api/auth",
+            "10.0.0.1",
+            Some("1.1.1.1, 198.51.100.1"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = get_forwarded(
+            &state,
+            "/api/auth",
+            "10.0.0.1",
+            Some("2.2.2.2, 198.51.100.1"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let response = get_forwarded(&state, "/api/auth", "10.0.0.1", Some("198.51.100.2")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_rejects_requests_over_the_limit() {
+        let state = RateLimitMiddleware::new(Arc::new(InMemoryStore::new()), quota(2, 60));
+
+        let response = get_path(&state, "/api/kitchens", "10.0.0.1").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-ratelimit-limit"], "2");
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "1");
+        let reset: i64 = response.headers()["x-ratelimit-reset"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((reset - Utc::now().timestamp() - 60).abs() <= 1);
+
+        let response = get_path(&state, "/api/kitchens", "10.0.0.1").await;
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+
+        let response = get_path(&state, "/api/kitchens", "10.0.0.1").await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+        assert_eq!(response.headers()["retry-after"], "60");
+
+        // Other clients have their own count
+        let response = get_path(&state, "/api/kitchens", "10.0.0.2").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_applies_longest_matching_rule() {
+        let state = RateLimitMiddleware::new(Arc::new(InMemoryStore::new()), quota(100, 1))
+            .with_rule("/api/auth", quota(5, 60))
+            .with_rule("/api/auth/otp/", quota(1, 60));
+
+        let limit_of = |response: Response| response.headers()["x-ratelimit-limit"].clone();
+        assert_eq!(
+            limit_of(get_path(&state, "/api/auth/login", "10.0.0.1").await),
+            "5"
+        );
+        assert_eq!(
+            limit_of(get_path(&state, "/api/auth", "10.0.0.1").await),
+            "5"
+        );
+        assert_eq!(
+            limit_of(get_path(&state, "/api/auth/otp/send", "10.0.0.1").await),
+            "1"
+        );
+        assert_eq!(
+            limit_of(get_path(&state, "/api/authors", "10.0.0.1").await),
+            "100"
+        );
+        assert_eq!(
+            limit_of(get_path(&state, "/api/kitchens", "10.0.0.1").await),
+            "100"
+        );
+
+        // Rules are counted separately
+        assert_eq!(
+            get_path(&state, "/api/auth/otp/verify", "10.0.0.1")
+                .await
+                .status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(
+            get_path(&state, "/api/auth/login", "10.0.0.1")
+                .await
+                .status(),
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_allows_requests_when_store_is_down() {
+        let state = RateLimitMiddleware::new(Arc::new(UnavailableStore), quota(1, 1));
+        let response = get_path(&state, "/api/kitchens", "10.0.0.1").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key("x-ratelimit-limit"));
+    }
+
+    #[tokio::test]
+    async fn test_from_config_requires_redis_url() {
+        let config = RateLimitConfig {
+            store: "redis".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            RateLimitMiddleware::from_config(&config).await,
+            Err(RateLimitError::ConfigurationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_store_type_from_str() {
+        assert_eq!(
+            "memory".parse::<RateLimitStoreType>().unwrap(),
+            RateLimitStoreType::Memory
+        );
+        assert_eq!(
+            "Redis".parse::<RateLimitStoreType>().unwrap(),
+            RateLimitStoreType::Redis
+        );
+        assert!("memcached".parse::<RateLimitStoreType>().is_err());
     }
-    Ok(next.run(req).await)
 }
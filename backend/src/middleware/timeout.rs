@@ -0,0 +1,122 @@
+//! Request timeout middleware.
+
+use std::time::Duration;
+
+use axum::extract::Request;
+use axum::extract::State;
+use axum::middleware::Next;
+use axum::response::Response;
+use log::warn;
+
+use crate::config::RequestTimeoutConfig;
+use crate::error::AppError;
+
+/// State for the request timeout middleware.
+#[derive(Clone)]
+pub struct RequestTimeout {
+    /// Limit applied to regular requests.
+    pub default: Duration,
+    /// Longer limit applied to upload requests.
+    pub upload: Duration,
+}
+
+impl RequestTimeout {
+    /// Creates the timeout state from configuration.
+    pub fn new(config: &RequestTimeoutConfig) -> Self {
+        Self {
+            default: Duration::from_secs(config.request_seconds),
+            upload: Duration::from_secs(config.upload_seconds),
+        }
+    }
+}
+
+/// Middleware that aborts requests taking longer than the configured limit.
+///
+/// Requests under an `/upload` path segment, and review photo uploads, get the
+/// longer upload limit since large files legitimately take a while. Aborted
+/// requests respond with [`AppError::GatewayTimeout`].
+pub async fn request_timeout(
+    State(state): State<RequestTimeout>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let limit = if is_upload_path(req.uri().path()) {
+        state.upload
+    } else {
+        state.default
+    };
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+
+    tokio::time::timeout(limit, next.run(req))
+        .await
+        .map_err(|_| {
+            warn!(
+                "{} {} exceeded the {}s request timeout",
+                method,
+                path,
+                limit.as_secs_f64()
+            );
+            AppError::GatewayTimeout("Request timed out".to_string())
+        })
+}
+
+fn is_upload_path(path: &str) -> bool {
+    path.split('/').any(|segment| segment == "upload")
+        || (path.contains("/reviews/") && path.ends_with("/photos"))
+        || (path.contains("/inspections/") && path.ends_with("/attachments"))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn slow_handler() -> &'static str {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        "done"
+    }
+
+    fn app() -> Router {
+        let state = RequestTimeout {
+            default: Duration::from_millis(50),
+            upload: Duration::from_secs(5),
+        };
+        Router::new()
+            .route("/slow", get(slow_handler))
+            .route("/upload/image", get(slow_handler))
+            .layer(axum::middleware::from_fn_with_state(state, request_timeout))
+    }
+
+    async fn status_of(uri: &str) -> StatusCode {
+        app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_slow_request_times_out() {
+        assert_eq!(status_of("/slow").await, StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_upload_uses_longer_timeout() {
+        assert_eq!(status_of("/upload/image").await, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_is_upload_path() {
+        assert!(is_upload_path("/api/upload/images"));
+        assert!(is_upload_path("/api/reviews/0b7e/photos"));
+        assert!(is_upload_path("/api/inspections/0b7e/attachments"));
+        assert!(!is_upload_path("/api/uploads"));
+        assert!(!is_upload_path("/api/kitchens"));
+    }
+}
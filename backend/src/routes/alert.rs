@@ -0,0 +1,45 @@
+//! Compliance alert routes.
+
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::Router;
+use axum::extract::State;
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::post;
+
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::service::alert::AcknowledgeAlertsRequest;
+use crate::service::alert::AlertService;
+
+/// State for alert routes.
+#[derive(Clone)]
+pub struct AlertState {
+    /// The alert service.
+    pub service: Arc<AlertService>,
+}
+
+/// Handler for acknowledging alerts in bulk (admin or kitchen owner).
+pub async fn acknowledge_alerts_handler(
+    State(state): State<AlertState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<AcknowledgeAlertsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.acknowledge_alerts(&claims, payload).await?;
+    Ok(Json(response))
+}
+
+pub fn alert_routes(state: AlertState, auth_middleware_state: MiddlewareAuthState) -> Router {
+    Router::new()
+        .route("/acknowledge", post(acknowledge_alerts_handler))
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,80 @@
+//! Audit trail routes.
+
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::Router;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::routes::pagination::PaginationParams;
+use crate::service::audit::AuditLogQuery;
+use crate::service::audit::AuditService;
+
+/// State for audit log routes.
+#[derive(Clone)]
+pub struct AuditLogState {
+    /// The audit service.
+    pub service: Arc<AuditService>,
+}
+
+#[derive(Deserialize)]
+pub struct ListAuditLogsQuery {
+    #[serde(rename = "userId", alias = "user_id")]
+    pub user_id: Option<Uuid>,
+    #[serde(rename = "entityType", alias = "entity_type")]
+    pub entity_type: Option<String>,
+    pub action: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Handler for listing audit log entries (admin only).
+pub async fn list_audit_logs_handler(
+    State(state): State<AuditLogState>,
+    Extension(claims): Extension<Claims>,
+    pagination: PaginationParams,
+    Query(query): Query<ListAuditLogsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let PaginationParams { limit, offset, .. } = pagination;
+
+    let response = state
+        .service
+        .list_audit_logs(
+            claims.role,
+            limit,
+            offset,
+            AuditLogQuery {
+                user_id: query.user_id,
+                entity_type: query.entity_type,
+                action: query.action,
+                from: query.from,
+                to: query.to,
+            },
+        )
+        .await?;
+    Ok(Json(response))
+}
+
+pub fn audit_log_routes(
+    state: AuditLogState,
+    auth_middleware_state: MiddlewareAuthState,
+) -> Router {
+    Router::new()
+        .route("/", get(list_audit_logs_handler))
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ))
+        .with_state(state)
+}
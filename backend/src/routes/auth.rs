@@ -2,19 +2,32 @@
 
 use std::sync::Arc;
 
+use axum::Extension;
 use axum::Router;
+use axum::extract::Path;
 use axum::extract::State;
 use axum::http::StatusCode;
+use axum::middleware;
 use axum::response::IntoResponse;
-use axum::response::Json;
+use axum::routing::get;
 use axum::routing::post;
 use serde::Deserialize;
 use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
 
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::auth::utils::TOKEN_TTL_SECONDS;
 use crate::database::model::User;
 use crate::database::model::UserRole;
 use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::routes::openapi::ErrorResponse;
 use crate::service::auth::AuthService;
+use crate::service::channel::Recipient;
+use crate::service::otp::DeliveryStatus;
 use crate::service::otp::OtpService;
 
 /// State for authentication routes.
@@ -26,7 +39,7 @@ pub struct AuthState {
     pub otp_service: Arc<OtpService>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct RegisterRequest {
     pub name: String,
     pub role: UserRole,
@@ -36,26 +49,42 @@ pub struct RegisterRequest {
     pub institution_name: Option<String>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct LoginRequest {
     pub unique_code: String,
     pub password: String,
 }
 
-#[derive(Serialize)]
+/// Returned by both registration and login, so a new user is signed in
+/// straight away.
+#[derive(Serialize, ToSchema)]
 pub struct AuthResponse {
     pub token: String,
+    /// Seconds until `token` expires.
+    #[serde(rename = "expiresIn")]
+    pub expires_in: u64,
     pub user: User,
 }
 
-/// Request to send OTP to a phone number.
-#[derive(Deserialize)]
+impl AuthResponse {
+    fn new(token: String, user: User) -> Self {
+        Self {
+            token,
+            expires_in: TOKEN_TTL_SECONDS,
+            user,
+        }
+    }
+}
+
+/// Request to send OTP to a phone number and/or email address.
+#[derive(Deserialize, ToSchema)]
 pub struct SendOtpRequest {
-    pub phone: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
 }
 
 /// Response after sending OTP.
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct SendOtpResponse {
     pub success: bool,
     pub message: String,
@@ -63,26 +92,56 @@ pub struct SendOtpResponse {
     pub reference_id: String,
     #[serde(rename = "expiresIn")]
     pub expires_in: u64,
+    /// The OTP code, only present when `OTP_DEBUG_RETURN_CODE` is enabled in
+    /// a debug build.
+    #[serde(rename = "debugCode", skip_serializing_if = "Option::is_none")]
+    pub debug_code: Option<String>,
 }
 
 /// Request to verify an OTP code.
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct VerifyOtpRequest {
-    pub phone: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
     pub code: String,
     #[serde(rename = "referenceId")]
     pub reference_id: String,
 }
 
 /// Response after verifying OTP.
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct VerifyOtpResponse {
     pub success: bool,
     pub message: String,
     pub verified: bool,
 }
 
+/// State of a pending OTP.
+#[derive(Serialize, ToSchema)]
+pub struct OtpStatusResponse {
+    #[serde(rename = "referenceId")]
+    pub reference_id: String,
+    /// Last status reported by the delivery channel; `null` until the channel
+    /// reports one.
+    #[serde(rename = "deliveryStatus")]
+    pub delivery_status: Option<DeliveryStatus>,
+    #[serde(rename = "expiresIn")]
+    pub expires_in: u64,
+    pub verified: bool,
+}
+
 /// Handler for user registration.
+#[utoipa::path(
+    post,
+    path = "/api/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "User registered and logged in", body = AuthResponse),
+        (status = 400, description = "Invalid name, code, password or phone", body = ErrorResponse),
+        (status = 409, description = "Unique code already registered", body = ErrorResponse),
+    )
+)]
 pub async fn register_handler(
     State(state): State<AuthState>,
     Json(mut payload): Json<RegisterRequest>,
@@ -120,14 +179,27 @@ pub async fn register_handler(
             payload.role,
             payload.unique_code,
             payload.password,
-            payload.phone,
+            non_empty(payload.phone),
             payload.institution_name,
         )
         .await?;
 
-    Ok((StatusCode::CREATED, Json(AuthResponse { token, user })))
+    Ok((StatusCode::CREATED, Json(AuthResponse::new(token, user))))
 }
 
+/// Handler for logging in with a unique code and password.
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Logged in", body = AuthResponse),
+        (status = 401, description = "Invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Account deactivated", body = ErrorResponse),
+        (status = 429, description = "Too many failed attempts", body = ErrorResponse),
+    )
+)]
 pub async fn login_handler(
     State(state): State<AuthState>,
     Json(mut payload): Json<LoginRequest>,
@@ -145,44 +217,85 @@ pub async fn login_handler(
         .login_user(payload.unique_code, payload.password)
         .await?;
 
-    Ok(Json(AuthResponse { token, user }))
+    Ok(Json(AuthResponse::new(token, user)))
 }
 
-/// Handler for sending OTP to a phone number.
+/// Handler for sending OTP to a phone number or email address.
+#[utoipa::path(
+    post,
+    path = "/api/auth/otp/send",
+    tag = "auth",
+    request_body = SendOtpRequest,
+    responses(
+        (status = 200, description = "OTP sent", body = SendOtpResponse),
+        (status = 400, description = "Missing or invalid phone or email", body = ErrorResponse),
+        (status = 429, description = "OTP requested too often", body = ErrorResponse),
+        (status = 503, description = "No delivery channel available", body = ErrorResponse),
+    )
+)]
 pub async fn send_otp_handler(
     State(state): State<AuthState>,
     Json(payload): Json<SendOtpRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let phone = payload.phone.trim().to_string();
+    let recipient = Recipient {
+        phone: non_empty(payload.phone),
+        email: non_empty(payload.email),
+    };
 
-    let (reference_id, expires_in) = state.otp_service.send_otp(phone).await?;
+    let sent = state.otp_service.send_otp(recipient).await?;
+
+    let message = match sent.channel {
+        Some("email") => "OTP sent via email",
+        Some(_) => "OTP sent via WhatsApp",
+        None => "OTP generated",
+    };
 
     Ok(Json(SendOtpResponse {
         success: true,
-        message: "OTP sent via WhatsApp".to_string(),
-        reference_id,
-        expires_in,
+        message: message.to_string(),
+        reference_id: sent.reference_id,
+        expires_in: sent.expires_in,
+        debug_code: sent.debug_code,
     }))
 }
 
 /// Handler for verifying an OTP code.
+#[utoipa::path(
+    post,
+    path = "/api/auth/otp/verify",
+    tag = "auth",
+    request_body = VerifyOtpRequest,
+    responses(
+        (status = 200, description = "Phone or email verified", body = VerifyOtpResponse),
+        (status = 400, description = "Invalid, expired or exhausted code", body = ErrorResponse),
+        (status = 404, description = "Unknown reference ID", body = ErrorResponse),
+    )
+)]
 pub async fn verify_otp_handler(
     State(state): State<AuthState>,
     Json(payload): Json<VerifyOtpRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let phone = payload.phone.trim().to_string();
+    let (contact, label) = match (non_empty(payload.phone), non_empty(payload.email)) {
+        (Some(phone), _) => (phone, "Phone"),
+        (None, Some(email)) => (email, "Email"),
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "Phone number or email is required".to_string(),
+            ));
+        }
+    };
     let code = payload.code.trim().to_string();
     let reference_id = payload.reference_id.trim().to_string();
 
     let verified = state
         .otp_service
-        .verify_otp(&reference_id, &phone, &code)
+        .verify_otp(&reference_id, &contact, &code)
         .await?;
 
     if verified {
         Ok(Json(VerifyOtpResponse {
             success: true,
-            message: "Phone verified successfully".to_string(),
+            message: format!("{} verified successfully", label),
             verified: true,
         }))
     } else {
@@ -190,11 +303,124 @@ pub async fn verify_otp_handler(
     }
 }
 
-pub fn auth_routes(state: AuthState) -> Router {
-    Router::new()
+/// Handler returning the delivery status of an OTP, so the client can offer
+/// another channel when the message failed or never arrived.
+#[utoipa::path(
+    get,
+    path = "/api/auth/otp/{reference_id}/status",
+    tag = "auth",
+    params(("reference_id" = String, Path, description = "Reference ID returned when the OTP was sent")),
+    responses(
+        (status = 200, description = "State of the OTP", body = OtpStatusResponse),
+        (status = 404, description = "Unknown or expired reference ID", body = ErrorResponse),
+    )
+)]
+pub async fn otp_status_handler(
+    State(state): State<AuthState>,
+    Path(reference_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = state.otp_service.status(&reference_id).await?;
+    Ok(Json(OtpStatusResponse {
+        reference_id,
+        delivery_status: status.delivery,
+        expires_in: status.expires_in,
+        verified: status.verified,
+    }))
+}
+
+/// Handler returning the profile of the authenticated user.
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The user's profile", body = User),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "User no longer exists", body = ErrorResponse),
+    )
+)]
+pub async fn me_handler(
+    State(state): State<AuthState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = state.service.get_user(claims.sub).await?;
+    Ok(Json(user))
+}
+
+/// Handler for an admin marking a user as verified.
+#[utoipa::path(
+    post,
+    path = "/api/auth/users/{id}/verify",
+    tag = "auth",
+    params(("id" = Uuid, Path, description = "User ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The verified user", body = User),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    )
+)]
+pub async fn verify_user_handler(
+    State(state): State<AuthState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = state.service.verify_user(claims.role, id).await?;
+    Ok(Json(user))
+}
+
+/// Handler for an admin deactivating a user.
+#[utoipa::path(
+    post,
+    path = "/api/auth/users/{id}/deactivate",
+    tag = "auth",
+    params(("id" = Uuid, Path, description = "User ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The deactivated user", body = User),
+        (status = 400, description = "Caller tried to deactivate themselves", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    )
+)]
+pub async fn deactivate_user_handler(
+    State(state): State<AuthState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = state.service.deactivate_user(&claims, id).await?;
+    Ok(Json(user))
+}
+
+/// Trims an optional input, treating blank values as absent.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+pub fn auth_routes(state: AuthState, auth_middleware_state: MiddlewareAuthState) -> Router {
+    let protected_routes = Router::new()
+        .route("/me", get(me_handler))
+        .route("/users/{id}/verify", post(verify_user_handler))
+        .route("/users/{id}/deactivate", post(deactivate_user_handler))
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ));
+
+    let public_routes = Router::new()
         .route("/register", post(register_handler))
         .route("/login", post(login_handler))
         .route("/otp/send", post(send_otp_handler))
         .route("/otp/verify", post(verify_otp_handler))
+        .route("/otp/{reference_id}/status", get(otp_status_handler));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
         .with_state(state)
 }
@@ -0,0 +1,189 @@
+//! Complaint routes.
+
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::routing::patch;
+use axum::routing::post;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::error::AppError;
+use crate::routes::created::Created;
+use crate::routes::json::Json;
+use crate::routes::pagination::PaginationParams;
+use crate::service::complaint::ComplaintListQuery;
+use crate::service::complaint::ComplaintService;
+use crate::service::complaint::CreateCommentRequest;
+use crate::service::complaint::CreateComplaintRequest;
+use crate::service::complaint::CreateEvidenceRequest;
+use crate::service::complaint::UpdateComplaintRequest;
+
+/// State for complaint routes.
+#[derive(Clone)]
+pub struct ComplaintState {
+    /// The complaint service.
+    pub service: Arc<ComplaintService>,
+}
+
+#[derive(Deserialize)]
+pub struct ListComplaintsQuery {
+    #[serde(rename = "kitchenId", alias = "kitchen_id")]
+    pub kitchen_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "assignedTo", alias = "assigned_to")]
+    pub assigned_to: Option<Uuid>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Handler for listing complaints, most urgent first (admin only).
+pub async fn list_complaints_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+    pagination: PaginationParams,
+    Query(query): Query<ListComplaintsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let PaginationParams { limit, offset, .. } = pagination;
+
+    let response = state
+        .service
+        .list_complaints(
+            claims.role,
+            limit,
+            offset,
+            ComplaintListQuery {
+                kitchen_id: query.kitchen_id,
+                category: query.category,
+                status: query.status,
+                assigned_to: query.assigned_to,
+                from: query.from,
+                to: query.to,
+            },
+        )
+        .await?;
+    Ok(Json(response))
+}
+
+/// Handler for filing a complaint.
+pub async fn create_complaint_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateComplaintRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let complaint_id = state.service.create_complaint(&claims, payload).await?;
+    Ok(Created::new(
+        format!("/api/complaints/{}", complaint_id),
+        serde_json::json!({
+            "id": complaint_id,
+            "message": "Complaint submitted successfully"
+        }),
+    ))
+}
+
+/// Handler for commenting on a complaint (reporter or admin).
+pub async fn add_comment_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let comment_id = state.service.add_comment(id, &claims, payload).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": comment_id,
+            "message": "Comment added successfully"
+        })),
+    ))
+}
+
+/// Handler for attaching evidence to a complaint (reporter or admin).
+pub async fn add_evidence_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateEvidenceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let evidence_id = state.service.add_evidence(id, &claims, payload).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": evidence_id,
+            "message": "Evidence added successfully"
+        })),
+    ))
+}
+
+/// Handler for moving a complaint to its next status (admin only).
+pub async fn update_complaint_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateComplaintRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    state
+        .service
+        .update_status(id, claims.role, payload)
+        .await?;
+    Ok(Json(serde_json::json!({
+        "message": "Complaint updated successfully"
+    })))
+}
+
+/// Handler for deleting a complaint (admin only).
+pub async fn delete_complaint_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    state.service.delete_complaint(id, &claims).await?;
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Complaint deleted successfully"
+    })))
+}
+
+/// Handler for listing complaints past their SLA deadline (admin only).
+pub async fn list_sla_breached_handler(
+    State(state): State<ComplaintState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.list_sla_breached(claims.role).await?;
+    Ok(Json(response))
+}
+
+pub fn complaint_routes(
+    state: ComplaintState,
+    auth_middleware_state: MiddlewareAuthState,
+) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(list_complaints_handler).post(create_complaint_handler),
+        )
+        .route("/sla-breached", get(list_sla_breached_handler))
+        .route(
+            "/{id}",
+            patch(update_complaint_handler).delete(delete_complaint_handler),
+        )
+        .route("/{id}/comments", post(add_comment_handler))
+        .route("/{id}/evidence", post(add_evidence_handler))
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ))
+        .with_state(state)
+}
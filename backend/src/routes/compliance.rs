@@ -0,0 +1,78 @@
+//! Kitchen compliance metric routes.
+
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::routing::post;
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::service::compliance::ComplianceService;
+use crate::service::compliance::RecordComplianceRequest;
+
+/// State for compliance routes.
+#[derive(Clone)]
+pub struct ComplianceState {
+    /// The compliance service.
+    pub service: Arc<ComplianceService>,
+}
+
+/// Handler for recording a kitchen's compliance metrics (admin only).
+pub async fn record_compliance_handler(
+    State(state): State<ComplianceState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RecordComplianceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let metric_id = state
+        .service
+        .record_compliance(id, claims.role, payload)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": metric_id,
+            "message": "Compliance metrics recorded successfully"
+        })),
+    ))
+}
+
+/// Handler for getting a kitchen's latest compliance metrics.
+pub async fn get_compliance_handler(
+    State(state): State<ComplianceState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.get_latest_compliance(id).await?;
+    Ok(Json(response))
+}
+
+/// Routes nested under `/kitchens`, merged into the kitchen router.
+pub fn kitchen_compliance_routes(
+    state: ComplianceState,
+    auth_middleware_state: MiddlewareAuthState,
+) -> Router {
+    let protected_routes = Router::new()
+        .route("/{id}/compliance", post(record_compliance_handler))
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ));
+
+    let public_routes = Router::new().route("/{id}/compliance", get(get_compliance_handler));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .with_state(state)
+}
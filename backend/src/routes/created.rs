@@ -0,0 +1,41 @@
+//! `201 Created` responses pointing at the new resource.
+
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::Serialize;
+
+use crate::routes::json::Json;
+
+/// A `201 Created` JSON response whose `Location` header holds the path of
+/// the created resource, e.g. `/api/reviews/{id}`, so clients can fetch it
+/// without parsing the body.
+#[derive(Debug, Clone)]
+pub struct Created<T> {
+    /// Absolute path of the new resource.
+    pub location: String,
+    /// The response body.
+    pub body: T,
+}
+
+impl<T> Created<T> {
+    /// Creates a response for the resource at `location`.
+    pub fn new(location: impl Into<String>, body: T) -> Self {
+        Self {
+            location: location.into(),
+            body,
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Created<T> {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::CREATED,
+            [(header::LOCATION, self.location)],
+            Json(self.body),
+        )
+            .into_response()
+    }
+}
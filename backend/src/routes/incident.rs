@@ -2,18 +2,36 @@
 
 use std::sync::Arc;
 
+use axum::Extension;
 use axum::Router;
 use axum::extract::Path;
 use axum::extract::Query;
 use axum::extract::State;
+use axum::http::StatusCode;
+use axum::middleware;
 use axum::response::IntoResponse;
-use axum::response::Json;
+use axum::routing::delete;
 use axum::routing::get;
+use axum::routing::patch;
+use axum::routing::post;
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
 use crate::error::AppError;
+use crate::routes::created::Created;
+use crate::routes::json::Json;
+use crate::routes::pagination::IncidentList;
+use crate::routes::pagination::PaginationParams;
+use crate::service::incident::CreateAffectedInstitutionRequest;
+use crate::service::incident::CreateCorrectiveActionRequest;
+use crate::service::incident::CreateIncidentRequest;
+use crate::service::incident::CreateLabResultRequest;
+use crate::service::incident::CreateTimelineEventRequest;
 use crate::service::incident::IncidentService;
+use crate::service::incident::UpdateIncidentStatusRequest;
 
 /// State for incident routes.
 #[derive(Clone)]
@@ -24,8 +42,7 @@ pub struct IncidentState {
 
 #[derive(Deserialize)]
 pub struct ListIncidentsQuery {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
+    pub q: Option<String>,
     pub status: Option<String>,
     pub province: Option<String>,
     #[serde(rename = "dateFrom")]
@@ -34,21 +51,39 @@ pub struct ListIncidentsQuery {
     pub date_to: Option<String>,
     #[serde(rename = "minVictims")]
     pub min_victims: Option<i32>,
+    /// Opaque keyset cursor; when present (even empty), offset is ignored.
+    pub cursor: Option<String>,
 }
 
 /// Handler for listing incidents.
 pub async fn list_incidents_handler(
     State(state): State<IncidentState>,
+    pagination: PaginationParams<IncidentList>,
     Query(query): Query<ListIncidentsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let limit = query.limit.unwrap_or(100).min(500);
-    let offset = query.offset.unwrap_or(0);
+    let PaginationParams { limit, offset, .. } = pagination;
+
+    if let Some(cursor) = query.cursor.as_deref() {
+        let response = state
+            .service
+            .list_incidents_after_cursor(
+                limit,
+                cursor,
+                query.q,
+                query.status,
+                query.province,
+                query.min_victims,
+            )
+            .await?;
+        return Ok(Json(response));
+    }
 
     let response = state
         .service
         .list_incidents(
             limit,
             offset,
+            query.q,
             query.status,
             query.province,
             query.date_from,
@@ -68,9 +103,153 @@ pub async fn get_incident_detail_handler(
     Ok(Json(response))
 }
 
-pub fn incident_routes(state: IncidentState) -> Router {
-    Router::new()
+/// Handler for reporting an incident (admin only).
+pub async fn create_incident_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateIncidentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.create_incident(&claims, payload).await?;
+    if response.deduplicated {
+        return Ok(Json(response.detail).into_response());
+    }
+    Ok(Created::new(
+        format!("/api/incidents/{}", response.detail.incident.id),
+        response.detail,
+    )
+    .into_response())
+}
+
+/// Handler for deleting an incident (admin only).
+pub async fn delete_incident_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    state.service.delete_incident(id, &claims).await?;
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Incident deleted successfully"
+    })))
+}
+
+/// Handler for adding an event to an incident's timeline (admin only).
+pub async fn add_timeline_event_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateTimelineEventRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let event_id = state
+        .service
+        .add_timeline_event(id, claims.role, payload)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": event_id,
+            "message": "Timeline event added successfully"
+        })),
+    ))
+}
+
+/// Handler for recording a laboratory result (admin only).
+pub async fn add_lab_result_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateLabResultRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let result_id = state
+        .service
+        .add_lab_result(id, claims.role, payload)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": result_id,
+            "message": "Laboratory result added successfully"
+        })),
+    ))
+}
+
+/// Handler for recording an affected institution (admin only).
+pub async fn add_affected_institution_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateAffectedInstitutionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let institution_id = state
+        .service
+        .add_affected_institution(id, claims.role, payload)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": institution_id,
+            "message": "Affected institution added successfully"
+        })),
+    ))
+}
+
+/// Handler for recording a corrective action (admin only).
+pub async fn add_corrective_action_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateCorrectiveActionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let action_id = state
+        .service
+        .add_corrective_action(id, claims.role, payload)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": action_id,
+            "message": "Corrective action added successfully"
+        })),
+    ))
+}
+
+/// Handler for changing an incident's status (admin only).
+pub async fn update_incident_status_handler(
+    State(state): State<IncidentState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateIncidentStatusRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.update_status(id, &claims, payload).await?;
+    Ok(Json(response))
+}
+
+pub fn incident_routes(state: IncidentState, auth_middleware_state: MiddlewareAuthState) -> Router {
+    let protected_routes = Router::new()
+        .route("/", post(create_incident_handler))
+        .route("/{id}", delete(delete_incident_handler))
+        .route("/{id}/status", patch(update_incident_status_handler))
+        .route("/{id}/timeline", post(add_timeline_event_handler))
+        .route("/{id}/lab-results", post(add_lab_result_handler))
+        .route(
+            "/{id}/affected-institutions",
+            post(add_affected_institution_handler),
+        )
+        .route(
+            "/{id}/corrective-actions",
+            post(add_corrective_action_handler),
+        )
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ));
+
+    let public_routes = Router::new()
         .route("/", get(list_incidents_handler))
-        .route("/{id}", get(get_incident_detail_handler))
+        .route("/{id}", get(get_incident_detail_handler));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
         .with_state(state)
 }
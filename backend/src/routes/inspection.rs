@@ -0,0 +1,124 @@
+//! Kitchen inspection routes.
+
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::routing::post;
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::routes::multipart::FileUploads;
+use crate::routes::multipart::UploadLimits;
+use crate::routes::multipart::upload_layers;
+use crate::routes::precondition::IfMatch;
+use crate::service::inspection::CreateInspectionRequest;
+use crate::service::inspection::InspectionService;
+
+/// State for inspection routes.
+#[derive(Clone)]
+pub struct InspectionState {
+    /// The inspection service.
+    pub service: Arc<InspectionService>,
+}
+
+/// Handler for recording an inspection with its findings (admin only).
+pub async fn create_inspection_handler(
+    State(state): State<InspectionState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateInspectionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let inspection_id = state
+        .service
+        .create_inspection(claims.role, payload)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": inspection_id,
+            "message": "Inspection recorded successfully"
+        })),
+    ))
+}
+
+/// Handler for attaching files to an inspection (admin only).
+///
+/// Accepts `multipart/form-data` with one or more file parts and responds
+/// with the inspection's full attachment list. A file over the size limit
+/// rejects the whole request.
+pub async fn upload_inspection_attachments_handler(
+    State(state): State<InspectionState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_updated_at): IfMatch,
+    FileUploads(files): FileUploads,
+) -> Result<impl IntoResponse, AppError> {
+    let files = files
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|rejected| AppError::BadRequest(rejected.reason))?;
+    let response = state
+        .service
+        .upload_attachments(claims.role, id, files, expected_updated_at)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Handler for getting an inspection with its findings.
+pub async fn get_inspection_handler(
+    State(state): State<InspectionState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.get_inspection(id).await?;
+    Ok(Json(response))
+}
+
+/// Handler for listing a kitchen's inspections.
+pub async fn list_kitchen_inspections_handler(
+    State(state): State<InspectionState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.list_kitchen_inspections(id).await?;
+    Ok(Json(response))
+}
+
+pub fn inspection_routes(
+    state: InspectionState,
+    auth_middleware_state: MiddlewareAuthState,
+) -> Router {
+    let protected_routes = Router::new()
+        .route("/", post(create_inspection_handler))
+        .route(
+            "/{id}/attachments",
+            post(upload_inspection_attachments_handler)
+                .layer(upload_layers(UploadLimits::INSPECTION_ATTACHMENTS)),
+        )
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ));
+
+    let public_routes = Router::new().route("/{id}", get(get_inspection_handler));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .with_state(state)
+}
+
+/// Routes nested under `/kitchens`, merged into the kitchen router.
+pub fn kitchen_inspection_routes(state: InspectionState) -> Router {
+    Router::new()
+        .route("/{id}/inspections", get(list_kitchen_inspections_handler))
+        .with_state(state)
+}
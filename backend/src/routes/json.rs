@@ -0,0 +1,154 @@
+//! JSON request bodies with field-level error reporting.
+
+use axum::extract::FromRequest;
+use axum::extract::Request;
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::AppError;
+
+/// Drop-in replacement for [`axum::Json`].
+///
+/// As a response it behaves exactly like `axum::Json`. As an extractor it
+/// rejects bodies that don't match `T` with [`AppError::InvalidBody`], naming
+/// the offending field (e.g. `ratings.taste`) and the type that was expected,
+/// instead of axum's plain-text rejection.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // Parse into a `Value` first so syntax errors are reported separately
+        // from values of the wrong shape
+        let axum::Json(value) = axum::Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(rejection_error)?;
+        serde_path_to_error::deserialize(value)
+            .map(Json)
+            .map_err(data_error)
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// Maps axum's rejection for bodies that aren't JSON at all, or are larger
+/// than the route's `DefaultBodyLimit`.
+fn rejection_error(rejection: JsonRejection) -> AppError {
+    if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return AppError::PayloadTooLarge("Request body is too large".to_string());
+    }
+    let message = match &rejection {
+        JsonRejection::MissingJsonContentType(_) => {
+            "Expected request with `Content-Type: application/json`".to_string()
+        }
+        JsonRejection::JsonSyntaxError(_) => "Request body is not valid JSON".to_string(),
+        _ => rejection.body_text(),
+    };
+    AppError::InvalidBody {
+        message,
+        field: None,
+        expected: None,
+    }
+}
+
+/// Maps a body that is valid JSON but doesn't match the target type.
+fn data_error(err: serde_path_to_error::Error<serde_json::Error>) -> AppError {
+    let path = err.path().to_string();
+    let detail = err.inner().to_string();
+
+    // Missing fields are reported at their parent's path
+    let field = match missing_field(&detail) {
+        Some(name) if path == "." => Some(name.to_string()),
+        Some(name) => Some(format!("{}.{}", path, name)),
+        None if path == "." => None,
+        None => Some(path),
+    };
+    let expected = detail
+        .split_once("expected ")
+        .map(|(_, expected)| expected.to_string());
+
+    let message = match &field {
+        Some(field) => format!("Invalid value for `{}`: {}", field, detail),
+        None => format!("Invalid request body: {}", detail),
+    };
+    AppError::InvalidBody {
+        message,
+        field,
+        expected,
+    }
+}
+
+/// Extracts `name` from serde's "missing field `name`" message.
+fn missing_field(detail: &str) -> Option<&str> {
+    detail
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(name, _)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Ratings {
+        taste: f64,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Body {
+        name: String,
+        ratings: Ratings,
+    }
+
+    fn parse(json: serde_json::Value) -> AppError {
+        data_error(serde_path_to_error::deserialize::<_, Body>(json).unwrap_err())
+    }
+
+    #[test]
+    fn test_wrong_type_names_field_and_expected_type() {
+        let err = parse(serde_json::json!({"name": "x", "ratings": {"taste": "five"}}));
+        match err {
+            AppError::InvalidBody {
+                field, expected, ..
+            } => {
+                assert_eq!(field.as_deref(), Some("ratings.taste"));
+                assert_eq!(expected.as_deref(), Some("f64"));
+            }
+            other => panic!("Expected InvalidBody, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_field_names_full_path() {
+        let err = parse(serde_json::json!({"name": "x", "ratings": {}}));
+        assert!(matches!(
+            err,
+            AppError::InvalidBody { field: Some(ref f), expected: None, .. } if f == "ratings.taste"
+        ));
+
+        let err = parse(serde_json::json!({"ratings": {"taste": 4.0}}));
+        assert!(matches!(
+            err,
+            AppError::InvalidBody { field: Some(ref f), .. } if f == "name"
+        ));
+    }
+}
@@ -2,18 +2,46 @@
 
 use std::sync::Arc;
 
+use axum::Extension;
 use axum::Router;
+use axum::body::Body;
+use axum::body::Bytes;
+use axum::extract::DefaultBodyLimit;
 use axum::extract::Path;
 use axum::extract::Query;
 use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::header;
+use axum::middleware;
 use axum::response::IntoResponse;
-use axum::response::Json;
 use axum::routing::get;
+use axum::routing::patch;
+use axum::routing::post;
 use serde::Deserialize;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
 use crate::error::AppError;
+use crate::routes::created::Created;
+use crate::routes::json::Json;
+use crate::routes::openapi::ErrorResponse;
+use crate::routes::pagination::KitchenList;
+use crate::routes::pagination::PaginationParams;
+use crate::routes::precondition::IfMatch;
+use crate::service::export::ExportFormat;
+use crate::service::kitchen::BatchKitchensResponse;
+use crate::service::kitchen::CreateKitchenRequest;
+use crate::service::kitchen::KitchenDetailDto;
+use crate::service::kitchen::KitchenDto;
+use crate::service::kitchen::KitchenImportFormat;
+use crate::service::kitchen::KitchenImportResponse;
 use crate::service::kitchen::KitchenService;
+use crate::service::kitchen::KitchenStatsDto;
+use crate::service::kitchen::UpdateKitchenRequest;
+use crate::service::pagination::PaginatedResponse;
 
 /// State for kitchen routes.
 #[derive(Clone)]
@@ -22,29 +50,62 @@ pub struct KitchenState {
     pub service: Arc<KitchenService>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct ListKitchensQuery {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
     pub q: Option<String>,
     pub loc: Option<String>,
     pub r#type: Option<String>,
     #[serde(rename = "minRating")]
     pub min_rating: Option<f64>,
+    /// Latitude of the point to search around; requires `lng` and `radius`.
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    /// Search radius in km.
+    pub radius: Option<f64>,
+    /// One of `rating`, `name`, `mealsServed`, `totalReviews`, `createdAt`.
+    #[serde(rename = "sortBy")]
+    pub sort_by: Option<String>,
+    /// `asc` or `desc`.
+    pub order: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct BatchKitchensQuery {
     pub ids: String,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct ExportReviewsQuery {
+    /// `csv` (default) or `json`.
+    pub format: Option<String>,
+}
+
 /// Handler for listing kitchens.
+#[utoipa::path(
+    get,
+    path = "/api/kitchens",
+    tag = "kitchens",
+    params(PaginationParams, ListKitchensQuery),
+    responses(
+        (status = 200, description = "A page of kitchens", body = PaginatedResponse<KitchenDto>),
+        (status = 400, description = "Invalid filter, sort or pagination", body = ErrorResponse),
+    )
+)]
 pub async fn list_kitchens_handler(
     State(state): State<KitchenState>,
+    pagination: PaginationParams<KitchenList>,
     Query(query): Query<ListKitchensQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let PaginationParams { limit, offset, .. } = pagination;
+    let near = match (query.lat, query.lng, query.radius) {
+        (Some(lat), Some(lng), Some(radius)) => Some((lat, lng, radius)),
+        (None, None, None) => None,
+        _ => {
+            return Err(AppError::BadRequest(
+                "lat, lng and radius must be given together".into(),
+            ));
+        }
+    };
 
     let response = state
         .service
@@ -55,12 +116,26 @@ pub async fn list_kitchens_handler(
             query.loc,
             query.r#type,
             query.min_rating,
+            near,
+            query.sort_by,
+            query.order,
         )
         .await?;
 
     Ok(Json(response))
 }
 
+/// Handler for getting a kitchen's details.
+#[utoipa::path(
+    get,
+    path = "/api/kitchens/{id}",
+    tag = "kitchens",
+    params(("id" = Uuid, Path, description = "Kitchen ID")),
+    responses(
+        (status = 200, description = "The kitchen", body = KitchenDetailDto),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_kitchen_detail_handler(
     State(state): State<KitchenState>,
     Path(id): Path<Uuid>,
@@ -69,6 +144,17 @@ pub async fn get_kitchen_detail_handler(
     Ok(Json(response))
 }
 
+/// Handler for getting a kitchen's review statistics.
+#[utoipa::path(
+    get,
+    path = "/api/kitchens/{id}/stats",
+    tag = "kitchens",
+    params(("id" = Uuid, Path, description = "Kitchen ID")),
+    responses(
+        (status = 200, description = "The kitchen's statistics", body = KitchenStatsDto),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_kitchen_stats_handler(
     State(state): State<KitchenState>,
     Path(id): Path<Uuid>,
@@ -77,6 +163,89 @@ pub async fn get_kitchen_stats_handler(
     Ok(Json(response))
 }
 
+/// Handler for downloading a kitchen's printable PDF report.
+#[utoipa::path(
+    get,
+    path = "/api/kitchens/{id}/report.pdf",
+    tag = "kitchens",
+    params(("id" = Uuid, Path, description = "Kitchen ID")),
+    responses(
+        (status = 200, description = "The report", content_type = "application/pdf", body = Vec<u8>),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_kitchen_report_handler(
+    State(state): State<KitchenState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let report = state.service.kitchen_report_pdf(id).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", report.file_name),
+            ),
+        ],
+        report.content,
+    ))
+}
+
+/// Handler for exporting a kitchen's reviews (admin or the kitchen's owner).
+///
+/// The export is streamed as reviews are read, so it is never buffered whole.
+#[utoipa::path(
+    get,
+    path = "/api/kitchens/{id}/reviews/export",
+    tag = "kitchens",
+    params(("id" = Uuid, Path, description = "Kitchen ID"), ExportReviewsQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The reviews, oldest first",
+            content((String = "text/csv"), (serde_json::Value = "application/json"))),
+        (status = 400, description = "Unknown format", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor the owner", body = ErrorResponse),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+    )
+)]
+pub async fn export_kitchen_reviews_handler(
+    State(state): State<KitchenState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportReviewsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let format = ExportFormat::parse(query.format.as_deref())?;
+    let export = state
+        .service
+        .export_reviews(id, claims.sub, claims.role, format)
+        .await?;
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                export.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", export.file_name),
+            ),
+        ],
+        Body::from_stream(export.content),
+    ))
+}
+
+/// Handler for getting up to 50 kitchens by ID.
+#[utoipa::path(
+    get,
+    path = "/api/kitchens/batch",
+    tag = "kitchens",
+    params(BatchKitchensQuery),
+    responses(
+        (status = 200, description = "The kitchens found and the IDs that weren't", body = BatchKitchensResponse),
+        (status = 400, description = "No valid IDs given", body = ErrorResponse),
+    )
+)]
 pub async fn get_multiple_kitchens_handler(
     State(state): State<KitchenState>,
     Query(query): Query<BatchKitchensQuery>,
@@ -92,20 +261,147 @@ pub async fn get_multiple_kitchens_handler(
         return Err(AppError::BadRequest("No valid IDs provided".into()));
     }
 
-    let kitchens = state.service.get_multiple_kitchens(ids).await?;
+    let response = state.service.get_multiple_kitchens(ids).await?;
+    Ok(Json(response))
+}
+
+/// Handler for registering a kitchen (admin only).
+#[utoipa::path(
+    post,
+    path = "/api/kitchens",
+    tag = "kitchens",
+    request_body = CreateKitchenRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Kitchen created, as `{ id, message }`", body = serde_json::Value,
+            headers(("Location" = String, description = "Path of the new kitchen"))),
+        (status = 400, description = "Invalid kitchen fields", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+    )
+)]
+pub async fn create_kitchen_handler(
+    State(state): State<KitchenState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateKitchenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let kitchen_id = state.service.create_kitchen(claims.role, payload).await?;
+    Ok(Created::new(
+        format!("/api/kitchens/{}", kitchen_id),
+        serde_json::json!({
+            "id": kitchen_id,
+            "message": "Kitchen created successfully"
+        }),
+    ))
+}
 
-    // The API schema expects { data: [...], notFound: [...] }
-    // For now, just returning the list as per service implementation
-    // We can enhance this later to match exact schema if needed
+/// Handler for bulk-importing kitchens from JSON or CSV (admin only).
+///
+/// The format is picked from the `Content-Type` header: `application/json`
+/// for an array of kitchens, `text/csv` for a file with a header row.
+#[utoipa::path(
+    post,
+    path = "/api/kitchens/import",
+    tag = "kitchens",
+    request_body(
+        content((Vec<CreateKitchenRequest> = "application/json"), (String = "text/csv")),
+        description = "Kitchens to create or update, matched by name and city",
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Per-row import report", body = KitchenImportResponse),
+        (status = 400, description = "Unsupported content type, unreadable body, or too many rows", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is not an admin", body = ErrorResponse),
+    )
+)]
+pub async fn import_kitchens_handler(
+    State(state): State<KitchenState>,
+    Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let mime = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase());
+    let format = match mime.as_deref() {
+        Some("application/json") => KitchenImportFormat::Json,
+        Some("text/csv") => KitchenImportFormat::Csv,
+        _ => {
+            return Err(AppError::BadRequest(
+                "Content-Type must be application/json or text/csv".into(),
+            ));
+        }
+    };
+    let report = state
+        .service
+        .import_kitchens(claims.role, format, &body)
+        .await?;
+    Ok(Json(report))
+}
 
-    Ok(Json(serde_json::json!({ "data": kitchens })))
+/// Handler for updating a kitchen (admin or the kitchen's owner).
+#[utoipa::path(
+    patch,
+    path = "/api/kitchens/{id}",
+    tag = "kitchens",
+    params(
+        ("id" = Uuid, Path, description = "Kitchen ID"),
+        ("If-Match" = Option<String>, Header, description = "`updatedAt` of the copy being edited; a stale one gets 409"),
+    ),
+    request_body = UpdateKitchenRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The updated kitchen", body = KitchenDetailDto),
+        (status = 400, description = "Invalid kitchen fields", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither an admin nor the owner", body = ErrorResponse),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+        (status = 409, description = "Kitchen was modified since `If-Match` or concurrently", body = ErrorResponse),
+    )
+)]
+pub async fn update_kitchen_handler(
+    State(state): State<KitchenState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_updated_at): IfMatch,
+    Json(payload): Json<UpdateKitchenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state
+        .service
+        .update_kitchen(id, claims.sub, claims.role, payload, expected_updated_at)
+        .await?;
+    Ok(Json(response))
 }
 
-pub fn kitchen_routes(state: KitchenState) -> Router {
-    Router::new()
+/// Largest kitchen import file accepted, in bytes.
+const MAX_IMPORT_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+pub fn kitchen_routes(state: KitchenState, auth_middleware_state: MiddlewareAuthState) -> Router {
+    let protected_routes = Router::new()
+        .route("/", post(create_kitchen_handler))
+        .route(
+            "/import",
+            post(import_kitchens_handler).layer(DefaultBodyLimit::max(MAX_IMPORT_BODY_BYTES)),
+        )
+        .route("/{id}", patch(update_kitchen_handler))
+        .route("/{id}/reviews/export", get(export_kitchen_reviews_handler))
+        .layer(middleware::from_fn_with_state(
+            auth_middleware_state,
+            auth_middleware,
+        ));
+
+    let public_routes = Router::new()
         .route("/", get(list_kitchens_handler))
         .route("/batch", get(get_multiple_kitchens_handler))
         .route("/{id}", get(get_kitchen_detail_handler))
         .route("/{id}/stats", get(get_kitchen_stats_handler))
+        .route("/{id}/report.pdf", get(get_kitchen_report_handler));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
         .with_state(state)
 }
@@ -0,0 +1,38 @@
+//! Prometheus scrape endpoint.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+use crate::database::Database;
+
+/// State for the metrics route.
+#[derive(Clone)]
+pub struct MetricsState {
+    /// Handle to the installed Prometheus recorder.
+    pub handle: PrometheusHandle,
+    /// The database, whose pool utilization is sampled on each scrape.
+    pub db: Arc<Database>,
+}
+
+/// Handler rendering all recorded metrics in the Prometheus text format.
+pub async fn metrics_handler(State(state): State<MetricsState>) -> impl IntoResponse {
+    metrics::gauge!("db_pool_connections").set(state.db.pool_size() as f64);
+    metrics::gauge!("db_pool_idle_connections").set(state.db.pool_idle() as f64);
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.handle.render(),
+    )
+}
+
+pub fn metrics_routes(state: MetricsState) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(state)
+}
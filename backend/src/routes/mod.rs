@@ -2,9 +2,23 @@
 //!
 //! This module contains the route handlers and router configurations for the API.
 
+pub mod alert;
+pub mod audit;
 pub mod auth;
+pub mod complaint;
+pub mod compliance;
+pub mod created;
 pub mod incident;
+pub mod inspection;
+pub mod json;
 pub mod kitchen;
+pub mod metrics;
+pub mod multipart;
+pub mod openapi;
+pub mod pagination;
+pub mod precondition;
 pub mod review;
 pub mod stats;
 pub mod utility;
+pub mod video;
+pub mod webhook;
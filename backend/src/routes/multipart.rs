@@ -0,0 +1,176 @@
+//! `multipart/form-data` extractor for file upload endpoints.
+use axum::Extension;
+use axum::extract::DefaultBodyLimit;
+use axum::extract::FromRequest;
+use axum::extract::Multipart;
+use axum::extract::Request;
+use axum::extract::multipart::Field;
+use axum::extract::multipart::MultipartError;
+
+use crate::error::AppError;
+use crate::service::utility::RejectedFile;
+use crate::service::utility::UploadedFile;
+
+/// Content type recorded for file parts that don't declare one.
+pub const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Room left in the body limit for multipart boundaries and part headers.
+const FRAMING_BYTES: usize = 64 * 1024;
+
+/// Per-request bounds of a file upload.
+///
+/// Handlers read them from the request extensions, so routes set them with
+/// `.layer(Extension(limits))`; [`UploadLimits::default`] applies otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadLimits {
+    /// Most file parts a request may contain.
+    pub max_files: usize,
+    /// Largest accepted file, in bytes. Larger files are reported as
+    /// rejected rather than failing the request.
+    pub max_file_bytes: usize,
+    /// Most file bytes a request may carry in total.
+    pub max_total_bytes: usize,
+}
+
+impl UploadLimits {
+    /// Limits of `POST /upload/image`.
+    pub const SINGLE_IMAGE: Self = Self {
+        max_files: 1,
+        max_file_bytes: 5 * 1024 * 1024,
+        max_total_bytes: 5 * 1024 * 1024,
+    };
+
+    /// Limits of `POST /upload/images`.
+    pub const MULTIPLE_IMAGES: Self = Self {
+        max_files: 5,
+        max_file_bytes: 5 * 1024 * 1024,
+        max_total_bytes: 20 * 1024 * 1024,
+    };
+
+    /// Limits of `POST /inspections/{id}/attachments`; signed reports are
+    /// often scanned PDFs, so files may be larger than images.
+    pub const INSPECTION_ATTACHMENTS: Self = Self {
+        max_files: 5,
+        max_file_bytes: 10 * 1024 * 1024,
+        max_total_bytes: 25 * 1024 * 1024,
+    };
+
+    /// Request body limit that fits [`Self::max_total_bytes`] of files plus
+    /// multipart framing, for use with `DefaultBodyLimit::max`.
+    pub fn body_limit(&self) -> usize {
+        self.max_total_bytes + FRAMING_BYTES
+    }
+}
+
+/// Applies `limits` to an upload route, including its request body limit.
+pub fn upload_layers(limits: UploadLimits) -> (Extension<UploadLimits>, DefaultBodyLimit) {
+    (
+        Extension(limits),
+        DefaultBodyLimit::max(limits.body_limit()),
+    )
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self::MULTIPLE_IMAGES
+    }
+}
+
+/// File parts of a `multipart/form-data` request, in the order they were sent.
+///
+/// Every part must be a file, i.e. carry a `filename`; any other part rejects
+/// the request with [`AppError::BadRequest`], as do more than
+/// [`UploadLimits::max_files`] files or more than
+/// [`UploadLimits::max_total_bytes`] in total. A file larger than
+/// [`UploadLimits::max_file_bytes`] is drained and returned as a
+/// [`RejectedFile`] so the rest of the request can still be processed.
+#[derive(Debug)]
+pub struct FileUploads(pub Vec<Result<UploadedFile, RejectedFile>>);
+
+impl<S: Send + Sync> FromRequest<S> for FileUploads {
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let limits = req
+            .extensions()
+            .get::<UploadLimits>()
+            .copied()
+            .unwrap_or_default();
+        let mut multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(|e| AppError::BadRequest(e.body_text()))?;
+
+        let mut files = Vec::new();
+        let mut total_bytes = 0;
+        while let Some(field) = multipart.next_field().await.map_err(bad_request)? {
+            let Some(file_name) = field.file_name().map(str::to_string) else {
+                return Err(AppError::BadRequest(format!(
+                    "Field '{}' is not a file",
+                    field.name().unwrap_or_default()
+                )));
+            };
+            if files.len() >= limits.max_files {
+                return Err(AppError::BadRequest(format!(
+                    "Maximum {} files allowed",
+                    limits.max_files
+                )));
+            }
+            let content_type = field
+                .content_type()
+                .unwrap_or(DEFAULT_CONTENT_TYPE)
+                .to_string();
+
+            let data = read_file(field, &limits, &mut total_bytes).await?;
+            files.push(match data {
+                Some(data) => Ok(UploadedFile {
+                    file_name,
+                    content_type,
+                    data,
+                }),
+                None => Err(RejectedFile {
+                    reason: format!(
+                        "File '{}' exceeds {}MB limit",
+                        file_name,
+                        limits.max_file_bytes / (1024 * 1024)
+                    ),
+                    file_name,
+                }),
+            });
+        }
+
+        Ok(Self(files))
+    }
+}
+
+/// Reads a file part chunk by chunk, adding its size to `total_bytes`.
+///
+/// Returns `None` once the file outgrows [`UploadLimits::max_file_bytes`],
+/// after discarding the rest of it.
+async fn read_file(
+    mut field: Field<'_>,
+    limits: &UploadLimits,
+    total_bytes: &mut usize,
+) -> Result<Option<Vec<u8>>, AppError> {
+    let mut data = Some(Vec::new());
+    while let Some(chunk) = field.chunk().await.map_err(bad_request)? {
+        *total_bytes += chunk.len();
+        if *total_bytes > limits.max_total_bytes {
+            return Err(AppError::BadRequest(format!(
+                "Upload exceeds {}MB in total",
+                limits.max_total_bytes / (1024 * 1024)
+            )));
+        }
+        if let Some(buf) = &mut data {
+            if buf.len() + chunk.len() > limits.max_file_bytes {
+                data = None;
+            } else {
+                buf.extend_from_slice(&chunk);
+            }
+        }
+    }
+    Ok(data)
+}
+
+fn bad_request(e: MultipartError) -> AppError {
+    AppError::BadRequest(e.body_text())
+}
@@ -0,0 +1,103 @@
+//! OpenAPI document and Swagger UI.
+//!
+//! Handlers are described with `#[utoipa::path]` next to their definitions
+//! and DTOs derive `ToSchema`; [`ApiDoc`] collects them. Paths are written
+//! with the `/api` prefix the router nests them under.
+
+use axum::Router;
+use serde::Serialize;
+use utoipa::Modify;
+use utoipa::OpenApi;
+use utoipa::ToSchema;
+use utoipa::openapi::security::HttpAuthScheme;
+use utoipa::openapi::security::HttpBuilder;
+use utoipa::openapi::security::SecurityScheme;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::routes::auth;
+use crate::routes::kitchen;
+use crate::routes::review;
+
+/// Name of the JWT bearer security scheme referenced by protected endpoints.
+pub const BEARER_AUTH: &str = "bearer_auth";
+
+/// Body of every error response.
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Human-readable description of the error.
+    pub error: String,
+}
+
+/// Registers the JWT bearer scheme.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BEARER_AUTH,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// OpenAPI document of the documented endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "MonitorMBG API",
+        description = "Food safety monitoring for the Makan Bergizi Gratis programme."
+    ),
+    paths(
+        auth::register_handler,
+        auth::login_handler,
+        auth::send_otp_handler,
+        auth::verify_otp_handler,
+        auth::otp_status_handler,
+        auth::me_handler,
+        auth::verify_user_handler,
+        auth::deactivate_user_handler,
+        kitchen::list_kitchens_handler,
+        kitchen::get_multiple_kitchens_handler,
+        kitchen::get_kitchen_detail_handler,
+        kitchen::get_kitchen_stats_handler,
+        kitchen::get_kitchen_report_handler,
+        kitchen::export_kitchen_reviews_handler,
+        kitchen::create_kitchen_handler,
+        kitchen::import_kitchens_handler,
+        kitchen::update_kitchen_handler,
+        review::submit_review_handler,
+        review::submit_batch_reviews_handler,
+        review::get_kitchen_reviews_handler,
+        review::get_public_reviews_handler,
+        review::get_my_drafts_handler,
+        review::update_review_handler,
+        review::get_review_history_handler,
+        review::upload_review_photos_handler,
+        review::delete_review_handler,
+    ),
+    components(schemas(ErrorResponse)),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "auth", description = "Registration, login and OTP verification"),
+        (name = "kitchens", description = "Kitchen directory and statistics"),
+        (name = "reviews", description = "HACCP reviews of kitchens"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Serves the OpenAPI document at `/api/openapi.json` and Swagger UI at
+/// `/api/docs`.
+///
+/// Paths are absolute, so merge this router at the top level rather than
+/// nesting it under `/api`.
+pub fn openapi_routes() -> Router {
+    SwaggerUi::new("/api/docs")
+        .url("/api/openapi.json", ApiDoc::openapi())
+        .into()
+}
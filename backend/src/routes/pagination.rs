@@ -0,0 +1,178 @@
+//! Shared `limit`/`offset` query parameters for list endpoints.
+use std::marker::PhantomData;
+
+use axum::extract::FromRequestParts;
+use axum::extract::Query;
+use axum::http::request::Parts;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::config::ListEndpoint;
+use crate::config::PaginationConfig;
+use crate::error::AppError;
+
+/// Picks the page size a list uses when a request omits `limit`.
+///
+/// Handlers choose it through the type parameter of [`PaginationParams`].
+pub trait PageDefault {
+    /// The default page size under `config`.
+    fn default_limit(config: &PaginationConfig) -> i64;
+}
+
+/// [`PaginationConfig::default_limit`], for lists without a default of their
+/// own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnyList;
+
+impl PageDefault for AnyList {
+    fn default_limit(config: &PaginationConfig) -> i64 {
+        config.default_limit
+    }
+}
+
+/// The kitchen list's default page size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KitchenList;
+
+impl PageDefault for KitchenList {
+    fn default_limit(config: &PaginationConfig) -> i64 {
+        config.default_limit_for(ListEndpoint::Kitchens)
+    }
+}
+
+/// The review lists' default page size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReviewList;
+
+impl PageDefault for ReviewList {
+    fn default_limit(config: &PaginationConfig) -> i64 {
+        config.default_limit_for(ListEndpoint::Reviews)
+    }
+}
+
+/// The incident list's default page size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncidentList;
+
+impl PageDefault for IncidentList {
+    fn default_limit(config: &PaginationConfig) -> i64 {
+        config.default_limit_for(ListEndpoint::Incidents)
+    }
+}
+
+/// Effective page bounds of a list request.
+///
+/// Extracted from the `limit` and `offset` query parameters. A missing
+/// `limit` falls back to the default picked by `L` and a larger one is
+/// clamped to the configured maximum; a missing `offset` is 0. Zero or
+/// negative limits and negative offsets are rejected with
+/// [`AppError::BadRequest`].
+///
+/// Limits come from the [`PaginationConfig`] request extension, or its
+/// defaults when the router doesn't provide one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoParams)]
+pub struct PaginationParams<L: PageDefault = AnyList> {
+    /// Number of items to return, already clamped.
+    pub limit: i64,
+    /// Number of items to skip.
+    pub offset: i64,
+    #[param(ignore)]
+    _list: PhantomData<L>,
+}
+
+#[derive(Deserialize)]
+struct RawPagination {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl<L: PageDefault> PaginationParams<L> {
+    /// Applies `default_limit` to a missing `limit`, then caps it at
+    /// `max_limit`.
+    fn resolve(
+        limit: Option<i64>,
+        offset: Option<i64>,
+        default_limit: i64,
+        max_limit: i64,
+    ) -> Result<Self, AppError> {
+        let limit = limit.unwrap_or(default_limit);
+        if limit < 1 {
+            return Err(AppError::BadRequest("limit must be at least 1".into()));
+        }
+        let offset = offset.unwrap_or(0);
+        if offset < 0 {
+            return Err(AppError::BadRequest("offset must not be negative".into()));
+        }
+
+        Ok(Self {
+            limit: limit.min(max_limit),
+            offset,
+            _list: PhantomData,
+        })
+    }
+}
+
+impl<S: Send + Sync, L: PageDefault> FromRequestParts<S> for PaginationParams<L> {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(raw) = Query::<RawPagination>::try_from_uri(&parts.uri)
+            .map_err(|_| AppError::BadRequest("limit and offset must be integers".into()))?;
+        let config = parts
+            .extensions
+            .get::<PaginationConfig>()
+            .cloned()
+            .unwrap_or_default();
+
+        Self::resolve(
+            raw.limit,
+            raw.offset,
+            L::default_limit(&config),
+            config.max_limit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(
+        limit: Option<i64>,
+        offset: Option<i64>,
+        default_limit: i64,
+    ) -> Result<(i64, i64), AppError> {
+        PaginationParams::<AnyList>::resolve(limit, offset, default_limit, 100)
+            .map(|p| (p.limit, p.offset))
+    }
+
+    #[test]
+    fn test_resolve_defaults_and_clamps() {
+        assert_eq!(resolve(None, None, 20).unwrap(), (20, 0));
+        assert_eq!(resolve(Some(200), Some(40), 20).unwrap(), (100, 40));
+        // The default is a soft value; the cap still applies to it
+        assert_eq!(resolve(None, None, 150).unwrap(), (100, 0));
+    }
+
+    #[test]
+    fn test_resolve_rejects_negative_values() {
+        assert!(resolve(Some(0), None, 20).is_err());
+        assert!(resolve(Some(-5), None, 20).is_err());
+        assert!(resolve(None, Some(-1), 20).is_err());
+    }
+
+    #[test]
+    fn test_endpoint_defaults_fall_back_to_overall_default() {
+        let config = PaginationConfig {
+            default_limit: 25,
+            kitchens_default_limit: Some(12),
+            reviews_default_limit: None,
+            ..Default::default()
+        };
+
+        assert_eq!(AnyList::default_limit(&config), 25);
+        assert_eq!(KitchenList::default_limit(&config), 12);
+        assert_eq!(ReviewList::default_limit(&config), 25);
+        assert_eq!(IncidentList::default_limit(&config), 50);
+    }
+}
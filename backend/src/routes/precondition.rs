@@ -0,0 +1,78 @@
+//! `If-Match` preconditions for optimistic concurrency on updates.
+use axum::extract::FromRequestParts;
+use axum::http::header;
+use axum::http::request::Parts;
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::error::AppError;
+
+/// The `updatedAt` of the copy a client edited, from its `If-Match` header.
+///
+/// Clients send the resource's `updatedAt` as returned by the API, optionally
+/// quoted like an entity tag. An update made from a stale copy then fails with
+/// `409 Conflict` instead of overwriting a change the client never saw.
+/// `None` when the header is absent or `*`, in which case the update only
+/// guards against changes made while the server handles the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfMatch(pub Option<DateTime<Utc>>);
+
+impl IfMatch {
+    fn parse(value: &str) -> Result<Self, AppError> {
+        let value = value.trim();
+        if value == "*" {
+            return Ok(Self(None));
+        }
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        DateTime::parse_from_rfc3339(value)
+            .map(|version| Self(Some(version.to_utc())))
+            .map_err(|_| {
+                AppError::BadRequest(
+                    "If-Match must be the updatedAt timestamp of the resource".into(),
+                )
+            })
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.headers.get(header::IF_MATCH) {
+            None => Ok(Self(None)),
+            Some(value) => Self::parse(value.to_str().map_err(|_| {
+                AppError::BadRequest(
+                    "If-Match must be the updatedAt timestamp of the resource".into(),
+                )
+            })?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_if_match() {
+        let expected = DateTime::parse_from_rfc3339("2026-03-01T12:00:00.123456Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(
+            IfMatch::parse("2026-03-01T12:00:00.123456Z").unwrap(),
+            IfMatch(Some(expected))
+        );
+        assert_eq!(
+            IfMatch::parse("\"2026-03-01T19:00:00.123456+07:00\"").unwrap(),
+            IfMatch(Some(expected))
+        );
+        assert_eq!(IfMatch::parse("*").unwrap(), IfMatch(None));
+        assert!(matches!(
+            IfMatch::parse("W/\"abc\""),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
@@ -4,23 +4,41 @@ use std::sync::Arc;
 
 use axum::Extension;
 use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::extract::Multipart;
 use axum::extract::Path;
 use axum::extract::Query;
 use axum::extract::State;
 use axum::middleware;
 use axum::response::IntoResponse;
-use axum::response::Json;
 use axum::routing::get;
 use axum::routing::patch;
 use axum::routing::post;
 use serde::Deserialize;
+use utoipa::IntoParams;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::auth::middleware::AuthState as MiddlewareAuthState;
 use crate::auth::middleware::auth_middleware;
 use crate::auth::utils::Claims;
 use crate::error::AppError;
+use crate::middleware::audit::AuditState;
+use crate::middleware::audit::audit_middleware;
+use crate::routes::created::Created;
+use crate::routes::json::Json;
+use crate::routes::openapi::ErrorResponse;
+use crate::routes::pagination::PaginationParams;
+use crate::routes::pagination::ReviewList;
+use crate::routes::precondition::IfMatch;
+use crate::service::review::BatchMode;
+use crate::service::review::BatchReviewResponse;
 use crate::service::review::CreateReviewRequest;
+use crate::service::review::MAX_PHOTO_BYTES;
+use crate::service::review::ReviewDto;
+use crate::service::review::ReviewHistoryResponse;
+use crate::service::review::ReviewListResponse;
+use crate::service::review::ReviewPhotosResponse;
 use crate::service::review::ReviewService;
 use crate::service::review::UpdateReviewRequest;
 
@@ -31,10 +49,8 @@ pub struct ReviewState {
     pub service: Arc<ReviewService>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct ListReviewsQuery {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
     pub verified: Option<bool>,
     #[serde(rename = "minRating")]
     pub min_rating: Option<f64>,
@@ -42,58 +58,166 @@ pub struct ListReviewsQuery {
     pub reviewer_type: Option<String>,
     pub sort: Option<String>,
     pub order: Option<String>,
+    /// Opaque keyset cursor; when present (even empty), offset is ignored.
+    pub cursor: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+pub struct ForceQuery {
+    /// Lets moderators modify a verified review.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Deserialize, IntoParams)]
 pub struct PublicReviewsQuery {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
     pub province: Option<String>,
     #[serde(rename = "minRating")]
     pub min_rating: Option<f64>,
+    /// Opaque keyset cursor; when present (even empty), offset is ignored.
+    pub cursor: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct BatchReviewsRequest {
     pub reviews: Vec<CreateReviewRequest>,
+    #[serde(default)]
+    pub mode: BatchMode,
+}
+
+/// `multipart/form-data` body of a review photo upload.
+#[derive(ToSchema)]
+pub struct ReviewPhotosForm {
+    /// Image files of at most 5MB each; `photos[]` is accepted too.
+    #[schema(value_type = Vec<String>, format = Binary)]
+    pub photos: Vec<Vec<u8>>,
 }
 
 /// Handler for submitting a review.
+#[utoipa::path(
+    post,
+    path = "/api/reviews",
+    tag = "reviews",
+    request_body = CreateReviewRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Review created", body = ReviewDto,
+            headers(("Location" = String, description = "Path of the new review"))),
+        (status = 400, description = "Invalid ratings or fields, or the review is verified", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+        (status = 409, description = "Kitchen reviewed too recently by this user", body = ErrorResponse),
+    )
+)]
 pub async fn submit_review_handler(
     State(state): State<ReviewState>,
     Extension(claims): Extension<Claims>,
     Json(payload): Json<CreateReviewRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let response = state.service.submit_review(claims.sub, payload).await?;
-    Ok((axum::http::StatusCode::CREATED, Json(response)))
+    Ok(Created::new(
+        format!("/api/reviews/{}", response.id),
+        response,
+    ))
 }
 
+/// Handler for listing a kitchen's reviews.
+#[utoipa::path(
+    get,
+    path = "/api/reviews/kitchen/{kitchenId}",
+    tag = "reviews",
+    params(
+        ("kitchenId" = Uuid, Path, description = "Kitchen ID"),
+        PaginationParams,
+        ListReviewsQuery,
+    ),
+    responses(
+        (status = 200, description = "A page of reviews", body = ReviewListResponse),
+        (status = 400, description = "Invalid cursor or pagination", body = ErrorResponse),
+        (status = 404, description = "Kitchen not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_kitchen_reviews_handler(
     State(state): State<ReviewState>,
     Path(kitchen_id): Path<Uuid>,
+    pagination: PaginationParams<ReviewList>,
     Query(query): Query<ListReviewsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let PaginationParams { limit, offset, .. } = pagination;
 
     let response = state
         .service
-        .get_kitchen_reviews(kitchen_id, limit, offset)
+        .get_kitchen_reviews(kitchen_id, limit, offset, query.cursor.as_deref())
         .await?;
     Ok(Json(response))
 }
 
+/// Handler for listing recent reviews across all kitchens.
+#[utoipa::path(
+    get,
+    path = "/api/reviews/public",
+    tag = "reviews",
+    params(PaginationParams, PublicReviewsQuery),
+    responses(
+        (status = 200, description = "A page of reviews", body = ReviewListResponse),
+        (status = 400, description = "Invalid cursor or pagination", body = ErrorResponse),
+    )
+)]
 pub async fn get_public_reviews_handler(
     State(state): State<ReviewState>,
+    pagination: PaginationParams<ReviewList>,
     Query(query): Query<PublicReviewsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let PaginationParams { limit, offset, .. } = pagination;
 
-    let response = state.service.get_public_reviews(limit, offset).await?;
+    let response = state
+        .service
+        .get_public_reviews(limit, offset, query.cursor.as_deref())
+        .await?;
     Ok(Json(response))
 }
 
+/// Handler for listing the caller's own draft reviews.
+#[utoipa::path(
+    get,
+    path = "/api/reviews/drafts",
+    tag = "reviews",
+    params(PaginationParams),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "A page of the caller's drafts", body = ReviewListResponse),
+        (status = 400, description = "Invalid pagination", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
+pub async fn get_my_drafts_handler(
+    State(state): State<ReviewState>,
+    Extension(claims): Extension<Claims>,
+    pagination: PaginationParams<ReviewList>,
+) -> Result<impl IntoResponse, AppError> {
+    let PaginationParams { limit, offset, .. } = pagination;
+
+    let response = state
+        .service
+        .get_my_drafts(claims.sub, limit, offset)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Handler for submitting several reviews at once.
+#[utoipa::path(
+    post,
+    path = "/api/reviews/batch",
+    tag = "reviews",
+    request_body = BatchReviewsRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Batch processed; see each result", body = BatchReviewResponse),
+        (status = 400, description = "Empty or oversized batch", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 422, description = "`all_or_nothing` batch rolled back", body = BatchReviewResponse),
+    )
+)]
 pub async fn submit_batch_reviews_handler(
     State(state): State<ReviewState>,
     Extension(claims): Extension<Claims>,
@@ -101,44 +225,205 @@ pub async fn submit_batch_reviews_handler(
 ) -> Result<impl IntoResponse, AppError> {
     let response = state
         .service
-        .submit_batch_reviews(claims.sub, payload.reviews)
+        .submit_batch_reviews(claims.sub, payload.reviews, payload.mode)
         .await?;
-    Ok((axum::http::StatusCode::CREATED, Json(response)))
+    // A rolled back all_or_nothing batch created nothing
+    let status = if response.success {
+        axum::http::StatusCode::CREATED
+    } else {
+        axum::http::StatusCode::UNPROCESSABLE_ENTITY
+    };
+    Ok((status, Json(response)))
 }
 
+/// Handler for editing an unverified review.
+#[utoipa::path(
+    patch,
+    path = "/api/reviews/{id}",
+    tag = "reviews",
+    params(
+        ("id" = Uuid, Path, description = "Review ID"),
+        ForceQuery,
+        ("If-Match" = Option<String>, Header, description = "`updatedAt` of the copy being edited; a stale one gets 409"),
+    ),
+    request_body = UpdateReviewRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Review updated, as `{ id, updatedAt, message }`", body = serde_json::Value),
+        (status = 400, description = "Invalid ratings or fields, or the review is verified", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither the author nor a moderator", body = ErrorResponse),
+        (status = 404, description = "Review not found", body = ErrorResponse),
+        (status = 409, description = "Review was modified since `If-Match` or concurrently", body = ErrorResponse),
+    )
+)]
 pub async fn update_review_handler(
     State(state): State<ReviewState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
+    Query(query): Query<ForceQuery>,
+    IfMatch(expected_updated_at): IfMatch,
     Json(payload): Json<UpdateReviewRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = state.service.update_review(id, claims.sub, payload).await?;
+    let response = state
+        .service
+        .update_review(id, &claims, query.force, payload, expected_updated_at)
+        .await?;
     Ok(Json(serde_json::json!({
         "id": response.id,
+        "updatedAt": response.updated_at,
         "message": "Review updated successfully"
     })))
 }
 
+/// Handler for listing what a review said before each edit.
+#[utoipa::path(
+    get,
+    path = "/api/reviews/{id}/history",
+    tag = "reviews",
+    params(("id" = Uuid, Path, description = "Review ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Prior versions of the review, oldest first", body = ReviewHistoryResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither the author nor a moderator", body = ErrorResponse),
+        (status = 404, description = "Review not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_review_history_handler(
+    State(state): State<ReviewState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.get_edit_history(id, &claims).await?;
+    Ok(Json(response))
+}
+
+/// Handler for uploading photos to a review.
+///
+/// Accepts `multipart/form-data` with one or more `photos` (or `photos[]`)
+/// file fields and responds with the review's full photo list.
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{id}/photos",
+    tag = "reviews",
+    params(
+        ("id" = Uuid, Path, description = "Review ID"),
+        ("If-Match" = Option<String>, Header, description = "`updatedAt` of the copy being edited; a stale one gets 409"),
+    ),
+    request_body(content = ReviewPhotosForm, content_type = "multipart/form-data"),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The review's photos", body = ReviewPhotosResponse),
+        (status = 400, description = "Too many, too large or invalid photos", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is not the author, or the review is verified", body = ErrorResponse),
+        (status = 404, description = "Review not found", body = ErrorResponse),
+        (status = 409, description = "Review was modified since `If-Match` or during the upload", body = ErrorResponse),
+    )
+)]
+pub async fn upload_review_photos_handler(
+    State(state): State<ReviewState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_updated_at): IfMatch,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let max_photos = state.service.max_photos_per_review();
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.to_string()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        if name != "photos" && name != "photos[]" {
+            continue;
+        }
+        if files.len() >= max_photos {
+            return Err(AppError::BadRequest(format!(
+                "Maximum {} photos allowed",
+                max_photos
+            )));
+        }
+
+        let file_name = field.file_name().unwrap_or("unknown").to_string();
+        let data = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if data.len() > MAX_PHOTO_BYTES {
+            return Err(AppError::BadRequest(format!(
+                "File '{}' exceeds 5MB limit",
+                file_name
+            )));
+        }
+        files.push((file_name, data.to_vec()));
+    }
+
+    let response = state
+        .service
+        .upload_photos(id, claims.sub, files, expected_updated_at)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Handler for deleting an unverified review.
+#[utoipa::path(
+    delete,
+    path = "/api/reviews/{id}",
+    tag = "reviews",
+    params(("id" = Uuid, Path, description = "Review ID"), ForceQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Review deleted, as `{ success, message }`", body = serde_json::Value),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Caller is neither the author nor a moderator, or the review is verified", body = ErrorResponse),
+        (status = 404, description = "Review not found", body = ErrorResponse),
+    )
+)]
 pub async fn delete_review_handler(
     State(state): State<ReviewState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
+    Query(query): Query<ForceQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    state.service.delete_review(id, claims.sub).await?;
+    state
+        .service
+        .delete_review(id, &claims, query.force)
+        .await?;
     Ok(Json(serde_json::json!({
         "success": true,
         "message": "Review deleted successfully"
     })))
 }
 
-pub fn review_routes(state: ReviewState, auth_middleware_state: MiddlewareAuthState) -> Router {
+pub fn review_routes(
+    state: ReviewState,
+    auth_middleware_state: MiddlewareAuthState,
+    audit_state: AuditState,
+) -> Router {
+    // Room for a full set of photos plus multipart framing
+    let photo_body_limit = MAX_PHOTO_BYTES * state.service.max_photos_per_review() + 64 * 1024;
+
     let protected_routes = Router::new()
         .route("/", post(submit_review_handler))
+        .route(
+            "/{id}/photos",
+            post(upload_review_photos_handler).layer(DefaultBodyLimit::max(photo_body_limit)),
+        )
         .route("/batch", post(submit_batch_reviews_handler))
+        .route("/drafts", get(get_my_drafts_handler))
+        .route("/{id}/history", get(get_review_history_handler))
         .route(
             "/{id}",
             patch(update_review_handler).delete(delete_review_handler),
         )
+        .layer(middleware::from_fn_with_state(
+            audit_state,
+            audit_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             auth_middleware_state,
             auth_middleware,
@@ -12,6 +12,7 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::routes::pagination::PaginationParams;
 use crate::service::stats::StatsService;
 
 /// State for statistics routes.
@@ -27,6 +28,12 @@ pub struct NationalStatsQuery {
     pub month: Option<i32>,
 }
 
+#[derive(Deserialize)]
+pub struct NationalTimeseriesQuery {
+    pub months: Option<i32>,
+    pub province: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct RegionalStatsQuery {
     pub province: Option<String>,
@@ -35,6 +42,12 @@ pub struct RegionalStatsQuery {
     pub month: Option<i32>,
 }
 
+#[derive(Deserialize)]
+pub struct LeaderboardQuery {
+    pub province: Option<String>,
+    pub kabupaten: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct ComplianceTrendsQuery {
     pub province: Option<String>,
@@ -64,6 +77,18 @@ pub async fn get_national_stats_handler(
     Ok(Json(response))
 }
 
+/// Handler for getting national statistics month by month.
+pub async fn get_national_timeseries_handler(
+    State(state): State<StatsState>,
+    Query(query): Query<NationalTimeseriesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state
+        .service
+        .get_national_timeseries(query.months, query.province)
+        .await?;
+    Ok(Json(response))
+}
+
 pub async fn get_regional_stats_handler(
     State(state): State<StatsState>,
     Query(query): Query<RegionalStatsQuery>,
@@ -75,6 +100,24 @@ pub async fn get_regional_stats_handler(
     Ok(Json(response))
 }
 
+/// Handler for getting the kitchen leaderboard.
+pub async fn get_leaderboard_handler(
+    State(state): State<StatsState>,
+    Query(query): Query<LeaderboardQuery>,
+    pagination: PaginationParams,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state
+        .service
+        .get_leaderboard(
+            query.province,
+            query.kabupaten,
+            pagination.limit,
+            pagination.offset,
+        )
+        .await?;
+    Ok(Json(response))
+}
+
 pub async fn get_compliance_trends_handler(
     State(state): State<StatsState>,
     Query(query): Query<ComplianceTrendsQuery>,
@@ -105,7 +148,9 @@ pub async fn get_incident_trends_handler(
 pub fn stats_routes(state: StatsState) -> Router {
     Router::new()
         .route("/national", get(get_national_stats_handler))
+        .route("/national/timeseries", get(get_national_timeseries_handler))
         .route("/regional", get(get_regional_stats_handler))
+        .route("/leaderboard", get(get_leaderboard_handler))
         .route("/trends/compliance", get(get_compliance_trends_handler))
         .route("/trends/incidents", get(get_incident_trends_handler))
         .with_state(state)
@@ -2,18 +2,30 @@
 
 use std::sync::Arc;
 
+use axum::Extension;
 use axum::Router;
-use axum::extract::Multipart;
+use axum::body::Body;
+use axum::extract::Path;
 use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::Method;
+use axum::http::StatusCode;
+use axum::http::header;
 use axum::middleware;
 use axum::response::IntoResponse;
 use axum::response::Json;
+use axum::response::Response;
 use axum::routing::get;
 use axum::routing::post;
 
 use crate::auth::middleware::AuthState as MiddlewareAuthState;
 use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
 use crate::error::AppError;
+use crate::middleware::internal::internal_only;
+use crate::routes::multipart::FileUploads;
+use crate::routes::multipart::UploadLimits;
+use crate::routes::multipart::upload_layers;
 use crate::service::utility::UtilityService;
 
 /// State for utility routes.
@@ -23,124 +35,182 @@ pub struct UtilityState {
     pub service: Arc<UtilityService>,
 }
 
-/// Handler for system health check.
+/// Handler for the liveness probe.
+///
+/// Always responds `200 OK` while the process can serve requests, without
+/// checking dependencies, so a database outage doesn't get the pod restarted.
+pub async fn liveness_handler() -> impl IntoResponse {
+    Json(serde_json::json!({ "status": "alive" }))
+}
+
+/// Handler for system health check, also served as the readiness probe.
+///
+/// Responds with `503 Service Unavailable` (and the same body) when a critical
+/// dependency is unhealthy so load balancers can take the instance out of rotation.
 pub async fn health_check_handler(
     State(state): State<UtilityState>,
 ) -> Result<impl IntoResponse, AppError> {
     let response = state.service.health_check().await?;
+    let status = if response.status == "healthy" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((status, Json(response)))
+}
+
+/// Handler for the detailed health check (admin only).
+///
+/// Adds database pool usage and migration state to the [health
+/// check](health_check_handler) response, with the same status codes.
+pub async fn detailed_health_check_handler(
+    State(state): State<UtilityState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.detailed_health_check(claims.role).await?;
+    let status = if response.status == "healthy" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((status, Json(response)))
+}
+
+/// Handler for applying pending database migrations (admin only, from the
+/// server itself).
+pub async fn migrate_handler(
+    State(state): State<UtilityState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.run_migrations(&claims).await?;
     Ok(Json(response))
 }
 
-pub async fn upload_image_handler(
+/// Handler for purging data past its retention period (admin only).
+pub async fn cleanup_handler(
     State(state): State<UtilityState>,
-    mut multipart: Multipart,
+    Extension(claims): Extension<Claims>,
 ) -> Result<impl IntoResponse, AppError> {
-    // Simple single file upload handling
-    while let Some(field) =
-        multipart
-            .next_field()
-            .await
-            .map_err(|e: axum::extract::multipart::MultipartError| {
-                AppError::BadRequest(e.to_string())
-            })?
-    {
-        let name = field.name().unwrap_or("file").to_string();
-        let file_name = field.file_name().unwrap_or("unknown").to_string();
-        let content_type = field
-            .content_type()
-            .unwrap_or("application/octet-stream")
-            .to_string();
-
-        if name == "file" {
-            let data =
-                field
-                    .bytes()
-                    .await
-                    .map_err(|e: axum::extract::multipart::MultipartError| {
-                        AppError::BadRequest(e.to_string())
-                    })?;
-
-            if data.len() > 5 * 1024 * 1024 {
-                return Err(AppError::BadRequest("File too large".into()));
-            }
-
-            let response = state
-                .service
-                .upload_image(file_name, data.to_vec(), content_type)
-                .await?;
-            return Ok((axum::http::StatusCode::CREATED, Json(response)));
-        }
+    let response = state.service.cleanup(&claims).await?;
+    Ok(Json(response))
+}
+
+/// Handler for downloading a stored file.
+///
+/// The file is streamed to the client chunk by chunk, so large videos and PDFs
+/// are never buffered in memory. Responses carry an `ETag`; a request whose
+/// `If-None-Match` matches it gets `304 Not Modified` without the body, and so
+/// does every `HEAD` request (with `200 OK`).
+pub async fn download_file_handler(
+    State(state): State<UtilityState>,
+    Path(file_id): Path<String>,
+    method: Method,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let meta = state.service.file_meta(&file_id).await?;
+    let etag = format!("\"{}\"", meta.etag);
+    let last_modified = meta
+        .last_modified
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
+    let validators = [
+        (header::ETAG, etag.clone()),
+        (header::LAST_MODIFIED, last_modified),
+    ];
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, &etag));
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, validators).into_response());
+    }
+
+    let content = [
+        (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+        (header::CONTENT_LENGTH, meta.size.to_string()),
+    ];
+    if method == Method::HEAD {
+        return Ok((validators, content).into_response());
     }
 
-    Err(AppError::BadRequest("No file uploaded".into()))
+    let stream = state.service.download_file(&file_id).await?;
+    Ok((validators, content, Body::from_stream(stream)).into_response())
 }
 
-pub async fn upload_multiple_images_handler(
+/// Whether an `If-None-Match` header value matches `etag` under the weak
+/// comparison RFC 9110 prescribes for it.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// Handler for uploading a single image.
+///
+/// Accepts exactly one file part; a file over the size limit is rejected.
+pub async fn upload_image_handler(
     State(state): State<UtilityState>,
-    mut multipart: Multipart,
+    FileUploads(files): FileUploads,
 ) -> Result<impl IntoResponse, AppError> {
-    let mut files = Vec::new();
-    let mut file_count = 0;
-
-    while let Some(field) =
-        multipart
-            .next_field()
-            .await
-            .map_err(|e: axum::extract::multipart::MultipartError| {
-                AppError::BadRequest(e.to_string())
-            })?
-    {
-        let name = field.name().unwrap_or("files").to_string();
-        let file_name = field.file_name().unwrap_or("unknown").to_string();
-        let content_type = field
-            .content_type()
-            .unwrap_or("application/octet-stream")
-            .to_string();
-
-        // Handle both "files" and "files[]" field names
-        if name == "files" || name.starts_with("files[") {
-            if file_count >= 5 {
-                return Err(AppError::BadRequest("Maximum 5 files allowed".into()));
-            }
-
-            let data =
-                field
-                    .bytes()
-                    .await
-                    .map_err(|e: axum::extract::multipart::MultipartError| {
-                        AppError::BadRequest(e.to_string())
-                    })?;
-
-            if data.len() > 5 * 1024 * 1024 {
-                return Err(AppError::BadRequest(format!(
-                    "File '{}' exceeds 5MB limit",
-                    file_name
-                )));
-            }
-
-            files.push((file_name, data.to_vec(), content_type));
-            file_count += 1;
-        }
-    }
+    let file = match files.into_iter().next() {
+        Some(Ok(file)) => file,
+        Some(Err(rejected)) => return Err(AppError::BadRequest(rejected.reason)),
+        None => return Err(AppError::BadRequest("No file uploaded".into())),
+    };
+
+    let response = state
+        .service
+        .upload_image(file.file_name, file.data, file.content_type)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
 
+/// Handler for uploading several images at once.
+///
+/// Files that are too large or fail to store are reported per file in the
+/// response instead of failing the whole request.
+pub async fn upload_multiple_images_handler(
+    State(state): State<UtilityState>,
+    FileUploads(files): FileUploads,
+) -> Result<impl IntoResponse, AppError> {
     if files.is_empty() {
         return Err(AppError::BadRequest("No files uploaded".into()));
     }
 
     let response = state.service.upload_multiple_images(files).await?;
-    Ok((axum::http::StatusCode::CREATED, Json(response)))
+    Ok((StatusCode::CREATED, Json(response)))
 }
 
 pub fn utility_routes(state: UtilityState, auth_middleware_state: MiddlewareAuthState) -> Router {
     let protected_routes = Router::new()
-        .route("/upload/image", post(upload_image_handler))
-        .route("/upload/images", post(upload_multiple_images_handler))
+        .route("/health/detailed", get(detailed_health_check_handler))
+        .route(
+            "/admin/migrate",
+            post(migrate_handler).layer(middleware::from_fn(internal_only)),
+        )
+        .route("/admin/cleanup", post(cleanup_handler))
+        .route(
+            "/upload/image",
+            post(upload_image_handler).layer(upload_layers(UploadLimits::SINGLE_IMAGE)),
+        )
+        .route(
+            "/upload/images",
+            post(upload_multiple_images_handler)
+                .layer(upload_layers(UploadLimits::MULTIPLE_IMAGES)),
+        )
         .layer(middleware::from_fn_with_state(
             auth_middleware_state,
             auth_middleware,
         ));
 
-    let public_routes = Router::new().route("/health", get(health_check_handler));
+    let public_routes = Router::new()
+        .route("/health", get(health_check_handler))
+        .route("/health/live", get(liveness_handler))
+        .route("/health/ready", get(health_check_handler))
+        .route("/files/{*file_id}", get(download_file_handler));
 
     Router::new()
         .merge(public_routes)
@@ -0,0 +1,98 @@
+//! Training video library routes.
+
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::routing::post;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::auth::middleware::AuthState as MiddlewareAuthState;
+use crate::auth::middleware::auth_middleware;
+use crate::auth::utils::Claims;
+use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::routes::pagination::PaginationParams;
+use crate::service::video::CreateVideoRequest;
+use crate::service::video::VideoService;
+
+/// State for video routes.
+#[derive(Clone)]
+pub struct VideoState {
+    /// The video service.
+    pub service: Arc<VideoService>,
+}
+
+#[derive(Deserialize)]
+pub struct ListVideosQuery {
+    pub category: Option<String>,
+    #[serde(rename = "haccpRelevance", alias = "haccp_relevance")]
+    pub haccp_relevance: Option<String>,
+}
+
+/// Handler for listing videos.
+pub async fn list_videos_handler(
+    State(state): State<VideoState>,
+    pagination: PaginationParams,
+    Query(query): Query<ListVideosQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let PaginationParams { limit, offset, .. } = pagination;
+
+    let response = state
+        .service
+        .list_videos(limit, offset, query.category, query.haccp_relevance)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Handler for getting a single video.
+pub async fn get_video_handler(
+    State(state): State<VideoState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = state.service.get_video(id).await?;
+    Ok(Json(response))
+}
+
+/// Handler for adding a video to the library (admin only).
+pub async fn create_video_handler(
+    State(state): State<VideoState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateVideoRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let video_id = state.service.create_video(claims.role, payload).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "id": video_id,
+            "message": "Video added successfully"
+        })),
+    ))
+}
+
+pub fn video_routes(state: VideoState, auth_middleware_state: MiddlewareAuthState) -> Router {
+    let protected_routes =
+        Router::new()
+            .route("/", post(create_video_handler))
+            .layer(middleware::from_fn_with_state(
+                auth_middleware_state,
+                auth_middleware,
+            ));
+
+    let public_routes = Router::new()
+        .route("/", get(list_videos_handler))
+        .route("/{id}", get(get_video_handler));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .with_state(state)
+}
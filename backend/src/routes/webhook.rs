@@ -0,0 +1,114 @@
+//! Routes receiving callbacks from external providers.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use log::debug;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::routes::json::Json;
+use crate::service::otp::OtpService;
+use crate::service::whatsapp::SIGNATURE_HEADER;
+use crate::service::whatsapp::WhatsAppClient;
+use crate::service::whatsapp::parse_status_callback;
+
+/// State for webhook routes.
+#[derive(Clone)]
+pub struct WebhookState {
+    /// Verifies WhatsApp callback signatures.
+    pub whatsapp: WhatsAppClient,
+    /// Holds the OTPs whose delivery status is reported.
+    pub otp_service: Arc<OtpService>,
+}
+
+/// Response to a WhatsApp status callback.
+#[derive(Debug, Serialize)]
+pub struct WhatsAppWebhookResponse {
+    /// Statuses found in the callback.
+    pub received: usize,
+    /// Statuses that updated a pending OTP.
+    pub updated: usize,
+}
+
+/// Query of a WhatsApp webhook subscription request.
+#[derive(Debug, Deserialize)]
+pub struct WhatsAppSubscribeQuery {
+    #[serde(rename = "hub.mode", default)]
+    pub mode: String,
+    #[serde(rename = "hub.verify_token", default)]
+    pub verify_token: String,
+    #[serde(rename = "hub.challenge", default)]
+    pub challenge: String,
+}
+
+/// Handler for the subscription request Meta sends when the callback URL is
+/// registered. Echoes `hub.challenge` if the verify token matches.
+pub async fn whatsapp_subscribe_handler(
+    State(state): State<WebhookState>,
+    Query(query): Query<WhatsAppSubscribeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    state
+        .whatsapp
+        .verify_subscription(&query.mode, &query.verify_token)?;
+    Ok(query.challenge)
+}
+
+/// Handler for WhatsApp delivery status callbacks.
+///
+/// The raw body is read so its signature can be checked before parsing.
+/// Statuses for messages that aren't pending OTPs are acknowledged and
+/// dropped, since Meta retries callbacks that don't get a `200`.
+pub async fn whatsapp_webhook_handler(
+    State(state): State<WebhookState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok());
+    state.whatsapp.verify_signature(&body, signature)?;
+
+    let statuses = parse_status_callback(&body)?;
+    let mut updated = 0;
+    for status in &statuses {
+        let Some(reference_id) = &status.reference_id else {
+            continue;
+        };
+        if state
+            .otp_service
+            .record_delivery_status(reference_id, status.status)
+            .await
+        {
+            updated += 1;
+        } else {
+            debug!(
+                "Ignored {} status of WhatsApp message {} for {}",
+                status.status.as_str(),
+                status.message_id,
+                reference_id
+            );
+        }
+    }
+
+    Ok(Json(WhatsAppWebhookResponse {
+        received: statuses.len(),
+        updated,
+    }))
+}
+
+pub fn webhook_routes(state: WebhookState) -> Router {
+    Router::new()
+        .route(
+            "/whatsapp",
+            get(whatsapp_subscribe_handler).post(whatsapp_webhook_handler),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,102 @@
+//! Compliance alert service.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::utils::Claims;
+use crate::database::Database;
+use crate::database::model::UserRole;
+use crate::error::AppError;
+
+/// Maximum number of alerts acknowledged in one request.
+pub const MAX_ACKNOWLEDGE_IDS: usize = 100;
+
+/// Request body for acknowledging alerts in bulk.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AcknowledgeAlertsRequest {
+    pub ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AcknowledgeAlertsResponse {
+    /// Number of alerts now acknowledged, including ones that already were.
+    pub acknowledged: usize,
+    /// Number of IDs that don't exist or that the caller may not acknowledge.
+    #[serde(rename = "notFound")]
+    pub not_found: usize,
+    #[serde(rename = "notFoundIds")]
+    pub not_found_ids: Vec<Uuid>,
+}
+
+/// Service for compliance alerts.
+pub struct AlertService {
+    db: Arc<Database>,
+}
+
+impl AlertService {
+    /// Creates a new `AlertService`.
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Acknowledges the alerts in `req.ids`.
+    ///
+    /// Admins may acknowledge any alert; kitchen users only alerts for kitchens
+    /// they own. Other alerts are reported as not found, as are unknown IDs.
+    /// Duplicate IDs are counted once.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for users who are neither admins nor kitchen users,
+    /// and `BadRequest` if no IDs or more than [`MAX_ACKNOWLEDGE_IDS`] are
+    /// given.
+    pub async fn acknowledge_alerts(
+        &self,
+        claims: &Claims,
+        req: AcknowledgeAlertsRequest,
+    ) -> Result<AcknowledgeAlertsResponse, AppError> {
+        let owner_id = match claims.role {
+            UserRole::Admin => None,
+            UserRole::Kitchen => Some(claims.sub),
+            _ => {
+                return Err(AppError::Forbidden(
+                    "Only administrators and kitchen owners can acknowledge alerts".into(),
+                ));
+            }
+        };
+
+        let mut seen = HashSet::new();
+        let ids: Vec<Uuid> = req.ids.into_iter().filter(|id| seen.insert(*id)).collect();
+        if ids.is_empty() {
+            return Err(AppError::BadRequest("ids must not be empty".into()));
+        }
+        if ids.len() > MAX_ACKNOWLEDGE_IDS {
+            return Err(AppError::BadRequest(format!(
+                "At most {} alerts can be acknowledged at once",
+                MAX_ACKNOWLEDGE_IDS
+            )));
+        }
+
+        let acknowledged: HashSet<Uuid> = self
+            .db
+            .alert_table
+            .acknowledge(&ids, owner_id)
+            .await?
+            .into_iter()
+            .collect();
+        let not_found_ids: Vec<Uuid> = ids
+            .into_iter()
+            .filter(|id| !acknowledged.contains(id))
+            .collect();
+
+        Ok(AcknowledgeAlertsResponse {
+            acknowledged: acknowledged.len(),
+            not_found: not_found_ids.len(),
+            not_found_ids,
+        })
+    }
+}
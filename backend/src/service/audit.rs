@@ -0,0 +1,213 @@
+//! Audit trail service.
+
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::utils::Claims;
+use crate::database::Database;
+use crate::database::model::AuditLog;
+use crate::database::model::UserRole;
+use crate::database::table::AuditLogFilter;
+use crate::database::table::Table;
+use crate::error::AppError;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::pagination::PaginatedResponse;
+use crate::service::pagination::Pagination;
+
+#[derive(Debug, Serialize)]
+pub struct AuditLogDto {
+    pub id: Uuid,
+    #[serde(rename = "userId")]
+    pub user_id: Option<Uuid>,
+    #[serde(rename = "userName")]
+    pub user_name: Option<String>,
+    pub action: String,
+    #[serde(rename = "entityType")]
+    pub entity_type: String,
+    #[serde(rename = "entityId")]
+    pub entity_id: String,
+    pub timestamp: Option<String>,
+    #[serde(rename = "ipAddress")]
+    pub ip_address: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Raw filters for [`AuditService::list_audit_logs`], as given in the query
+/// string.
+#[derive(Debug, Default)]
+pub struct AuditLogQuery {
+    pub user_id: Option<Uuid>,
+    pub entity_type: Option<String>,
+    pub action: Option<String>,
+    /// `YYYY-MM-DD` or RFC 3339 timestamp.
+    pub from: Option<String>,
+    /// `YYYY-MM-DD` (inclusive of the whole day) or RFC 3339 timestamp.
+    pub to: Option<String>,
+}
+
+/// Service for browsing the audit trail.
+pub struct AuditService {
+    db: Arc<Database>,
+}
+
+impl AuditService {
+    /// Creates a new `AuditService`.
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Lists audit log entries, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `BadRequest` if `from` or `to`
+    /// is not a valid date, or `from` is after `to`.
+    pub async fn list_audit_logs(
+        &self,
+        role: UserRole,
+        limit: i64,
+        offset: i64,
+        query: AuditLogQuery,
+    ) -> Result<PaginatedResponse<AuditLogDto>, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can view audit logs".into(),
+            ));
+        }
+
+        let from = query
+            .from
+            .as_deref()
+            .map(|v| parse_bound(v, "from", false))
+            .transpose()?;
+        let until = query
+            .to
+            .as_deref()
+            .map(|v| parse_bound(v, "to", true))
+            .transpose()?;
+        if let (Some(from), Some(until)) = (from, until)
+            && from >= until
+        {
+            return Err(AppError::BadRequest("from must be before to".into()));
+        }
+
+        let filter = AuditLogFilter {
+            user_id: query.user_id,
+            entity_type: non_empty(query.entity_type),
+            action: non_empty(query.action),
+            from,
+            until,
+        };
+        let (logs, total) = self.db.audit_log_table.list(&filter, limit, offset).await?;
+
+        Ok(PaginatedResponse {
+            data: logs.into_iter().map(map_to_dto).collect(),
+            pagination: Pagination::new(total, limit, offset),
+        })
+    }
+}
+
+/// Builds the `audit_logs` entry recorded when `claims`' user deletes the
+/// `entity_type` with id `entity_id`.
+pub(crate) async fn deletion_entry(
+    db: &Database,
+    claims: &Claims,
+    entity_type: &str,
+    entity_id: Uuid,
+) -> Result<AuditLog, AppError> {
+    // `user_id` references `users`, so it's only set for users still on record
+    let user = db.user_table.select(&claims.sub).await?;
+    Ok(AuditLog {
+        user_id: user.as_ref().map(|u| u.id),
+        user_name: user.map(|u| u.name),
+        action: format!("DELETE {}", entity_type),
+        entity_type: entity_type.to_string(),
+        entity_id: entity_id.to_string(),
+        timestamp: Some(Utc::now()),
+        ..Default::default()
+    })
+}
+
+pub(crate) fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Parses a date range bound given as `YYYY-MM-DD` or an RFC 3339 timestamp.
+///
+/// Timestamps are converted to UTC. A date-only upper bound is exclusive of
+/// the following midnight, so `to=2025-01-31` includes all of January 31.
+pub(crate) fn parse_bound(value: &str, name: &str, upper: bool) -> Result<DateTime<Utc>, AppError> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let date = if upper {
+            date.checked_add_days(Days::new(1))
+        } else {
+            Some(date)
+        };
+        if let Some(start) = date.and_then(|d| d.and_hms_opt(0, 0, 0)) {
+            return Ok(start.and_utc());
+        }
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.to_utc())
+        .map_err(|_| {
+            AppError::BadRequest(format!(
+                "{} must be a YYYY-MM-DD date or ISO 8601 timestamp",
+                name
+            ))
+        })
+}
+
+fn map_to_dto(log: AuditLog) -> AuditLogDto {
+    AuditLogDto {
+        id: log.id,
+        user_id: log.user_id,
+        user_name: log.user_name,
+        action: log.action,
+        entity_type: log.entity_type,
+        entity_id: log.entity_id,
+        timestamp: log.timestamp.map(utc_to_rfc3339),
+        ip_address: log.ip_address,
+        metadata: log.metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bound() {
+        let midnight = |y, m, d| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        assert_eq!(
+            parse_bound("2025-01-31", "from", false).unwrap(),
+            midnight(2025, 1, 31)
+        );
+        assert_eq!(
+            parse_bound("2025-01-31", "to", true).unwrap(),
+            midnight(2025, 2, 1)
+        );
+        assert_eq!(
+            parse_bound("2025-01-31T07:00:00+07:00", "to", true).unwrap(),
+            midnight(2025, 1, 31)
+        );
+        assert!(matches!(
+            parse_bound("yesterday", "from", false),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
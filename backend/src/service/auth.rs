@@ -1,11 +1,22 @@
 //! Authentication service.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
 
 use log::error;
+use log::info;
+use log::warn;
+use tokio::sync::RwLock;
+use uuid::Uuid;
 
+use crate::auth::utils::Claims;
+use crate::auth::utils::JwtKeys;
 use crate::auth::utils::generate_token;
 use crate::auth::utils::hash_password;
+use crate::auth::utils::needs_rehash;
 use crate::auth::utils::verify_password;
 use crate::config::Config;
 use crate::database::Database;
@@ -14,25 +25,58 @@ use crate::database::model::User;
 use crate::database::model::UserRole;
 use crate::database::table::Table;
 use crate::error::AppError;
+use crate::service::phone::parse_phone;
+
+/// Argon2id hash of a throwaway password, made with the default
+/// [`PasswordHashConfig`](crate::config::PasswordHashConfig) parameters.
+///
+/// Logins for unknown unique codes are verified against it, so they take as
+/// long as logins with a wrong password.
+const DUMMY_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$l09BGBfzsLZd6Je9yOeDDA$2H0Cw8O46AW0LxukzPH+jqXmpu6Ud12/llgHGKVaKQs";
+
+/// Failed login attempts recorded for a single unique code.
+#[derive(Clone, Copy, Debug)]
+struct FailedLogins {
+    count: u32,
+    window_start: Instant,
+}
 
 /// Service for handling authentication and user registration.
 #[derive(Clone)]
 pub struct AuthService {
     db: Arc<Database>,
     config: Arc<Config>,
+    /// Keys tokens are signed with.
+    keys: Arc<JwtKeys>,
+    /// In-memory failed login attempts (unique_code -> FailedLogins).
+    failed_logins: Arc<RwLock<HashMap<String, FailedLogins>>>,
+    /// Stand-in for [`DUMMY_PASSWORD_HASH`] under non-default hashing
+    /// parameters, computed on first use.
+    dummy_hash: Arc<OnceLock<String>>,
 }
 
 impl AuthService {
-    /// Creates a new `AuthService`.
-    pub fn new(db: Arc<Database>, config: Arc<Config>) -> Self {
-        Self { db, config }
+    /// Creates a new `AuthService` issuing tokens signed with `keys`.
+    pub fn new(db: Arc<Database>, config: Arc<Config>, keys: Arc<JwtKeys>) -> Self {
+        Self {
+            db,
+            config,
+            keys,
+            failed_logins: Arc::new(RwLock::new(HashMap::new())),
+            dummy_hash: Arc::new(OnceLock::new()),
+        }
     }
 
     /// Registers a new user.
     ///
+    /// `phone` is stored in its canonical `62...` form.
+    ///
     /// # Errors
     ///
-    /// Returns [`AppError::BadRequest`] if the unique code is already taken.
+    /// Returns [`AppError::BadRequest`] if `phone` is not a valid phone number.
+    /// Returns [`AppError::Conflict`] if the unique code is already taken; the
+    /// `users.unique_code` constraint decides, so concurrent registrations
+    /// cannot both succeed.
     /// Returns [`AppError::InternalServerError`] for database or hashing errors.
     pub async fn register_user(
         &self,
@@ -43,7 +87,8 @@ impl AuthService {
         phone: Option<String>,
         institution_name: Option<String>,
     ) -> Result<(String, User), AppError> {
-        let password_hash = hash_password(&password)?;
+        let phone = phone.as_deref().map(parse_phone).transpose()?;
+        let password_hash = hash_password(&password, &self.config.password_hash)?;
 
         let user = User {
             name,
@@ -55,19 +100,20 @@ impl AuthService {
             ..Default::default()
         };
 
-        let user_id = self
-            .db
-            .user_table
-            .insert(&user)
-            .await
-            .map_err(|e: DatabaseError| {
-                if e.to_string().contains("duplicate key") {
-                    AppError::BadRequest("User with this unique code already exists".to_string())
-                } else {
-                    error!("Database error during registration: {}", e);
-                    AppError::InternalServerError("An unexpected error occurred".to_string())
-                }
-            })?;
+        let user_id =
+            self.db
+                .user_table
+                .insert(&user)
+                .await
+                .map_err(|e: DatabaseError| match e {
+                    DatabaseError::UniqueViolation { .. } => {
+                        AppError::Conflict("User with this unique code already exists".to_string())
+                    }
+                    e => {
+                        error!("Database error during registration: {}", e);
+                        AppError::InternalServerError("An unexpected error occurred".to_string())
+                    }
+                })?;
 
         let created_user = self
             .db
@@ -83,16 +129,37 @@ impl AuthService {
                 AppError::InternalServerError("User not found after creation".to_string())
             })?;
 
-        let token = generate_token(created_user.id, created_user.role, &self.config.jwt_secret)?;
+        let token = generate_token(
+            created_user.id,
+            created_user.role,
+            created_user.institution_id,
+            created_user.institution_name.clone(),
+            &self.keys,
+        )?;
 
         Ok((token, created_user))
     }
 
+    /// Logs a user in with their unique code and password.
+    ///
+    /// Failed attempts are counted per unique code (whether or not it exists).
+    /// After `login_lockout.max_failed_attempts` failures within
+    /// `login_lockout.window_seconds`, further attempts are rejected until the
+    /// window expires. A successful login resets the counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::TooManyRequests`] if the unique code is locked.
+    /// Returns [`AppError::Unauthorized`] if the credentials are invalid.
+    /// Returns [`AppError::Forbidden`] if the account has been deactivated.
+    /// Returns [`AppError::InternalServerError`] for database errors.
     pub async fn login_user(
         &self,
         unique_code: String,
         password: String,
     ) -> Result<(String, User), AppError> {
+        self.ensure_login_not_locked(&unique_code).await?;
+
         let user = self
             .db
             .user_table
@@ -106,33 +173,192 @@ impl AuthService {
                 AppError::InternalServerError("Database error".to_string())
             })?;
 
-        // Mitigate User Enumeration
-        let (user_found, stored_hash) = if let Some(u) = user {
-            let hash = u.password_hash.clone();
-            (Some(u), hash)
-        } else {
-            // Dummy hash for "password"
-            (
-                None,
-                Some(
-                    "$argon2id$v=19$m=4096,t=3,p=1$c2FsdHNhbHQ$aaaaaaaaaaaaaaaaaaaaaa".to_string(),
-                ),
-            )
-        };
-
-        let password_valid = if let Some(hash) = stored_hash {
-            verify_password(&password, &hash).unwrap_or_default()
-        } else {
-            false
+        // Mitigate User Enumeration: always verify against some hash
+        let stored_hash = user.as_ref().and_then(|u| u.password_hash.clone());
+        let hash = match &stored_hash {
+            Some(hash) => hash.clone(),
+            None => self.dummy_hash()?,
         };
+        let password_valid = verify_password(&password, &hash, &self.config.password_hash)
+            .unwrap_or_default()
+            && stored_hash.is_some();
 
-        if let Some(user) = user_found
+        if let Some(mut user) = user
             && password_valid
         {
-            let token = generate_token(user.id, user.role, &self.config.jwt_secret)?;
+            if user.active == Some(false) {
+                return Err(AppError::Forbidden("account deactivated".to_string()));
+            }
+            self.failed_logins.write().await.remove(&unique_code);
+            if needs_rehash(&hash, &self.config.password_hash) {
+                self.rehash_password(&user, &password).await;
+            }
+            match self.db.user_table.record_login(user.id).await {
+                Ok(last_login) => user.last_login = last_login,
+                Err(e) => warn!("Failed to record login for user {}: {}", user.id, e),
+            }
+            let token = generate_token(
+                user.id,
+                user.role,
+                user.institution_id,
+                user.institution_name.clone(),
+                &self.keys,
+            )?;
             return Ok((token, user));
         }
 
+        self.record_failed_login(&unique_code).await;
         Err(AppError::Unauthorized("Invalid credentials".to_string()))
     }
+
+    /// Returns the profile of the user with `id`, without the password hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::NotFound`] if the user no longer exists.
+    pub async fn get_user(&self, id: Uuid) -> Result<User, AppError> {
+        let mut user = self
+            .db
+            .user_table
+            .select(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        user.password_hash = None;
+        Ok(user)
+    }
+
+    /// Marks the user with `id` as verified and returns their profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Forbidden`] if `role` is not an admin.
+    /// Returns [`AppError::NotFound`] if the user does not exist.
+    pub async fn verify_user(&self, role: UserRole, id: Uuid) -> Result<User, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can verify users".to_string(),
+            ));
+        }
+        if !self.db.user_table.set_verified(id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        info!("User {} verified", id);
+        self.get_user(id).await
+    }
+
+    /// Deactivates the user with `id` so they can no longer sign in or use
+    /// tokens issued earlier. Their reviews and audit entries are kept.
+    /// Deactivating an already deactivated user is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Forbidden`] if the caller is not an admin.
+    /// Returns [`AppError::BadRequest`] if admins target their own account.
+    /// Returns [`AppError::NotFound`] if the user does not exist.
+    pub async fn deactivate_user(&self, claims: &Claims, id: Uuid) -> Result<User, AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can deactivate users".to_string(),
+            ));
+        }
+        if claims.sub == id {
+            return Err(AppError::BadRequest(
+                "You cannot deactivate your own account".to_string(),
+            ));
+        }
+        if !self.db.user_table.deactivate(id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        info!("User {} deactivated by {}", id, claims.sub);
+        self.get_user(id).await
+    }
+
+    /// Re-hashes `password` with the current Argon2 parameters after a
+    /// successful login. Failures are logged but don't fail the login; the
+    /// upgrade is retried on the next one.
+    async fn rehash_password(&self, user: &User, password: &str) {
+        let result = match hash_password(password, &self.config.password_hash) {
+            Ok(hash) => self
+                .db
+                .user_table
+                .update_password_hash(user.id, &hash)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(()) => info!("Upgraded password hash for user {}", user.id),
+            Err(e) => warn!(
+                "Failed to upgrade password hash for user {}: {}",
+                user.id, e
+            ),
+        }
+    }
+
+    /// Rejects the login if `unique_code` has too many recent failures.
+    async fn ensure_login_not_locked(&self, unique_code: &str) -> Result<(), AppError> {
+        let lockout = &self.config.login_lockout;
+        if lockout.max_failed_attempts == 0 {
+            return Ok(());
+        }
+
+        let window = Duration::from_secs(lockout.window_seconds);
+        let failed_logins = self.failed_logins.read().await;
+        if let Some(entry) = failed_logins.get(unique_code)
+            && entry.count >= lockout.max_failed_attempts
+            && entry.window_start.elapsed() < window
+        {
+            warn!(
+                "Login locked for {} after {} failures",
+                unique_code, entry.count
+            );
+            return Err(AppError::TooManyRequests(
+                "Too many failed login attempts. Please try again later.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Records a failed login for `unique_code`, pruning expired entries.
+    async fn record_failed_login(&self, unique_code: &str) {
+        let window = Duration::from_secs(self.config.login_lockout.window_seconds);
+        let mut failed_logins = self.failed_logins.write().await;
+        failed_logins.retain(|_, entry| entry.window_start.elapsed() < window);
+
+        let entry = failed_logins
+            .entry(unique_code.to_string())
+            .or_insert(FailedLogins {
+                count: 0,
+                window_start: Instant::now(),
+            });
+        entry.count += 1;
+    }
+
+    /// Returns a hash created with the configured parameters: normally
+    /// [`DUMMY_PASSWORD_HASH`], or one computed once when the parameters
+    /// differ from the defaults, since verification cost depends on them.
+    fn dummy_hash(&self) -> Result<String, AppError> {
+        if !needs_rehash(DUMMY_PASSWORD_HASH, &self.config.password_hash) {
+            return Ok(DUMMY_PASSWORD_HASH.to_string());
+        }
+        if let Some(hash) = self.dummy_hash.get() {
+            return Ok(hash.clone());
+        }
+        let hash = hash_password("dummy-password", &self.config.password_hash)?;
+        Ok(self.dummy_hash.get_or_init(|| hash).clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PasswordHashConfig;
+
+    #[test]
+    fn test_dummy_hash_matches_default_parameters() {
+        let config = PasswordHashConfig::default();
+        // Otherwise every unknown unique code would pay for a fresh hash
+        assert!(!needs_rehash(DUMMY_PASSWORD_HASH, &config));
+        assert!(verify_password("dummy-password", DUMMY_PASSWORD_HASH, &config).unwrap());
+    }
 }
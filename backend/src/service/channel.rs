@@ -0,0 +1,229 @@
+//! Message delivery channels.
+//!
+//! This module defines the [`MessageChannel`] trait implemented by each way we
+//! can reach a user (WhatsApp, email), and a [`ChannelDispatcher`] that picks a
+//! channel based on the recipient's contact info and falls back to the next
+//! one when delivery fails.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::info;
+use log::warn;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::service::email::SmtpEmailClient;
+use crate::service::whatsapp::WhatsAppClient;
+
+/// Contact information of a message recipient.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recipient {
+    /// Phone number, used by WhatsApp.
+    pub phone: Option<String>,
+    /// Email address, used by SMTP email.
+    pub email: Option<String>,
+}
+
+/// A message to deliver through a [`MessageChannel`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// A one-time password for verification.
+    Otp {
+        /// The OTP code.
+        code: String,
+        /// Reference ID of the OTP request, for logging.
+        reference_id: String,
+        /// Seconds until the code expires.
+        expires_in: u64,
+    },
+    /// A free-form notification.
+    Text {
+        /// Subject line (ignored by channels without subjects).
+        subject: String,
+        /// Message body.
+        body: String,
+    },
+}
+
+/// A way of delivering messages to users.
+#[async_trait]
+pub trait MessageChannel: Send + Sync {
+    /// Short channel name used in logs and API responses (e.g. `"whatsapp"`).
+    fn name(&self) -> &'static str;
+
+    /// Whether this channel is configured and the recipient has the contact
+    /// info it needs.
+    fn can_deliver(&self, recipient: &Recipient) -> bool;
+
+    /// Delivers `message` to `recipient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ServiceUnavailable`] if delivery fails.
+    async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), AppError>;
+}
+
+/// Delivers messages through the first channel that succeeds.
+#[derive(Clone)]
+pub struct ChannelDispatcher {
+    channels: Vec<Arc<dyn MessageChannel>>,
+}
+
+impl ChannelDispatcher {
+    /// Creates a dispatcher trying `channels` in the given order.
+    pub fn new(channels: Vec<Arc<dyn MessageChannel>>) -> Self {
+        Self { channels }
+    }
+
+    /// Creates a dispatcher with every channel enabled in `config`, preferring
+    /// WhatsApp over email.
+    pub fn from_config(config: Arc<Config>) -> Self {
+        let mut channels: Vec<Arc<dyn MessageChannel>> = Vec::new();
+
+        let whatsapp = WhatsAppClient::new(config.clone());
+        if whatsapp.is_enabled() {
+            channels.push(Arc::new(whatsapp));
+        }
+
+        let email = SmtpEmailClient::new(config);
+        if email.is_enabled() {
+            channels.push(Arc::new(email));
+        }
+
+        Self::new(channels)
+    }
+
+    /// Whether any channel can reach `recipient`.
+    pub fn can_deliver(&self, recipient: &Recipient) -> bool {
+        self.channels.iter().any(|c| c.can_deliver(recipient))
+    }
+
+    /// Sends `message` through the first channel that can reach `recipient`,
+    /// falling back to the next one on failure.
+    ///
+    /// # Returns
+    ///
+    /// The name of the channel that delivered the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ServiceUnavailable`] if no channel can reach the
+    /// recipient or every attempt failed.
+    pub async fn send(
+        &self,
+        recipient: &Recipient,
+        message: &Message,
+    ) -> Result<&'static str, AppError> {
+        for channel in self.channels.iter().filter(|c| c.can_deliver(recipient)) {
+            match channel.send(recipient, message).await {
+                Ok(()) => {
+                    info!("Message delivered via {}", channel.name());
+                    return Ok(channel.name());
+                }
+                Err(e) => warn!("Delivery via {} failed: {}", channel.name(), e),
+            }
+        }
+
+        Err(AppError::ServiceUnavailable(
+            "No delivery channel available".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    struct FakeChannel {
+        name: &'static str,
+        needs_email: bool,
+        fails: bool,
+        sent: AtomicUsize,
+    }
+
+    impl FakeChannel {
+        fn new(name: &'static str, needs_email: bool, fails: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                needs_email,
+                fails,
+                sent: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl MessageChannel for FakeChannel {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn can_deliver(&self, recipient: &Recipient) -> bool {
+            if self.needs_email {
+                recipient.email.is_some()
+            } else {
+                recipient.phone.is_some()
+            }
+        }
+
+        async fn send(&self, _recipient: &Recipient, _message: &Message) -> Result<(), AppError> {
+            self.sent.fetch_add(1, Ordering::SeqCst);
+            if self.fails {
+                Err(AppError::ServiceUnavailable("down".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn text() -> Message {
+        Message::Text {
+            subject: "Hi".to_string(),
+            body: "Hello".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_picks_channel_by_contact_info() {
+        let whatsapp = FakeChannel::new("whatsapp", false, false);
+        let email = FakeChannel::new("email", true, false);
+        let dispatcher = ChannelDispatcher::new(vec![whatsapp.clone(), email.clone()]);
+
+        let email_only = Recipient {
+            email: Some("school@example.com".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            dispatcher.send(&email_only, &text()).await.unwrap(),
+            "email"
+        );
+        assert_eq!(whatsapp.sent.load(Ordering::SeqCst), 0);
+
+        assert!(!dispatcher.can_deliver(&Recipient::default()));
+        assert!(
+            dispatcher
+                .send(&Recipient::default(), &text())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_falls_back_on_failure() {
+        let whatsapp = FakeChannel::new("whatsapp", false, true);
+        let email = FakeChannel::new("email", true, false);
+        let dispatcher = ChannelDispatcher::new(vec![whatsapp.clone(), email.clone()]);
+
+        let recipient = Recipient {
+            phone: Some("08123456789".to_string()),
+            email: Some("school@example.com".to_string()),
+        };
+        assert_eq!(dispatcher.send(&recipient, &text()).await.unwrap(), "email");
+        assert_eq!(whatsapp.sent.load(Ordering::SeqCst), 1);
+        assert_eq!(email.sent.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,481 @@
+//! Kitchen complaint service.
+
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::utils::Claims;
+use crate::config::ComplaintSlaConfig;
+use crate::database::Database;
+use crate::database::model::Complaint;
+use crate::database::model::ComplaintCategory;
+use crate::database::model::ComplaintComment;
+use crate::database::model::ComplaintEvidence;
+use crate::database::model::ComplaintStatus;
+use crate::database::model::EvidenceCaptureMethod;
+use crate::database::model::EvidenceMetadataStatus;
+use crate::database::model::UserRole;
+use crate::database::table::ComplaintFilter;
+use crate::database::table::Table;
+use crate::error::AppError;
+use crate::service::audit::deletion_entry;
+use crate::service::audit::non_empty;
+use crate::service::audit::parse_bound;
+use crate::service::datetime::deserialize_utc;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::filter::FilterEnum;
+use crate::service::filter::parse_enum;
+use crate::service::incident::require_non_empty;
+use crate::service::pagination::PaginatedResponse;
+use crate::service::pagination::Pagination;
+
+/// Request body for filing a complaint against a kitchen.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateComplaintRequest {
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Uuid,
+    pub category: String,
+    pub description: String,
+}
+
+/// Request body for commenting on a complaint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateCommentRequest {
+    pub message: String,
+}
+
+/// Request body for attaching evidence to a complaint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateEvidenceRequest {
+    pub url: String,
+    /// When the evidence was captured.
+    #[serde(deserialize_with = "deserialize_utc")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "captureMethod")]
+    pub capture_method: Option<String>,
+}
+
+/// Request body for moving a complaint to its next status.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateComplaintRequest {
+    pub status: String,
+    /// Required when resolving.
+    pub resolution: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplaintDto {
+    pub id: Uuid,
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Uuid,
+    pub category: &'static str,
+    pub description: String,
+    pub status: &'static str,
+    #[serde(rename = "reportedAt")]
+    pub reported_at: String,
+    #[serde(rename = "slaDeadline")]
+    pub sla_deadline: Option<String>,
+    /// Whether the complaint is unresolved past its SLA deadline.
+    #[serde(rename = "slaBreached")]
+    pub sla_breached: bool,
+    #[serde(rename = "assignedTo")]
+    pub assigned_to: Option<Uuid>,
+    pub resolution: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplaintListResponse {
+    pub data: Vec<ComplaintDto>,
+}
+
+/// Raw filters for [`ComplaintService::list_complaints`], as given in the
+/// query string.
+#[derive(Debug, Default)]
+pub struct ComplaintListQuery {
+    pub kitchen_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub status: Option<String>,
+    pub assigned_to: Option<Uuid>,
+    /// `YYYY-MM-DD` or RFC 3339 timestamp, matched against `reportedAt`.
+    pub from: Option<String>,
+    /// `YYYY-MM-DD` (inclusive of the whole day) or RFC 3339 timestamp.
+    pub to: Option<String>,
+}
+
+/// Service for filing complaints and tracking their resolution.
+pub struct ComplaintService {
+    db: Arc<Database>,
+    sla: ComplaintSlaConfig,
+}
+
+impl ComplaintService {
+    /// Creates a new `ComplaintService` with the default SLA windows.
+    pub fn new(db: Arc<Database>) -> Self {
+        Self::with_sla(db, ComplaintSlaConfig::default())
+    }
+
+    /// Creates a new `ComplaintService` with the given SLA windows.
+    pub fn with_sla(db: Arc<Database>, sla: ComplaintSlaConfig) -> Self {
+        Self { db, sla }
+    }
+
+    /// Files a complaint. Its SLA deadline depends on the category.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the kitchen doesn't exist and `BadRequest` if the
+    /// description is empty or the category is not a valid value.
+    pub async fn create_complaint(
+        &self,
+        claims: &Claims,
+        req: CreateComplaintRequest,
+    ) -> Result<Uuid, AppError> {
+        let category = parse_enum::<ComplaintCategory>("category", &req.category)?;
+        let description = require_non_empty(&req.description, "description")?;
+
+        self.db
+            .kitchen_table
+            .select(&req.kitchen_id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        let reported_at = Utc::now();
+        let complaint = Complaint {
+            kitchen_id: req.kitchen_id,
+            category,
+            description,
+            status: Some(ComplaintStatus::Pending),
+            reported_by: Some(claims.sub.to_string()),
+            reported_at,
+            sla_deadline: Some(reported_at + Duration::hours(self.sla.hours_for(category).into())),
+            ..Default::default()
+        };
+        Ok(self.db.complaint_table.insert(&complaint).await?)
+    }
+
+    /// Adds a comment to a complaint.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the complaint doesn't exist, `Forbidden` unless the
+    /// caller filed it or is an admin, and `BadRequest` if the message is empty.
+    pub async fn add_comment(
+        &self,
+        complaint_id: Uuid,
+        claims: &Claims,
+        req: CreateCommentRequest,
+    ) -> Result<Uuid, AppError> {
+        self.ensure_can_contribute(complaint_id, claims).await?;
+        let message = require_non_empty(&req.message, "message")?;
+
+        // Tokens can outlive their user, so only link authors that still exist
+        let author = self.db.user_table.select(&claims.sub).await?;
+        let comment = ComplaintComment {
+            complaint_id,
+            author_id: author.as_ref().map(|u| u.id),
+            author_name: author.map(|u| u.name),
+            role: Some(claims.role.as_str().to_string()),
+            message,
+            ..Default::default()
+        };
+        Ok(self.db.complaint_comment_table.insert(&comment).await?)
+    }
+
+    /// Attaches evidence to a complaint. New evidence starts unverified.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the complaint doesn't exist, `Forbidden` unless the
+    /// caller filed it or is an admin, and `BadRequest` if the URL is empty or the
+    /// capture method is not a valid value.
+    pub async fn add_evidence(
+        &self,
+        complaint_id: Uuid,
+        claims: &Claims,
+        req: CreateEvidenceRequest,
+    ) -> Result<Uuid, AppError> {
+        self.ensure_can_contribute(complaint_id, claims).await?;
+        let url = require_non_empty(&req.url, "url")?;
+        let capture_method = req
+            .capture_method
+            .as_deref()
+            .map(|m| parse_enum::<EvidenceCaptureMethod>("captureMethod", m))
+            .transpose()?;
+
+        let evidence = ComplaintEvidence {
+            complaint_id,
+            url,
+            timestamp: req.timestamp,
+            metadata_status: Some(EvidenceMetadataStatus::Unverified),
+            capture_method,
+            ..Default::default()
+        };
+        Ok(self.db.complaint_evidence_table.insert(&evidence).await?)
+    }
+
+    /// Moves a complaint to its next status: `pending` to `in-progress`, then
+    /// `in-progress` to `resolved`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the complaint doesn't
+    /// exist, `BadRequest` for an unknown status, an illegal transition or a
+    /// missing resolution when resolving, and `Conflict` if the complaint's
+    /// status changed concurrently.
+    pub async fn update_status(
+        &self,
+        complaint_id: Uuid,
+        role: UserRole,
+        req: UpdateComplaintRequest,
+    ) -> Result<(), AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can update complaint status".into(),
+            ));
+        }
+        let to = parse_enum::<ComplaintStatus>("status", &req.status)?;
+
+        let complaint = self
+            .db
+            .complaint_table
+            .select(&complaint_id)
+            .await?
+            .ok_or(AppError::NotFound("Complaint not found".into()))?;
+        let from = complaint.status.unwrap_or(ComplaintStatus::Pending);
+
+        if !is_legal_transition(from, to) {
+            return Err(AppError::BadRequest(format!(
+                "Cannot change complaint status from '{}' to '{}'",
+                from.as_str(),
+                to.as_str()
+            )));
+        }
+        let resolution = match to {
+            ComplaintStatus::Resolved => Some(require_non_empty(
+                req.resolution.as_deref().unwrap_or_default(),
+                "resolution",
+            )?),
+            _ => None,
+        };
+
+        let updated = self
+            .db
+            .complaint_table
+            .transition_status(&complaint_id, from, to, resolution.as_deref())
+            .await?;
+        if !updated {
+            return Err(AppError::Conflict(
+                "Complaint status was changed by another request".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Deletes a complaint filed by mistake, with its evidence and comments.
+    ///
+    /// The complaint is recorded in `audit_logs` in the same transaction, so
+    /// it can still be traced after removal.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `NotFound` if the complaint
+    /// doesn't exist.
+    pub async fn delete_complaint(
+        &self,
+        complaint_id: Uuid,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can delete complaints".into(),
+            ));
+        }
+
+        let entry = deletion_entry(&self.db, claims, "complaint", complaint_id).await?;
+        let table = &self.db.complaint_table;
+        self.db
+            .transaction_with_retry(|tx| {
+                Box::pin(table.delete_with_audit(tx, &complaint_id, &entry))
+            })
+            .await?
+            .ok_or(AppError::NotFound("Complaint not found".into()))?;
+        Ok(())
+    }
+
+    /// Lists complaints, nearest SLA deadline first, so the most urgent ones
+    /// surface at the top.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `BadRequest` for an unknown
+    /// category or status, or if `from` or `to` is not a valid date or `from`
+    /// is not before `to`.
+    pub async fn list_complaints(
+        &self,
+        role: UserRole,
+        limit: i64,
+        offset: i64,
+        query: ComplaintListQuery,
+    ) -> Result<PaginatedResponse<ComplaintDto>, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can list complaints".into(),
+            ));
+        }
+
+        let category = non_empty(query.category)
+            .map(|c| parse_enum::<ComplaintCategory>("category", &c))
+            .transpose()?;
+        let status = non_empty(query.status)
+            .map(|s| parse_enum::<ComplaintStatus>("status", &s))
+            .transpose()?;
+        let from = query
+            .from
+            .as_deref()
+            .map(|v| parse_bound(v, "from", false))
+            .transpose()?;
+        let until = query
+            .to
+            .as_deref()
+            .map(|v| parse_bound(v, "to", true))
+            .transpose()?;
+        if let (Some(from), Some(until)) = (from, until)
+            && from >= until
+        {
+            return Err(AppError::BadRequest("from must be before to".into()));
+        }
+
+        let filter = ComplaintFilter {
+            kitchen_id: query.kitchen_id,
+            category,
+            status,
+            assigned_to: query.assigned_to,
+            from,
+            until,
+        };
+        let (complaints, total) = self.db.complaint_table.list(&filter, limit, offset).await?;
+
+        let now = Utc::now();
+        Ok(PaginatedResponse {
+            data: complaints.into_iter().map(|c| map_to_dto(c, now)).collect(),
+            pagination: Pagination::new(total, limit, offset),
+        })
+    }
+
+    /// Lists unresolved complaints past their SLA deadline, most overdue first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins.
+    pub async fn list_sla_breached(
+        &self,
+        role: UserRole,
+    ) -> Result<ComplaintListResponse, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can view SLA breaches".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        let complaints = self.db.complaint_table.select_sla_breached(now).await?;
+
+        Ok(ComplaintListResponse {
+            data: complaints.into_iter().map(|c| map_to_dto(c, now)).collect(),
+        })
+    }
+
+    /// Ensures the complaint exists and the caller filed it or is an admin.
+    async fn ensure_can_contribute(
+        &self,
+        complaint_id: Uuid,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        let complaint = self
+            .db
+            .complaint_table
+            .select(&complaint_id)
+            .await?
+            .ok_or(AppError::NotFound("Complaint not found".into()))?;
+
+        if claims.role != UserRole::Admin
+            && complaint.reported_by.as_deref() != Some(claims.sub.to_string().as_str())
+        {
+            return Err(AppError::Forbidden(
+                "Only the reporter or an administrator can add to this complaint".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether a complaint may move from `from` to `to`.
+fn is_legal_transition(from: ComplaintStatus, to: ComplaintStatus) -> bool {
+    matches!(
+        (from, to),
+        (ComplaintStatus::Pending, ComplaintStatus::InProgress)
+            | (ComplaintStatus::InProgress, ComplaintStatus::Resolved)
+    )
+}
+
+/// Whether a complaint in `status` with `sla_deadline` is overdue at `now`.
+fn is_sla_breached(
+    status: ComplaintStatus,
+    sla_deadline: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    status != ComplaintStatus::Resolved && sla_deadline.is_some_and(|deadline| deadline < now)
+}
+
+fn map_to_dto(c: Complaint, now: DateTime<Utc>) -> ComplaintDto {
+    let status = c.status.unwrap_or(ComplaintStatus::Pending);
+    ComplaintDto {
+        id: c.id,
+        kitchen_id: c.kitchen_id,
+        category: c.category.as_str(),
+        description: c.description,
+        status: status.as_str(),
+        reported_at: utc_to_rfc3339(c.reported_at),
+        sla_deadline: c.sla_deadline.map(utc_to_rfc3339),
+        sla_breached: is_sla_breached(status, c.sla_deadline, now),
+        assigned_to: c.assigned_to,
+        resolution: c.resolution,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_legal_transition() {
+        use ComplaintStatus::*;
+
+        assert!(is_legal_transition(Pending, InProgress));
+        assert!(is_legal_transition(InProgress, Resolved));
+        assert!(!is_legal_transition(Pending, Resolved));
+        assert!(!is_legal_transition(Resolved, InProgress));
+        assert!(!is_legal_transition(InProgress, Pending));
+        assert!(!is_legal_transition(Pending, Pending));
+    }
+
+    #[test]
+    fn test_is_sla_breached() {
+        use ComplaintStatus::*;
+
+        let now = Utc::now();
+        let past = Some(now - Duration::hours(1));
+        let future = Some(now + Duration::hours(1));
+
+        assert!(is_sla_breached(Pending, past, now));
+        assert!(is_sla_breached(InProgress, past, now));
+        assert!(!is_sla_breached(Resolved, past, now));
+        assert!(!is_sla_breached(Pending, future, now));
+        assert!(!is_sla_breached(Pending, None, now));
+    }
+}
@@ -0,0 +1,316 @@
+//! Kitchen compliance metrics service.
+
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::database::model::ChecklistItem;
+use crate::database::model::ChecklistStatus;
+use crate::database::model::ComplianceMetric;
+use crate::database::model::ComplianceTrend;
+use crate::database::model::UserRole;
+use crate::database::table::Table;
+use crate::error::AppError;
+use crate::service::datetime::deserialize_optional_utc;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::filter::FilterEnum;
+use crate::service::filter::parse_enum;
+use crate::service::incident::require_non_empty;
+
+/// Score change (in percentage points) from the previous month beyond which
+/// the trend counts as improving or declining rather than stable.
+const TREND_THRESHOLD: Decimal = Decimal::ONE;
+
+/// Request body for a single checklist item.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChecklistItemRequest {
+    pub category: Option<String>,
+    pub item: String,
+    pub status: String,
+    pub notes: Option<String>,
+}
+
+/// Request body for recording a kitchen's compliance metrics for this month.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecordComplianceRequest {
+    #[serde(rename = "hygieneScore")]
+    pub hygiene_score: Option<f64>,
+    #[serde(rename = "portionCompliance")]
+    pub portion_compliance: Option<f64>,
+    #[serde(rename = "nutritionCompliance")]
+    pub nutrition_compliance: Option<f64>,
+    #[serde(rename = "temperatureControl")]
+    pub temperature_control: Option<f64>,
+    #[serde(rename = "slaPerformance")]
+    pub sla_performance: Option<Value>,
+    #[serde(
+        rename = "lastInspectionDate",
+        default,
+        deserialize_with = "deserialize_optional_utc"
+    )]
+    pub last_inspection_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItemRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChecklistItemDto {
+    pub id: Uuid,
+    pub category: Option<String>,
+    pub item: Option<String>,
+    pub status: Option<&'static str>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplianceDto {
+    pub id: Uuid,
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Uuid,
+    #[serde(rename = "hygieneScore")]
+    pub hygiene_score: Option<f64>,
+    #[serde(rename = "portionCompliance")]
+    pub portion_compliance: Option<f64>,
+    #[serde(rename = "nutritionCompliance")]
+    pub nutrition_compliance: Option<f64>,
+    #[serde(rename = "temperatureControl")]
+    pub temperature_control: Option<f64>,
+    /// Mean of the scores above that were recorded.
+    pub score: Option<f64>,
+    #[serde(rename = "slaPerformance")]
+    pub sla_performance: Option<Value>,
+    #[serde(rename = "lastInspectionDate")]
+    pub last_inspection_date: Option<String>,
+    pub trend: Option<&'static str>,
+    pub checklist: Vec<ChecklistItemDto>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// Service for recording and viewing kitchen compliance metrics.
+pub struct ComplianceService {
+    db: Arc<Database>,
+}
+
+impl ComplianceService {
+    /// Creates a new `ComplianceService`.
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Records this month's compliance metrics and checklist for a kitchen.
+    ///
+    /// Recording again in the same month replaces the earlier values. The trend
+    /// is derived from the previous month's score.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the kitchen doesn't exist
+    /// and `BadRequest` if a score is outside 0-100, a checklist item is empty or
+    /// has an invalid `status`.
+    pub async fn record_compliance(
+        &self,
+        kitchen_id: Uuid,
+        role: UserRole,
+        req: RecordComplianceRequest,
+    ) -> Result<Uuid, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can record compliance metrics".into(),
+            ));
+        }
+
+        let items = req
+            .checklist
+            .iter()
+            .map(|i| {
+                Ok(ChecklistItem {
+                    category: i.category.as_deref().map(|c| c.trim().to_string()),
+                    item: Some(require_non_empty(&i.item, "item")?),
+                    status: Some(parse_enum::<ChecklistStatus>("status", &i.status)?),
+                    notes: i.notes.clone(),
+                    ..Default::default()
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        let mut metric = ComplianceMetric {
+            kitchen_id,
+            hygiene_score: percentage(req.hygiene_score, "hygieneScore")?,
+            portion_compliance: percentage(req.portion_compliance, "portionCompliance")?,
+            nutrition_compliance: percentage(req.nutrition_compliance, "nutritionCompliance")?,
+            temperature_control: percentage(req.temperature_control, "temperatureControl")?,
+            sla_performance: req.sla_performance,
+            last_inspection_date: req.last_inspection_date,
+            ..Default::default()
+        };
+
+        self.db
+            .kitchen_table
+            .select(&kitchen_id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        let previous = self
+            .db
+            .compliance_metric_table
+            .select_previous_month(&kitchen_id)
+            .await?;
+        metric.trend = Some(compute_trend(
+            compliance_score(&metric),
+            previous.as_ref().and_then(compliance_score),
+        ));
+
+        let table = &self.db.compliance_metric_table;
+        Ok(self
+            .db
+            .transaction_with_retry(|tx| Box::pin(table.upsert_with_items(tx, &metric, &items)))
+            .await?)
+    }
+
+    /// Gets a kitchen's latest compliance metrics with their checklist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the kitchen doesn't exist or has no metrics yet.
+    pub async fn get_latest_compliance(&self, kitchen_id: Uuid) -> Result<ComplianceDto, AppError> {
+        self.db
+            .kitchen_table
+            .select(&kitchen_id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        let metric = self
+            .db
+            .compliance_metric_table
+            .select_latest(&kitchen_id)
+            .await?
+            .ok_or(AppError::NotFound(
+                "No compliance metrics recorded for this kitchen".into(),
+            ))?;
+
+        let checklist = self
+            .db
+            .checklist_item_table
+            .select_by_metric(&metric.id)
+            .await?
+            .into_iter()
+            .map(|i| ChecklistItemDto {
+                id: i.id,
+                category: i.category,
+                item: i.item,
+                status: i.status.map(FilterEnum::as_str),
+                notes: i.notes,
+            })
+            .collect();
+
+        Ok(ComplianceDto {
+            id: metric.id,
+            kitchen_id: metric.kitchen_id,
+            hygiene_score: metric.hygiene_score.and_then(|d| d.to_f64()),
+            portion_compliance: metric.portion_compliance.and_then(|d| d.to_f64()),
+            nutrition_compliance: metric.nutrition_compliance.and_then(|d| d.to_f64()),
+            temperature_control: metric.temperature_control.and_then(|d| d.to_f64()),
+            score: compliance_score(&metric).and_then(|d| d.to_f64()),
+            sla_performance: metric.sla_performance,
+            last_inspection_date: metric.last_inspection_date.map(utc_to_rfc3339),
+            trend: metric.trend.map(FilterEnum::as_str),
+            checklist,
+            updated_at: utc_to_rfc3339(metric.updated_at.unwrap_or_default()),
+        })
+    }
+}
+
+/// Overall compliance score of a metric row: the mean of its recorded
+/// hygiene, portion, nutrition and temperature scores.
+///
+/// Returns `None` if none of them were recorded.
+pub fn compliance_score(metric: &ComplianceMetric) -> Option<Decimal> {
+    let scores: Vec<Decimal> = [
+        metric.hygiene_score,
+        metric.portion_compliance,
+        metric.nutrition_compliance,
+        metric.temperature_control,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if scores.is_empty() {
+        return None;
+    }
+    Some((scores.iter().sum::<Decimal>() / Decimal::from(scores.len())).round_dp(2))
+}
+
+/// Compares this month's score with the previous month's.
+///
+/// Without both scores there is nothing to compare, so the trend is stable.
+pub fn compute_trend(current: Option<Decimal>, previous: Option<Decimal>) -> ComplianceTrend {
+    match (current, previous) {
+        (Some(current), Some(previous)) if current - previous > TREND_THRESHOLD => {
+            ComplianceTrend::Improving
+        }
+        (Some(current), Some(previous)) if previous - current > TREND_THRESHOLD => {
+            ComplianceTrend::Declining
+        }
+        _ => ComplianceTrend::Stable,
+    }
+}
+
+/// Validates an optional 0-100 score and converts it for storage.
+fn percentage(value: Option<f64>, field: &str) -> Result<Option<Decimal>, AppError> {
+    match value {
+        Some(v) if !(0.0..=100.0).contains(&v) => Err(AppError::BadRequest(format!(
+            "{} must be between 0 and 100",
+            field
+        ))),
+        Some(v) => Ok(Decimal::from_f64(v)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compliance_score_averages_recorded_scores() {
+        let metric = ComplianceMetric {
+            hygiene_score: Some(Decimal::from(90)),
+            portion_compliance: Some(Decimal::from(80)),
+            temperature_control: Some(Decimal::from(85)),
+            ..Default::default()
+        };
+        assert_eq!(compliance_score(&metric), Some(Decimal::from(85)));
+        assert_eq!(compliance_score(&ComplianceMetric::default()), None);
+    }
+
+    #[test]
+    fn test_compute_trend() {
+        assert_eq!(
+            compute_trend(Some(Decimal::from(90)), Some(Decimal::from(85))),
+            ComplianceTrend::Improving
+        );
+        assert_eq!(
+            compute_trend(Some(Decimal::from(80)), Some(Decimal::from(85))),
+            ComplianceTrend::Declining
+        );
+        assert_eq!(
+            compute_trend(Some(Decimal::new(855, 1)), Some(Decimal::from(85))),
+            ComplianceTrend::Stable
+        );
+        assert_eq!(
+            compute_trend(Some(Decimal::from(90)), None),
+            ComplianceTrend::Stable
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Timestamp formatting for API responses.
+//!
+//! Timestamps are stored as `TIMESTAMPTZ` and handled as `DateTime<Utc>`.
+//! Responses render them as RFC 3339 with a `Z` suffix, e.g.
+//! `2025-01-30T12:00:00Z`, so clients can parse them with a standard ISO 8601
+//! parser and show them in the viewer's local time. Calendar dates without a
+//! time stay `YYYY-MM-DD`.
+//!
+//! Request bodies may give timestamps with an offset, or without one, in which
+//! case they are taken to be UTC.
+//!
+//! Monthly reports group by calendar month in Asia/Jakarta
+//! (`AT TIME ZONE 'Asia/Jakarta'`), so an incident just after midnight local
+//! time lands in the right month.
+
+use chrono::DateTime;
+use chrono::NaiveDateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::de;
+
+/// Formats a timestamp as RFC 3339 in UTC, keeping any fractional seconds.
+pub fn utc_to_rfc3339(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// The current time, formatted like [`utc_to_rfc3339`].
+pub fn now_rfc3339() -> String {
+    utc_to_rfc3339(Utc::now())
+}
+
+/// Parses an RFC 3339 timestamp, or one without an offset as UTC.
+fn parse_utc(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.to_utc())
+        .or_else(|_| value.parse::<NaiveDateTime>().map(|dt| dt.and_utc()))
+        .ok()
+}
+
+/// Deserializes a request timestamp, see the module docs.
+pub fn deserialize_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_utc(&value).ok_or_else(|| de::Error::custom(format!("invalid timestamp `{}`", value)))
+}
+
+/// Like [`deserialize_utc`], for optional fields.
+pub fn deserialize_optional_utc<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| {
+            parse_utc(&value)
+                .ok_or_else(|| de::Error::custom(format!("invalid timestamp `{}`", value)))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_utc_to_rfc3339() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert_eq!(
+            utc_to_rfc3339(date.and_hms_opt(12, 0, 0).unwrap().and_utc()),
+            "2024-01-01T12:00:00Z"
+        );
+        assert_eq!(
+            utc_to_rfc3339(date.and_hms_micro_opt(12, 0, 0, 123_456).unwrap().and_utc()),
+            "2024-01-01T12:00:00.123456Z"
+        );
+        assert_eq!(
+            utc_to_rfc3339(DateTime::<Utc>::default()),
+            "1970-01-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_parse_utc() {
+        let expected = NaiveDate::from_ymd_opt(2026, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc();
+        assert_eq!(parse_utc("2026-03-01T12:00:00"), Some(expected));
+        assert_eq!(parse_utc("2026-03-01T12:00:00Z"), Some(expected));
+        assert_eq!(parse_utc("2026-03-01T19:00:00+07:00"), Some(expected));
+        assert_eq!(parse_utc("2026-03-01"), None);
+        assert_eq!(parse_utc("yesterday"), None);
+    }
+
+    #[test]
+    fn test_now_rfc3339_is_utc() {
+        let now = now_rfc3339();
+        assert!(now.contains('T'));
+        assert!(now.ends_with('Z'));
+    }
+}
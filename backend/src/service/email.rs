@@ -0,0 +1,454 @@
+//! SMTP email delivery.
+//!
+//! This module provides a small SMTP client used to deliver OTP codes (and,
+//! through [`Message::Text`], other messages) to recipients that only have an
+//! email address. It supports implicit TLS (SMTPS), STARTTLS and plain
+//! connections, with optional `AUTH PLAIN` over the encrypted ones.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use log::error;
+use log::info;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::service::channel::Message;
+use crate::service::channel::MessageChannel;
+use crate::service::channel::Recipient;
+
+/// Maximum time a whole SMTP session may take.
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// SMTP client for sending plain-text emails.
+#[derive(Clone)]
+pub struct SmtpEmailClient {
+    config: Arc<Config>,
+}
+
+impl SmtpEmailClient {
+    /// Creates a new SMTP email client.
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+
+    /// Checks if SMTP is configured with at least a host and sender address.
+    pub fn is_enabled(&self) -> bool {
+        let smtp = &self.config.smtp;
+        smtp.host.is_some() && smtp.from.is_some()
+    }
+
+    /// Sends a plain-text email.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::BadRequest`] if the address is malformed and
+    /// [`AppError::ServiceUnavailable`] if SMTP is not configured or the
+    /// server rejects the message.
+    pub async fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError> {
+        if !self.is_enabled() {
+            return Err(AppError::ServiceUnavailable(
+                "Email service is not available".to_string(),
+            ));
+        }
+        if !is_valid_address(to) {
+            return Err(AppError::BadRequest("Invalid email address".to_string()));
+        }
+
+        let smtp = &self.config.smtp;
+        let host = smtp.host.as_deref().unwrap();
+        let from = smtp.from.as_deref().unwrap();
+        let data = format_message(from, to, subject, body);
+
+        let session = async {
+            let stream = TcpStream::connect((host, smtp.port)).await?;
+            if smtp.tls {
+                let mut conn = BufReader::new(tls_connect(host, stream).await?);
+                expect_reply(&mut conn, 220).await?;
+                command(&mut conn, "EHLO monitormbg", 250).await?;
+                self.run_session(&mut conn, true, from, to, &data).await
+            } else {
+                let mut conn = BufReader::new(stream);
+                expect_reply(&mut conn, 220).await?;
+                command(&mut conn, "EHLO monitormbg", 250).await?;
+                if smtp.starttls {
+                    command(&mut conn, "STARTTLS", 220).await?;
+                    // Anything the server sent before the handshake is dropped
+                    let mut conn = BufReader::new(tls_connect(host, conn.into_inner()).await?);
+                    command(&mut conn, "EHLO monitormbg", 250).await?;
+                    self.run_session(&mut conn, true, from, to, &data).await
+                } else {
+                    self.run_session(&mut conn, false, from, to, &data).await
+                }
+            }
+        };
+
+        match tokio::time::timeout(SMTP_TIMEOUT, session).await {
+            Ok(Ok(())) => {
+                info!("Email sent to {}", to);
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                error!("Failed to send email to {}: {}", to, e);
+                Err(AppError::ServiceUnavailable(
+                    "Failed to send email".to_string(),
+                ))
+            }
+            Err(_) => {
+                error!("Timed out sending email to {}", to);
+                Err(AppError::ServiceUnavailable(
+                    "Failed to send email".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Authenticates and sends the message on a connection that has already
+    /// been greeted with `EHLO`.
+    ///
+    /// Credentials are only sent when `encrypted` is set, so a misconfigured
+    /// client fails instead of leaking them.
+    async fn run_session<S>(
+        &self,
+        conn: &mut BufReader<S>,
+        encrypted: bool,
+        from: &str,
+        to: &str,
+        data: &str,
+    ) -> std::io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let smtp = &self.config.smtp;
+        if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+            if !encrypted {
+                return Err(std::io::Error::other(
+                    "Refusing to send SMTP credentials over an unencrypted connection",
+                ));
+            }
+            let credentials = BASE64.encode(format!("\0{}\0{}", username, password));
+            command(conn, &format!("AUTH PLAIN {}", credentials), 235).await?;
+        }
+
+        command(conn, &format!("MAIL FROM:<{}>", from), 250).await?;
+        command(conn, &format!("RCPT TO:<{}>", to), 250).await?;
+        command(conn, "DATA", 354).await?;
+        conn.get_mut().write_all(data.as_bytes()).await?;
+        command(conn, ".", 250).await?;
+
+        // The message is accepted at this point; a failed QUIT doesn't matter.
+        let _ = command(conn, "QUIT", 221).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageChannel for SmtpEmailClient {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    fn can_deliver(&self, recipient: &Recipient) -> bool {
+        self.is_enabled() && recipient.email.is_some()
+    }
+
+    async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), AppError> {
+        let to = recipient
+            .email
+            .as_deref()
+            .ok_or_else(|| AppError::BadRequest("Recipient has no email address".to_string()))?;
+
+        match message {
+            Message::Otp {
+                code, expires_in, ..
+            } => {
+                let body = format!(
+                    "Kode verifikasi MonitorMBG Anda adalah {}.\r\n\r\nKode ini berlaku selama {} menit. Jangan bagikan kode ini kepada siapa pun.",
+                    code,
+                    expires_in / 60
+                );
+                self.send_email(to, "Kode Verifikasi MonitorMBG", &body)
+                    .await
+            }
+            Message::Text { subject, body } => self.send_email(to, subject, body).await,
+        }
+    }
+}
+
+/// Starts a TLS session with `host` over `stream`.
+async fn tls_connect(
+    host: &str,
+    stream: TcpStream,
+) -> std::io::Result<tokio_native_tls::TlsStream<TcpStream>> {
+    let connector = tokio_native_tls::TlsConnector::from(
+        native_tls::TlsConnector::new().map_err(std::io::Error::other)?,
+    );
+    connector
+        .connect(host, stream)
+        .await
+        .map_err(std::io::Error::other)
+}
+
+/// Sends one SMTP command and waits for the expected reply code.
+async fn command<S>(conn: &mut BufReader<S>, line: &str, expected: u16) -> std::io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    conn.get_mut()
+        .write_all(format!("{}\r\n", line).as_bytes())
+        .await?;
+    conn.get_mut().flush().await?;
+    expect_reply(conn, expected).await
+}
+
+/// Reads a (possibly multi-line) SMTP reply and checks its code.
+async fn expect_reply<S>(conn: &mut BufReader<S>, expected: u16) -> std::io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        let mut line = String::new();
+        if conn.read_line(&mut line).await? == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "SMTP server closed the connection",
+            ));
+        }
+
+        let code: u16 = line.get(..3).and_then(|c| c.parse().ok()).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Malformed SMTP reply: {}", line.trim_end()),
+            )
+        })?;
+
+        // "250-..." continues a multi-line reply, "250 ..." ends it
+        if line.as_bytes().get(3) == Some(&b'-') {
+            continue;
+        }
+
+        if code != expected {
+            return Err(std::io::Error::other(format!(
+                "Unexpected SMTP reply (expected {}): {}",
+                expected,
+                line.trim_end()
+            )));
+        }
+        return Ok(());
+    }
+}
+
+/// Rejects addresses that could inject SMTP commands or headers.
+fn is_valid_address(address: &str) -> bool {
+    address.contains('@')
+        && !address
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || c == '<' || c == '>')
+}
+
+/// Builds the `DATA` payload: headers, a blank line, and the dot-stuffed body.
+fn format_message(from: &str, to: &str, subject: &str, body: &str) -> String {
+    let subject = if subject.is_ascii() && !subject.contains(['\r', '\n']) {
+        subject.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(subject))
+    };
+
+    let body = body
+        .replace("\r\n", "\n")
+        .split('\n')
+        .map(|line| match line.strip_prefix('.') {
+            Some(_) => format!(".{}", line),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n");
+
+    format!(
+        "From: <{from}>\r\nTo: <{to}>\r\nSubject: {subject}\r\nDate: {date}\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{body}\r\n",
+        date = chrono::Utc::now().to_rfc2822(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::config::SmtpConfig;
+
+    /// Runs a scripted SMTP server for a single session and returns
+    /// everything the client sent.
+    async fn fake_smtp_server() -> (u16, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut conn = BufReader::new(stream);
+            let mut transcript = String::new();
+            let mut in_data = false;
+
+            conn.get_mut()
+                .write_all(b"220 fake ESMTP\r\n")
+                .await
+                .unwrap();
+            loop {
+                let mut line = String::new();
+                if conn.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                transcript.push_str(&line);
+
+                let reply: &[u8] = if in_data {
+                    if line == ".\r\n" {
+                        in_data = false;
+                        b"250 queued\r\n"
+                    } else {
+                        continue;
+                    }
+                } else if line.starts_with("EHLO") {
+                    b"250-fake\r\n250 AUTH PLAIN\r\n"
+                } else if line.starts_with("STARTTLS") {
+                    b"502 not supported\r\n"
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line.starts_with("QUIT") {
+                    conn.get_mut().write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+                conn.get_mut().write_all(reply).await.unwrap();
+            }
+            transcript
+        });
+
+        (port, handle)
+    }
+
+    /// An unencrypted SMTP setup on `port` with credentials.
+    fn smtp_config(port: u16) -> SmtpConfig {
+        SmtpConfig {
+            host: Some("127.0.0.1".to_string()),
+            port,
+            username: Some("user".to_string()),
+            password: Some("pass".to_string()),
+            from: Some("noreply@monitormbg.id".to_string()),
+            tls: false,
+            starttls: false,
+        }
+    }
+
+    fn client(smtp: SmtpConfig) -> SmtpEmailClient {
+        SmtpEmailClient::new(Arc::new(Config {
+            smtp,
+            ..Default::default()
+        }))
+    }
+
+    fn recipient() -> Recipient {
+        Recipient {
+            email: Some("school@example.com".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_email_client_disabled() {
+        let client = SmtpEmailClient::new(Arc::new(Config::default()));
+        assert!(!client.is_enabled());
+        assert!(!client.can_deliver(&Recipient {
+            email: Some("school@example.com".to_string()),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_format_message_dot_stuffs_body() {
+        let data = format_message("a@x.id", "b@x.id", "Hi", "line\n.hidden");
+        assert!(data.contains("Subject: Hi\r\n"));
+        assert!(data.ends_with("\r\n\r\nline\r\n..hidden\r\n"));
+
+        let data = format_message("a@x.id", "b@x.id", "Kode\r\nBcc: x", "");
+        assert!(!data.contains("Bcc: x"));
+    }
+
+    #[test]
+    fn test_is_valid_address() {
+        assert!(is_valid_address("school@example.com"));
+        assert!(!is_valid_address("no-at-sign"));
+        assert!(!is_valid_address("a@x.id>\r\nRCPT TO:<evil@x.id"));
+    }
+
+    #[tokio::test]
+    async fn test_send_otp_email() {
+        let (port, server) = fake_smtp_server().await;
+        let client = client(SmtpConfig {
+            username: None,
+            password: None,
+            ..smtp_config(port)
+        });
+
+        let message = Message::Otp {
+            code: "123456".to_string(),
+            reference_id: "otp_ref".to_string(),
+            expires_in: 300,
+        };
+        client.send(&recipient(), &message).await.unwrap();
+
+        let transcript = server.await.unwrap();
+        assert!(!transcript.contains("AUTH"));
+        assert!(transcript.contains("MAIL FROM:<noreply@monitormbg.id>\r\n"));
+        assert!(transcript.contains("RCPT TO:<school@example.com>\r\n"));
+        assert!(transcript.contains("123456"));
+    }
+
+    #[tokio::test]
+    async fn test_credentials_not_sent_unencrypted() {
+        let (port, server) = fake_smtp_server().await;
+        let client = client(smtp_config(port));
+
+        let message = Message::Text {
+            subject: "Hi".to_string(),
+            body: "Hello".to_string(),
+        };
+        assert!(client.send(&recipient(), &message).await.is_err());
+
+        let transcript = server.await.unwrap();
+        assert!(!transcript.contains("AUTH"));
+        assert!(!transcript.contains("MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn test_starttls_required_when_enabled() {
+        let (port, server) = fake_smtp_server().await;
+        let client = client(SmtpConfig {
+            starttls: true,
+            ..smtp_config(port)
+        });
+
+        let message = Message::Text {
+            subject: "Hi".to_string(),
+            body: "Hello".to_string(),
+        };
+        assert!(client.send(&recipient(), &message).await.is_err());
+
+        let transcript = server.await.unwrap();
+        assert!(transcript.contains("STARTTLS\r\n"));
+        assert!(!transcript.contains("AUTH"));
+        assert!(!transcript.contains("MAIL FROM"));
+    }
+}
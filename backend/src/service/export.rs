@@ -0,0 +1,169 @@
+//! Streaming CSV and JSON exports.
+//!
+//! Exports are encoded batch by batch as rows are read from the database, so
+//! a large export never has to be buffered in memory.
+
+use futures::Stream;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// A streamed export body.
+pub type ExportStream = BoxStream<'static, Result<Vec<u8>, AppError>>;
+
+/// The format of an export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row.
+    #[default]
+    Csv,
+    /// A JSON array of objects.
+    Json,
+}
+
+impl ExportFormat {
+    /// Parses the `format` query parameter, defaulting to CSV when absent.
+    pub fn parse(value: Option<&str>) -> Result<Self, AppError> {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("csv") => Ok(Self::Csv),
+            Some("json") => Ok(Self::Json),
+            Some(_) => Err(AppError::BadRequest("format must be csv or json".into())),
+        }
+    }
+
+    /// The `Content-Type` of an export in this format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+
+    /// The file extension of an export in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Encodes batches of rows as a byte stream in `format`.
+///
+/// Each batch becomes one chunk. CSV headers are taken from the first row's
+/// field names; an export without rows is empty for CSV and `[]` for JSON.
+pub fn encode<T, S>(format: ExportFormat, batches: S) -> ExportStream
+where
+    T: Serialize + Send + 'static,
+    S: Stream<Item = Result<Vec<T>, AppError>> + Send + 'static,
+{
+    let mut first = true;
+    let body = batches.map(move |batch| {
+        let rows = batch?;
+        let chunk = match format {
+            ExportFormat::Csv => encode_csv(&rows, first)?,
+            ExportFormat::Json => encode_json(&rows, first)?,
+        };
+        if !rows.is_empty() {
+            first = false;
+        }
+        Ok(chunk)
+    });
+
+    match format {
+        ExportFormat::Csv => body.boxed(),
+        ExportFormat::Json => futures::stream::once(async { Ok(b"[".to_vec()) })
+            .chain(body)
+            .chain(futures::stream::once(async { Ok(b"]".to_vec()) }))
+            .boxed(),
+    }
+}
+
+fn encode_csv<T: Serialize>(rows: &[T], with_headers: bool) -> Result<Vec<u8>, AppError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(with_headers)
+        .from_writer(Vec::new());
+    for row in rows {
+        writer
+            .serialize(row)
+            .map_err(|e| AppError::InternalServerError(format!("CSV export failed: {}", e)))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| AppError::InternalServerError(format!("CSV export failed: {}", e)))
+}
+
+fn encode_json<T: Serialize>(rows: &[T], first: bool) -> Result<Vec<u8>, AppError> {
+    let mut chunk = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        if !(first && i == 0) {
+            chunk.push(b',');
+        }
+        serde_json::to_writer(&mut chunk, row)
+            .map_err(|e| AppError::InternalServerError(format!("JSON export failed: {}", e)))?;
+    }
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        id: i32,
+        name: &'static str,
+    }
+
+    async fn collect(format: ExportFormat, batches: Vec<Vec<Row>>) -> String {
+        let stream = futures::stream::iter(batches.into_iter().map(Ok));
+        let chunks: Vec<Vec<u8>> = encode(format, stream).try_collect().await.unwrap();
+        String::from_utf8(chunks.concat()).unwrap()
+    }
+
+    fn batches() -> Vec<Vec<Row>> {
+        vec![
+            vec![],
+            vec![
+                Row { id: 1, name: "a" },
+                Row {
+                    id: 2,
+                    name: "b, c",
+                },
+            ],
+            vec![Row { id: 3, name: "d" }],
+        ]
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(ExportFormat::parse(None).unwrap(), ExportFormat::Csv);
+        assert_eq!(
+            ExportFormat::parse(Some("JSON")).unwrap(),
+            ExportFormat::Json
+        );
+        assert!(ExportFormat::parse(Some("xml")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encode_csv_writes_headers_once() {
+        assert_eq!(
+            collect(ExportFormat::Csv, batches()).await,
+            "id,name\n1,a\n2,\"b, c\"\n3,d\n"
+        );
+        assert_eq!(collect(ExportFormat::Csv, vec![]).await, "");
+    }
+
+    #[tokio::test]
+    async fn test_encode_json_is_one_array() {
+        let body = collect(ExportFormat::Json, batches()).await;
+        let value: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 3);
+        assert_eq!(value[1]["name"], "b, c");
+        assert_eq!(collect(ExportFormat::Json, vec![]).await, "[]");
+    }
+}
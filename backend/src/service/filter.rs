@@ -0,0 +1,304 @@
+//! Validation of enum-valued request strings.
+//!
+//! List endpoints accept enum filters (e.g. incident `status`, kitchen `type`)
+//! as plain strings and compare them against the database enum's text form.
+//! An unknown value would silently match nothing, so services validate these
+//! strings with [`parse_filter`] before querying. Enum fields in request
+//! bodies are converted with [`parse_enum`] so clients use the same values.
+//!
+//! The serde representation of every enum matches these database values, so
+//! JSON, query strings and the database all agree.
+
+use crate::database::model::AlertSeverity;
+use crate::database::model::AlertType;
+use crate::database::model::ChecklistStatus;
+use crate::database::model::ComplaintCategory;
+use crate::database::model::ComplaintStatus;
+use crate::database::model::ComplianceTrend;
+use crate::database::model::EvidenceCaptureMethod;
+use crate::database::model::EvidenceMetadataStatus;
+use crate::database::model::FindingCategory;
+use crate::database::model::IncidentSeverity;
+use crate::database::model::IncidentSource;
+use crate::database::model::IncidentStatus;
+use crate::database::model::IncidentType;
+use crate::database::model::InspectionFollowUpStatus;
+use crate::database::model::KitchenType;
+use crate::database::model::UserRole;
+use crate::database::table::KitchenSortColumn;
+use crate::error::AppError;
+
+/// An enum accepted as a plain string in query parameters or request bodies.
+pub trait FilterEnum: Copy + PartialEq + 'static {
+    /// Every variant with its value exactly as stored in the database.
+    const VARIANTS: &'static [(&'static str, Self)];
+
+    /// Returns the database value of this variant.
+    fn as_str(self) -> &'static str {
+        Self::VARIANTS
+            .iter()
+            .find(|(_, variant)| *variant == self)
+            .map(|(value, _)| *value)
+            .unwrap_or_default()
+    }
+}
+
+impl FilterEnum for IncidentStatus {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("investigating", Self::Investigating),
+        ("resolved", Self::Resolved),
+        ("escalated", Self::Escalated),
+    ];
+}
+
+impl FilterEnum for IncidentType {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("poisoning", Self::Poisoning),
+        ("nutrition", Self::Nutrition),
+        ("sanitation", Self::Sanitation),
+        ("other", Self::Other),
+    ];
+}
+
+impl FilterEnum for IncidentSource {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("consumer", Self::Consumer),
+        ("inspector", Self::Inspector),
+        ("public", Self::Public),
+    ];
+}
+
+impl FilterEnum for IncidentSeverity {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("minor", Self::Minor),
+        ("major", Self::Major),
+        ("critical", Self::Critical),
+    ];
+}
+
+impl FilterEnum for KitchenType {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("Central Kitchen", Self::CentralKitchen),
+        ("Regional Kitchen", Self::RegionalKitchen),
+        ("Satellite Kitchen", Self::SatelliteKitchen),
+    ];
+}
+
+impl FilterEnum for KitchenSortColumn {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("rating", Self::Rating),
+        ("name", Self::Name),
+        ("mealsServed", Self::MealsServed),
+        ("totalReviews", Self::TotalReviews),
+        ("createdAt", Self::CreatedAt),
+    ];
+}
+
+impl FilterEnum for InspectionFollowUpStatus {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("pending", Self::Pending),
+        ("in-progress", Self::InProgress),
+        ("completed", Self::Completed),
+    ];
+}
+
+impl FilterEnum for FindingCategory {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("major", Self::Major),
+        ("minor", Self::Minor),
+        ("observation", Self::Observation),
+    ];
+}
+
+impl FilterEnum for ChecklistStatus {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("pass", Self::Pass),
+        ("fail", Self::Fail),
+        ("warning", Self::Warning),
+    ];
+}
+
+impl FilterEnum for ComplianceTrend {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("improving", Self::Improving),
+        ("stable", Self::Stable),
+        ("declining", Self::Declining),
+    ];
+}
+
+impl FilterEnum for ComplaintCategory {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("hygiene", Self::Hygiene),
+        ("taste", Self::Taste),
+        ("portion", Self::Portion),
+        ("temperature", Self::Temperature),
+        ("packaging", Self::Packaging),
+        ("other", Self::Other),
+    ];
+}
+
+impl FilterEnum for ComplaintStatus {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("pending", Self::Pending),
+        ("in-progress", Self::InProgress),
+        ("resolved", Self::Resolved),
+    ];
+}
+
+impl FilterEnum for EvidenceCaptureMethod {
+    const VARIANTS: &'static [(&'static str, Self)] =
+        &[("camera", Self::Camera), ("fallback", Self::Fallback)];
+}
+
+impl FilterEnum for EvidenceMetadataStatus {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("verified", Self::Verified),
+        ("mismatch", Self::Mismatch),
+        ("unverified", Self::Unverified),
+    ];
+}
+
+impl FilterEnum for AlertType {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("compliance", Self::Compliance),
+        ("incident", Self::Incident),
+        ("inspection", Self::Inspection),
+        ("complaint", Self::Complaint),
+    ];
+}
+
+impl FilterEnum for AlertSeverity {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("low", Self::Low),
+        ("medium", Self::Medium),
+        ("high", Self::High),
+        ("critical", Self::Critical),
+    ];
+}
+
+impl FilterEnum for UserRole {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("kitchen", Self::Kitchen),
+        ("supplier", Self::Supplier),
+        ("school", Self::School),
+        ("admin", Self::Admin),
+    ];
+}
+
+/// Checks an optional filter value against the variants of `T`.
+///
+/// # Arguments
+///
+/// * `param` - The query parameter name, used in the error message.
+/// * `value` - The raw filter value, if provided.
+///
+/// # Errors
+///
+/// Returns `BadRequest` listing the valid options if `value` is not one of
+/// them.
+pub fn parse_filter<T: FilterEnum>(
+    param: &str,
+    value: Option<String>,
+) -> Result<Option<String>, AppError> {
+    match value {
+        Some(v) => parse_enum::<T>(param, &v).map(|_| Some(v)),
+        None => Ok(None),
+    }
+}
+
+/// Converts a string to the variant of `T` with that database value.
+///
+/// # Errors
+///
+/// Returns `BadRequest` listing the valid options if `value` is not one of
+/// them.
+pub fn parse_enum<T: FilterEnum>(param: &str, value: &str) -> Result<T, AppError> {
+    T::VARIANTS
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, variant)| *variant)
+        .ok_or_else(|| {
+            let valid: Vec<&str> = T::VARIANTS.iter().map(|(name, _)| *name).collect();
+            AppError::BadRequest(format!(
+                "Invalid {} '{}'; expected one of: {}",
+                param,
+                value,
+                valid.join(", ")
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            parse_filter::<IncidentStatus>("status", None).unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_filter::<IncidentStatus>("status", Some("resolved".to_string())).unwrap(),
+            Some("resolved".to_string())
+        );
+
+        let err =
+            parse_filter::<KitchenType>("type", Some("central_kitchen".to_string())).unwrap_err();
+        match err {
+            AppError::BadRequest(msg) => {
+                assert!(msg.contains("central_kitchen"));
+                assert!(msg.contains("Central Kitchen, Regional Kitchen, Satellite Kitchen"));
+            }
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_enum_round_trips() {
+        let status =
+            parse_enum::<InspectionFollowUpStatus>("followUpStatus", "in-progress").unwrap();
+        assert_eq!(status, InspectionFollowUpStatus::InProgress);
+        assert_eq!(status.as_str(), "in-progress");
+        assert!(parse_enum::<FindingCategory>("category", "Major").is_err());
+    }
+
+    /// Asserts that every variant of `T` serializes to its database value and
+    /// deserializes back from it.
+    fn assert_serde_matches_db<T>()
+    where
+        T: FilterEnum + std::fmt::Debug + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        for (value, variant) in T::VARIANTS {
+            assert_eq!(serde_json::to_value(variant).unwrap(), *value);
+            assert_eq!(
+                serde_json::from_value::<T>(serde_json::json!(value)).unwrap(),
+                *variant
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde_matches_db_values() {
+        assert_serde_matches_db::<UserRole>();
+        assert_serde_matches_db::<ComplianceTrend>();
+        assert_serde_matches_db::<ChecklistStatus>();
+        assert_serde_matches_db::<IncidentType>();
+        assert_serde_matches_db::<IncidentSource>();
+        assert_serde_matches_db::<IncidentSeverity>();
+        assert_serde_matches_db::<IncidentStatus>();
+        assert_serde_matches_db::<InspectionFollowUpStatus>();
+        assert_serde_matches_db::<FindingCategory>();
+        assert_serde_matches_db::<ComplaintCategory>();
+        assert_serde_matches_db::<ComplaintStatus>();
+        assert_serde_matches_db::<EvidenceMetadataStatus>();
+        assert_serde_matches_db::<EvidenceCaptureMethod>();
+        assert_serde_matches_db::<AlertType>();
+        assert_serde_matches_db::<AlertSeverity>();
+        assert_serde_matches_db::<KitchenType>();
+
+        // Rust variant names are not accepted
+        assert!(serde_json::from_str::<ComplaintStatus>("\"InProgress\"").is_err());
+        assert!(serde_json::from_str::<UserRole>("\"Admin\"").is_err());
+    }
+}
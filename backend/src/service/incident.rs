@@ -2,17 +2,49 @@
 
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::auth::utils::Claims;
+use crate::config::IncidentDedupConfig;
 use crate::database::Database;
+use crate::database::cursor::Cursor;
+use crate::database::model::AffectedInstitution;
+use crate::database::model::Alert;
+use crate::database::model::AlertSeverity;
+use crate::database::model::AlertType;
+use crate::database::model::CorrectiveAction;
 use crate::database::model::Incident;
+use crate::database::model::IncidentLabResult;
 use crate::database::model::IncidentSeverity;
 use crate::database::model::IncidentSource;
 use crate::database::model::IncidentStatus;
+use crate::database::model::IncidentStatusChange;
+use crate::database::model::IncidentTimelineEvent;
+use crate::database::model::IncidentType;
+use crate::database::model::UserRole;
+use crate::database::table::IncidentFilter;
 use crate::database::table::Table;
 use crate::error::AppError;
+use crate::service::audit::deletion_entry;
+use crate::service::datetime::deserialize_optional_utc;
+use crate::service::datetime::deserialize_utc;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::filter::FilterEnum;
+use crate::service::filter::parse_enum;
+use crate::service::filter::parse_filter;
+use crate::service::pagination::PaginatedResponse;
+use crate::service::pagination::Pagination;
+use crate::service::webhook::WebhookDispatcher;
+
+/// Webhook event sent when a critical incident is reported.
+pub const CRITICAL_INCIDENT_EVENT: &str = "incident.critical";
 
 #[derive(Debug, Serialize)]
 pub struct IncidentDto {
@@ -29,7 +61,7 @@ pub struct IncidentDto {
     pub cause: Option<String>,
     pub status: Option<IncidentStatus>,
     pub severity: IncidentSeverity,
-    pub coordinates: Option<CoordinatesDto>,
+    pub coordinates: Option<Coordinates>,
     #[serde(rename = "relatedKitchenId")]
     pub related_kitchen_id: Uuid,
     pub source: IncidentSource,
@@ -41,12 +73,51 @@ pub struct IncidentDto {
     pub resolved_at: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct CoordinatesDto {
+/// A validated map point, stored in `Incident.map_coordinates` and
+/// `gps_coordinates` as `{"lat": .., "lng": ..}`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Coordinates {
     pub lat: f64,
     pub lng: f64,
 }
 
+impl Coordinates {
+    /// Parses coordinates from their stored JSON form.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` unless `value` is an object with a numeric `lat` in
+    /// [-90, 90] and a numeric `lng` in [-180, 180]; `field` names the offending
+    /// field in the message.
+    pub fn from_value(value: &Value, field: &str) -> Result<Self, AppError> {
+        let invalid = || {
+            AppError::BadRequest(format!(
+                "{} must be an object with numeric lat and lng",
+                field
+            ))
+        };
+        // serde would otherwise also accept a `[lat, lng]` array
+        if !value.is_object() {
+            return Err(invalid());
+        }
+        let coordinates: Coordinates =
+            serde_json::from_value(value.clone()).map_err(|_| invalid())?;
+        if !(-90.0..=90.0).contains(&coordinates.lat) {
+            return Err(AppError::BadRequest(format!(
+                "{}.lat must be between -90 and 90",
+                field
+            )));
+        }
+        if !(-180.0..=180.0).contains(&coordinates.lng) {
+            return Err(AppError::BadRequest(format!(
+                "{}.lng must be between -180 and 180",
+                field
+            )));
+        }
+        Ok(coordinates)
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct IncidentDetailDto {
     #[serde(flatten)]
@@ -63,6 +134,17 @@ pub struct IncidentDetailDto {
     pub corrective_actions: Vec<String>,
     #[serde(rename = "sourceUrl")]
     pub source_url: Option<String>,
+    #[serde(rename = "statusHistory")]
+    pub status_history: Vec<StatusChangeDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusChangeDto {
+    pub from: IncidentStatus,
+    pub to: IncidentStatus,
+    pub note: Option<String>,
+    #[serde(rename = "changedAt")]
+    pub changed_at: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -81,50 +163,327 @@ pub struct LaboratoryResultsDto {
     pub confirmed_by: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct IncidentListResponse {
-    pub data: Vec<IncidentDto>,
-    pub pagination: Pagination,
+/// Request body for reporting a new incident.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateIncidentRequest {
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Uuid,
+    pub r#type: String,
+    pub source: String,
+    pub severity: String,
+    #[serde(deserialize_with = "deserialize_utc")]
+    pub date: DateTime<Utc>,
+    pub location: Option<String>,
+    pub province: Option<String>,
+    #[serde(rename = "foodType")]
+    pub food_type: Option<String>,
+    #[serde(rename = "affectedCount")]
+    pub affected_count: Option<i32>,
+    pub deaths: Option<i32>,
+    pub hospitalized: Option<i32>,
+    pub cause: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "mapCoordinates")]
+    pub map_coordinates: Option<Value>,
+    #[serde(rename = "gpsCoordinates")]
+    pub gps_coordinates: Option<Value>,
+    #[serde(rename = "sourceUrl")]
+    pub source_url: Option<String>,
+    /// Also raise a `critical` alert for the kitchen.
+    #[serde(rename = "createAlert", default)]
+    pub create_alert: bool,
+}
+
+/// Outcome of [`IncidentService::create_incident`].
+#[derive(Debug)]
+pub struct ReportedIncident {
+    pub detail: IncidentDetailDto,
+    /// Whether a similar existing incident was returned instead of storing a
+    /// new one.
+    pub deduplicated: bool,
+}
+
+/// Request body for adding an event to an incident's timeline.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateTimelineEventRequest {
+    #[serde(deserialize_with = "deserialize_utc")]
+    pub date: DateTime<Utc>,
+    pub event: String,
+    pub description: Option<String>,
+}
+
+/// Request body for recording a laboratory result for an incident.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateLabResultRequest {
+    pub pathogen: String,
+    #[serde(rename = "testDate")]
+    pub test_date: NaiveDate,
+    #[serde(rename = "confirmedBy")]
+    pub confirmed_by: String,
+}
+
+/// Request body for recording an institution affected by an incident.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateAffectedInstitutionRequest {
+    #[serde(rename = "institutionName")]
+    pub institution_name: String,
+    #[serde(rename = "institutionType")]
+    pub institution_type: Option<String>,
+    #[serde(rename = "affectedCount")]
+    pub affected_count: Option<i32>,
+}
+
+/// Request body for recording a corrective action for an incident.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateCorrectiveActionRequest {
+    pub action: String,
+    pub completed: Option<bool>,
+    #[serde(
+        rename = "completedDate",
+        default,
+        deserialize_with = "deserialize_optional_utc"
+    )]
+    pub completed_date: Option<DateTime<Utc>>,
+}
+
+/// Request body for changing an incident's status.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateIncidentStatusRequest {
+    pub status: String,
+    /// Must be set to move a resolved incident back to `investigating`.
+    #[serde(default)]
+    pub reopen: bool,
+    pub note: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct Pagination {
-    pub total: i64,
-    pub limit: i64,
-    pub offset: i64,
-    #[serde(rename = "hasMore")]
-    pub has_more: bool,
+pub struct IncidentListResponse {
+    #[serde(flatten)]
+    pub page: PaginatedResponse<IncidentDto>,
+    /// Cursor for the next page when using keyset pagination (`null` on the last page
+    /// or when paginating by offset).
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<String>,
 }
 
 /// Service for handling food safety incidents and reports.
 pub struct IncidentService {
     db: Arc<Database>,
+    webhooks: Option<WebhookDispatcher>,
+    dedup: IncidentDedupConfig,
 }
 
 impl IncidentService {
     /// Creates a new `IncidentService`.
     pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+        Self {
+            db,
+            webhooks: None,
+            dedup: IncidentDedupConfig::default(),
+        }
+    }
+
+    /// Sets how close to an existing one a public incident must be to count
+    /// as a duplicate.
+    pub fn with_dedup(mut self, dedup: IncidentDedupConfig) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Notifies `webhooks` whenever a critical incident is created.
+    pub fn with_webhooks(mut self, webhooks: WebhookDispatcher) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Stores a new incident and returns its ID.
+    ///
+    /// Critical incidents are also sent to the configured webhooks on a
+    /// background task, so delivery doesn't delay the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if `map_coordinates` or `gps_coordinates` are not
+    /// valid coordinates.
+    pub async fn create(&self, incident: Incident) -> Result<Uuid, AppError> {
+        if let Some(value) = &incident.map_coordinates {
+            Coordinates::from_value(value, "mapCoordinates")?;
+        }
+        if let Some(value) = &incident.gps_coordinates {
+            Coordinates::from_value(value, "gpsCoordinates")?;
+        }
+
+        let id = self.db.incident_table.insert(&incident).await?;
+
+        if incident.severity == IncidentSeverity::Critical
+            && let Some(webhooks) = &self.webhooks
+        {
+            // Re-read so the payload carries the stored timestamps
+            let stored = self.db.incident_table.select(&id).await?;
+            let dto = self.map_to_dto(stored.unwrap_or(Incident { id, ..incident }));
+            webhooks.dispatch(CRITICAL_INCIDENT_EVENT, &dto);
+        }
+
+        Ok(id)
+    }
+
+    /// Reports a new incident and returns it with its details.
+    ///
+    /// New incidents start as `investigating`. With `create_alert` set, a
+    /// `critical` incident alert is raised for the kitchen as well.
+    ///
+    /// Incidents from public sources are checked for duplicates first: if
+    /// the kitchen already has an incident of the same type dated within the
+    /// configured window, that incident is returned with `deduplicated` set
+    /// and nothing is stored or alerted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `BadRequest` for an unknown
+    /// kitchen, type, source or severity, a negative count, more deaths than
+    /// affected people, or invalid coordinates.
+    pub async fn create_incident(
+        &self,
+        claims: &Claims,
+        req: CreateIncidentRequest,
+    ) -> Result<ReportedIncident, AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can report incidents".into(),
+            ));
+        }
+        let r#type = parse_enum::<IncidentType>("type", &req.r#type)?;
+        let source = parse_enum::<IncidentSource>("source", &req.source)?;
+        let severity = parse_enum::<IncidentSeverity>("severity", &req.severity)?;
+        for (field, count) in [
+            ("affectedCount", req.affected_count),
+            ("deaths", req.deaths),
+            ("hospitalized", req.hospitalized),
+        ] {
+            if count.is_some_and(|c| c < 0) {
+                return Err(AppError::BadRequest(format!(
+                    "{} must not be negative",
+                    field
+                )));
+            }
+        }
+        if req.deaths.unwrap_or(0) > req.affected_count.unwrap_or(0) {
+            return Err(AppError::BadRequest(
+                "deaths must not exceed affectedCount".into(),
+            ));
+        }
+
+        if !self.db.kitchen_table.exists(&req.kitchen_id).await? {
+            return Err(AppError::BadRequest(
+                "referenced kitchen does not exist".into(),
+            ));
+        }
+
+        if source == IncidentSource::Public && self.dedup.window_hours > 0 {
+            let window = Duration::hours(i64::from(self.dedup.window_hours));
+            let (Some(from), Some(to)) = (
+                req.date.checked_sub_signed(window),
+                req.date.checked_add_signed(window),
+            ) else {
+                return Err(AppError::BadRequest("date is out of range".into()));
+            };
+            let existing = self
+                .db
+                .incident_table
+                .find_similar(&req.kitchen_id, r#type, req.date, from, to)
+                .await?;
+            if let Some(existing) = existing {
+                return Ok(ReportedIncident {
+                    detail: self.get_incident_detail(existing.id).await?,
+                    deduplicated: true,
+                });
+            }
+        }
+
+        let trim = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let incident = Incident {
+            kitchen_id: req.kitchen_id,
+            r#type,
+            source,
+            date: req.date,
+            location: trim(req.location),
+            province: trim(req.province),
+            food_type: trim(req.food_type),
+            affected_count: req.affected_count,
+            deaths: req.deaths,
+            cause: trim(req.cause),
+            severity,
+            status: Some(IncidentStatus::Investigating),
+            description: trim(req.description),
+            reported_by: Some(claims.sub.to_string()),
+            map_coordinates: req.map_coordinates,
+            gps_coordinates: req.gps_coordinates,
+            hospitalized: req.hospitalized,
+            source_url: trim(req.source_url),
+            ..Default::default()
+        };
+        let id = self.create(incident).await?;
+
+        if req.create_alert {
+            let alert = Alert {
+                kitchen_id: Some(req.kitchen_id),
+                r#type: AlertType::Incident,
+                severity: AlertSeverity::Critical,
+                title: format!("New {} incident", r#type.as_str()),
+                message: format!(
+                    "A {} {} incident was reported on {}",
+                    severity.as_str(),
+                    r#type.as_str(),
+                    req.date.date_naive()
+                ),
+                timestamp: Some(Utc::now()),
+                acknowledged: Some(false),
+                ..Default::default()
+            };
+            self.db.alert_table.insert(&alert).await?;
+        }
+
+        Ok(ReportedIncident {
+            detail: self.get_incident_detail(id).await?,
+            deduplicated: false,
+        })
     }
 
     /// Lists incidents with optional filtering and pagination.
+    ///
+    /// `q` searches incident descriptions; multi-word queries are ranked by
+    /// relevance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if `status` is not a valid incident status.
+    #[allow(clippy::too_many_arguments)]
     pub async fn list_incidents(
         &self,
         limit: i64,
         offset: i64,
+        q: Option<String>,
         status: Option<String>,
         province: Option<String>,
         _date_from: Option<String>,
         _date_to: Option<String>,
         min_victims: Option<i32>,
     ) -> Result<IncidentListResponse, AppError> {
+        let status = parse_filter::<IncidentStatus>("status", status)?;
         let (incidents, total) = self
             .db
             .incident_table
             .list_incidents(
-                status.as_deref(),
-                province.as_deref(),
-                min_victims,
+                &IncidentFilter {
+                    query: q.as_deref(),
+                    status: status.as_deref(),
+                    province: province.as_deref(),
+                    min_victims,
+                },
                 limit,
                 offset,
             )
@@ -133,13 +492,78 @@ impl IncidentService {
         let dtos = incidents.into_iter().map(|i| self.map_to_dto(i)).collect();
 
         Ok(IncidentListResponse {
-            data: dtos,
-            pagination: Pagination {
-                total,
-                limit,
-                offset,
-                has_more: offset + limit < total,
+            page: PaginatedResponse {
+                data: dtos,
+
+                pagination: Pagination::new(total, limit, offset),
+            },
+            next_cursor: None,
+        })
+    }
+
+    /// Lists incidents with optional filtering using keyset pagination.
+    ///
+    /// An empty `cursor` requests the first page. Results are ordered by
+    /// `created_at DESC, id DESC`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if the cursor or `status` filter is malformed.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn list_incidents_after_cursor(
+        &self,
+        limit: i64,
+        cursor: &str,
+        q: Option<String>,
+        status: Option<String>,
+        province: Option<String>,
+        min_victims: Option<i32>,
+    ) -> Result<IncidentListResponse, AppError> {
+        let status = parse_filter::<IncidentStatus>("status", status)?;
+        let cursor = if cursor.trim().is_empty() {
+            None
+        } else {
+            Some(
+                Cursor::decode(cursor)
+                    .ok_or_else(|| AppError::BadRequest("Invalid cursor".into()))?,
+            )
+        };
+
+        let (mut incidents, total) = self
+            .db
+            .incident_table
+            .list_after_cursor(
+                &IncidentFilter {
+                    query: q.as_deref(),
+                    status: status.as_deref(),
+                    province: province.as_deref(),
+                    min_victims,
+                },
+                cursor.as_ref(),
+                limit + 1,
+            )
+            .await?;
+
+        let has_more = incidents.len() as i64 > limit;
+        incidents.truncate(limit as usize);
+
+        let next_cursor = if has_more {
+            incidents
+                .last()
+                .map(|i| Cursor::new(i.created_at.unwrap_or_default(), i.id).encode())
+        } else {
+            None
+        };
+
+        let dtos = incidents.into_iter().map(|i| self.map_to_dto(i)).collect();
+
+        Ok(IncidentListResponse {
+            page: PaginatedResponse {
+                data: dtos,
+
+                pagination: Pagination::keyset(total, limit, has_more),
             },
+            next_cursor,
         })
     }
 
@@ -164,7 +588,7 @@ impl IncidentService {
         let timeline = timeline_events
             .into_iter()
             .map(|e| TimelineEventDto {
-                date: e.event_date.to_string(),
+                date: utc_to_rfc3339(e.event_date),
                 event: e.event_title,
                 description: e.description.unwrap_or_default(),
             })
@@ -189,6 +613,16 @@ impl IncidentService {
         // Get corrective actions
         let corrective_actions = self.db.incident_table.get_corrective_actions(&id).await?;
 
+        let status_history = self
+            .db
+            .incident_table
+            .get_status_history(&id)
+            .await?
+            .into_iter()
+            .map(map_status_change_to_dto)
+            .collect();
+
+        let source_url = incident.source_url.clone();
         let dto = self.map_to_dto(incident);
 
         Ok(IncidentDetailDto {
@@ -199,25 +633,256 @@ impl IncidentService {
             affected_institutions,
             laboratory_results,
             corrective_actions,
-            source_url: None, // Not in base incident model
+            source_url,
+            status_history,
         })
     }
 
-    fn map_to_dto(&self, i: Incident) -> IncidentDto {
-        let coords = if let Some(Value::Object(map)) = i.map_coordinates {
-            if let (Some(Value::Number(lat)), Some(Value::Number(lng))) =
-                (map.get("lat"), map.get("lng"))
-            {
-                Some(CoordinatesDto {
-                    lat: lat.as_f64().unwrap_or_default(),
-                    lng: lng.as_f64().unwrap_or_default(),
-                })
-            } else {
-                None
-            }
-        } else {
-            None
+    /// Changes an incident's status and records the change in its status
+    /// history.
+    ///
+    /// Open incidents move freely between `investigating` and `escalated` and
+    /// can be resolved. A resolved incident only goes back to `investigating`
+    /// when `reopen` is set. Resolving stamps `resolvedAt`; reopening clears it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the incident doesn't
+    /// exist, `BadRequest` for an unknown status or an illegal transition (the
+    /// message lists the allowed next states), and `Conflict` if the status
+    /// changed concurrently.
+    pub async fn update_status(
+        &self,
+        incident_id: Uuid,
+        claims: &Claims,
+        req: UpdateIncidentStatusRequest,
+    ) -> Result<IncidentDto, AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can update incident status".into(),
+            ));
+        }
+        let to = parse_enum::<IncidentStatus>("status", &req.status)?;
+
+        let incident = self
+            .db
+            .incident_table
+            .select(&incident_id)
+            .await?
+            .ok_or(AppError::NotFound("Incident not found".into()))?;
+        let from = incident.status.unwrap_or(IncidentStatus::Investigating);
+
+        if req.reopen && from != IncidentStatus::Resolved {
+            return Err(AppError::BadRequest(
+                "Only resolved incidents can be reopened".into(),
+            ));
+        }
+        let allowed = allowed_transitions(from, req.reopen);
+        if !allowed.contains(&to) {
+            let allowed = match allowed {
+                [] => "none (set reopen to reopen a resolved incident)".to_string(),
+                _ => allowed
+                    .iter()
+                    .map(|s| format!("'{}'", s.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            return Err(AppError::BadRequest(format!(
+                "Cannot change incident status from '{}' to '{}'; allowed next states: {}",
+                from.as_str(),
+                to.as_str(),
+                allowed
+            )));
+        }
+        let note = req
+            .note
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+
+        let table = &self.db.incident_table;
+        let updated = self
+            .db
+            .transaction_with_retry(|tx| {
+                Box::pin(table.transition_status(
+                    tx,
+                    &incident_id,
+                    from,
+                    to,
+                    Some(claims.sub),
+                    note.as_deref(),
+                ))
+            })
+            .await?
+            .ok_or(AppError::Conflict(
+                "Incident status was changed by another request".into(),
+            ))?;
+        Ok(self.map_to_dto(updated))
+    }
+
+    /// Deletes a mistaken incident report, with its timeline, lab results,
+    /// affected institutions, corrective actions and status history.
+    ///
+    /// The incident is recorded in `audit_logs` in the same transaction, so
+    /// it can still be traced after removal.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `NotFound` if the incident
+    /// doesn't exist.
+    pub async fn delete_incident(
+        &self,
+        incident_id: Uuid,
+        claims: &Claims,
+    ) -> Result<(), AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can delete incidents".into(),
+            ));
+        }
+
+        let entry = deletion_entry(&self.db, claims, "incident", incident_id).await?;
+        let table = &self.db.incident_table;
+        self.db
+            .transaction_with_retry(|tx| {
+                Box::pin(table.delete_with_audit(tx, &incident_id, &entry))
+            })
+            .await?
+            .ok_or(AppError::NotFound("Incident not found".into()))?;
+        Ok(())
+    }
+
+    /// Adds an event to an incident's timeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the incident doesn't exist
+    /// and `BadRequest` if the event title is empty.
+    pub async fn add_timeline_event(
+        &self,
+        incident_id: Uuid,
+        role: UserRole,
+        req: CreateTimelineEventRequest,
+    ) -> Result<Uuid, AppError> {
+        self.ensure_can_edit(incident_id, role).await?;
+        let event_title = require_non_empty(&req.event, "event")?;
+
+        let event = IncidentTimelineEvent {
+            incident_id,
+            event_date: req.date,
+            event_title,
+            description: req.description.map(|d| d.trim().to_string()),
+            ..Default::default()
         };
+        Ok(self.db.incident_timeline_event_table.insert(&event).await?)
+    }
+
+    /// Records a laboratory result for an incident.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the incident doesn't exist
+    /// and `BadRequest` if the pathogen or confirming lab is empty.
+    pub async fn add_lab_result(
+        &self,
+        incident_id: Uuid,
+        role: UserRole,
+        req: CreateLabResultRequest,
+    ) -> Result<Uuid, AppError> {
+        self.ensure_can_edit(incident_id, role).await?;
+        let pathogen = require_non_empty(&req.pathogen, "pathogen")?;
+        let confirmed_by = require_non_empty(&req.confirmed_by, "confirmedBy")?;
+
+        let result = IncidentLabResult {
+            incident_id,
+            pathogen,
+            test_date: req.test_date,
+            confirmed_by,
+            ..Default::default()
+        };
+        Ok(self.db.incident_lab_result_table.insert(&result).await?)
+    }
+
+    /// Records an institution affected by an incident.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the incident doesn't exist
+    /// and `BadRequest` if the name is empty or the affected count is negative.
+    pub async fn add_affected_institution(
+        &self,
+        incident_id: Uuid,
+        role: UserRole,
+        req: CreateAffectedInstitutionRequest,
+    ) -> Result<Uuid, AppError> {
+        self.ensure_can_edit(incident_id, role).await?;
+        let institution_name = require_non_empty(&req.institution_name, "institutionName")?;
+        if req.affected_count.is_some_and(|c| c < 0) {
+            return Err(AppError::BadRequest(
+                "affectedCount must not be negative".into(),
+            ));
+        }
+
+        let institution = AffectedInstitution {
+            incident_id,
+            institution_name,
+            institution_type: req.institution_type.map(|t| t.trim().to_string()),
+            affected_count: req.affected_count,
+            ..Default::default()
+        };
+        Ok(self
+            .db
+            .affected_institution_table
+            .insert(&institution)
+            .await?)
+    }
+
+    /// Records a corrective action taken for an incident.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the incident doesn't exist
+    /// and `BadRequest` if the action description is empty.
+    pub async fn add_corrective_action(
+        &self,
+        incident_id: Uuid,
+        role: UserRole,
+        req: CreateCorrectiveActionRequest,
+    ) -> Result<Uuid, AppError> {
+        self.ensure_can_edit(incident_id, role).await?;
+        let action_description = require_non_empty(&req.action, "action")?;
+
+        let action = CorrectiveAction {
+            incident_id,
+            action_description,
+            completed: Some(req.completed.unwrap_or(false)),
+            completed_date: req.completed_date,
+            ..Default::default()
+        };
+        Ok(self.db.corrective_action_table.insert(&action).await?)
+    }
+
+    /// Ensures the caller may edit incident details and that the incident exists.
+    async fn ensure_can_edit(&self, incident_id: Uuid, role: UserRole) -> Result<(), AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can modify incident details".into(),
+            ));
+        }
+        self.db
+            .incident_table
+            .select(&incident_id)
+            .await?
+            .ok_or(AppError::NotFound("Incident not found".into()))?;
+        Ok(())
+    }
+
+    fn map_to_dto(&self, i: Incident) -> IncidentDto {
+        // Rows ingested before validation may hold garbage; omit those points
+        // rather than handing the map an unplottable one
+        let coords = i
+            .map_coordinates
+            .as_ref()
+            .and_then(|v| Coordinates::from_value(v, "mapCoordinates").ok());
 
         IncidentDto {
             id: i.id,
@@ -225,23 +890,101 @@ impl IncidentService {
             address: None, // Will be populated from kitchen in detail view
             province: i.province,
             kabupaten: None, // Will be populated from kitchen in detail view
-            date: i.date.to_string(),
+            date: utc_to_rfc3339(i.date),
             affected_count: i.affected_count.unwrap_or(0),
             deaths: i.deaths.unwrap_or(0),
-            hospitalized: 0, // Not in DB model
+            hospitalized: i.hospitalized.unwrap_or(0),
             cause: i.cause,
             status: i.status,
             severity: i.severity,
             coordinates: coords,
             related_kitchen_id: i.kitchen_id,
             source: i.source,
-            created_at: i.created_at.unwrap_or_default().to_string(),
-            updated_at: i.updated_at.unwrap_or_default().to_string(),
-            resolved_at: if i.status == Some(IncidentStatus::Resolved) {
-                i.updated_at.map(|d| d.to_string())
-            } else {
-                None
-            },
+            created_at: utc_to_rfc3339(i.created_at.unwrap_or_default()),
+            updated_at: utc_to_rfc3339(i.updated_at.unwrap_or_default()),
+            resolved_at: i.resolved_at.map(utc_to_rfc3339),
+        }
+    }
+}
+
+/// Statuses an incident in `from` may move to.
+fn allowed_transitions(from: IncidentStatus, reopen: bool) -> &'static [IncidentStatus] {
+    use IncidentStatus::*;
+
+    match (from, reopen) {
+        (Investigating, _) => &[Escalated, Resolved],
+        (Escalated, _) => &[Investigating, Resolved],
+        (Resolved, false) => &[],
+        (Resolved, true) => &[Investigating],
+    }
+}
+
+fn map_status_change_to_dto(c: IncidentStatusChange) -> StatusChangeDto {
+    StatusChangeDto {
+        from: c.from_status,
+        to: c.to_status,
+        note: c.note,
+        changed_at: utc_to_rfc3339(c.changed_at.unwrap_or_default()),
+    }
+}
+
+/// Trims `value` and rejects it if empty.
+pub(crate) fn require_non_empty(value: &str, field: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(AppError::BadRequest(format!("{} is required", field)));
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_transitions() {
+        use IncidentStatus::*;
+
+        assert_eq!(
+            allowed_transitions(Investigating, false),
+            [Escalated, Resolved]
+        );
+        assert_eq!(
+            allowed_transitions(Escalated, false),
+            [Investigating, Resolved]
+        );
+        assert!(allowed_transitions(Resolved, false).is_empty());
+        assert_eq!(allowed_transitions(Resolved, true), [Investigating]);
+        assert!(!allowed_transitions(Investigating, false).contains(&Investigating));
+    }
+
+    #[test]
+    fn test_coordinates_from_value() {
+        let parsed =
+            Coordinates::from_value(&serde_json::json!({"lat": -6.2, "lng": 106.8}), "gps");
+        assert_eq!(
+            parsed.unwrap(),
+            Coordinates {
+                lat: -6.2,
+                lng: 106.8
+            }
+        );
+        assert!(
+            Coordinates::from_value(&serde_json::json!({"lat": 90, "lng": -180}), "gps").is_ok()
+        );
+
+        for invalid in [
+            serde_json::json!({"lat": 999}),
+            serde_json::json!({"lat": 91, "lng": 0}),
+            serde_json::json!({"lat": 0, "lng": 180.5}),
+            serde_json::json!({"lat": "-6.2", "lng": "106.8"}),
+            serde_json::json!([-6.2, 106.8]),
+            serde_json::json!(null),
+        ] {
+            assert!(matches!(
+                Coordinates::from_value(&invalid, "gps"),
+                Err(AppError::BadRequest(_))
+            ));
         }
     }
 }
@@ -0,0 +1,427 @@
+//! Kitchen inspection service.
+
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use log::warn;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::database::model::FindingCategory;
+use crate::database::model::Inspection;
+use crate::database::model::InspectionFinding;
+use crate::database::model::InspectionFollowUpStatus;
+use crate::database::model::UserRole;
+use crate::database::table::Table;
+use crate::database::table::VersionedTable;
+use crate::error::AppError;
+use crate::service::datetime::deserialize_optional_utc;
+use crate::service::datetime::deserialize_utc;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::filter::FilterEnum;
+use crate::service::filter::parse_enum;
+use crate::service::incident::require_non_empty;
+use crate::service::review::detect_image_type;
+use crate::service::storage::StorageService;
+use crate::service::storage::UploadResult;
+use crate::service::utility::UploadedFile;
+
+/// Most files a single inspection may have attached.
+pub const MAX_ATTACHMENTS_PER_INSPECTION: usize = 20;
+
+/// Request body for a single finding recorded with an inspection.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateFindingRequest {
+    pub category: String,
+    pub description: String,
+    pub evidence: Option<String>,
+    #[serde(rename = "correctionRequired")]
+    pub correction_required: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_optional_utc")]
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+/// Request body for recording an inspection together with its findings.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateInspectionRequest {
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Uuid,
+    #[serde(rename = "inspectorName")]
+    pub inspector_name: Option<String>,
+    #[serde(deserialize_with = "deserialize_utc")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "overallScore")]
+    pub overall_score: Option<f64>,
+    pub recommendations: Option<Vec<String>>,
+    #[serde(rename = "followUpStatus")]
+    pub follow_up_status: Option<String>,
+    pub attachments: Option<Vec<String>>,
+    #[serde(
+        rename = "nextInspectionDate",
+        default,
+        deserialize_with = "deserialize_optional_utc"
+    )]
+    pub next_inspection_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub findings: Vec<CreateFindingRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectionDto {
+    pub id: Uuid,
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Uuid,
+    #[serde(rename = "inspectorName")]
+    pub inspector_name: Option<String>,
+    pub date: String,
+    #[serde(rename = "overallScore")]
+    pub overall_score: Option<f64>,
+    pub recommendations: Option<serde_json::Value>,
+    #[serde(rename = "followUpStatus")]
+    pub follow_up_status: Option<&'static str>,
+    pub attachments: Option<serde_json::Value>,
+    #[serde(rename = "nextInspectionDate")]
+    pub next_inspection_date: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FindingDto {
+    pub id: Uuid,
+    pub category: &'static str,
+    pub description: String,
+    pub evidence: Option<String>,
+    #[serde(rename = "correctionRequired")]
+    pub correction_required: bool,
+    pub deadline: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectionDetailDto {
+    #[serde(flatten)]
+    pub inspection: InspectionDto,
+    pub findings: Vec<FindingDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectionListResponse {
+    pub data: Vec<InspectionDto>,
+}
+
+/// Response for an attachment upload: the inspection's full attachment list.
+#[derive(Debug, Serialize)]
+pub struct InspectionAttachmentsResponse {
+    pub id: Uuid,
+    pub attachments: Vec<String>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// Service for recording and viewing kitchen inspections.
+pub struct InspectionService {
+    db: Arc<Database>,
+    attachment_storage: Option<Arc<StorageService>>,
+}
+
+impl InspectionService {
+    /// Creates a new `InspectionService`.
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            db,
+            attachment_storage: None,
+        }
+    }
+
+    /// Enables attachment uploads through
+    /// [`InspectionService::upload_attachments`], storing them in `storage`.
+    pub fn with_attachment_storage(mut self, storage: Arc<StorageService>) -> Self {
+        self.attachment_storage = Some(storage);
+        self
+    }
+
+    /// Records an inspection and its findings atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the kitchen doesn't exist
+    /// and `BadRequest` if the score is outside 0-100, a finding description is
+    /// empty, or `followUpStatus` or a finding `category` is not a valid value.
+    pub async fn create_inspection(
+        &self,
+        role: UserRole,
+        req: CreateInspectionRequest,
+    ) -> Result<Uuid, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can record inspections".into(),
+            ));
+        }
+
+        let overall_score = match req.overall_score {
+            Some(score) if !(0.0..=100.0).contains(&score) => {
+                return Err(AppError::BadRequest(
+                    "overallScore must be between 0 and 100".into(),
+                ));
+            }
+            Some(score) => Decimal::from_f64(score),
+            None => None,
+        };
+        let follow_up_status = req
+            .follow_up_status
+            .as_deref()
+            .map(|s| parse_enum::<InspectionFollowUpStatus>("followUpStatus", s))
+            .transpose()?;
+
+        let findings = req
+            .findings
+            .iter()
+            .map(|f| {
+                Ok(InspectionFinding {
+                    category: parse_enum::<FindingCategory>("category", &f.category)?,
+                    description: require_non_empty(&f.description, "description")?,
+                    evidence: f.evidence.clone(),
+                    correction_required: Some(f.correction_required.unwrap_or(false)),
+                    deadline: f.deadline,
+                    ..Default::default()
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        self.db
+            .kitchen_table
+            .select(&req.kitchen_id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        let inspection = Inspection {
+            kitchen_id: req.kitchen_id,
+            inspector_name: req.inspector_name.map(|n| n.trim().to_string()),
+            date: req.date,
+            overall_score,
+            recommendations: req.recommendations.map(|r| serde_json::json!(r)),
+            follow_up_status,
+            attachments: req.attachments.map(|a| serde_json::json!(a)),
+            next_inspection_date: req.next_inspection_date,
+            ..Default::default()
+        };
+
+        let table = &self.db.inspection_table;
+        Ok(self
+            .db
+            .transaction_with_retry(|tx| {
+                Box::pin(table.insert_with_findings(tx, &inspection, &findings))
+            })
+            .await?)
+    }
+
+    /// Uploads files for an inspection, such as signed reports or photos of
+    /// findings, and appends their URLs to its attachments.
+    ///
+    /// Only PDFs and JPEG, PNG, GIF or WebP images are accepted; the type is
+    /// detected from the contents, not the client-supplied name or MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `NotFound` if the inspection doesn't
+    /// exist, `BadRequest` if no files are given, a file is neither a PDF nor
+    /// an image, or the inspection would exceed
+    /// [`MAX_ATTACHMENTS_PER_INSPECTION`], and `Conflict` if the inspection
+    /// changed since `expected_updated_at` or during the upload.
+    pub async fn upload_attachments(
+        &self,
+        role: UserRole,
+        inspection_id: Uuid,
+        files: Vec<UploadedFile>,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> Result<InspectionAttachmentsResponse, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can attach files to inspections".into(),
+            ));
+        }
+        let storage = self
+            .attachment_storage
+            .as_ref()
+            .ok_or(AppError::InternalServerError(
+                "Attachment storage is not configured".into(),
+            ))?;
+
+        let inspection = self
+            .db
+            .inspection_table
+            .select(&inspection_id)
+            .await?
+            .ok_or(AppError::NotFound("Inspection not found".into()))?;
+
+        if files.is_empty() {
+            return Err(AppError::BadRequest("No files uploaded".into()));
+        }
+
+        let mut attachments: Vec<String> = inspection
+            .attachments
+            .clone()
+            .and_then(|a| serde_json::from_value(a).ok())
+            .unwrap_or_default();
+        if attachments.len() + files.len() > MAX_ATTACHMENTS_PER_INSPECTION {
+            return Err(AppError::BadRequest(format!(
+                "An inspection can have at most {} attachments; it already has {}",
+                MAX_ATTACHMENTS_PER_INSPECTION,
+                attachments.len()
+            )));
+        }
+
+        let mut typed_files = Vec::with_capacity(files.len());
+        for file in files {
+            let mime_type = detect_attachment_type(&file.data).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "File '{}' is not a PDF or a JPEG, PNG, GIF or WebP image",
+                    file.file_name
+                ))
+            })?;
+            typed_files.push((file.file_name, file.data, mime_type));
+        }
+
+        let mut uploaded = Vec::with_capacity(typed_files.len());
+        for (file_name, data, mime_type) in typed_files {
+            match storage.upload(data, file_name, mime_type.to_string()).await {
+                Ok(result) => uploaded.push(result),
+                Err(e) => {
+                    discard_uploads(storage, &uploaded).await;
+                    return Err(AppError::InternalServerError(e.to_string()));
+                }
+            }
+        }
+        attachments.extend(uploaded.iter().map(|u| u.url.clone()));
+
+        let mut updated = inspection.clone();
+        updated.attachments = Some(json!(attachments));
+        let updated_at = match self
+            .db
+            .inspection_table
+            .update_if_unchanged(&updated, expected_updated_at.or(inspection.updated_at))
+            .await
+        {
+            Ok(updated_at) => updated_at,
+            Err(e) => {
+                discard_uploads(storage, &uploaded).await;
+                return Err(e.into());
+            }
+        };
+
+        Ok(InspectionAttachmentsResponse {
+            id: inspection_id,
+            attachments,
+            updated_at: utc_to_rfc3339(updated_at),
+        })
+    }
+
+    /// Lists a kitchen's inspections, most recent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the kitchen doesn't exist.
+    pub async fn list_kitchen_inspections(
+        &self,
+        kitchen_id: Uuid,
+    ) -> Result<InspectionListResponse, AppError> {
+        self.db
+            .kitchen_table
+            .select(&kitchen_id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        let inspections = self
+            .db
+            .inspection_table
+            .list_by_kitchen(&kitchen_id)
+            .await?;
+
+        Ok(InspectionListResponse {
+            data: inspections.into_iter().map(map_to_dto).collect(),
+        })
+    }
+
+    /// Gets an inspection with its findings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the inspection doesn't exist.
+    pub async fn get_inspection(&self, id: Uuid) -> Result<InspectionDetailDto, AppError> {
+        let inspection = self
+            .db
+            .inspection_table
+            .select(&id)
+            .await?
+            .ok_or(AppError::NotFound("Inspection not found".into()))?;
+
+        let findings = self
+            .db
+            .inspection_finding_table
+            .select_by_inspection(&id)
+            .await?
+            .into_iter()
+            .map(|f| FindingDto {
+                id: f.id,
+                category: f.category.as_str(),
+                description: f.description,
+                evidence: f.evidence,
+                correction_required: f.correction_required.unwrap_or(false),
+                deadline: f.deadline.map(utc_to_rfc3339),
+            })
+            .collect();
+
+        Ok(InspectionDetailDto {
+            inspection: map_to_dto(inspection),
+            findings,
+        })
+    }
+}
+
+/// Detects the MIME type of an inspection attachment from its leading bytes,
+/// returning `None` for anything other than a PDF or an image.
+fn detect_attachment_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        detect_image_type(data)
+    }
+}
+
+/// Deletes files that were uploaded for a request that then failed.
+async fn discard_uploads(storage: &StorageService, uploaded: &[UploadResult]) {
+    // Deduplicated files belong to earlier uploads too
+    for upload in uploaded.iter().filter(|u| !u.deduplicated) {
+        if let Err(e) = storage.delete(&upload.file_id).await {
+            warn!(
+                "Failed to delete orphaned inspection attachment {}: {}",
+                upload.file_id, e
+            );
+        }
+    }
+}
+
+fn map_to_dto(i: Inspection) -> InspectionDto {
+    InspectionDto {
+        id: i.id,
+        kitchen_id: i.kitchen_id,
+        inspector_name: i.inspector_name,
+        date: utc_to_rfc3339(i.date),
+        overall_score: i.overall_score.and_then(|s| s.to_f64()),
+        recommendations: i.recommendations,
+        follow_up_status: i.follow_up_status.map(FilterEnum::as_str),
+        attachments: i.attachments,
+        next_inspection_date: i.next_inspection_date.map(utc_to_rfc3339),
+        created_at: utc_to_rfc3339(i.created_at.unwrap_or_default()),
+        updated_at: utc_to_rfc3339(i.updated_at.unwrap_or_default()),
+    }
+}
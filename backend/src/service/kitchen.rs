@@ -1,22 +1,55 @@
 //! Kitchen management service.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use rust_decimal::RoundingStrategy;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use serde::de;
+use sqlx::types::Json;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::config::HaccpWeights;
+use crate::config::KitchenRatingConfig;
 use crate::database::Database;
+use crate::database::model::ComplianceMetric;
+use crate::database::model::Incident;
+use crate::database::model::Kitchen;
 use crate::database::model::KitchenType;
+use crate::database::model::Review;
+use crate::database::model::UserRole;
+use crate::database::table::GeoRadius;
+use crate::database::table::KitchenFilter;
+use crate::database::table::KitchenSort;
+use crate::database::table::KitchenSortColumn;
 use crate::database::table::Table;
+use crate::database::table::VersionedTable;
 use crate::error::AppError;
+use crate::service::compliance::compliance_score;
+use crate::service::datetime::now_rfc3339;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::export;
+use crate::service::export::ExportFormat;
+use crate::service::export::ExportStream;
+use crate::service::filter::FilterEnum;
+use crate::service::filter::parse_enum;
+use crate::service::filter::parse_filter;
+use crate::service::incident::require_non_empty;
+use crate::service::pagination::PaginatedResponse;
+use crate::service::pagination::Pagination;
+use crate::service::pdf::PdfDocument;
+use crate::service::review::weighted_average;
 
-#[derive(Debug, Serialize)]
-pub struct KitchenListResponse {
-    pub data: Vec<KitchenDto>,
-    pub pagination: Pagination,
-}
-
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct KitchenDto {
     pub id: Uuid,
     pub name: String,
@@ -26,25 +59,23 @@ pub struct KitchenDto {
     pub meals_served: i32,
     pub certifications: Vec<String>,
     pub image: Option<String>,
+    /// Average rating rounded to one decimal, 0 without reviews.
     pub rating: f64,
+    /// Whether `rating` rests on fewer reviews than the configured minimum.
+    #[serde(rename = "ratingProvisional")]
+    pub rating_provisional: bool,
     #[serde(rename = "totalReviews")]
     pub total_reviews: i32,
     #[serde(rename = "createdAt")]
     pub created_at: String,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
+    /// Distance from the requested point; only set when filtering by radius.
+    #[serde(rename = "distanceKm", skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Pagination {
-    pub total: i64,
-    pub limit: i64,
-    pub offset: i64,
-    #[serde(rename = "hasMore")]
-    pub has_more: bool,
-}
-
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct KitchenDetailDto {
     #[serde(flatten)]
     pub kitchen: KitchenDto,
@@ -62,7 +93,158 @@ pub struct KitchenDetailDto {
     pub compliance_trend: Vec<ComplianceTrendDto>,
 }
 
-#[derive(Debug, Serialize)]
+/// Optional kitchen fields shared by the create and update requests.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct KitchenFieldsRequest {
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub province: Option<String>,
+    pub r#type: Option<String>,
+    #[serde(rename = "mealsServed")]
+    pub meals_served: Option<i32>,
+    /// Certification names; anything but an array of strings is rejected.
+    #[serde(default, deserialize_with = "deserialize_certifications")]
+    pub certifications: Option<Vec<String>>,
+    pub image: Option<String>,
+    /// Must be given together with `longitude`.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    #[serde(rename = "contactPhone")]
+    pub contact_phone: Option<String>,
+    #[serde(rename = "contactEmail")]
+    pub contact_email: Option<String>,
+    /// Free-form opening times, e.g. `"Mon-Fri 05:00-14:00"`.
+    #[serde(rename = "operatingHours")]
+    pub operating_hours: Option<String>,
+    pub capacity: Option<i32>,
+}
+
+/// Names the field in the error, which `#[serde(flatten)]` would otherwise
+/// drop from the reported path.
+fn deserialize_certifications<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Vec<String>>::deserialize(deserializer)
+        .map_err(|_| de::Error::custom("certifications must be an array of strings"))
+}
+
+/// Request body for registering a kitchen.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateKitchenRequest {
+    pub name: String,
+    /// User managing the kitchen, who may then update it.
+    #[serde(rename = "ownerId")]
+    pub owner_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub fields: KitchenFieldsRequest,
+}
+
+/// Request body for updating a kitchen; omitted fields are left unchanged.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct UpdateKitchenRequest {
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub fields: KitchenFieldsRequest,
+}
+
+/// Most kitchens accepted by one import.
+pub const MAX_IMPORT_ROWS: usize = 1000;
+
+/// Body format of a kitchen import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KitchenImportFormat {
+    /// A JSON array of [`CreateKitchenRequest`]s.
+    Json,
+    /// CSV with a header row naming the [`CreateKitchenRequest`] fields.
+    Csv,
+}
+
+/// One CSV row of a kitchen import; `certifications` are `;`-separated.
+#[derive(Debug, Deserialize)]
+struct KitchenCsvRow {
+    name: String,
+    #[serde(rename = "ownerId")]
+    owner_id: Option<Uuid>,
+    address: Option<String>,
+    city: Option<String>,
+    province: Option<String>,
+    r#type: Option<String>,
+    #[serde(rename = "mealsServed")]
+    meals_served: Option<i32>,
+    certifications: Option<String>,
+    image: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    #[serde(rename = "contactPhone")]
+    contact_phone: Option<String>,
+    #[serde(rename = "contactEmail")]
+    contact_email: Option<String>,
+    #[serde(rename = "operatingHours")]
+    operating_hours: Option<String>,
+    capacity: Option<i32>,
+}
+
+impl From<KitchenCsvRow> for CreateKitchenRequest {
+    fn from(row: KitchenCsvRow) -> Self {
+        Self {
+            name: row.name,
+            owner_id: row.owner_id,
+            fields: KitchenFieldsRequest {
+                address: row.address,
+                city: row.city,
+                province: row.province,
+                r#type: row.r#type,
+                meals_served: row.meals_served,
+                certifications: row.certifications.map(|c| {
+                    c.split(';')
+                        .map(|c| c.trim().to_string())
+                        .filter(|c| !c.is_empty())
+                        .collect()
+                }),
+                image: row.image,
+                latitude: row.latitude,
+                longitude: row.longitude,
+                contact_phone: row.contact_phone,
+                contact_email: row.contact_email,
+                operating_hours: row.operating_hours,
+                capacity: row.capacity,
+            },
+        }
+    }
+}
+
+/// Outcome of one row of a kitchen import.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KitchenImportResult {
+    /// 1-based position among the data rows.
+    pub row: usize,
+    pub name: Option<String>,
+    /// `created`, `updated` or `failed`.
+    pub status: String,
+    #[serde(rename = "kitchenId")]
+    pub kitchen_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KitchenImportResponse {
+    pub created: usize,
+    pub updated: usize,
+    pub failed: usize,
+    pub results: Vec<KitchenImportResult>,
+}
+
+/// Kitchens found by a batch lookup, plus the requested IDs that weren't.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchKitchensResponse {
+    pub data: Vec<KitchenDto>,
+    /// Requested IDs with no kitchen, in request order.
+    #[serde(rename = "notFound")]
+    pub not_found: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PerformanceBadgeDto {
     pub r#type: String,
     pub title: String,
@@ -72,14 +254,14 @@ pub struct PerformanceBadgeDto {
     pub icon: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ComplianceTrendDto {
     pub month: String,
     pub score: f64,
     pub incidents: i32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct KitchenStatsDto {
     #[serde(rename = "kitchenId")]
     pub kitchen_id: Uuid,
@@ -97,7 +279,7 @@ pub struct KitchenStatsDto {
     pub last_updated: String,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct HaccpScoresDto {
     pub taste: f64,
     pub hygiene: f64,
@@ -107,7 +289,7 @@ pub struct HaccpScoresDto {
     pub handling: f64,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct ReviewDistributionDto {
     #[serde(rename = "5")]
     pub five: i32,
@@ -124,15 +306,53 @@ pub struct ReviewDistributionDto {
 /// Service for managing kitchen data and statistics.
 pub struct KitchenService {
     db: Arc<Database>,
+    rating_weights: HaccpWeights,
+    rating_config: KitchenRatingConfig,
 }
 
 impl KitchenService {
-    /// Creates a new `KitchenService`.
+    /// Creates a new `KitchenService` that weighs all HACCP ratings equally.
     pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+        Self::with_rating_weights(db, HaccpWeights::default())
+    }
+
+    /// Creates a new `KitchenService` averaging HACCP ratings with `rating_weights`.
+    pub fn with_rating_weights(db: Arc<Database>, rating_weights: HaccpWeights) -> Self {
+        Self {
+            db,
+            rating_weights,
+            rating_config: KitchenRatingConfig::default(),
+        }
+    }
+
+    /// Sets how many reviews a kitchen needs before its rating is no longer
+    /// provisional.
+    pub fn with_rating_config(mut self, rating_config: KitchenRatingConfig) -> Self {
+        self.rating_config = rating_config;
+        self
+    }
+
+    /// The `rating` and `ratingProvisional` of a kitchen whose
+    /// `total_reviews` reviews average `average`.
+    fn display_rating(&self, average: Option<Decimal>, total_reviews: i64) -> (f64, bool) {
+        display_rating(average, total_reviews, self.rating_config.min_reviews)
     }
 
     /// Lists kitchens with optional filtering and pagination.
+    ///
+    /// `near` is `(lat, lng, radius_km)`; when given, only kitchens within the
+    /// radius are listed, nearest first, with their distance.
+    ///
+    /// `sort_by` (`rating`, `name`, `mealsServed`, `totalReviews`,
+    /// `createdAt`) and `order` (`asc`, `desc`) override that ordering. A
+    /// missing `sort_by` sorts by rating; a missing `order` is ascending for
+    /// names and descending otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if `type_` is not a valid kitchen type, `near` is
+    /// out of range, or `sort_by` or `order` is not one of the values above.
+    #[allow(clippy::too_many_arguments)]
     pub async fn list_kitchens(
         &self,
         limit: i64,
@@ -141,55 +361,61 @@ impl KitchenService {
         loc: Option<String>,
         type_: Option<String>,
         min_rating: Option<f64>,
-    ) -> Result<KitchenListResponse, AppError> {
+        near: Option<(f64, f64, f64)>,
+        sort_by: Option<String>,
+        order: Option<String>,
+    ) -> Result<PaginatedResponse<KitchenDto>, AppError> {
+        let type_ = parse_filter::<KitchenType>("type", type_)?;
+        let near = near.map(parse_geo_radius).transpose()?;
+        let sort = parse_kitchen_sort(sort_by, order)?;
         let (kitchens, total) = self
             .db
             .kitchen_table
             .list_kitchens_with_stats(
-                q.as_deref(),
-                loc.as_deref(),
-                type_.as_deref(),
-                min_rating,
+                &KitchenFilter {
+                    query: q.as_deref(),
+                    location: loc.as_deref(),
+                    kitchen_type: type_.as_deref(),
+                    min_rating,
+                    near,
+                },
+                sort,
                 limit,
                 offset,
+                &self.rating_weights,
             )
             .await?;
 
         let dtos = kitchens
             .into_iter()
-            .map(|k| KitchenDto {
-                id: k.id,
-                name: k.name,
-                location: format!(
-                    "{}, {}",
-                    k.city.unwrap_or_default(),
-                    k.province.unwrap_or_default()
-                ),
-                r#type: k.r#type,
-                meals_served: k.meals_served.unwrap_or(0),
-                certifications: k
-                    .certifications
-                    .and_then(|c| serde_json::from_value(c).ok())
-                    .unwrap_or_default(),
-                image: k.image_url,
-                rating: k
-                    .average_rating
-                    .and_then(|d| d.try_into().ok())
-                    .unwrap_or(0.0),
-                total_reviews: k.total_reviews as i32,
-                created_at: k.created_at.unwrap_or_default().to_string(),
-                updated_at: k.updated_at.unwrap_or_default().to_string(),
+            .map(|k| {
+                let (rating, rating_provisional) =
+                    self.display_rating(k.average_rating, k.total_reviews);
+                KitchenDto {
+                    id: k.id,
+                    name: k.name,
+                    location: format!(
+                        "{}, {}",
+                        k.city.unwrap_or_default(),
+                        k.province.unwrap_or_default()
+                    ),
+                    r#type: k.r#type,
+                    meals_served: k.meals_served.unwrap_or(0),
+                    certifications: k.certifications.map(|c| c.0).unwrap_or_default(),
+                    image: k.image_url,
+                    rating,
+                    rating_provisional,
+                    total_reviews: k.total_reviews as i32,
+                    created_at: utc_to_rfc3339(k.created_at.unwrap_or_default()),
+                    updated_at: utc_to_rfc3339(k.updated_at.unwrap_or_default()),
+                    distance_km: k.distance_km.map(|d| (d * 100.0).round() / 100.0),
+                }
             })
             .collect();
 
-        Ok(KitchenListResponse {
+        Ok(PaginatedResponse {
             data: dtos,
-            pagination: Pagination {
-                total,
-                limit,
-                offset,
-                has_more: offset + limit < total,
-            },
+            pagination: Pagination::new(total, limit, offset),
         })
     }
 
@@ -218,33 +444,29 @@ impl KitchenService {
             })
             .collect();
 
-        // Get compliance trend
-        let trend = self
+        let trend: Vec<ComplianceTrendDto> = self
             .db
             .kitchen_table
-            .get_compliance_trend(&id, 6)
+            .get_compliance_trend(&id, 6, &self.rating_weights)
             .await?
             .into_iter()
             .map(|t| ComplianceTrendDto {
                 month: t.month,
-                score: t
-                    .average_score
-                    .and_then(|d| d.try_into().ok())
-                    .unwrap_or(0.0),
+                score: t.average_score.and_then(|d| d.to_f64()).unwrap_or(0.0),
                 incidents: t.incidents as i32,
             })
             .collect();
 
         // Get stats for rating
-        let stats = self.db.kitchen_table.get_kitchen_stats(&id).await?;
-
-        let rating = stats
-            .as_ref()
-            .and_then(|s| s.average_rating)
-            .and_then(|d| d.try_into().ok())
-            .unwrap_or(0.0);
+        let stats = self
+            .db
+            .kitchen_table
+            .get_kitchen_stats(&id, &self.rating_weights)
+            .await?;
 
-        let total_reviews = stats.map(|s| s.total_reviews as i32).unwrap_or(0);
+        let total_reviews = stats.as_ref().map(|s| s.total_reviews).unwrap_or(0);
+        let (rating, rating_provisional) =
+            self.display_rating(stats.and_then(|s| s.average_rating), total_reviews);
 
         Ok(KitchenDetailDto {
             kitchen: KitchenDto {
@@ -260,19 +482,21 @@ impl KitchenService {
                 certifications: kitchen
                     .certifications
                     .clone()
-                    .and_then(|c| serde_json::from_value(c).ok())
+                    .map(|c| c.0)
                     .unwrap_or_default(),
                 image: kitchen.image_url.clone(),
                 rating,
-                total_reviews,
-                created_at: kitchen.created_at.unwrap_or_default().to_string(),
-                updated_at: kitchen.updated_at.unwrap_or_default().to_string(),
+                rating_provisional,
+                total_reviews: total_reviews as i32,
+                created_at: utc_to_rfc3339(kitchen.created_at.unwrap_or_default()),
+                updated_at: utc_to_rfc3339(kitchen.updated_at.unwrap_or_default()),
+                distance_km: None,
             },
             address: kitchen.address,
-            contact_phone: None,   // Not in DB model
-            contact_email: None,   // Not in DB model
-            operating_hours: None, // Not in DB model
-            capacity: None,        // Not in DB model
+            contact_phone: kitchen.contact_phone,
+            contact_email: kitchen.contact_email,
+            operating_hours: kitchen.operating_hours,
+            capacity: kitchen.capacity,
             performance_badges: badges,
             compliance_trend: trend,
         })
@@ -288,10 +512,18 @@ impl KitchenService {
             .ok_or(AppError::NotFound("Kitchen not found".into()))?;
 
         // Get stats from reviews
-        let stats = self.db.kitchen_table.get_kitchen_stats(&id).await?;
+        let stats = self
+            .db
+            .kitchen_table
+            .get_kitchen_stats(&id, &self.rating_weights)
+            .await?;
 
         // Get review distribution
-        let distribution = self.db.kitchen_table.get_review_distribution(&id).await?;
+        let distribution = self
+            .db
+            .kitchen_table
+            .get_review_distribution(&id, &self.rating_weights)
+            .await?;
 
         let mut review_dist = ReviewDistributionDto::default();
         for d in distribution {
@@ -343,24 +575,36 @@ impl KitchenService {
                 .unwrap_or(0.0),
             haccp_scores,
             review_distribution: review_dist,
-            last_updated: chrono::Utc::now().to_rfc3339(),
+            last_updated: now_rfc3339(),
         })
     }
 
-    pub async fn get_multiple_kitchens(&self, ids: Vec<Uuid>) -> Result<Vec<KitchenDto>, AppError> {
-        let mut kitchens = Vec::new();
-        for id in ids {
-            if let Ok(Some(k)) = self.db.kitchen_table.select(&id).await {
-                // Get stats for this kitchen
-                let stats = self
-                    .db
-                    .kitchen_table
-                    .get_kitchen_stats(&id)
-                    .await
-                    .ok()
-                    .flatten();
-
-                kitchens.push(KitchenDto {
+    /// Gets several kitchens by ID with their review stats.
+    ///
+    /// Runs two queries regardless of how many IDs are requested. Kitchens are
+    /// returned in the order of `ids`; unknown IDs are listed in `not_found`.
+    pub async fn get_multiple_kitchens(
+        &self,
+        ids: Vec<Uuid>,
+    ) -> Result<BatchKitchensResponse, AppError> {
+        let kitchens = self.db.kitchen_table.select_by_ids(&ids).await?;
+        let mut stats = self
+            .db
+            .kitchen_table
+            .get_kitchen_stats_for(&ids, &self.rating_weights)
+            .await?;
+
+        let found: HashSet<Uuid> = kitchens.iter().map(|k| k.id).collect();
+        let not_found = ids.into_iter().filter(|id| !found.contains(id)).collect();
+
+        let data = kitchens
+            .into_iter()
+            .map(|k| {
+                let stats = stats.remove(&k.id);
+                let total_reviews = stats.as_ref().map(|s| s.total_reviews).unwrap_or(0);
+                let (rating, rating_provisional) =
+                    self.display_rating(stats.and_then(|s| s.average_rating), total_reviews);
+                KitchenDto {
                     id: k.id,
                     name: k.name,
                     location: format!(
@@ -370,22 +614,820 @@ impl KitchenService {
                     ),
                     r#type: k.r#type,
                     meals_served: k.meals_served.unwrap_or(0),
-                    certifications: k
-                        .certifications
-                        .and_then(|c| serde_json::from_value(c).ok())
-                        .unwrap_or_default(),
+                    certifications: k.certifications.map(|c| c.0).unwrap_or_default(),
                     image: k.image_url,
-                    rating: stats
-                        .as_ref()
-                        .and_then(|s| s.average_rating)
-                        .and_then(|d| d.try_into().ok())
-                        .unwrap_or(0.0),
-                    total_reviews: stats.map(|s| s.total_reviews as i32).unwrap_or(0),
-                    created_at: k.created_at.unwrap_or_default().to_string(),
-                    updated_at: k.updated_at.unwrap_or_default().to_string(),
-                });
+                    rating,
+                    rating_provisional,
+                    total_reviews: total_reviews as i32,
+                    created_at: utc_to_rfc3339(k.created_at.unwrap_or_default()),
+                    updated_at: utc_to_rfc3339(k.updated_at.unwrap_or_default()),
+                    distance_km: None,
+                }
+            })
+            .collect();
+
+        Ok(BatchKitchensResponse { data, not_found })
+    }
+
+    /// Registers a kitchen.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `BadRequest` if the name is empty,
+    /// `ownerId` is not a user, or any field is invalid.
+    pub async fn create_kitchen(
+        &self,
+        role: UserRole,
+        req: CreateKitchenRequest,
+    ) -> Result<Uuid, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can register kitchens".into(),
+            ));
+        }
+
+        if let Some(owner_id) = req.owner_id
+            && self.db.user_table.select(&owner_id).await?.is_none()
+        {
+            return Err(AppError::BadRequest(
+                "ownerId must be an existing user".into(),
+            ));
+        }
+
+        let mut kitchen = Kitchen {
+            name: require_non_empty(&req.name, "name")?,
+            owner_id: req.owner_id,
+            ..Default::default()
+        };
+        apply_fields(&mut kitchen, req.fields)?;
+
+        Ok(self.db.kitchen_table.insert(&kitchen).await?)
+    }
+
+    /// Updates a kitchen's profile and returns its new details.
+    ///
+    /// `expected_updated_at` is the `updatedAt` of the copy the caller edited,
+    /// if it sent one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the kitchen doesn't exist, `Forbidden` unless the
+    /// user is an admin or the kitchen's owner, `BadRequest` if any field is
+    /// invalid, and `Conflict` if the kitchen changed since
+    /// `expected_updated_at` or concurrently.
+    pub async fn update_kitchen(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        role: UserRole,
+        req: UpdateKitchenRequest,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> Result<KitchenDetailDto, AppError> {
+        let kitchen = self
+            .db
+            .kitchen_table
+            .select(&id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        if role != UserRole::Admin && kitchen.owner_id != Some(user_id) {
+            return Err(AppError::Forbidden(
+                "Only administrators or the kitchen's owner can update it".into(),
+            ));
+        }
+
+        let mut updated = kitchen.clone();
+        if let Some(name) = req.name {
+            updated.name = require_non_empty(&name, "name")?;
+        }
+        apply_fields(&mut updated, req.fields)?;
+
+        self.db
+            .kitchen_table
+            .update_if_unchanged(&updated, expected_updated_at.or(kitchen.updated_at))
+            .await?;
+
+        self.get_kitchen_detail(id).await
+    }
+
+    /// Imports kitchens from a JSON array or CSV file.
+    ///
+    /// Each row is validated like [`create_kitchen`](Self::create_kitchen).
+    /// Rows naming the same kitchen as an existing one, by name and city
+    /// compared case-insensitively, update it instead; fields a row leaves
+    /// empty keep their current values. Valid rows are saved in one
+    /// transaction; invalid ones, and repeats of an earlier row, are
+    /// reported as failed without affecting the rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, and `BadRequest` if the body isn't
+    /// a JSON array or CSV with a header row, or holds no rows or more than
+    /// [`MAX_IMPORT_ROWS`].
+    pub async fn import_kitchens(
+        &self,
+        role: UserRole,
+        format: KitchenImportFormat,
+        body: &[u8],
+    ) -> Result<KitchenImportResponse, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can import kitchens".into(),
+            ));
+        }
+
+        let rows = match format {
+            KitchenImportFormat::Json => parse_json_import(body)?,
+            KitchenImportFormat::Csv => parse_csv_import(body)?,
+        };
+        if rows.is_empty() {
+            return Err(AppError::BadRequest("No kitchens to import".into()));
+        }
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::BadRequest(format!(
+                "At most {} kitchens can be imported at once",
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        let mut owner_ids: Vec<Uuid> = rows
+            .iter()
+            .filter_map(|row| row.as_ref().ok()?.owner_id)
+            .collect();
+        owner_ids.sort_unstable();
+        owner_ids.dedup();
+        let known_owners: HashSet<Uuid> = self
+            .db
+            .user_table
+            .select_by_ids(&owner_ids)
+            .await?
+            .into_iter()
+            .map(|u| u.id)
+            .collect();
+
+        let mut results = Vec::with_capacity(rows.len());
+        let mut kitchens = Vec::new();
+        let mut first_row_of: HashMap<(String, String), usize> = HashMap::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let row_number = index + 1;
+            let prepared = row.and_then(|req| {
+                if let Some(owner_id) = req.owner_id
+                    && !known_owners.contains(&owner_id)
+                {
+                    return Err(AppError::BadRequest(
+                        "ownerId must be an existing user".into(),
+                    ));
+                }
+                let mut kitchen = Kitchen {
+                    name: require_non_empty(&req.name, "name")?,
+                    owner_id: req.owner_id,
+                    ..Default::default()
+                };
+                apply_fields(&mut kitchen, req.fields)?;
+                Ok(kitchen)
+            });
+            let kitchen = prepared.and_then(|kitchen| {
+                let key = (
+                    kitchen.name.to_lowercase(),
+                    kitchen.city.as_deref().unwrap_or_default().to_lowercase(),
+                );
+                match first_row_of.get(&key) {
+                    Some(first) => Err(AppError::BadRequest(format!("Duplicate of row {}", first))),
+                    None => {
+                        first_row_of.insert(key, row_number);
+                        Ok(kitchen)
+                    }
+                }
+            });
+            match kitchen {
+                Ok(kitchen) => {
+                    results.push(KitchenImportResult {
+                        row: row_number,
+                        name: Some(kitchen.name.clone()),
+                        status: String::new(),
+                        kitchen_id: None,
+                        error: None,
+                    });
+                    kitchens.push(kitchen);
+                }
+                Err(e) => results.push(KitchenImportResult {
+                    row: row_number,
+                    name: None,
+                    status: "failed".to_string(),
+                    kitchen_id: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        let table = &self.db.kitchen_table;
+        let saved = if kitchens.is_empty() {
+            Vec::new()
+        } else {
+            self.db
+                .transaction_with_retry(|tx| Box::pin(table.upsert_by_name_and_city(tx, &kitchens)))
+                .await?
+        };
+
+        let mut saved = saved.into_iter();
+        let (mut created, mut updated, mut failed) = (0, 0, 0);
+        for result in &mut results {
+            if result.error.is_some() {
+                failed += 1;
+                continue;
             }
+            if let Some((id, was_created)) = saved.next() {
+                result.kitchen_id = Some(id);
+                result.status = if was_created {
+                    created += 1;
+                    "created".to_string()
+                } else {
+                    updated += 1;
+                    "updated".to_string()
+                };
+            }
+        }
+
+        Ok(KitchenImportResponse {
+            created,
+            updated,
+            failed,
+            results,
+        })
+    }
+
+    /// Renders a printable PDF report of a kitchen: its details, compliance,
+    /// review statistics and its most recent reviews and incidents.
+    ///
+    /// Rendering runs on the blocking thread pool so large reports don't stall
+    /// other requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the kitchen does not exist.
+    pub async fn kitchen_report_pdf(&self, id: Uuid) -> Result<KitchenReportPdf, AppError> {
+        let detail = self.get_kitchen_detail(id).await?;
+        let stats = self.get_kitchen_stats(id).await?;
+        let latest_metric = self
+            .db
+            .compliance_metric_table
+            .select_recent(&id, 12)
+            .await?
+            .into_iter()
+            .next();
+        let (reviews, _) = self
+            .db
+            .review_table
+            .list_after_cursor(Some(&id), false, None, REPORT_RECENT_ITEMS)
+            .await?;
+        let incidents = self
+            .db
+            .incident_table
+            .list_by_kitchen(&id, REPORT_RECENT_ITEMS)
+            .await?;
+
+        let report = KitchenReport {
+            detail,
+            stats,
+            latest_metric,
+            reviews,
+            incidents,
+            rating_weights: self.rating_weights.clone(),
+        };
+        let content = tokio::task::spawn_blocking(move || render_kitchen_report(&report))
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        Ok(KitchenReportPdf {
+            file_name: format!("kitchen-{}-report.pdf", id),
+            content,
+        })
+    }
+
+    /// Exports every non-draft review of a kitchen as CSV or JSON.
+    ///
+    /// Reviews are read and encoded in batches of [`EXPORT_BATCH_SIZE`] while
+    /// the response is sent, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the kitchen doesn't exist, and `Forbidden` unless
+    /// the caller is an admin or the kitchen's owner.
+    pub async fn export_reviews(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        role: UserRole,
+        format: ExportFormat,
+    ) -> Result<KitchenReviewExport, AppError> {
+        let kitchen = self
+            .db
+            .kitchen_table
+            .select(&id)
+            .await?
+            .ok_or(AppError::NotFound("Kitchen not found".into()))?;
+
+        if role != UserRole::Admin && kitchen.owner_id != Some(user_id) {
+            return Err(AppError::Forbidden(
+                "Only administrators or the kitchen's owner can export its reviews".into(),
+            ));
+        }
+
+        let weights = self.rating_weights.clone();
+        let batches = self
+            .db
+            .review_table
+            .stream_by_kitchen(id, EXPORT_BATCH_SIZE)
+            .map(move |batch| {
+                Ok(batch?
+                    .into_iter()
+                    .map(|r| ReviewExportRow::new(r, &weights))
+                    .collect::<Vec<_>>())
+            });
+
+        Ok(KitchenReviewExport {
+            file_name: format!("kitchen-{}-reviews.{}", id, format.extension()),
+            format,
+            content: export::encode(format, batches),
+        })
+    }
+}
+
+/// Number of reviews read from the database at a time during an export.
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+/// A kitchen's reviews, encoded as they are streamed.
+pub struct KitchenReviewExport {
+    /// Suggested download name.
+    pub file_name: String,
+    pub format: ExportFormat,
+    pub content: ExportStream,
+}
+
+/// One review in a review export.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewExportRow {
+    id: Uuid,
+    reviewer_name: String,
+    reviewer_type: UserRole,
+    taste_rating: f64,
+    hygiene_rating: f64,
+    freshness_rating: f64,
+    temperature_rating: f64,
+    packaging_rating: f64,
+    handling_rating: f64,
+    average_rating: f64,
+    comment: String,
+    verified: bool,
+    verification_status: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl ReviewExportRow {
+    fn new(r: Review, weights: &HaccpWeights) -> Self {
+        let ratings = [
+            r.taste_rating,
+            r.hygiene_rating,
+            r.freshness_rating,
+            r.temperature_rating,
+            r.packaging_rating,
+            r.handling_rating,
+        ];
+        let average = weighted_average(ratings, weights).round_dp(2);
+        Self {
+            id: r.id,
+            reviewer_name: r.reviewer_name,
+            reviewer_type: r.reviewer_type,
+            taste_rating: r.taste_rating.to_f64().unwrap_or_default(),
+            hygiene_rating: r.hygiene_rating.to_f64().unwrap_or_default(),
+            freshness_rating: r.freshness_rating.to_f64().unwrap_or_default(),
+            temperature_rating: r.temperature_rating.to_f64().unwrap_or_default(),
+            packaging_rating: r.packaging_rating.to_f64().unwrap_or_default(),
+            handling_rating: r.handling_rating.to_f64().unwrap_or_default(),
+            average_rating: average.to_f64().unwrap_or_default(),
+            comment: r.comment,
+            verified: r.verified.unwrap_or(false),
+            verification_status: r.verification_status.unwrap_or("unverified".to_string()),
+            created_at: utc_to_rfc3339(r.created_at.unwrap_or_default()),
+            updated_at: utc_to_rfc3339(r.updated_at.unwrap_or_default()),
         }
-        Ok(kitchens)
+    }
+}
+
+/// Number of recent reviews and incidents listed in a kitchen report.
+const REPORT_RECENT_ITEMS: i64 = 10;
+
+/// A rendered kitchen report.
+#[derive(Debug)]
+pub struct KitchenReportPdf {
+    /// Suggested download name.
+    pub file_name: String,
+    /// The PDF document.
+    pub content: Vec<u8>,
+}
+
+/// Data shown in a kitchen report.
+struct KitchenReport {
+    detail: KitchenDetailDto,
+    stats: KitchenStatsDto,
+    latest_metric: Option<ComplianceMetric>,
+    reviews: Vec<Review>,
+    incidents: Vec<Incident>,
+    rating_weights: HaccpWeights,
+}
+
+fn render_kitchen_report(report: &KitchenReport) -> Vec<u8> {
+    let kitchen = &report.detail.kitchen;
+    let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    let score = |value: Option<Decimal>| {
+        value
+            .map(|d| format!("{:.1}", d.to_f64().unwrap_or_default()))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let mut doc = PdfDocument::new();
+    doc.heading(&format!("Kitchen Report: {}", kitchen.name));
+    doc.text(&format!(
+        "Generated {}",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+    doc.blank();
+
+    doc.heading("Details");
+    doc.text(&format!("Location: {}", kitchen.location));
+    doc.text(&format!("Address: {}", or_dash(&report.detail.address)));
+    doc.text(&format!(
+        "Type: {}",
+        kitchen.r#type.map(FilterEnum::as_str).unwrap_or("-")
+    ));
+    doc.text(&format!("Meals served: {}", kitchen.meals_served));
+    doc.text(&format!(
+        "Capacity: {}",
+        report
+            .detail
+            .capacity
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    ));
+    doc.text(&format!(
+        "Operating hours: {}",
+        or_dash(&report.detail.operating_hours)
+    ));
+    doc.text(&format!("Phone: {}", or_dash(&report.detail.contact_phone)));
+    doc.text(&format!("Email: {}", or_dash(&report.detail.contact_email)));
+    if !kitchen.certifications.is_empty() {
+        doc.text(&format!(
+            "Certifications: {}",
+            kitchen.certifications.join(", ")
+        ));
+    }
+    doc.blank();
+
+    doc.heading("Compliance");
+    match &report.latest_metric {
+        Some(metric) => {
+            doc.text(&format!(
+                "Latest metrics ({}): overall {}, hygiene {}, portion {}, nutrition {}, temperature {}",
+                metric.created_at.unwrap_or_default().format("%Y-%m"),
+                score(compliance_score(metric)),
+                score(metric.hygiene_score),
+                score(metric.portion_compliance),
+                score(metric.nutrition_compliance),
+                score(metric.temperature_control),
+            ));
+            doc.text(&format!(
+                "Last inspection: {}, trend: {}",
+                metric
+                    .last_inspection_date
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                metric.trend.map(FilterEnum::as_str).unwrap_or("-")
+            ));
+        }
+        None => doc.text("No compliance metrics recorded."),
+    }
+    for month in &report.detail.compliance_trend {
+        doc.text(&format!(
+            "{}: score {:.1}, {} incident(s)",
+            month.month, month.score, month.incidents
+        ));
+    }
+    doc.blank();
+
+    let stats = &report.stats;
+    doc.heading("Reviews");
+    doc.text(&format!(
+        "Average rating {:.1} from {} review(s), {} verified",
+        stats.average_rating, stats.total_reviews, stats.verified_reviews
+    ));
+    let haccp = &stats.haccp_scores;
+    doc.text(&format!(
+        "HACCP averages: taste {:.1}, hygiene {:.1}, freshness {:.1}, temperature {:.1}, packaging {:.1}, handling {:.1}",
+        haccp.taste,
+        haccp.hygiene,
+        haccp.freshness,
+        haccp.temperature,
+        haccp.packaging,
+        haccp.handling
+    ));
+    for review in &report.reviews {
+        let average = weighted_average(
+            [
+                review.taste_rating,
+                review.hygiene_rating,
+                review.freshness_rating,
+                review.temperature_rating,
+                review.packaging_rating,
+                review.handling_rating,
+            ],
+            &report.rating_weights,
+        );
+        doc.text(&format!(
+            "{}  {}/5  {}{}",
+            review.created_at.unwrap_or_default().format("%Y-%m-%d"),
+            score(Some(average)),
+            review.reviewer_name,
+            if review.verified.unwrap_or(false) {
+                " (verified)"
+            } else {
+                ""
+            }
+        ));
+        doc.text(&format!("    {}", review.comment));
+    }
+    doc.blank();
+
+    doc.heading("Incidents");
+    if report.incidents.is_empty() {
+        doc.text("No incidents recorded.");
+    }
+    for incident in &report.incidents {
+        doc.text(&format!(
+            "{}  {}, {}, {}: {} affected, {} death(s)",
+            incident.date.format("%Y-%m-%d"),
+            incident.r#type.as_str(),
+            incident.severity.as_str(),
+            incident.status.map(FilterEnum::as_str).unwrap_or("-"),
+            incident.affected_count.unwrap_or(0),
+            incident.deaths.unwrap_or(0)
+        ));
+        if let Some(description) = &incident.description {
+            doc.text(&format!("    {}", description));
+        }
+    }
+
+    doc.finish()
+}
+
+/// Longest accepted `operatingHours` value, matching the column size.
+const MAX_OPERATING_HOURS_LEN: usize = 100;
+
+/// Splits a JSON import into rows, each parsed on its own so one malformed
+/// row doesn't reject the rest.
+fn parse_json_import(body: &[u8]) -> Result<Vec<Result<CreateKitchenRequest, AppError>>, AppError> {
+    let rows: Vec<serde_json::Value> = serde_json::from_slice(body).map_err(|_| {
+        AppError::BadRequest("Request body must be a JSON array of kitchens".into())
+    })?;
+    Ok(rows
+        .into_iter()
+        .map(|row| serde_json::from_value(row).map_err(|e| AppError::BadRequest(e.to_string())))
+        .collect())
+}
+
+/// Splits a CSV import into rows. The header row names the columns, using
+/// the same names as the JSON fields; unknown columns are ignored and empty
+/// cells are treated as missing.
+fn parse_csv_import(body: &[u8]) -> Result<Vec<Result<CreateKitchenRequest, AppError>>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(body);
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::BadRequest(format!("Invalid CSV header: {}", e)))?;
+    if !headers.iter().any(|h| h == "name") {
+        return Err(AppError::BadRequest(
+            "CSV header must include a name column".into(),
+        ));
+    }
+    Ok(reader
+        .deserialize::<KitchenCsvRow>()
+        .map(|row| {
+            row.map(CreateKitchenRequest::from)
+                .map_err(|e| AppError::BadRequest(csv_error_message(&e)))
+        })
+        .collect())
+}
+
+/// Describes a CSV row error without the position, which the import report
+/// already gives.
+fn csv_error_message(error: &csv::Error) -> String {
+    match error.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => match err.field() {
+            Some(field) => format!("column {}: {}", field + 1, err.kind()),
+            None => err.kind().to_string(),
+        },
+        _ => error.to_string(),
+    }
+}
+
+/// Validates the given fields and copies them onto `kitchen`.
+fn apply_fields(kitchen: &mut Kitchen, fields: KitchenFieldsRequest) -> Result<(), AppError> {
+    if let Some(address) = fields.address {
+        kitchen.address = Some(address.trim().to_string());
+    }
+    if let Some(city) = fields.city {
+        kitchen.city = Some(city.trim().to_string());
+    }
+    if let Some(province) = fields.province {
+        kitchen.province = Some(province.trim().to_string());
+    }
+    if let Some(r#type) = fields.r#type {
+        kitchen.r#type = Some(parse_enum("type", &r#type)?);
+    }
+    if let Some(meals_served) = fields.meals_served {
+        if meals_served < 0 {
+            return Err(AppError::BadRequest(
+                "mealsServed must not be negative".into(),
+            ));
+        }
+        kitchen.meals_served = Some(meals_served);
+    }
+    if let Some(certifications) = fields.certifications {
+        let certifications = certifications
+            .iter()
+            .map(|name| {
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(AppError::BadRequest(
+                        "certifications must not contain blank names".into(),
+                    ));
+                }
+                Ok(name.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        kitchen.certifications = Some(Json(certifications));
+    }
+    if let Some(image) = fields.image {
+        kitchen.image_url = Some(image);
+    }
+    match (fields.latitude, fields.longitude) {
+        (Some(lat), Some(lng)) => {
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+                return Err(AppError::BadRequest(
+                    "latitude must be between -90 and 90 and longitude between -180 and 180".into(),
+                ));
+            }
+            kitchen.latitude = Some(lat);
+            kitchen.longitude = Some(lng);
+        }
+        (None, None) => {}
+        _ => {
+            return Err(AppError::BadRequest(
+                "latitude and longitude must be given together".into(),
+            ));
+        }
+    }
+    if let Some(phone) = fields.contact_phone {
+        let phone = phone.trim().to_string();
+        if !is_valid_contact_phone(&phone) {
+            return Err(AppError::BadRequest(
+                "contactPhone must be a phone number of up to 20 characters".into(),
+            ));
+        }
+        kitchen.contact_phone = Some(phone);
+    }
+    if let Some(email) = fields.contact_email {
+        let email = email.trim().to_string();
+        if !is_valid_contact_email(&email) {
+            return Err(AppError::BadRequest(
+                "contactEmail must be a valid email address".into(),
+            ));
+        }
+        kitchen.contact_email = Some(email);
+    }
+    if let Some(hours) = fields.operating_hours {
+        let hours = hours.trim().to_string();
+        if hours.chars().count() > MAX_OPERATING_HOURS_LEN {
+            return Err(AppError::BadRequest(format!(
+                "operatingHours must be at most {} characters",
+                MAX_OPERATING_HOURS_LEN
+            )));
+        }
+        kitchen.operating_hours = Some(hours);
+    }
+    if let Some(capacity) = fields.capacity {
+        if capacity < 0 {
+            return Err(AppError::BadRequest("capacity must not be negative".into()));
+        }
+        kitchen.capacity = Some(capacity);
+    }
+    Ok(())
+}
+
+/// Whether `phone` looks like a phone number: digits with optional `+`,
+/// spaces, dashes and parentheses, e.g. `"(021) 555-0123"`.
+fn is_valid_contact_phone(phone: &str) -> bool {
+    let re = regex::Regex::new(r"^\+?[0-9 ()-]{6,19}$").unwrap();
+    re.is_match(phone) && phone.chars().filter(char::is_ascii_digit).count() >= 6
+}
+
+fn is_valid_contact_email(email: &str) -> bool {
+    let re = regex::Regex::new(r"^[^@\s<>]+@[^@\s<>]+\.[^@\s<>]+$").unwrap();
+    email.len() <= 255 && re.is_match(email)
+}
+
+/// Largest radius accepted for "kitchens near me" searches.
+const MAX_RADIUS_KM: f64 = 1000.0;
+
+/// Validates a `(lat, lng, radius_km)` search circle.
+fn parse_geo_radius((lat, lng, radius_km): (f64, f64, f64)) -> Result<GeoRadius, AppError> {
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(AppError::BadRequest(
+            "lat must be between -90 and 90".into(),
+        ));
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(AppError::BadRequest(
+            "lng must be between -180 and 180".into(),
+        ));
+    }
+    if !(radius_km > 0.0 && radius_km <= MAX_RADIUS_KM) {
+        return Err(AppError::BadRequest(format!(
+            "radius must be greater than 0 and at most {} km",
+            MAX_RADIUS_KM
+        )));
+    }
+    Ok(GeoRadius {
+        lat,
+        lng,
+        radius_km,
+    })
+}
+
+/// Validates the kitchen list `sortBy`/`order` params; `None` when neither is
+/// given, keeping the default ordering.
+fn parse_kitchen_sort(
+    sort_by: Option<String>,
+    order: Option<String>,
+) -> Result<Option<KitchenSort>, AppError> {
+    if sort_by.is_none() && order.is_none() {
+        return Ok(None);
+    }
+    let column = match sort_by {
+        Some(sort_by) => parse_enum::<KitchenSortColumn>("sortBy", &sort_by)?,
+        None => KitchenSortColumn::Rating,
+    };
+    let descending = match order.as_deref() {
+        Some("asc") => false,
+        Some("desc") => true,
+        Some(other) => {
+            return Err(AppError::BadRequest(format!(
+                "Invalid order '{}'; expected one of: asc, desc",
+                other
+            )));
+        }
+        None => column.default_descending(),
+    };
+    Ok(Some(KitchenSort { column, descending }))
+}
+
+/// Rounds `average` to one decimal (half away from zero, 0 without reviews)
+/// and flags it as provisional when it rests on fewer than `min_reviews`
+/// reviews, so a single review doesn't read as a definitive 5.0 or 1.0.
+fn display_rating(average: Option<Decimal>, total_reviews: i64, min_reviews: u32) -> (f64, bool) {
+    let rating = average
+        .map(|d| d.round_dp_with_strategy(1, RoundingStrategy::MidpointAwayFromZero))
+        .and_then(|d| d.to_f64())
+        .unwrap_or(0.0);
+    (rating, total_reviews < i64::from(min_reviews))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_rating() {
+        let avg = |s: &str| Some(s.parse::<Decimal>().unwrap());
+        assert_eq!(display_rating(avg("4.6666"), 5, 5), (4.7, false));
+        assert_eq!(display_rating(avg("4.25"), 5, 5), (4.3, false));
+        assert_eq!(display_rating(avg("4.24"), 6, 5), (4.2, false));
+        // One review short of the minimum
+        assert_eq!(display_rating(avg("5"), 4, 5), (5.0, true));
+        assert_eq!(display_rating(None, 0, 5), (0.0, true));
+        // A minimum of 0 never flags ratings
+        assert_eq!(display_rating(None, 0, 0), (0.0, false));
+    }
+
+    #[test]
+    fn test_is_valid_contact_phone() {
+        assert!(is_valid_contact_phone("081234567890"));
+        assert!(is_valid_contact_phone("+62 21 555 0123"));
+        assert!(is_valid_contact_phone("(021) 555-0123"));
+        assert!(!is_valid_contact_phone("12345"));
+        assert!(!is_valid_contact_phone("call 021 555 0123"));
+        assert!(!is_valid_contact_phone("+62 21 555 0123 ext 4567"));
     }
 }
@@ -3,12 +3,27 @@
 //! This module contains the service layer, which orchestrates database operations
 //! and implements business rules.
 
+pub mod alert;
+pub mod audit;
 pub mod auth;
+pub mod channel;
+pub mod complaint;
+pub mod compliance;
+pub mod datetime;
+pub mod email;
+pub mod export;
+pub mod filter;
 pub mod incident;
+pub mod inspection;
 pub mod kitchen;
 pub mod otp;
+pub mod pagination;
+pub mod pdf;
+pub mod phone;
 pub mod review;
 pub mod stats;
 pub mod storage;
 pub mod utility;
+pub mod video;
+pub mod webhook;
 pub mod whatsapp;
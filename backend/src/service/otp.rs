@@ -1,9 +1,13 @@
-//! OTP (One-Time Password) service for phone verification.
+//! OTP (One-Time Password) service for phone and email verification.
 //!
 //! This module provides functionality to generate, store, and verify OTP codes
-//! for user phone verification. OTPs can be delivered via WhatsApp when enabled.
+//! for user verification. OTPs are delivered through the enabled
+//! [`MessageChannel`](crate::service::channel::MessageChannel)s: WhatsApp for
+//! phone numbers, email otherwise.
 
 use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::RandomState;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -11,119 +15,271 @@ use std::time::Instant;
 use log::info;
 use log::warn;
 use rand::Rng;
+use serde::Serialize;
 use tokio::sync::RwLock;
+use utoipa::ToSchema;
 
 use crate::config::Config;
 use crate::error::AppError;
-use crate::service::whatsapp::WhatsAppClient;
+use crate::service::channel::ChannelDispatcher;
+use crate::service::channel::Message;
+use crate::service::channel::Recipient;
+use crate::service::phone::is_valid_phone;
+use crate::service::phone::normalize_phone;
+
+/// How far an OTP message got, as reported by the channel that sent it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    /// Accepted by the channel.
+    Sent,
+    /// Reached the recipient's device.
+    Delivered,
+    /// Opened by the recipient.
+    Read,
+    /// Could not be delivered.
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeliveryStatus::Sent => "sent",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Read => "read",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+
+    /// Whether a callback reporting `self` should replace `current`.
+    ///
+    /// Providers don't guarantee callbacks arrive in order, so a late `sent`
+    /// must not undo `delivered`, and a message that was delivered can no
+    /// longer fail.
+    fn supersedes(self, current: Option<DeliveryStatus>) -> bool {
+        let rank = |status| match status {
+            DeliveryStatus::Sent => 0,
+            DeliveryStatus::Delivered | DeliveryStatus::Failed => 1,
+            DeliveryStatus::Read => 2,
+        };
+        current.is_none_or(|current| rank(self) > rank(current))
+    }
+}
 
 /// OTP entry containing the code and metadata.
 #[derive(Clone, Debug)]
 struct OtpEntry {
     code: String,
-    phone: String,
+    recipient: Recipient,
     created_at: Instant,
     attempts: u32,
     verified: bool,
+    /// `None` until a channel accepted the message.
+    delivery: Option<DeliveryStatus>,
+}
+
+/// Number of independently locked shards in an [`OtpStore`].
+const OTP_SHARDS: usize = 16;
+
+/// In-memory OTP storage (reference_id -> OtpEntry), split into shards by a
+/// hash of the reference ID so operations on different references rarely
+/// wait on the same lock.
+///
+/// Everything done to one reference happens under its shard's write lock, so
+/// concurrent verifications of the same reference are still serialized.
+struct OtpStore {
+    hasher: RandomState,
+    shards: Box<[RwLock<HashMap<String, OtpEntry>>]>,
+}
+
+impl OtpStore {
+    fn new() -> Self {
+        Self {
+            hasher: RandomState::new(),
+            shards: (0..OTP_SHARDS)
+                .map(|_| RwLock::new(HashMap::new()))
+                .collect(),
+        }
+    }
+
+    /// The shard holding `reference_id`.
+    fn shard(&self, reference_id: &str) -> &RwLock<HashMap<String, OtpEntry>> {
+        let hash = self.hasher.hash_one(reference_id);
+        &self.shards[hash as usize % self.shards.len()]
+    }
+
+    async fn insert(&self, reference_id: String, entry: OtpEntry) {
+        self.shard(&reference_id)
+            .write()
+            .await
+            .insert(reference_id, entry);
+    }
+
+    async fn remove(&self, reference_id: &str) {
+        self.shard(reference_id).write().await.remove(reference_id);
+    }
+}
+
+/// An OTP that was generated and, if a channel is available, delivered.
+#[derive(Debug)]
+pub struct SentOtp {
+    /// Reference to pass back when verifying the code.
+    pub reference_id: String,
+    /// Seconds until the code expires.
+    pub expires_in: u64,
+    /// Channel the code was sent through, `None` if none is configured.
+    pub channel: Option<&'static str>,
+    /// The code itself, only set when `otp_debug_return_code` is enabled in a
+    /// debug build.
+    pub debug_code: Option<String>,
+}
+
+/// State of a pending OTP, see [`OtpService::status`].
+#[derive(Debug)]
+pub struct OtpStatus {
+    /// Last delivery status reported by the channel, `None` if it never
+    /// accepted the message or reports no statuses.
+    pub delivery: Option<DeliveryStatus>,
+    /// Seconds until the code expires.
+    pub expires_in: u64,
+    /// Whether the code was verified.
+    pub verified: bool,
 }
 
 /// Service for managing OTP codes.
 pub struct OtpService {
     config: Arc<Config>,
-    whatsapp_client: Option<WhatsAppClient>,
-    /// In-memory storage of active OTPs (reference_id -> OtpEntry)
-    otps: RwLock<HashMap<String, OtpEntry>>,
+    /// Delivery channels, tried in order of preference.
+    channels: ChannelDispatcher,
+    /// In-memory storage of active OTPs
+    otps: OtpStore,
 }
 
 impl OtpService {
     /// Creates a new OTP service.
     pub fn new(config: Arc<Config>) -> Self {
-        let whatsapp_client = if config.whatsapp.enabled {
-            Some(WhatsAppClient::new(config.clone()))
-        } else {
-            None
-        };
+        let channels = ChannelDispatcher::from_config(config.clone());
+        Self::with_channels(config, channels)
+    }
 
+    /// Creates a new OTP service delivering through the given channels.
+    pub fn with_channels(config: Arc<Config>, channels: ChannelDispatcher) -> Self {
         Self {
             config,
-            whatsapp_client,
-            otps: RwLock::new(HashMap::new()),
+            channels,
+            otps: OtpStore::new(),
         }
     }
 
-    /// Generates and sends an OTP code to the specified phone number.
+    /// Generates and sends an OTP code to the recipient.
+    ///
+    /// The channel is picked from the recipient's contact info: WhatsApp for a
+    /// phone number, email otherwise, falling back to the next channel if
+    /// delivery fails.
     ///
     /// # Arguments
     ///
-    /// * `phone` - The phone number to send the OTP to
+    /// * `recipient` - The phone number and/or email address to send the OTP to
     ///
     /// # Returns
     ///
-    /// Returns a tuple of (reference_id, expires_in_seconds)
-    pub async fn send_otp(&self, phone: String) -> Result<(String, u64), AppError> {
-        // Validate phone format
-        if !Self::is_valid_phone(&phone) {
+    /// Returns the reference ID, expiry and delivery channel of the OTP; see
+    /// [`SentOtp`].
+    pub async fn send_otp(&self, recipient: Recipient) -> Result<SentOtp, AppError> {
+        if recipient.phone.is_none() && recipient.email.is_none() {
+            return Err(AppError::BadRequest(
+                "Phone number or email is required".to_string(),
+            ));
+        }
+        if let Some(phone) = &recipient.phone
+            && !is_valid_phone(phone)
+        {
             return Err(AppError::BadRequest(
                 "Invalid phone number format".to_string(),
             ));
         }
+        if let Some(email) = &recipient.email
+            && !Self::is_valid_email(email)
+        {
+            return Err(AppError::BadRequest("Invalid email format".to_string()));
+        }
 
         // Generate a 6-digit OTP code
         let otp_code = Self::generate_otp_code();
+        let debug_code = self.returns_debug_code().then(|| otp_code.clone());
+        let expires_in = self.config.whatsapp.otp_expiry_seconds;
 
         // Generate a unique reference ID
         let reference_id = format!("otp_{}", uuid::Uuid::new_v4());
 
         // Store the OTP
-        let _expiry_duration = Duration::from_secs(self.config.whatsapp.otp_expiry_seconds);
         let entry = OtpEntry {
             code: otp_code.clone(),
-            phone: phone.clone(),
+            recipient: recipient.clone(),
             created_at: Instant::now(),
             attempts: 0,
             verified: false,
+            delivery: None,
         };
 
-        {
-            let mut otps = self.otps.write().await;
-            otps.insert(reference_id.clone(), entry);
-        }
+        self.otps.insert(reference_id.clone(), entry).await;
 
-        // Send via WhatsApp if enabled
-        if let Some(ref client) = self.whatsapp_client {
-            match client.send_otp(&phone, &otp_code, &reference_id).await {
-                Ok(()) => {
-                    info!(
-                        "OTP sent via WhatsApp to {} with reference {}",
-                        phone, reference_id
-                    );
-                }
-                Err(e) => {
-                    // If WhatsApp fails, we still return success but log the error
-                    // In development mode, we can return the code in the response
-                    warn!("Failed to send WhatsApp OTP: {}", e);
-
-                    // If WhatsApp is not properly configured, return error
-                    if !client.is_enabled() {
-                        // Clean up the stored OTP
-                        let mut otps = self.otps.write().await;
-                        otps.remove(&reference_id);
-                        return Err(AppError::ServiceUnavailable(
-                            "WhatsApp service is not available".to_string(),
-                        ));
-                    }
-                }
-            }
-        } else {
-            warn!("WhatsApp client not available, OTP not sent");
+        if !self.channels.can_deliver(&recipient) {
+            warn!("No delivery channel available, OTP not sent");
             // In development, we might want to return the code
             #[cfg(debug_assertions)]
             {
-                info!("Development mode: OTP code for {} is: {}", phone, otp_code);
+                info!(
+                    "Development mode: OTP code for {:?} is: {}",
+                    recipient, otp_code
+                );
+            }
+            metrics::counter!("otp_sends_total", "result" => "undelivered").increment(1);
+            return Ok(SentOtp {
+                reference_id,
+                expires_in,
+                channel: None,
+                debug_code,
+            });
+        }
+
+        let message = Message::Otp {
+            code: otp_code,
+            reference_id: reference_id.clone(),
+            expires_in,
+        };
+        match self.channels.send(&recipient, &message).await {
+            Ok(channel) => {
+                info!("OTP sent via {} with reference {}", channel, reference_id);
+                self.record_delivery_status(&reference_id, DeliveryStatus::Sent)
+                    .await;
+                metrics::counter!("otp_sends_total", "result" => "sent", "channel" => channel)
+                    .increment(1);
+                Ok(SentOtp {
+                    reference_id,
+                    expires_in,
+                    channel: Some(channel),
+                    debug_code,
+                })
+            }
+            Err(e) => {
+                // Don't leave an undeliverable reference behind so the
+                // client can cleanly request a new code
+                warn!("Failed to send OTP: {}", e);
+                metrics::counter!("otp_sends_total", "result" => "failed").increment(1);
+                self.otps.remove(&reference_id).await;
+                Err(AppError::ServiceUnavailable(
+                    "OTP delivery service is not available".to_string(),
+                ))
             }
         }
+    }
 
-        Ok((reference_id, self.config.whatsapp.otp_expiry_seconds))
+    /// Whether generated codes are handed back to the caller. Requires both a
+    /// debug build and `otp_debug_return_code`, so release binaries can never
+    /// leak codes whatever their configuration.
+    fn returns_debug_code(&self) -> bool {
+        cfg!(debug_assertions) && self.config.otp_debug_return_code
     }
 
     /// Verifies an OTP code.
@@ -131,7 +287,7 @@ impl OtpService {
     /// # Arguments
     ///
     /// * `reference_id` - The reference ID from the send_otp call
-    /// * `phone` - The phone number associated with the OTP
+    /// * `contact` - The phone number or email address the OTP was sent to
     /// * `code` - The OTP code to verify
     ///
     /// # Returns
@@ -140,10 +296,26 @@ impl OtpService {
     pub async fn verify_otp(
         &self,
         reference_id: &str,
-        phone: &str,
+        contact: &str,
         code: &str,
     ) -> Result<bool, AppError> {
-        let mut otps = self.otps.write().await;
+        let result = self.check_otp(reference_id, contact, code).await;
+        let outcome = match &result {
+            Ok(true) => "verified",
+            Ok(false) => "mismatch",
+            Err(_) => "rejected",
+        };
+        metrics::counter!("otp_verifications_total", "result" => outcome).increment(1);
+        result
+    }
+
+    async fn check_otp(
+        &self,
+        reference_id: &str,
+        contact: &str,
+        code: &str,
+    ) -> Result<bool, AppError> {
+        let mut otps = self.otps.shard(reference_id).write().await;
 
         let entry = otps
             .get_mut(reference_id)
@@ -179,10 +351,18 @@ impl OtpService {
             return Ok(false);
         }
 
-        // Verify phone matches
-        let normalized_entry_phone = Self::normalize_phone(&entry.phone);
-        let normalized_input_phone = Self::normalize_phone(phone);
-        if normalized_entry_phone != normalized_input_phone {
+        // Verify the contact matches the one the OTP was sent to
+        let phone_matches = entry
+            .recipient
+            .phone
+            .as_deref()
+            .is_some_and(|phone| normalize_phone(phone) == normalize_phone(contact));
+        let email_matches = entry
+            .recipient
+            .email
+            .as_deref()
+            .is_some_and(|email| email.eq_ignore_ascii_case(contact.trim()));
+        if !phone_matches && !email_matches {
             return Ok(false);
         }
 
@@ -193,21 +373,86 @@ impl OtpService {
         Ok(true)
     }
 
-    /// Cleans up expired OTPs. Should be called periodically.
-    pub async fn cleanup_expired(&self) {
-        let mut otps = self.otps.write().await;
-        let expiry_duration = Duration::from_secs(self.config.whatsapp.otp_expiry_seconds);
+    /// Records a delivery status reported for the OTP sent with
+    /// `reference_id`, unless the OTP has a later status already.
+    ///
+    /// # Returns
+    ///
+    /// Whether a pending OTP was updated; `false` if the reference is unknown
+    /// or has expired, or the status was stale.
+    pub async fn record_delivery_status(&self, reference_id: &str, status: DeliveryStatus) -> bool {
+        let mut otps = self.otps.shard(reference_id).write().await;
+        let Some(entry) = otps.get_mut(reference_id) else {
+            return false;
+        };
+        if !status.supersedes(entry.delivery) {
+            return false;
+        }
+        entry.delivery = Some(status);
+        if status == DeliveryStatus::Failed {
+            warn!(
+                "OTP {} could not be delivered, the client should request a new code",
+                reference_id
+            );
+        }
+        metrics::counter!("otp_delivery_status_total", "status" => status.as_str()).increment(1);
+        true
+    }
 
-        let expired_keys: Vec<String> = otps
-            .iter()
-            .filter(|(_, entry)| entry.created_at.elapsed() > expiry_duration)
-            .map(|(key, _)| key.clone())
-            .collect();
+    /// The last delivery status recorded for the OTP sent with
+    /// `reference_id`, `None` if it is unknown or nothing was recorded.
+    pub async fn delivery_status(&self, reference_id: &str) -> Option<DeliveryStatus> {
+        self.otps
+            .shard(reference_id)
+            .read()
+            .await
+            .get(reference_id)
+            .and_then(|entry| entry.delivery)
+    }
+
+    /// The state of the OTP sent with `reference_id`, so clients can tell a
+    /// message that failed or hasn't arrived and offer another channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::NotFound`] if the reference is unknown or the OTP
+    /// has expired.
+    pub async fn status(&self, reference_id: &str) -> Result<OtpStatus, AppError> {
+        let otps = self.otps.shard(reference_id).read().await;
+        let expiry = Duration::from_secs(self.config.whatsapp.otp_expiry_seconds);
+        let entry = otps
+            .get(reference_id)
+            .filter(|entry| entry.created_at.elapsed() <= expiry)
+            .ok_or_else(|| AppError::NotFound("Invalid reference ID".to_string()))?;
+        Ok(OtpStatus {
+            delivery: entry.delivery,
+            expires_in: expiry.saturating_sub(entry.created_at.elapsed()).as_secs(),
+            verified: entry.verified,
+        })
+    }
+
+    /// Cleans up expired OTPs, returning how many were removed. Should be
+    /// called periodically.
+    ///
+    /// Shards are swept one at a time, so verifications are only held up
+    /// while their own shard is being swept.
+    pub async fn cleanup_expired(&self) -> usize {
+        let expiry_duration = Duration::from_secs(self.config.whatsapp.otp_expiry_seconds);
 
-        for key in expired_keys {
-            otps.remove(&key);
-            info!("Cleaned up expired OTP: {}", key);
+        let mut removed = 0;
+        for shard in &self.otps.shards {
+            let mut otps = shard.write().await;
+            let before = otps.len();
+            otps.retain(|key, entry| {
+                let expired = entry.created_at.elapsed() > expiry_duration;
+                if expired {
+                    info!("Cleaned up expired OTP: {}", key);
+                }
+                !expired
+            });
+            removed += before - otps.len();
         }
+        removed
     }
 
     /// Generates a 6-digit OTP code.
@@ -217,21 +462,10 @@ impl OtpService {
         code.to_string()
     }
 
-    /// Validates phone number format.
-    fn is_valid_phone(phone: &str) -> bool {
-        // Support Indonesian formats: 08xxxxxxxx, 628xxxxxxxx, +628xxxxxxxx
-        let re = regex::Regex::new(r"^(\+62|62|0)[0-9]{9,12}$").unwrap();
-        re.is_match(phone)
-    }
-
-    /// Normalizes phone number for comparison.
-    fn normalize_phone(phone: &str) -> String {
-        let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
-        if digits.starts_with('0') {
-            format!("62{}", &digits[1..])
-        } else {
-            digits
-        }
+    /// Validates email address format.
+    fn is_valid_email(email: &str) -> bool {
+        let re = regex::Regex::new(r"^[^@\s<>]+@[^@\s<>]+\.[^@\s<>]+$").unwrap();
+        re.is_match(email)
     }
 }
 
@@ -247,10 +481,206 @@ mod tests {
         assert!(code.parse::<u32>().unwrap() <= 999999);
     }
 
-    #[test]
-    fn test_normalize_phone() {
-        assert_eq!(OtpService::normalize_phone("08123456789"), "628123456789");
-        assert_eq!(OtpService::normalize_phone("628123456789"), "628123456789");
-        assert_eq!(OtpService::normalize_phone("+628123456789"), "628123456789");
+    #[tokio::test]
+    async fn test_send_otp_failure_removes_entry() {
+        let server = httpmock::MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::POST);
+                then.status(500);
+            })
+            .await;
+
+        let mut config = Config::default();
+        config.whatsapp.enabled = true;
+        config.whatsapp.api_url = Some(server.base_url());
+        config.whatsapp.api_token = Some("token".to_string());
+        config.whatsapp.phone_number_id = Some("123".to_string());
+        config.whatsapp.send_attempts = 2;
+        config.whatsapp.retry_delay_ms = 1;
+        let service = OtpService::new(Arc::new(config));
+
+        let recipient = Recipient {
+            phone: Some("08123456789".to_string()),
+            ..Default::default()
+        };
+        let result = service.send_otp(recipient).await;
+        assert!(matches!(result, Err(AppError::ServiceUnavailable(_))));
+        for shard in &service.otps.shards {
+            assert!(shard.read().await.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_otp_by_email() {
+        let service = OtpService::with_channels(
+            Arc::new(Config {
+                whatsapp: crate::config::WhatsAppConfig {
+                    otp_expiry_seconds: 300,
+                    max_attempts: 5,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ChannelDispatcher::new(Vec::new()),
+        );
+        let recipient = Recipient {
+            email: Some("School@Example.com".to_string()),
+            ..Default::default()
+        };
+        let sent = service.send_otp(recipient).await.unwrap();
+        assert_eq!(sent.channel, None);
+        assert_eq!(sent.debug_code, None);
+        let reference_id = sent.reference_id;
+
+        let code = service.otps.shard(&reference_id).read().await[&reference_id]
+            .code
+            .clone();
+        assert!(
+            !service
+                .verify_otp(&reference_id, "08123456789", &code)
+                .await
+                .unwrap()
+        );
+        assert!(
+            service
+                .verify_otp(&reference_id, "school@example.com", &code)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_otp_returns_debug_code_when_enabled() {
+        let service = OtpService::with_channels(
+            Arc::new(Config {
+                whatsapp: crate::config::WhatsAppConfig {
+                    otp_expiry_seconds: 300,
+                    max_attempts: 5,
+                    ..Default::default()
+                },
+                otp_debug_return_code: true,
+                ..Default::default()
+            }),
+            ChannelDispatcher::new(Vec::new()),
+        );
+        let recipient = Recipient {
+            phone: Some("08123456789".to_string()),
+            ..Default::default()
+        };
+        let sent = service.send_otp(recipient).await.unwrap();
+
+        if cfg!(debug_assertions) {
+            let code = sent.debug_code.expect("debug code returned");
+            assert!(
+                service
+                    .verify_otp(&sent.reference_id, "08123456789", &code)
+                    .await
+                    .unwrap()
+            );
+        } else {
+            assert_eq!(sent.debug_code, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_delivery_status() {
+        let service = OtpService::with_channels(
+            Arc::new(Config {
+                whatsapp: crate::config::WhatsAppConfig {
+                    otp_expiry_seconds: 300,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ChannelDispatcher::new(Vec::new()),
+        );
+        let recipient = Recipient {
+            phone: Some("08123456789".to_string()),
+            ..Default::default()
+        };
+        let reference_id = service.send_otp(recipient).await.unwrap().reference_id;
+        assert_eq!(service.delivery_status(&reference_id).await, None);
+
+        assert!(
+            service
+                .record_delivery_status(&reference_id, DeliveryStatus::Delivered)
+                .await
+        );
+        // A late "sent" callback and a failure after delivery are ignored
+        for stale in [DeliveryStatus::Sent, DeliveryStatus::Failed] {
+            assert!(!service.record_delivery_status(&reference_id, stale).await);
+        }
+        assert!(
+            service
+                .record_delivery_status(&reference_id, DeliveryStatus::Read)
+                .await
+        );
+        assert_eq!(
+            service.delivery_status(&reference_id).await,
+            Some(DeliveryStatus::Read)
+        );
+        let status = service.status(&reference_id).await.unwrap();
+        assert_eq!(status.delivery, Some(DeliveryStatus::Read));
+        assert!(!status.verified);
+        assert!(status.expires_in > 295 && status.expires_in <= 300);
+
+        assert!(
+            !service
+                .record_delivery_status("otp_unknown", DeliveryStatus::Read)
+                .await
+        );
+        assert!(matches!(
+            service.status("otp_unknown").await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_parallel_verifies_count_every_attempt() {
+        let service = Arc::new(OtpService::with_channels(
+            Arc::new(Config {
+                whatsapp: crate::config::WhatsAppConfig {
+                    otp_expiry_seconds: 300,
+                    max_attempts: 20,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ChannelDispatcher::new(Vec::new()),
+        ));
+        let recipient = Recipient {
+            phone: Some("08123456789".to_string()),
+            ..Default::default()
+        };
+        let reference_id = service.send_otp(recipient).await.unwrap().reference_id;
+
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let service = service.clone();
+                let reference_id = reference_id.clone();
+                tokio::spawn(async move {
+                    service
+                        .verify_otp(&reference_id, "08123456789", "000000")
+                        .await
+                })
+            })
+            .collect();
+        let mut mismatches = 0;
+        for task in tasks {
+            if let Ok(false) = task.await.unwrap() {
+                mismatches += 1;
+            }
+        }
+
+        // Exactly max_attempts wrong guesses were allowed before the
+        // reference was dropped
+        assert_eq!(mismatches, 20);
+        assert!(matches!(
+            service
+                .verify_otp(&reference_id, "08123456789", "000000")
+                .await,
+            Err(AppError::NotFound(_))
+        ));
     }
 }
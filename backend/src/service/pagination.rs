@@ -0,0 +1,115 @@
+//! Page metadata and the response envelope shared by list endpoints.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Where a page sits within a list, returned as `pagination` next to `data`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Pagination {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+    /// 1-based page number, `offset / limit + 1`.
+    pub page: i64,
+    /// `ceil(total / limit)`; 0 when the list is empty.
+    #[serde(rename = "totalPages")]
+    pub total_pages: i64,
+}
+
+impl Pagination {
+    /// Metadata for the page of `total` items starting at `offset`.
+    pub fn new(total: i64, limit: i64, offset: i64) -> Self {
+        Self::with_has_more(total, limit, offset, offset + limit < total)
+    }
+
+    /// Metadata for a keyset page, which has no offset and knows whether more
+    /// items follow from the extra row it fetched.
+    pub fn keyset(total: i64, limit: i64, has_more: bool) -> Self {
+        Self::with_has_more(total, limit, 0, has_more)
+    }
+
+    fn with_has_more(total: i64, limit: i64, offset: i64, has_more: bool) -> Self {
+        // PaginationParams never yields a zero limit, but don't divide by one
+        let per_page = limit.max(1);
+        Self {
+            total,
+            limit,
+            offset,
+            has_more,
+            page: offset / per_page + 1,
+            total_pages: (total + per_page - 1) / per_page,
+        }
+    }
+}
+
+/// A page of a list endpoint's items with its [`Pagination`].
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub pagination: Pagination,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages_are_computed_from_offset_and_total() {
+        let first = Pagination::new(45, 20, 0);
+        assert_eq!(
+            (first.page, first.total_pages, first.has_more),
+            (1, 3, true)
+        );
+
+        let last = Pagination::new(45, 20, 40);
+        assert_eq!((last.page, last.total_pages, last.has_more), (3, 3, false));
+
+        let exact = Pagination::new(40, 20, 20);
+        assert_eq!(
+            (exact.page, exact.total_pages, exact.has_more),
+            (2, 2, false)
+        );
+
+        // Offsets that aren't a multiple of the limit round down
+        assert_eq!(Pagination::new(45, 20, 30).page, 2);
+
+        let empty = Pagination::new(0, 20, 0);
+        assert_eq!(
+            (empty.page, empty.total_pages, empty.has_more),
+            (1, 0, false)
+        );
+    }
+
+    #[test]
+    fn test_keyset_page_has_no_offset() {
+        let page = Pagination::keyset(45, 20, true);
+        assert_eq!(page.offset, 0);
+        assert_eq!(page.page, 1);
+        assert_eq!(page.total_pages, 3);
+        assert!(page.has_more);
+    }
+
+    #[test]
+    fn test_serializes_camel_case() {
+        let response = PaginatedResponse {
+            data: vec!["a", "b"],
+            pagination: Pagination::new(45, 20, 20),
+        };
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            serde_json::json!({
+                "data": ["a", "b"],
+                "pagination": {
+                    "total": 45,
+                    "limit": 20,
+                    "offset": 20,
+                    "hasMore": true,
+                    "page": 2,
+                    "totalPages": 3
+                }
+            })
+        );
+    }
+}
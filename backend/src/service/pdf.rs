@@ -0,0 +1,265 @@
+//! Minimal text-only PDF writer.
+//!
+//! Produces A4 pages of left-aligned Helvetica text, which is all the printable
+//! reports need. Characters outside Latin-1 are replaced with `?` since the
+//! built-in PDF fonts only cover the WinAnsi character set.
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+
+/// Font sizes and line heights of the two text styles, in points.
+const HEADING_SIZE: f32 = 14.0;
+const HEADING_LEADING: f32 = 22.0;
+const BODY_SIZE: f32 = 10.0;
+const BODY_LEADING: f32 = 14.0;
+
+/// Body lines are wrapped at this many characters, which fits the text width
+/// for typical Helvetica text.
+const WRAP_COLUMNS: usize = 95;
+
+/// A PDF document built line by line, starting a new page when the current one
+/// is full.
+#[derive(Debug)]
+pub struct PdfDocument {
+    /// Content stream of each page; the last one is being written.
+    pages: Vec<Vec<u8>>,
+    /// Baseline of the next line on the current page.
+    cursor_y: f32,
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfDocument {
+    /// Creates a document with one empty page.
+    pub fn new() -> Self {
+        Self {
+            pages: vec![Vec::new()],
+            cursor_y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Adds a bold heading line.
+    pub fn heading(&mut self, text: &str) {
+        self.write_line("F2", HEADING_SIZE, HEADING_LEADING, text);
+    }
+
+    /// Adds body text, wrapping it over as many lines as needed.
+    pub fn text(&mut self, text: &str) {
+        for line in wrap(text, WRAP_COLUMNS) {
+            self.write_line("F1", BODY_SIZE, BODY_LEADING, &line);
+        }
+    }
+
+    /// Adds an empty line.
+    pub fn blank(&mut self) {
+        self.cursor_y -= BODY_LEADING;
+    }
+
+    /// Returns the number of pages written so far.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    fn write_line(&mut self, font: &str, size: f32, leading: f32, text: &str) {
+        if self.cursor_y - leading < MARGIN {
+            self.pages.push(Vec::new());
+            self.cursor_y = PAGE_HEIGHT - MARGIN;
+        }
+        self.cursor_y -= leading;
+
+        let page = self.pages.last_mut().expect("document has a page");
+        page.extend_from_slice(
+            format!("BT /{} {} Tf {} {} Td (", font, size, MARGIN, self.cursor_y).as_bytes(),
+        );
+        page.extend_from_slice(&encode_text(text));
+        page.extend_from_slice(b") Tj ET\n");
+    }
+
+    /// Serializes the document.
+    pub fn finish(self) -> Vec<u8> {
+        // Objects 1-4 are the catalog, page tree and fonts; each page then
+        // takes a page object followed by its content stream.
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 5 + 2 * i).collect();
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids
+                    .iter()
+                    .map(|id| format!("{} 0 R", id))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                page_ids.len()
+            )
+            .into_bytes(),
+            font("Helvetica"),
+            font("Helvetica-Bold"),
+        ];
+        for (content, page_id) in self.pages.into_iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    page_id + 1
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(&content);
+            stream.extend_from_slice(b"endstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        out.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+fn font(base_font: &str) -> Vec<u8> {
+    format!(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+        base_font
+    )
+    .into_bytes()
+}
+
+/// Encodes text as a PDF string body in WinAnsi, escaping delimiters.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            ' '..='~' => out.push(c as u8),
+            // WinAnsi agrees with Latin-1 in this range
+            '\u{A0}'..='\u{FF}' => out.push(c as u32 as u8),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+/// Splits `text` into lines of at most `columns` characters, breaking at
+/// spaces where possible.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            // Break words that don't fit on a line by themselves
+            while word.chars().count() > columns {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let split = word
+                    .char_indices()
+                    .nth(columns)
+                    .map_or(word.len(), |(i, _)| i);
+                lines.push(word[..split].to_string());
+                word = &word[split..];
+            }
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > columns {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finish_writes_valid_structure() {
+        let mut doc = PdfDocument::new();
+        doc.heading("Kitchen (Report)");
+        doc.text("Hello");
+        let pdf = doc.finish();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("(Kitchen \\(Report\\)) Tj"));
+
+        // Every xref entry points at the start of its object
+        let xref_start: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(text[xref_start..].starts_with("xref\n"));
+        for (i, entry) in text[xref_start..].lines().skip(3).take(6).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_long_documents_span_pages() {
+        let mut doc = PdfDocument::new();
+        for i in 0..100 {
+            doc.text(&format!("Line {}", i));
+        }
+        assert_eq!(doc.page_count(), 2);
+        assert!(String::from_utf8_lossy(&doc.finish()).contains("/Count 2"));
+    }
+
+    #[test]
+    fn test_encode_text() {
+        assert_eq!(encode_text("Café (a\\b)"), b"Caf\xE9 \\(a\\\\b\\)");
+        assert_eq!(encode_text("日本"), b"??");
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("a\nb", 10), vec!["a", "b"]);
+    }
+}
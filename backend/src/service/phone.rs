@@ -0,0 +1,62 @@
+//! Indonesian phone number validation and normalization.
+//!
+//! Users type numbers as `08...`, `62...` or `+62...`. They are stored and
+//! compared in the canonical `62...` form so the same person always matches.
+
+use crate::error::AppError;
+
+/// Checks that `phone` is an Indonesian number: `08xxxxxxxx`, `628xxxxxxxx`
+/// or `+628xxxxxxxx`.
+pub fn is_valid_phone(phone: &str) -> bool {
+    let re = regex::Regex::new(r"^(\+62|62|0)[0-9]{9,12}$").unwrap();
+    re.is_match(phone)
+}
+
+/// Normalizes a phone number to the canonical `62...` form.
+pub fn normalize_phone(phone: &str) -> String {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+    match digits.strip_prefix('0') {
+        Some(rest) => format!("62{}", rest),
+        None => digits,
+    }
+}
+
+/// Validates `phone` and returns its canonical `62...` form.
+///
+/// # Errors
+///
+/// Returns `BadRequest` if `phone` is not a valid Indonesian number.
+pub fn parse_phone(phone: &str) -> Result<String, AppError> {
+    let phone = phone.trim();
+    if !is_valid_phone(phone) {
+        return Err(AppError::BadRequest(
+            "Invalid phone number format".to_string(),
+        ));
+    }
+    Ok(normalize_phone(phone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(normalize_phone("08123456789"), "628123456789");
+        assert_eq!(normalize_phone("628123456789"), "628123456789");
+        assert_eq!(normalize_phone("+628123456789"), "628123456789");
+    }
+
+    #[test]
+    fn test_parse_phone() {
+        assert_eq!(parse_phone(" 08123456789 ").unwrap(), "628123456789");
+        assert_eq!(parse_phone("+628123456789").unwrap(), "628123456789");
+        for invalid in ["12345", "0812-3456-789", "+14155550123", "08123"] {
+            assert!(
+                matches!(parse_phone(invalid), Err(AppError::BadRequest(_))),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
@@ -1,21 +1,43 @@
 //! Review and rating service.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+use log::warn;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::prelude::ToPrimitive;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::json;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::auth::utils::Claims;
+use crate::config::HaccpWeights;
+use crate::config::ReviewCooldownConfig;
+use crate::config::ReviewPhotoConfig;
 use crate::database::Database;
+use crate::database::cursor::Cursor;
+use crate::database::error::DatabaseError;
+use crate::database::model::ConfidenceLevel;
+use crate::database::model::ReportSource;
 use crate::database::model::Review;
 use crate::database::model::UserRole;
 use crate::database::table::Table;
+use crate::database::table::VersionedTable;
 use crate::error::AppError;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::pagination::PaginatedResponse;
+use crate::service::pagination::Pagination;
+use crate::service::storage::StorageService;
+use crate::service::storage::UploadResult;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HaccpRatingDto {
     pub taste: f64,
     pub hygiene: f64,
@@ -25,7 +47,91 @@ pub struct HaccpRatingDto {
     pub handling: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Lowest rating a reviewer may give.
+pub const MIN_RATING: f64 = 0.5;
+
+/// Highest rating a reviewer may give.
+pub const MAX_RATING: f64 = 5.0;
+
+impl HaccpRatingDto {
+    /// Validates the ratings and converts them to decimals, in the order
+    /// taste, hygiene, freshness, temperature, packaging, handling.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::BadRequest`] naming the first rating outside
+    /// [[`MIN_RATING`], [`MAX_RATING`]].
+    fn to_decimals(&self) -> Result<[Decimal; 6], AppError> {
+        let ratings = [
+            ("taste", self.taste),
+            ("hygiene", self.hygiene),
+            ("freshness", self.freshness),
+            ("temperature", self.temperature),
+            ("packaging", self.packaging),
+            ("handling", self.handling),
+        ];
+        let mut decimals = [Decimal::ZERO; 6];
+        for (decimal, (field, value)) in decimals.iter_mut().zip(ratings) {
+            if !(MIN_RATING..=MAX_RATING).contains(&value) {
+                return Err(AppError::BadRequest(format!(
+                    "ratings.{} must be between {} and {}",
+                    field, MIN_RATING, MAX_RATING
+                )));
+            }
+            *decimal = Decimal::from_f64(value).unwrap_or_default();
+        }
+        Ok(decimals)
+    }
+}
+
+/// Ratings to change on an existing review; omitted ones are kept.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct HaccpRatingPatch {
+    pub taste: Option<f64>,
+    pub hygiene: Option<f64>,
+    pub freshness: Option<f64>,
+    pub temperature: Option<f64>,
+    pub packaging: Option<f64>,
+    pub handling: Option<f64>,
+}
+
+impl HaccpRatingPatch {
+    /// Applies the given ratings over `current`.
+    fn merge(&self, current: HaccpRatingDto) -> HaccpRatingDto {
+        HaccpRatingDto {
+            taste: self.taste.unwrap_or(current.taste),
+            hygiene: self.hygiene.unwrap_or(current.hygiene),
+            freshness: self.freshness.unwrap_or(current.freshness),
+            temperature: self.temperature.unwrap_or(current.temperature),
+            packaging: self.packaging.unwrap_or(current.packaging),
+            handling: self.handling.unwrap_or(current.handling),
+        }
+    }
+}
+
+/// A review's ratings and comment as they were before one of its edits.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewEditDto {
+    pub ratings: HaccpRatingDto,
+    pub comment: String,
+    /// User who made the edit; `null` if they have since been deleted.
+    #[serde(rename = "editedBy")]
+    pub edited_by: Option<Uuid>,
+    #[serde(rename = "editedAt")]
+    pub edited_at: Option<String>,
+}
+
+/// Edit timeline returned by [`ReviewService::get_edit_history`].
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewHistoryResponse {
+    #[serde(rename = "reviewId")]
+    pub review_id: Uuid,
+    /// Prior versions, oldest first; the latest one is what the review said
+    /// before its most recent edit.
+    pub edits: Vec<ReviewEditDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateReviewRequest {
     #[serde(rename = "kitchenId")]
     pub kitchen_id: Uuid,
@@ -42,14 +148,18 @@ pub struct CreateReviewRequest {
     pub meal_type: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateReviewRequest {
-    pub ratings: Option<HaccpRatingDto>,
+    pub ratings: Option<HaccpRatingPatch>,
     pub comment: Option<String>,
     pub photos: Option<Vec<String>>,
+    #[serde(rename = "deliveryDate")]
+    pub delivery_date: Option<String>,
+    #[serde(rename = "mealType")]
+    pub meal_type: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ReviewDto {
     pub id: Uuid,
     #[serde(rename = "kitchenId")]
@@ -71,31 +181,38 @@ pub struct ReviewDto {
     #[serde(rename = "verificationStatus")]
     pub verification_status: String,
     #[serde(rename = "reportSource")]
-    pub report_source: String,
+    pub report_source: ReportSource,
     #[serde(rename = "confidenceLevel")]
-    pub confidence_level: String,
+    pub confidence_level: ConfidenceLevel,
     #[serde(rename = "createdAt")]
     pub created_at: String,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ReviewListResponse {
-    pub data: Vec<ReviewDto>,
-    pub pagination: Pagination,
+    #[serde(flatten)]
+    pub page: PaginatedResponse<ReviewDto>,
+    /// Cursor for the next page when using keyset pagination (`null` on the last page
+    /// or when paginating by offset).
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Pagination {
-    pub total: i64,
-    pub limit: i64,
-    pub offset: i64,
-    #[serde(rename = "hasMore")]
-    pub has_more: bool,
+/// How a batch of reviews is submitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// Each review is saved independently; failures don't affect the others.
+    #[default]
+    BestEffort,
+    /// The batch is saved in one transaction: either every review is saved or
+    /// none is.
+    AllOrNothing,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BatchReviewResult {
     #[serde(rename = "kitchenId")]
     pub kitchen_id: Uuid,
@@ -105,56 +222,119 @@ pub struct BatchReviewResult {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BatchReviewResponse {
+    /// `false` only when an `all_or_nothing` batch was rolled back.
     pub success: bool,
+    pub mode: BatchMode,
     pub created: i32,
     pub failed: i32,
     pub results: Vec<BatchReviewResult>,
 }
 
+/// Photo list of a review after an upload.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewPhotosResponse {
+    pub id: Uuid,
+    pub photos: Vec<String>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// Largest accepted review photo, in bytes.
+pub const MAX_PHOTO_BYTES: usize = 5 * 1024 * 1024;
+
 /// Service for managing kitchen reviews and ratings.
 pub struct ReviewService {
     db: Arc<Database>,
+    rating_weights: HaccpWeights,
+    photo_storage: Option<Arc<StorageService>>,
+    photo_config: ReviewPhotoConfig,
+    cooldown: ReviewCooldownConfig,
 }
 
 impl ReviewService {
-    /// Creates a new `ReviewService`.
+    /// Creates a new `ReviewService` that weighs all HACCP ratings equally.
     pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+        Self::with_rating_weights(db, HaccpWeights::default())
+    }
+
+    /// Creates a new `ReviewService` averaging HACCP ratings with `rating_weights`.
+    pub fn with_rating_weights(db: Arc<Database>, rating_weights: HaccpWeights) -> Self {
+        Self {
+            db,
+            rating_weights,
+            photo_storage: None,
+            photo_config: ReviewPhotoConfig::default(),
+            cooldown: ReviewCooldownConfig::default(),
+        }
+    }
+
+    /// Sets how long a reviewer must wait before reviewing the same kitchen
+    /// again.
+    pub fn with_cooldown(mut self, cooldown: ReviewCooldownConfig) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Enables photo uploads through [`ReviewService::upload_photos`], storing
+    /// them in `storage` within the limits of `config`.
+    pub fn with_photo_storage(
+        mut self,
+        storage: Arc<StorageService>,
+        config: ReviewPhotoConfig,
+    ) -> Self {
+        self.photo_storage = Some(storage);
+        self.photo_config = config;
+        self
+    }
+
+    /// Returns the most photos a single review may have.
+    pub fn max_photos_per_review(&self) -> usize {
+        self.photo_config.max_per_review
     }
 
     /// Submits a new review for a kitchen.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Conflict` if the reviewer already reviewed this kitchen within
+    /// the cooldown window; they should edit that review instead.
     pub async fn submit_review(
         &self,
         reviewer_id: Uuid,
         req: CreateReviewRequest,
     ) -> Result<ReviewDto, AppError> {
-        let review = Review {
-            id: Uuid::new_v4(),
-            kitchen_id: req.kitchen_id,
-            reviewer_id,
-            reviewer_name: req.reviewer_name.clone(),
-            reviewer_type: req.reviewer_type,
-            taste_rating: Decimal::from_f64(req.ratings.taste).unwrap_or_default(),
-            hygiene_rating: Decimal::from_f64(req.ratings.hygiene).unwrap_or_default(),
-            freshness_rating: Decimal::from_f64(req.ratings.freshness).unwrap_or_default(),
-            temperature_rating: Decimal::from_f64(req.ratings.temperature).unwrap_or_default(),
-            packaging_rating: Decimal::from_f64(req.ratings.packaging).unwrap_or_default(),
-            handling_rating: Decimal::from_f64(req.ratings.handling).unwrap_or_default(),
-            comment: req.comment.clone(),
-            photos: req.photos.clone().map(|p| json!(p)),
-            verification_status: Some("unverified".to_string()),
-            report_source: "public".to_string(),
-            confidence_level: "medium".to_string(),
-            verified: Some(false),
-            is_draft: Some(false),
-            created_at: Some(chrono::Utc::now().naive_utc()),
-            updated_at: Some(chrono::Utc::now().naive_utc()),
-            ..Default::default()
-        };
+        let review = new_review(reviewer_id, &req)?;
+        let since = self.cooldown_start()?;
 
-        let id = self.db.review_table.insert(&review).await?;
+        // Check the cooldown and insert in one transaction, so concurrent
+        // submissions can't both get past the check
+        let table = &self.db.review_table;
+        let days = self.cooldown.days;
+        let id = self
+            .db
+            .transaction_with_retry(|tx| {
+                Box::pin(async {
+                    if let Some(since) = since {
+                        let existing = table
+                            .find_recent_by_user_kitchen_in(
+                                tx,
+                                &reviewer_id,
+                                &req.kitchen_id,
+                                since,
+                            )
+                            .await?;
+                        if let Some(existing) = existing {
+                            return Err(DatabaseError::Conflict {
+                                message: cooldown_message(days, existing.id),
+                            });
+                        }
+                    }
+                    table.insert_in(tx, &review).await
+                })
+            })
+            .await?;
 
         // Fetch back to return DTO
         let saved =
@@ -169,84 +349,199 @@ impl ReviewService {
         self.map_to_dto(saved)
     }
 
+    /// Rejects a new review if `reviewer_id` has a non-draft review of
+    /// `kitchen_id` younger than the cooldown.
+    async fn ensure_cooldown_elapsed(
+        &self,
+        reviewer_id: Uuid,
+        kitchen_id: Uuid,
+    ) -> Result<(), AppError> {
+        let Some(since) = self.cooldown_start()? else {
+            return Ok(());
+        };
+        match self
+            .db
+            .review_table
+            .find_recent_by_user_kitchen(&reviewer_id, &kitchen_id, since)
+            .await?
+        {
+            Some(existing) => Err(AppError::Conflict(cooldown_message(
+                self.cooldown.days,
+                existing.id,
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// When the current cooldown window began, or `None` if it's disabled.
+    fn cooldown_start(&self) -> Result<Option<DateTime<Utc>>, AppError> {
+        if self.cooldown.days == 0 {
+            return Ok(None);
+        }
+        chrono::Duration::try_days(self.cooldown.days.into())
+            .and_then(|period| Utc::now().checked_sub_signed(period))
+            .map(Some)
+            .ok_or_else(|| {
+                AppError::InternalServerError(format!(
+                    "Review cooldown of {} days is out of range",
+                    self.cooldown.days
+                ))
+            })
+    }
+
+    /// Lists a kitchen's reviews, excluding drafts.
+    ///
+    /// When `cursor` is provided (an empty string requests the first page), keyset
+    /// pagination is used and `offset` is ignored; otherwise offset pagination is used.
     pub async fn get_kitchen_reviews(
         &self,
         kitchen_id: Uuid,
         limit: i64,
         offset: i64,
+        cursor: Option<&str>,
     ) -> Result<ReviewListResponse, AppError> {
-        // Get all reviews and filter by kitchen
-        let all_reviews = self.db.review_table.select_all().await?;
+        if let Some(cursor) = cursor {
+            return self
+                .list_reviews_after_cursor(Some(kitchen_id), false, cursor, limit)
+                .await;
+        }
 
-        let filtered: Vec<Review> = all_reviews
-            .into_iter()
-            .filter(|r| r.kitchen_id == kitchen_id)
-            .collect();
+        let (reviews, total) = self
+            .db
+            .review_table
+            .list_page(Some(&kitchen_id), false, limit, offset)
+            .await?;
+        Ok(self.offset_page(reviews, total, limit, offset))
+    }
 
-        let total = filtered.len() as i64;
-        let paginated: Vec<Review> = filtered
-            .into_iter()
-            .skip(offset as usize)
-            .take(limit as usize)
-            .collect();
+    /// Lists verified reviews across all kitchens, excluding drafts.
+    ///
+    /// Supports the same offset/cursor modes as [`ReviewService::get_kitchen_reviews`].
+    pub async fn get_public_reviews(
+        &self,
+        limit: i64,
+        offset: i64,
+        cursor: Option<&str>,
+    ) -> Result<ReviewListResponse, AppError> {
+        if let Some(cursor) = cursor {
+            return self
+                .list_reviews_after_cursor(None, true, cursor, limit)
+                .await;
+        }
+
+        let (reviews, total) = self
+            .db
+            .review_table
+            .list_page(None, true, limit, offset)
+            .await?;
+        Ok(self.offset_page(reviews, total, limit, offset))
+    }
+
+    /// Lists the drafts `reviewer_id` has saved but not yet submitted.
+    ///
+    /// Drafts never appear in the kitchen or public listings, so this is the
+    /// only way to read them back.
+    pub async fn get_my_drafts(
+        &self,
+        reviewer_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<ReviewListResponse, AppError> {
+        let (reviews, total) = self
+            .db
+            .review_table
+            .list_drafts(&reviewer_id, limit, offset)
+            .await?;
+        Ok(self.offset_page(reviews, total, limit, offset))
+    }
 
-        let dtos = paginated
+    /// Wraps one page of an offset-paginated listing.
+    fn offset_page(
+        &self,
+        reviews: Vec<Review>,
+        total: i64,
+        limit: i64,
+        offset: i64,
+    ) -> ReviewListResponse {
+        let dtos = reviews
             .into_iter()
             .map(|r| self.map_to_dto_sync(r))
             .collect();
 
-        Ok(ReviewListResponse {
-            data: dtos,
-            pagination: Pagination {
-                total,
-                limit,
-                offset,
-                has_more: offset + limit < total,
+        ReviewListResponse {
+            page: PaginatedResponse {
+                data: dtos,
+
+                pagination: Pagination::new(total, limit, offset),
             },
-        })
+            next_cursor: None,
+        }
     }
 
-    pub async fn get_public_reviews(
+    /// Lists reviews using keyset pagination, fetching one extra row to detect
+    /// whether another page exists.
+    async fn list_reviews_after_cursor(
         &self,
+        kitchen_id: Option<Uuid>,
+        verified_only: bool,
+        cursor: &str,
         limit: i64,
-        offset: i64,
     ) -> Result<ReviewListResponse, AppError> {
-        // Get verified reviews only
-        let all_reviews = self.db.review_table.select_all().await?;
+        let cursor = decode_cursor(cursor)?;
 
-        let filtered: Vec<Review> = all_reviews
-            .into_iter()
-            .filter(|r| r.verified.unwrap_or(false))
-            .collect();
+        let (mut reviews, total) = self
+            .db
+            .review_table
+            .list_after_cursor(
+                kitchen_id.as_ref(),
+                verified_only,
+                cursor.as_ref(),
+                limit + 1,
+            )
+            .await?;
 
-        let total = filtered.len() as i64;
-        let paginated: Vec<Review> = filtered
-            .into_iter()
-            .skip(offset as usize)
-            .take(limit as usize)
-            .collect();
+        let has_more = reviews.len() as i64 > limit;
+        reviews.truncate(limit as usize);
 
-        let dtos = paginated
+        let next_cursor = if has_more {
+            reviews
+                .last()
+                .map(|r| Cursor::new(r.created_at.unwrap_or_default(), r.id).encode())
+        } else {
+            None
+        };
+
+        let dtos = reviews
             .into_iter()
             .map(|r| self.map_to_dto_sync(r))
             .collect();
 
         Ok(ReviewListResponse {
-            data: dtos,
-            pagination: Pagination {
-                total,
-                limit,
-                offset,
-                has_more: offset + limit < total,
+            page: PaginatedResponse {
+                data: dtos,
+
+                pagination: Pagination::keyset(total, limit, has_more),
             },
+            next_cursor,
         })
     }
 
+    /// Submits several reviews at once.
+    ///
+    /// In [`BatchMode::BestEffort`] each review is saved independently. In
+    /// [`BatchMode::AllOrNothing`] the batch is saved in one transaction; if any
+    /// review is invalid or fails to save, nothing is saved and the response has
+    /// `success: false`.
     pub async fn submit_batch_reviews(
         &self,
         reviewer_id: Uuid,
         reviews: Vec<CreateReviewRequest>,
+        mode: BatchMode,
     ) -> Result<BatchReviewResponse, AppError> {
+        if mode == BatchMode::AllOrNothing {
+            return self.submit_batch_atomically(reviewer_id, reviews).await;
+        }
+
         let mut results = Vec::new();
         let mut created = 0;
         let mut failed = 0;
@@ -277,19 +572,113 @@ impl ReviewService {
 
         Ok(BatchReviewResponse {
             success: true,
+            mode,
             created,
             failed,
             results,
         })
     }
 
+    async fn submit_batch_atomically(
+        &self,
+        reviewer_id: Uuid,
+        reviews: Vec<CreateReviewRequest>,
+    ) -> Result<BatchReviewResponse, AppError> {
+        let kitchen_ids: Vec<Uuid> = reviews.iter().map(|r| r.kitchen_id).collect();
+
+        // Check kitchens and fields up front so the response can point at the
+        // offending reviews instead of reporting a bare constraint violation
+        let known: HashSet<Uuid> = self
+            .db
+            .kitchen_table
+            .select_by_ids(&kitchen_ids)
+            .await?
+            .into_iter()
+            .map(|k| k.id)
+            .collect();
+        let built: Vec<Result<Review, AppError>> = reviews
+            .iter()
+            .map(|req| new_review(reviewer_id, req))
+            .collect();
+        let mut cooldowns: HashMap<Uuid, String> = HashMap::new();
+        for id in known.iter() {
+            if let Err(e) = self.ensure_cooldown_elapsed(reviewer_id, *id).await {
+                cooldowns.insert(*id, e.to_string());
+            }
+        }
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        let errors: Vec<Option<String>> = kitchen_ids
+            .iter()
+            .zip(&built)
+            .map(|(id, review)| match review {
+                _ if !known.contains(id) => Some("Kitchen not found".to_string()),
+                Err(e) => Some(e.to_string()),
+                _ if cooldowns.contains_key(id) => cooldowns.get(id).cloned(),
+                _ if self.cooldown.days > 0 && !seen.insert(*id) => {
+                    Some("Kitchen appears more than once in the batch".to_string())
+                }
+                Ok(_) => None,
+            })
+            .collect();
+        if errors.iter().any(Option::is_some) {
+            return Ok(rolled_back(&kitchen_ids, errors));
+        }
+
+        let models: Vec<Review> = built.into_iter().flatten().collect();
+        let ids = match self.db.review_table.insert_many(&models).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                let error = AppError::from(e).to_string();
+                return Ok(rolled_back(
+                    &kitchen_ids,
+                    vec![Some(error); kitchen_ids.len()],
+                ));
+            }
+        };
+
+        let results: Vec<BatchReviewResult> = kitchen_ids
+            .into_iter()
+            .zip(ids)
+            .map(|(kitchen_id, id)| BatchReviewResult {
+                kitchen_id,
+                status: "created".to_string(),
+                review_id: Some(id),
+                error: None,
+            })
+            .collect();
+        Ok(BatchReviewResponse {
+            success: true,
+            mode: BatchMode::AllOrNothing,
+            created: results.len() as i32,
+            failed: 0,
+            results,
+        })
+    }
+
+    /// Updates a review.
+    ///
+    /// Reviewers may edit their own unverified reviews. Moderators (see
+    /// [`UserRole::can_moderate`]) may edit any review, and verified ones
+    /// too when `force` is set.
+    /// Only the given fields change; ratings are merged one by one over the
+    /// stored ones. When the ratings or comment change, their previous values
+    /// are kept in the review's edit history.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the review doesn't exist, `Forbidden` if the
+    /// caller may not edit it, `BadRequest` if it is verified (without an
+    /// allowed `force`) or a field is invalid, and `Conflict` if it changed
+    /// since `expected_updated_at`, the `updatedAt` of the copy the caller
+    /// edited, or concurrently.
     pub async fn update_review(
         &self,
         review_id: Uuid,
-        user_id: Uuid,
+        claims: &Claims,
+        force: bool,
         req: UpdateReviewRequest,
+        expected_updated_at: Option<DateTime<Utc>>,
     ) -> Result<ReviewDto, AppError> {
-        // First, check if review exists and belongs to the user
         let review = self
             .db
             .review_table
@@ -297,15 +686,12 @@ impl ReviewService {
             .await?
             .ok_or(AppError::NotFound("Review not found".into()))?;
 
-        // Check ownership
-        if review.reviewer_id != user_id {
-            return Err(AppError::Unauthorized(
+        if !can_modify(&review, claims) {
+            return Err(AppError::Forbidden(
                 "You can only update your own reviews".into(),
             ));
         }
-
-        // Check if review is already verified
-        if review.verified.unwrap_or(false) {
+        if review.verified.unwrap_or(false) && !(force && claims.role.can_moderate()) {
             return Err(AppError::BadRequest(
                 "Cannot update a verified review".into(),
             ));
@@ -315,16 +701,14 @@ impl ReviewService {
         let mut updated_review = review.clone();
 
         if let Some(ratings) = req.ratings {
-            updated_review.taste_rating = Decimal::from_f64(ratings.taste).unwrap_or_default();
-            updated_review.hygiene_rating = Decimal::from_f64(ratings.hygiene).unwrap_or_default();
-            updated_review.freshness_rating =
-                Decimal::from_f64(ratings.freshness).unwrap_or_default();
-            updated_review.temperature_rating =
-                Decimal::from_f64(ratings.temperature).unwrap_or_default();
-            updated_review.packaging_rating =
-                Decimal::from_f64(ratings.packaging).unwrap_or_default();
-            updated_review.handling_rating =
-                Decimal::from_f64(ratings.handling).unwrap_or_default();
+            [
+                updated_review.taste_rating,
+                updated_review.hygiene_rating,
+                updated_review.freshness_rating,
+                updated_review.temperature_rating,
+                updated_review.packaging_rating,
+                updated_review.handling_rating,
+            ] = ratings.merge(rating_dto(&review)).to_decimals()?;
         }
 
         if let Some(comment) = req.comment {
@@ -335,10 +719,37 @@ impl ReviewService {
             updated_review.photos = Some(serde_json::json!(photos));
         }
 
-        updated_review.updated_at = Some(chrono::Utc::now().naive_utc());
+        if let Some(delivery_date) = req.delivery_date {
+            updated_review.delivery_date = Some(parse_delivery_date(&delivery_date)?);
+        }
+
+        if let Some(meal_type) = req.meal_type {
+            updated_review.meal_type = Some(parse_meal_type(&meal_type)?);
+        }
+
+        // Keep the prior content for disputes, unless only photos or delivery
+        // details changed
+        let content_changed = updated_review.comment != review.comment
+            || rating_decimals(&updated_review) != rating_decimals(&review);
 
-        // Update in database
-        self.db.review_table.update(&updated_review).await?;
+        // Update in database, failing with 409 if the review changed since we read it
+        let table = &self.db.review_table;
+        self.db
+            .transaction_with_retry(|tx| {
+                Box::pin(async {
+                    if content_changed {
+                        table.record_edit(tx, &review, Some(claims.sub)).await?;
+                    }
+                    table
+                        .update_if_unchanged_in(
+                            tx,
+                            &updated_review,
+                            expected_updated_at.or(review.updated_at),
+                        )
+                        .await
+                })
+            })
+            .await?;
 
         // Fetch back to return DTO
         let saved =
@@ -353,8 +764,77 @@ impl ReviewService {
         self.map_to_dto(saved)
     }
 
-    pub async fn delete_review(&self, review_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
-        // First, check if review exists and belongs to the user
+    /// Lists what a review said before each of its edits, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the review doesn't exist and `Forbidden` if the
+    /// caller is neither its author nor a moderator.
+    pub async fn get_edit_history(
+        &self,
+        review_id: Uuid,
+        claims: &Claims,
+    ) -> Result<ReviewHistoryResponse, AppError> {
+        let review = self
+            .db
+            .review_table
+            .select(&review_id)
+            .await?
+            .ok_or(AppError::NotFound("Review not found".into()))?;
+        if !can_modify(&review, claims) {
+            return Err(AppError::Forbidden(
+                "You can only view the history of your own reviews".into(),
+            ));
+        }
+
+        let edits = self.db.review_table.get_edit_history(&review_id).await?;
+        Ok(ReviewHistoryResponse {
+            review_id,
+            edits: edits
+                .into_iter()
+                .map(|edit| ReviewEditDto {
+                    ratings: ratings_from_decimals([
+                        edit.taste_rating,
+                        edit.hygiene_rating,
+                        edit.freshness_rating,
+                        edit.temperature_rating,
+                        edit.packaging_rating,
+                        edit.handling_rating,
+                    ]),
+                    comment: edit.comment,
+                    edited_by: edit.edited_by,
+                    edited_at: edit.edited_at.map(utc_to_rfc3339),
+                })
+                .collect(),
+        })
+    }
+
+    /// Uploads photos for a review and appends their URLs to its photo list.
+    ///
+    /// `files` holds the original file name and contents of each photo. Only
+    /// JPEG, PNG, GIF and WebP images are accepted; the type is detected from
+    /// the contents, not the client-supplied name or MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the review does not exist, `Unauthorized` if it
+    /// belongs to another user, `BadRequest` if it is verified, a file is not
+    /// an image or the review would exceed its photo limit, and `Conflict` if
+    /// the review changed since `expected_updated_at` or during the upload.
+    pub async fn upload_photos(
+        &self,
+        review_id: Uuid,
+        user_id: Uuid,
+        files: Vec<(String, Vec<u8>)>,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> Result<ReviewPhotosResponse, AppError> {
+        let storage = self
+            .photo_storage
+            .as_ref()
+            .ok_or(AppError::InternalServerError(
+                "Photo storage is not configured".into(),
+            ))?;
+
         let review = self
             .db
             .review_table
@@ -362,15 +842,120 @@ impl ReviewService {
             .await?
             .ok_or(AppError::NotFound("Review not found".into()))?;
 
-        // Check ownership
         if review.reviewer_id != user_id {
             return Err(AppError::Unauthorized(
-                "You can only delete your own reviews".into(),
+                "You can only add photos to your own reviews".into(),
             ));
         }
 
-        // Check if review is already verified
         if review.verified.unwrap_or(false) {
+            return Err(AppError::BadRequest(
+                "Cannot add photos to a verified review".into(),
+            ));
+        }
+
+        if files.is_empty() {
+            return Err(AppError::BadRequest("No photos uploaded".into()));
+        }
+
+        let mut photos: Vec<String> = review
+            .photos
+            .clone()
+            .and_then(|p| serde_json::from_value(p).ok())
+            .unwrap_or_default();
+        let max = self.photo_config.max_per_review;
+        if photos.len() + files.len() > max {
+            return Err(AppError::BadRequest(format!(
+                "A review can have at most {} photos; it already has {}",
+                max,
+                photos.len()
+            )));
+        }
+
+        let mut typed_files = Vec::with_capacity(files.len());
+        for (file_name, data) in files {
+            let mime_type = detect_image_type(&data).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "File '{}' is not a JPEG, PNG, GIF or WebP image",
+                    file_name
+                ))
+            })?;
+            typed_files.push((file_name, data, mime_type));
+        }
+
+        let mut uploaded = Vec::with_capacity(typed_files.len());
+        for (file_name, data, mime_type) in typed_files {
+            match storage.upload(data, file_name, mime_type.to_string()).await {
+                Ok(result) => uploaded.push(result),
+                Err(e) => {
+                    self.discard_uploads(storage, &uploaded).await;
+                    return Err(AppError::InternalServerError(e.to_string()));
+                }
+            }
+        }
+        photos.extend(uploaded.iter().map(|u| u.url.clone()));
+
+        let mut updated_review = review.clone();
+        updated_review.photos = Some(json!(photos));
+        let updated_at = match self
+            .db
+            .review_table
+            .update_if_unchanged(&updated_review, expected_updated_at.or(review.updated_at))
+            .await
+        {
+            Ok(updated_at) => updated_at,
+            Err(e) => {
+                self.discard_uploads(storage, &uploaded).await;
+                return Err(e.into());
+            }
+        };
+
+        Ok(ReviewPhotosResponse {
+            id: review_id,
+            photos,
+            updated_at: utc_to_rfc3339(updated_at),
+        })
+    }
+
+    /// Deletes files that were uploaded for a request that then failed.
+    async fn discard_uploads(&self, storage: &StorageService, uploaded: &[UploadResult]) {
+        // Deduplicated files belong to earlier uploads too
+        for upload in uploaded.iter().filter(|u| !u.deduplicated) {
+            if let Err(e) = storage.delete(&upload.file_id).await {
+                warn!(
+                    "Failed to delete orphaned review photo {}: {}",
+                    upload.file_id, e
+                );
+            }
+        }
+    }
+
+    /// Deletes a review, with the same permissions as
+    /// [`update_review`](Self::update_review).
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the review doesn't exist and `Forbidden` if the
+    /// caller may not delete it or it is verified (without an allowed `force`).
+    pub async fn delete_review(
+        &self,
+        review_id: Uuid,
+        claims: &Claims,
+        force: bool,
+    ) -> Result<(), AppError> {
+        let review = self
+            .db
+            .review_table
+            .select(&review_id)
+            .await?
+            .ok_or(AppError::NotFound("Review not found".into()))?;
+
+        if !can_modify(&review, claims) {
+            return Err(AppError::Forbidden(
+                "You can only delete your own reviews".into(),
+            ));
+        }
+        if review.verified.unwrap_or(false) && !(force && claims.role.can_moderate()) {
             return Err(AppError::Forbidden(
                 "Cannot delete a verified review".into(),
             ));
@@ -387,46 +972,316 @@ impl ReviewService {
     }
 
     fn map_to_dto_sync(&self, r: Review) -> ReviewDto {
-        use rust_decimal::prelude::ToPrimitive;
-
+        let ratings = rating_dto(&r);
         let photos: Vec<String> = if let Some(p) = r.photos {
             serde_json::from_value(p).unwrap_or_default()
         } else {
             vec![]
         };
 
-        let avg = (r.taste_rating
-            + r.hygiene_rating
-            + r.freshness_rating
-            + r.temperature_rating
-            + r.packaging_rating
-            + r.handling_rating)
-            / Decimal::from(6);
+        let avg = weighted_average(
+            [
+                r.taste_rating,
+                r.hygiene_rating,
+                r.freshness_rating,
+                r.temperature_rating,
+                r.packaging_rating,
+                r.handling_rating,
+            ],
+            &self.rating_weights,
+        );
 
         ReviewDto {
             id: r.id,
             kitchen_id: r.kitchen_id,
             reviewer_name: r.reviewer_name,
             reviewer_type: r.reviewer_type,
-            ratings: HaccpRatingDto {
-                taste: r.taste_rating.to_f64().unwrap_or_default(),
-                hygiene: r.hygiene_rating.to_f64().unwrap_or_default(),
-                freshness: r.freshness_rating.to_f64().unwrap_or_default(),
-                temperature: r.temperature_rating.to_f64().unwrap_or_default(),
-                packaging: r.packaging_rating.to_f64().unwrap_or_default(),
-                handling: r.handling_rating.to_f64().unwrap_or_default(),
-            },
+            ratings,
             average_rating: avg.to_f64().unwrap_or_default(),
             comment: r.comment,
             photos,
-            delivery_date: None, // Not in DB model yet
-            meal_type: None,     // Not in DB model yet
+            delivery_date: r.delivery_date.map(|d| d.to_string()),
+            meal_type: r.meal_type,
             verified: r.verified.unwrap_or(false),
             verification_status: r.verification_status.unwrap_or("unverified".to_string()),
             report_source: r.report_source,
             confidence_level: r.confidence_level,
-            created_at: r.created_at.unwrap_or_default().to_string(),
-            updated_at: r.updated_at.unwrap_or_default().to_string(),
+            created_at: utc_to_rfc3339(r.created_at.unwrap_or_default()),
+            updated_at: utc_to_rfc3339(r.updated_at.unwrap_or_default()),
+        }
+    }
+}
+
+/// Averages HACCP ratings using the configured weights.
+///
+/// `ratings` are in the order taste, hygiene, freshness, temperature,
+/// packaging, handling. The weighted sum is divided by the weight total, so
+/// equal weights give exactly the plain mean.
+pub fn weighted_average(ratings: [Decimal; 6], weights: &HaccpWeights) -> Decimal {
+    let weights = weights
+        .as_array()
+        .map(|w| Decimal::from_f64(w).unwrap_or_default());
+    let total: Decimal = weights.iter().sum();
+    if total.is_zero() {
+        return Decimal::ZERO;
+    }
+
+    let sum: Decimal = ratings.iter().zip(weights).map(|(r, w)| r * w).sum();
+    sum / total
+}
+
+/// Error message for a review submitted within the cooldown of `existing_id`.
+fn cooldown_message(days: u32, existing_id: Uuid) -> String {
+    format!(
+        "You already reviewed this kitchen in the last {} days; edit review {} instead",
+        days, existing_id
+    )
+}
+
+/// Response for an `all_or_nothing` batch that saved nothing.
+///
+/// `errors` holds the reason for each review, or `None` for reviews that were
+/// fine but rolled back with the rest.
+fn rolled_back(kitchen_ids: &[Uuid], errors: Vec<Option<String>>) -> BatchReviewResponse {
+    let results: Vec<BatchReviewResult> = kitchen_ids
+        .iter()
+        .zip(errors)
+        .map(|(kitchen_id, error)| BatchReviewResult {
+            kitchen_id: *kitchen_id,
+            status: "failed".to_string(),
+            review_id: None,
+            error: Some(error.unwrap_or_else(|| "Batch rolled back".to_string())),
+        })
+        .collect();
+    BatchReviewResponse {
+        success: false,
+        mode: BatchMode::AllOrNothing,
+        created: 0,
+        failed: results.len() as i32,
+        results,
+    }
+}
+
+/// A review's stored ratings.
+fn rating_dto(review: &Review) -> HaccpRatingDto {
+    ratings_from_decimals(rating_decimals(review))
+}
+
+/// A review's ratings in the order taste, hygiene, freshness, temperature,
+/// packaging, handling.
+fn rating_decimals(review: &Review) -> [Decimal; 6] {
+    [
+        review.taste_rating,
+        review.hygiene_rating,
+        review.freshness_rating,
+        review.temperature_rating,
+        review.packaging_rating,
+        review.handling_rating,
+    ]
+}
+
+fn ratings_from_decimals(ratings: [Decimal; 6]) -> HaccpRatingDto {
+    let [taste, hygiene, freshness, temperature, packaging, handling] =
+        ratings.map(|r| r.to_f64().unwrap_or_default());
+    HaccpRatingDto {
+        taste,
+        hygiene,
+        freshness,
+        temperature,
+        packaging,
+        handling,
+    }
+}
+
+/// Whether the caller wrote `review` or may moderate reviews.
+fn can_modify(review: &Review, claims: &Claims) -> bool {
+    review.reviewer_id == claims.sub || claims.role.can_moderate()
+}
+
+/// Builds an unverified public review from a submission.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if a rating is out of range or
+/// `deliveryDate` or `mealType` is invalid.
+fn new_review(reviewer_id: Uuid, req: &CreateReviewRequest) -> Result<Review, AppError> {
+    let [taste, hygiene, freshness, temperature, packaging, handling] =
+        req.ratings.to_decimals()?;
+    Ok(Review {
+        id: Uuid::new_v4(),
+        kitchen_id: req.kitchen_id,
+        reviewer_id,
+        reviewer_name: req.reviewer_name.clone(),
+        reviewer_type: req.reviewer_type,
+        taste_rating: taste,
+        hygiene_rating: hygiene,
+        freshness_rating: freshness,
+        temperature_rating: temperature,
+        packaging_rating: packaging,
+        handling_rating: handling,
+        comment: req.comment.clone(),
+        photos: req.photos.clone().map(|p| json!(p)),
+        verification_status: Some("unverified".to_string()),
+        report_source: ReportSource::Public,
+        confidence_level: ConfidenceLevel::Medium,
+        verified: Some(false),
+        is_draft: Some(false),
+        delivery_date: req
+            .delivery_date
+            .as_deref()
+            .map(parse_delivery_date)
+            .transpose()?,
+        meal_type: req.meal_type.as_deref().map(parse_meal_type).transpose()?,
+        created_at: Some(chrono::Utc::now()),
+        updated_at: Some(chrono::Utc::now()),
+        ..Default::default()
+    })
+}
+
+/// Meal types a review can be about.
+const MEAL_TYPES: [&str; 4] = ["breakfast", "lunch", "dinner", "snack"];
+
+/// Parses a delivery date given as `YYYY-MM-DD` or an RFC 3339 timestamp, whose
+/// time part is dropped.
+fn parse_delivery_date(value: &str) -> Result<NaiveDate, AppError> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|dt| dt.date_naive())
+        })
+        .ok_or_else(|| {
+            AppError::BadRequest(
+                "deliveryDate must be a YYYY-MM-DD date or ISO 8601 timestamp".into(),
+            )
+        })
+}
+
+fn parse_meal_type(value: &str) -> Result<String, AppError> {
+    let value = value.trim().to_lowercase();
+    if MEAL_TYPES.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err(AppError::BadRequest(format!(
+            "mealType must be one of: {}",
+            MEAL_TYPES.join(", ")
+        )))
+    }
+}
+
+/// Detects the MIME type of an image from its leading bytes, returning `None`
+/// for anything other than JPEG, PNG, GIF or WebP.
+pub(crate) fn detect_image_type(data: &[u8]) -> Option<&'static str> {
+    match data {
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some("image/png"),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some("image/gif"),
+        [
+            b'R',
+            b'I',
+            b'F',
+            b'F',
+            _,
+            _,
+            _,
+            _,
+            b'W',
+            b'E',
+            b'B',
+            b'P',
+            ..,
+        ] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Decodes a client-supplied cursor; an empty string means "first page".
+fn decode_cursor(cursor: &str) -> Result<Option<Cursor>, AppError> {
+    if cursor.trim().is_empty() {
+        return Ok(None);
+    }
+    Cursor::decode(cursor)
+        .map(Some)
+        .ok_or_else(|| AppError::BadRequest("Invalid cursor".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratings() -> [Decimal; 6] {
+        [5, 3, 5, 3, 4, 4].map(Decimal::from)
+    }
+
+    #[test]
+    fn test_weighted_average_equal_weights_is_mean() {
+        let ratings = [
+            Decimal::new(45, 1),
+            Decimal::new(43, 1),
+            Decimal::new(38, 1),
+            Decimal::new(50, 1),
+            Decimal::new(41, 1),
+            Decimal::new(47, 1),
+        ];
+        let mean = ratings.iter().sum::<Decimal>() / Decimal::from(6);
+        assert_eq!(weighted_average(ratings, &HaccpWeights::default()), mean);
+    }
+
+    #[test]
+    fn test_weighted_average_favors_heavier_ratings() {
+        let weights = HaccpWeights {
+            taste: 0.1,
+            hygiene: 0.3,
+            freshness: 0.1,
+            temperature: 0.3,
+            packaging: 0.1,
+            handling: 0.1,
+        };
+        assert_eq!(weighted_average(ratings(), &weights), Decimal::new(36, 1));
+    }
+
+    #[test]
+    fn test_ratings_must_be_in_range() {
+        let ratings = |taste| HaccpRatingDto {
+            taste,
+            hygiene: 4.0,
+            freshness: 4.0,
+            temperature: 4.0,
+            packaging: 4.0,
+            handling: 4.0,
+        };
+
+        for valid in [MIN_RATING, 2.5, MAX_RATING] {
+            let decimals = ratings(valid).to_decimals().unwrap();
+            assert_eq!(decimals[0], Decimal::from_f64(valid).unwrap());
         }
+        for invalid in [0.0, 0.49, 5.01, 99.0, -3.0, f64::NAN] {
+            assert!(matches!(
+                ratings(invalid).to_decimals(),
+                Err(AppError::BadRequest(msg)) if msg.starts_with("ratings.taste ")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_detect_image_type() {
+        assert_eq!(
+            detect_image_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00]),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            detect_image_type(b"\x89PNG\r\n\x1a\n\0\0"),
+            Some("image/png")
+        );
+        assert_eq!(detect_image_type(b"GIF89a..."), Some("image/gif"));
+        assert_eq!(
+            detect_image_type(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(detect_image_type(b"%PDF-1.7"), None);
+        assert_eq!(detect_image_type(b"RIFF\0\0\0\0WAVE"), None);
+        assert_eq!(detect_image_type(&[]), None);
     }
 }
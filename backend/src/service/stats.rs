@@ -5,8 +5,14 @@ use std::sync::Arc;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::config::HaccpWeights;
+use crate::config::LeaderboardConfig;
 use crate::database::Database;
+use crate::database::table::TopKitchen;
 use crate::error::AppError;
+use crate::service::datetime::now_rfc3339;
+use crate::service::datetime::utc_to_rfc3339;
+use crate::service::pagination::Pagination;
 
 #[derive(Debug, Serialize)]
 pub struct NationalStatsDto {
@@ -43,6 +49,38 @@ pub struct NationalStatsDto {
     pub last_updated: String,
 }
 
+/// Monthly national statistics returned by
+/// [`StatsService::get_national_timeseries`].
+#[derive(Debug, Serialize)]
+pub struct NationalTimeseriesDto {
+    pub region: RegionDto,
+    /// One entry per month, oldest first, including months without activity.
+    pub data: Vec<NationalMonthDto>,
+    #[serde(rename = "lastUpdated")]
+    pub last_updated: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NationalMonthDto {
+    /// `YYYY-MM`, in Asia/Jakarta.
+    pub month: String,
+    #[serde(rename = "totalReviews")]
+    pub total_reviews: i32,
+    #[serde(rename = "verifiedReviews")]
+    pub verified_reviews: i32,
+    /// `null` for months without reviews.
+    #[serde(rename = "averageRating")]
+    pub average_rating: Option<f64>,
+    #[serde(rename = "totalIncidents")]
+    pub total_incidents: i32,
+    #[serde(rename = "criticalIncidents")]
+    pub critical_incidents: i32,
+    #[serde(rename = "totalVictims")]
+    pub total_victims: i32,
+    #[serde(rename = "totalDeaths")]
+    pub total_deaths: i32,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PeriodDto {
     pub year: i32,
@@ -100,6 +138,50 @@ pub struct TopKitchenDto {
     pub rating: f64,
     #[serde(rename = "complianceScore")]
     pub compliance_score: f64,
+    #[serde(rename = "reviewCount")]
+    pub review_count: i32,
+    /// Bayesian average the kitchen is ranked by.
+    #[serde(rename = "weightedScore")]
+    pub weighted_score: f64,
+}
+
+impl From<TopKitchen> for TopKitchenDto {
+    fn from(k: TopKitchen) -> Self {
+        Self {
+            id: k.id,
+            name: k.name,
+            rating: k.rating.and_then(|d| d.try_into().ok()).unwrap_or(0.0),
+            compliance_score: k
+                .compliance_score
+                .and_then(|d| d.try_into().ok())
+                .unwrap_or(0.0),
+            review_count: k.review_count as i32,
+            weighted_score: k
+                .weighted_score
+                .and_then(|d| d.try_into().ok())
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaderboardDto {
+    pub region: RegionDto,
+    /// `m` in the Bayesian average the kitchens are ranked by.
+    #[serde(rename = "minReviews")]
+    pub min_reviews: u32,
+    pub kitchens: Vec<LeaderboardEntryDto>,
+    pub pagination: Pagination,
+    #[serde(rename = "lastUpdated")]
+    pub last_updated: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaderboardEntryDto {
+    /// 1-based position on the whole leaderboard, not just this page.
+    pub rank: i64,
+    #[serde(flatten)]
+    pub kitchen: TopKitchenDto,
 }
 
 #[derive(Debug, Serialize)]
@@ -176,15 +258,36 @@ pub struct IncidentSummaryDto {
     pub trend: String,
 }
 
+/// Most months [`StatsService::get_national_timeseries`] covers.
+pub const MAX_TIMESERIES_MONTHS: i32 = 60;
+
 /// Service for generating national and regional statistics.
 pub struct StatsService {
     db: Arc<Database>,
+    rating_weights: HaccpWeights,
+    leaderboard: LeaderboardConfig,
 }
 
 impl StatsService {
-    /// Creates a new `StatsService`.
+    /// Creates a new `StatsService` that weighs all HACCP ratings equally.
     pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+        Self::with_rating_weights(db, HaccpWeights::default())
+    }
+
+    /// Creates a new `StatsService` averaging HACCP ratings with `rating_weights`.
+    pub fn with_rating_weights(db: Arc<Database>, rating_weights: HaccpWeights) -> Self {
+        Self {
+            db,
+            rating_weights,
+            leaderboard: LeaderboardConfig::default(),
+        }
+    }
+
+    /// Ranks the kitchen leaderboard with `leaderboard` instead of the
+    /// defaults.
+    pub fn with_leaderboard(mut self, leaderboard: LeaderboardConfig) -> Self {
+        self.leaderboard = leaderboard;
+        self
     }
 
     /// Retrieves national-level statistics.
@@ -193,8 +296,16 @@ impl StatsService {
         year: Option<i32>,
         month: Option<i32>,
     ) -> Result<NationalStatsDto, AppError> {
-        let stats = self.db.stats_queries.get_national_stats().await?;
-        let province_stats = self.db.stats_queries.get_province_stats().await?;
+        let stats = self
+            .db
+            .stats_queries
+            .get_national_stats(&self.rating_weights)
+            .await?;
+        let province_stats = self
+            .db
+            .stats_queries
+            .get_province_stats(&self.rating_weights)
+            .await?;
 
         Ok(NationalStatsDto {
             period: PeriodDto {
@@ -229,7 +340,58 @@ impl StatsService {
                     incidents: p.incidents as i32,
                 })
                 .collect(),
-            last_updated: chrono::Utc::now().to_rfc3339(),
+            last_updated: now_rfc3339(),
+        })
+    }
+
+    /// Retrieves national statistics for each of the last `months` calendar
+    /// months (default 12), optionally limited to one province.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if `months` is not between 1 and
+    /// [`MAX_TIMESERIES_MONTHS`].
+    pub async fn get_national_timeseries(
+        &self,
+        months: Option<i32>,
+        province: Option<String>,
+    ) -> Result<NationalTimeseriesDto, AppError> {
+        let months = months.unwrap_or(12);
+        if !(1..=MAX_TIMESERIES_MONTHS).contains(&months) {
+            return Err(AppError::BadRequest(format!(
+                "months must be between 1 and {}",
+                MAX_TIMESERIES_MONTHS
+            )));
+        }
+        let province = province
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+
+        let stats = self
+            .db
+            .stats_queries
+            .get_national_timeseries(months, province.as_deref(), &self.rating_weights)
+            .await?;
+
+        Ok(NationalTimeseriesDto {
+            region: RegionDto {
+                province: province.unwrap_or("National".to_string()),
+                kabupaten: None,
+            },
+            data: stats
+                .into_iter()
+                .map(|m| NationalMonthDto {
+                    month: m.month,
+                    total_reviews: m.total_reviews as i32,
+                    verified_reviews: m.verified_reviews as i32,
+                    average_rating: m.average_rating.and_then(|d| d.try_into().ok()),
+                    total_incidents: m.total_incidents as i32,
+                    critical_incidents: m.critical_incidents as i32,
+                    total_victims: m.total_victims as i32,
+                    total_deaths: m.total_deaths as i32,
+                })
+                .collect(),
+            last_updated: now_rfc3339(),
         })
     }
 
@@ -243,13 +405,24 @@ impl StatsService {
         let stats = self
             .db
             .stats_queries
-            .get_regional_stats(province.as_deref(), kabupaten.as_deref())
+            .get_regional_stats(
+                province.as_deref(),
+                kabupaten.as_deref(),
+                &self.rating_weights,
+            )
             .await?;
 
         let top_kitchens = self
             .db
             .stats_queries
-            .get_top_kitchens(province.as_deref(), kabupaten.as_deref(), 5)
+            .get_top_kitchens(
+                province.as_deref(),
+                kabupaten.as_deref(),
+                5,
+                0,
+                &self.rating_weights,
+                &self.leaderboard,
+            )
             .await?;
 
         Ok(RegionalStatsDto {
@@ -276,19 +449,58 @@ impl StatsService {
             total_incidents: stats.total_incidents as i32,
             resolved_incidents: stats.resolved_incidents as i32,
             active_incidents: stats.active_incidents as i32,
-            top_performing_kitchens: top_kitchens
+            top_performing_kitchens: top_kitchens.into_iter().map(TopKitchenDto::from).collect(),
+            last_updated: now_rfc3339(),
+        })
+    }
+
+    /// Retrieves a page of the kitchen leaderboard, optionally limited to a
+    /// province or kabupaten.
+    ///
+    /// Kitchens are ranked by a Bayesian average of their reviews, so a few
+    /// perfect reviews don't outrank many good ones; see
+    /// [`LeaderboardConfig`].
+    pub async fn get_leaderboard(
+        &self,
+        province: Option<String>,
+        kabupaten: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<LeaderboardDto, AppError> {
+        let kitchens = self
+            .db
+            .stats_queries
+            .get_top_kitchens(
+                province.as_deref(),
+                kabupaten.as_deref(),
+                limit,
+                offset,
+                &self.rating_weights,
+                &self.leaderboard,
+            )
+            .await?;
+        let total = self
+            .db
+            .stats_queries
+            .count_ranked_kitchens(province.as_deref(), kabupaten.as_deref())
+            .await?;
+
+        Ok(LeaderboardDto {
+            region: RegionDto {
+                province: province.unwrap_or("National".to_string()),
+                kabupaten,
+            },
+            min_reviews: self.leaderboard.min_reviews,
+            kitchens: kitchens
                 .into_iter()
-                .map(|k| TopKitchenDto {
-                    id: k.id,
-                    name: k.name,
-                    rating: k.rating.and_then(|d| d.try_into().ok()).unwrap_or(0.0),
-                    compliance_score: k
-                        .compliance_score
-                        .and_then(|d| d.try_into().ok())
-                        .unwrap_or(0.0),
+                .zip(offset + 1..)
+                .map(|(k, rank)| LeaderboardEntryDto {
+                    rank,
+                    kitchen: k.into(),
                 })
                 .collect(),
-            last_updated: chrono::Utc::now().to_rfc3339(),
+            pagination: Pagination::new(total, limit, offset),
+            last_updated: now_rfc3339(),
         })
     }
 
@@ -308,6 +520,7 @@ impl StatsService {
                 kabupaten.as_deref(),
                 kitchen_id,
                 months,
+                &self.rating_weights,
             )
             .await?;
 
@@ -364,11 +577,12 @@ impl StatsService {
                 kabupaten,
             },
             period: DateRangeDto {
-                from: chrono::Utc::now()
-                    .checked_sub_months(chrono::Months::new(months as u32))
-                    .unwrap_or_else(chrono::Utc::now)
-                    .to_rfc3339(),
-                to: chrono::Utc::now().to_rfc3339(),
+                from: utc_to_rfc3339(
+                    chrono::Utc::now()
+                        .checked_sub_months(chrono::Months::new(months as u32))
+                        .unwrap_or_else(chrono::Utc::now),
+                ),
+                to: now_rfc3339(),
             },
             data: trends
                 .into_iter()
@@ -392,7 +606,7 @@ impl StatsService {
                 total_incidents,
                 total_reviews,
             },
-            last_updated: chrono::Utc::now().to_rfc3339(),
+            last_updated: now_rfc3339(),
         })
     }
 
@@ -444,11 +658,12 @@ impl StatsService {
 
         Ok(IncidentTrendsDto {
             period: DateRangeDto {
-                from: chrono::Utc::now()
-                    .checked_sub_months(chrono::Months::new(months as u32))
-                    .unwrap_or_else(chrono::Utc::now)
-                    .to_rfc3339(),
-                to: chrono::Utc::now().to_rfc3339(),
+                from: utc_to_rfc3339(
+                    chrono::Utc::now()
+                        .checked_sub_months(chrono::Months::new(months as u32))
+                        .unwrap_or_else(chrono::Utc::now),
+                ),
+                to: now_rfc3339(),
             },
             group_by: group_by.unwrap_or("month".to_string()),
             data: trends
@@ -468,7 +683,7 @@ impl StatsService {
                 most_common_cause,
                 trend: trend.to_string(),
             },
-            last_updated: chrono::Utc::now().to_rfc3339(),
+            last_updated: now_rfc3339(),
         })
     }
 }
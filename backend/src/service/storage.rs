@@ -8,16 +8,28 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use bytes::Bytes;
+use chrono::DateTime;
 use chrono::Utc;
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
 use futures::StreamExt;
+use futures::stream::BoxStream;
+use log::warn;
 use object_store::ObjectStore;
 use object_store::PutPayload;
 use object_store::aws::AmazonS3Builder;
 use object_store::path::Path as ObjectPath;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 
 use crate::config::StorageConfig;
+use crate::database::Database;
+use crate::database::model::FileHash;
+use crate::database::table::Table;
 use crate::error::AppError;
+use crate::service::datetime::utc_to_rfc3339;
 
 /// Errors that can occur during storage operations.
 #[derive(Debug, thiserror::Error)]
@@ -96,10 +108,17 @@ impl std::str::FromStr for StorageType {
     }
 }
 
+/// Default `STORAGE_PARTITION_FORMAT`: one directory per upload day.
+pub const DEFAULT_PARTITION_FORMAT: &str = "%Y/%m/%d";
+
+/// Source of the current time, so tests can move the clock.
+pub type Clock = Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>;
+
 /// Result of a successful file upload.
 #[derive(Debug, Clone)]
 pub struct UploadResult {
-    /// The unique identifier for the uploaded file.
+    /// The unique identifier for the uploaded file: its path relative to the
+    /// storage root, e.g. `2025/01/30/<uuid>`.
     pub file_id: String,
     /// The public URL where the file can be accessed.
     pub url: String,
@@ -109,6 +128,94 @@ pub struct UploadResult {
     pub mime_type: String,
     /// The timestamp when the file was uploaded.
     pub uploaded_at: String,
+    /// Lowercase hex SHA-256 of the file contents.
+    pub sha256: String,
+    /// Whether an existing file with the same contents was returned instead
+    /// of storing a new one. Such files are shared, so callers must not
+    /// delete them when discarding their own upload.
+    pub deduplicated: bool,
+}
+
+/// Metadata of a stored file, as returned by [`StorageBackend::head`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectMeta {
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// An opaque tag that changes whenever the file's contents do, without
+    /// surrounding quotes.
+    pub etag: String,
+    /// When the file was last written.
+    pub last_modified: DateTime<Utc>,
+}
+
+/// File contents yielded chunk by chunk, so large files are never held in
+/// memory whole.
+pub type ByteStream = BoxStream<'static, Result<Bytes, StorageError>>;
+
+/// Returns the lowercase hex SHA-256 of `data`.
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Generates the ID of a new file: a fresh UUID inside the directory that
+/// `partition_format` (a `strftime` format, empty for none) gives for `now`.
+///
+/// The ID is the file's full path relative to the storage root, so the file
+/// can be found again no matter when it is requested.
+pub fn new_file_id(partition_format: &str, now: DateTime<Utc>) -> String {
+    let id = Uuid::new_v4();
+    if partition_format.is_empty() {
+        id.to_string()
+    } else {
+        format!("{}/{}", now.format(partition_format), id)
+    }
+}
+
+/// Checks that `file_id` has the shape [`new_file_id`] produces: directory
+/// segments of ASCII letters, digits, `-` and `_`, followed by a lowercase
+/// hyphenated UUID. This keeps client-supplied IDs inside the storage root.
+///
+/// # Errors
+///
+/// Returns [`StorageError::InvalidPath`] otherwise.
+pub fn validate_file_id(file_id: &str) -> Result<(), StorageError> {
+    let (dirs, name) = match file_id.rsplit_once('/') {
+        Some((dirs, name)) => (Some(dirs), name),
+        None => (None, file_id),
+    };
+    let valid_name = Uuid::try_parse(name).is_ok_and(|id| id.hyphenated().to_string() == name);
+    let valid_dirs = dirs.is_none_or(|dirs| {
+        dirs.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+    });
+
+    if valid_name && valid_dirs {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidPath(file_id.to_string()))
+    }
+}
+
+/// Checks that `format` is a usable partition format: valid `strftime` that
+/// renders to directory names accepted by [`validate_file_id`].
+pub fn validate_partition_format(format: &str) -> Result<(), String> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("\"{}\" is not a valid strftime format", format));
+    }
+    let sample = new_file_id(format, Utc::now());
+    validate_file_id(&sample).map_err(|_| {
+        format!(
+            "\"{}\" must render to directory names of letters, digits, '-' and '_' separated by '/'",
+            format
+        )
+    })
 }
 
 /// Trait defining the interface for storage backends.
@@ -147,6 +254,31 @@ pub trait StorageBackend: Send + Sync {
     /// Returns the raw bytes of the file.
     async fn download(&self, file_id: &str) -> Result<Vec<u8>, StorageError>;
 
+    /// Streams a file from the storage backend.
+    ///
+    /// Prefer this over [`StorageBackend::download`] for files that may be
+    /// large, such as videos or PDFs.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The unique identifier of the file to download.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the file's bytes.
+    async fn download_stream(&self, file_id: &str) -> Result<ByteStream, StorageError>;
+
+    /// Reads a file's metadata without downloading it.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The unique identifier of the file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the file's [`ObjectMeta`].
+    async fn head(&self, file_id: &str) -> Result<ObjectMeta, StorageError>;
+
     /// Deletes a file from the storage backend.
     ///
     /// # Arguments
@@ -177,10 +309,13 @@ pub trait StorageBackend: Send + Sync {
 pub struct LocalStorage {
     base_path: PathBuf,
     base_url: String,
+    partition_format: String,
+    clock: Clock,
 }
 
 impl LocalStorage {
-    /// Creates a new local storage backend.
+    /// Creates a new local storage backend that partitions files by upload
+    /// day.
     ///
     /// # Arguments
     ///
@@ -191,30 +326,26 @@ impl LocalStorage {
         Ok(Self {
             base_path,
             base_url,
+            partition_format: DEFAULT_PARTITION_FORMAT.to_string(),
+            clock: Arc::new(Utc::now),
         })
     }
 
-    fn get_file_path(&self, file_id: &str) -> PathBuf {
-        // Organize files by date: YYYY/MM/DD/file_id
-        let now = Utc::now();
-        let date_path = format!(
-            "{}/{}/{}/",
-            now.format("%Y"),
-            now.format("%m"),
-            now.format("%d")
-        );
-        self.base_path.join(&date_path).join(file_id)
+    /// Uses `format` (see [`new_file_id`]) to partition new uploads.
+    pub fn with_partition_format(mut self, format: String) -> Self {
+        self.partition_format = format;
+        self
     }
 
-    fn get_relative_path(&self, file_id: &str) -> String {
-        let now = Utc::now();
-        format!(
-            "{}/{}/{}/{}",
-            now.format("%Y"),
-            now.format("%m"),
-            now.format("%d"),
-            file_id
-        )
+    /// Reads the upload time from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn get_file_path(&self, file_id: &str) -> Result<PathBuf, StorageError> {
+        validate_file_id(file_id)?;
+        Ok(self.base_path.join(file_id))
     }
 }
 
@@ -226,8 +357,9 @@ impl StorageBackend for LocalStorage {
         _file_name: String,
         mime_type: String,
     ) -> Result<UploadResult, StorageError> {
-        let file_id = Uuid::new_v4().to_string();
-        let file_path = self.get_file_path(&file_id);
+        let now = (self.clock)();
+        let file_id = new_file_id(&self.partition_format, now);
+        let file_path = self.get_file_path(&file_id)?;
 
         // Create parent directories if they don't exist
         if let Some(parent) = file_path.parent() {
@@ -237,20 +369,21 @@ impl StorageBackend for LocalStorage {
         // Write file
         tokio::fs::write(&file_path, &data).await?;
 
-        let relative_path = self.get_relative_path(&file_id);
-        let url = format!("{}/{}", self.base_url, relative_path);
+        let url = self.get_url(&file_id);
 
         Ok(UploadResult {
             file_id,
             url,
             size: data.len(),
             mime_type,
-            uploaded_at: Utc::now().to_rfc3339(),
+            uploaded_at: utc_to_rfc3339(now),
+            sha256: content_hash(&data),
+            deduplicated: false,
         })
     }
 
     async fn download(&self, file_id: &str) -> Result<Vec<u8>, StorageError> {
-        let file_path = self.get_file_path(file_id);
+        let file_path = self.get_file_path(file_id)?;
 
         match tokio::fs::read(&file_path).await {
             Ok(data) => Ok(data),
@@ -261,8 +394,48 @@ impl StorageBackend for LocalStorage {
         }
     }
 
+    async fn download_stream(&self, file_id: &str) -> Result<ByteStream, StorageError> {
+        let file_path = self.get_file_path(file_id)?;
+
+        match tokio::fs::File::open(&file_path).await {
+            Ok(file) => Ok(ReaderStream::new(file)
+                .map(|r| r.map_err(StorageError::from))
+                .boxed()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(file_id.to_string()))
+            }
+            Err(e) => Err(StorageError::from(e)),
+        }
+    }
+
+    async fn head(&self, file_id: &str) -> Result<ObjectMeta, StorageError> {
+        let file_path = self.get_file_path(file_id)?;
+
+        let metadata = match tokio::fs::metadata(&file_path).await {
+            Ok(metadata) if metadata.is_file() => metadata,
+            Ok(_) => return Err(StorageError::NotFound(file_id.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(file_id.to_string()));
+            }
+            Err(e) => return Err(StorageError::from(e)),
+        };
+        let last_modified = DateTime::<Utc>::from(metadata.modified()?);
+
+        // Cheap stand-in for a content hash: files are only ever written
+        // once, so size and write time identify their contents
+        Ok(ObjectMeta {
+            size: metadata.len(),
+            etag: format!(
+                "{:x}-{:x}",
+                last_modified.timestamp_nanos_opt().unwrap_or_default(),
+                metadata.len()
+            ),
+            last_modified,
+        })
+    }
+
     async fn delete(&self, file_id: &str) -> Result<(), StorageError> {
-        let file_path = self.get_file_path(file_id);
+        let file_path = self.get_file_path(file_id)?;
 
         match tokio::fs::remove_file(&file_path).await {
             Ok(_) => Ok(()),
@@ -274,8 +447,7 @@ impl StorageBackend for LocalStorage {
     }
 
     fn get_url(&self, file_id: &str) -> String {
-        let relative_path = self.get_relative_path(file_id);
-        format!("{}/{}", self.base_url, relative_path)
+        format!("{}/{}", self.base_url, file_id)
     }
 
     async fn health_check(&self) -> bool {
@@ -293,6 +465,8 @@ pub struct S3Storage {
     /// The AWS region (stored for reference).
     #[allow(dead_code)]
     region: String,
+    partition_format: String,
+    clock: Clock,
 }
 
 impl S3Storage {
@@ -345,20 +519,23 @@ impl S3Storage {
             bucket: bucket.clone(),
             base_url,
             region,
+            partition_format: config
+                .partition_format
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PARTITION_FORMAT.to_string()),
+            clock: Arc::new(Utc::now),
         })
     }
 
-    fn get_object_path(&self, file_id: &str) -> ObjectPath {
-        // Organize files by date: uploads/YYYY/MM/DD/file_id
-        let now = Utc::now();
-        let path = format!(
-            "uploads/{}/{}/{}/{}",
-            now.format("%Y"),
-            now.format("%m"),
-            now.format("%d"),
-            file_id
-        );
-        ObjectPath::from(path)
+    /// Reads the upload time from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn get_object_path(&self, file_id: &str) -> Result<ObjectPath, StorageError> {
+        validate_file_id(file_id)?;
+        Ok(ObjectPath::from(format!("uploads/{}", file_id)))
     }
 }
 
@@ -370,10 +547,12 @@ impl StorageBackend for S3Storage {
         _file_name: String,
         mime_type: String,
     ) -> Result<UploadResult, StorageError> {
-        let file_id = Uuid::new_v4().to_string();
-        let path = self.get_object_path(&file_id);
+        let now = (self.clock)();
+        let file_id = new_file_id(&self.partition_format, now);
+        let path = self.get_object_path(&file_id)?;
 
         let size = data.len();
+        let sha256 = content_hash(&data);
         let payload = PutPayload::from(Bytes::from(data));
 
         // Upload to S3
@@ -387,12 +566,14 @@ impl StorageBackend for S3Storage {
             url,
             size,
             mime_type,
-            uploaded_at: Utc::now().to_rfc3339(),
+            uploaded_at: utc_to_rfc3339(now),
+            sha256,
+            deduplicated: false,
         })
     }
 
     async fn download(&self, file_id: &str) -> Result<Vec<u8>, StorageError> {
-        let path = self.get_object_path(file_id);
+        let path = self.get_object_path(file_id)?;
 
         let result = self.client.get(&path).await?;
         let bytes = result.bytes().await?;
@@ -400,15 +581,44 @@ impl StorageBackend for S3Storage {
         Ok(bytes.to_vec())
     }
 
+    async fn download_stream(&self, file_id: &str) -> Result<ByteStream, StorageError> {
+        let path = self.get_object_path(file_id)?;
+
+        let result = self.client.get(&path).await?;
+        Ok(result
+            .into_stream()
+            .map(|r| r.map_err(StorageError::from))
+            .boxed())
+    }
+
+    async fn head(&self, file_id: &str) -> Result<ObjectMeta, StorageError> {
+        let path = self.get_object_path(file_id)?;
+
+        let meta = self.client.head(&path).await?;
+        Ok(ObjectMeta {
+            size: meta.size,
+            etag: meta
+                .e_tag
+                .map(|tag| tag.trim_matches('"').to_string())
+                .unwrap_or_else(|| {
+                    format!(
+                        "{:x}-{:x}",
+                        meta.last_modified.timestamp_nanos_opt().unwrap_or_default(),
+                        meta.size
+                    )
+                }),
+            last_modified: meta.last_modified,
+        })
+    }
+
     async fn delete(&self, file_id: &str) -> Result<(), StorageError> {
-        let path = self.get_object_path(file_id);
+        let path = self.get_object_path(file_id)?;
         self.client.delete(&path).await?;
         Ok(())
     }
 
     fn get_url(&self, file_id: &str) -> String {
-        let path = self.get_object_path(file_id);
-        format!("{}/{}", self.base_url, path)
+        format!("{}/uploads/{}", self.base_url, file_id)
     }
 
     async fn health_check(&self) -> bool {
@@ -435,6 +645,9 @@ impl StorageBackend for S3Storage {
 pub struct StorageService {
     backend: Arc<dyn StorageBackend>,
     storage_type: StorageType,
+    dedup: bool,
+    /// Database holding the `file_hashes` table used for deduplication.
+    file_hashes: Option<Arc<Database>>,
 }
 
 impl StorageService {
@@ -468,7 +681,13 @@ impl StorageService {
                     .base_url
                     .clone()
                     .unwrap_or_else(|| "http://localhost:3000/uploads".to_string());
-                Arc::new(LocalStorage::new(base_path, base_url)?)
+                let partition_format = config
+                    .partition_format
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_PARTITION_FORMAT.to_string());
+                Arc::new(
+                    LocalStorage::new(base_path, base_url)?.with_partition_format(partition_format),
+                )
             }
             StorageType::S3 => Arc::new(S3Storage::new(config)?),
         };
@@ -476,11 +695,25 @@ impl StorageService {
         Ok(Self {
             backend,
             storage_type,
+            dedup: config.dedup,
+            file_hashes: None,
         })
     }
 
+    /// Tracks content hashes in `db` so repeated uploads of the same bytes
+    /// reuse the stored file. Has no effect when deduplication is disabled in
+    /// [`StorageConfig`].
+    pub fn with_file_hashes(mut self, db: Arc<Database>) -> Self {
+        self.file_hashes = Some(db);
+        self
+    }
+
     /// Uploads a file to storage.
     ///
+    /// With deduplication enabled, a file whose contents match an earlier
+    /// upload isn't stored again; the earlier upload's result is returned with
+    /// `deduplicated` set.
+    ///
     /// # Arguments
     ///
     /// * `data` - The raw bytes of the file.
@@ -496,7 +729,57 @@ impl StorageService {
         file_name: String,
         mime_type: String,
     ) -> Result<UploadResult, StorageError> {
-        self.backend.upload(data, file_name, mime_type).await
+        let file_hashes = self.file_hashes.as_ref().filter(|_| self.dedup);
+        let hash = file_hashes.map(|_| content_hash(&data));
+
+        if let (Some(db), Some(hash)) = (file_hashes, &hash) {
+            match db.file_hash_table.select(hash).await {
+                Ok(Some(existing)) => {
+                    metrics::counter!("storage_uploads_total", "result" => "deduplicated")
+                        .increment(1);
+                    return Ok(UploadResult {
+                        file_id: existing.file_id,
+                        url: existing.url,
+                        size: existing.size as usize,
+                        mime_type: existing.mime_type,
+                        uploaded_at: existing.created_at.map(utc_to_rfc3339).unwrap_or_default(),
+                        sha256: existing.hash,
+                        deduplicated: true,
+                    });
+                }
+                Ok(None) => {}
+                // Storing a duplicate beats failing the upload
+                Err(e) => warn!("Failed to look up file hash {}: {}", hash, e),
+            }
+        }
+
+        let result = self.backend.upload(data, file_name, mime_type).await;
+        match &result {
+            Ok(upload) => {
+                metrics::counter!("storage_uploads_total", "result" => "success").increment(1);
+                metrics::counter!("storage_upload_bytes_total").increment(upload.size as u64);
+            }
+            Err(_) => {
+                metrics::counter!("storage_uploads_total", "result" => "failure").increment(1)
+            }
+        }
+
+        if let (Some(db), Ok(upload)) = (file_hashes, &result) {
+            let file_hash = FileHash {
+                hash: upload.sha256.clone(),
+                file_id: upload.file_id.clone(),
+                url: upload.url.clone(),
+                size: upload.size as i64,
+                mime_type: upload.mime_type.clone(),
+                created_at: None,
+            };
+            // A concurrent upload of the same bytes may have won; keep its
+            // mapping and let this copy stand on its own
+            if let Err(e) = db.file_hash_table.insert_with_id(&file_hash).await {
+                warn!("Failed to record file hash {}: {}", upload.sha256, e);
+            }
+        }
+        result
     }
 
     /// Downloads a file from storage.
@@ -512,13 +795,58 @@ impl StorageService {
         self.backend.download(file_id).await
     }
 
+    /// Streams a file from storage without buffering it in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The unique identifier of the file.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the file's bytes.
+    pub async fn download_stream(&self, file_id: &str) -> Result<ByteStream, StorageError> {
+        self.backend.download_stream(file_id).await
+    }
+
+    /// Reads a file's metadata without downloading it.
+    ///
+    /// When the file's content hash was recorded for deduplication, it is
+    /// used as the ETag instead of the backend's own tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The unique identifier of the file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the file's [`ObjectMeta`].
+    pub async fn head(&self, file_id: &str) -> Result<ObjectMeta, StorageError> {
+        let mut meta = self.backend.head(file_id).await?;
+        if let Some(db) = &self.file_hashes {
+            match db.file_hash_table.select_by_file_id(file_id).await {
+                Ok(Some(file_hash)) => meta.etag = file_hash.hash,
+                Ok(None) => {}
+                // The backend's tag still identifies the contents
+                Err(e) => warn!("Failed to look up hash of file {}: {}", file_id, e),
+            }
+        }
+        Ok(meta)
+    }
+
     /// Deletes a file from storage.
     ///
     /// # Arguments
     ///
     /// * `file_id` - The unique identifier of the file.
     pub async fn delete(&self, file_id: &str) -> Result<(), StorageError> {
-        self.backend.delete(file_id).await
+        let result = self.backend.delete(file_id).await;
+        if matches!(result, Ok(()) | Err(StorageError::NotFound(_)))
+            && let Some(db) = &self.file_hashes
+            && let Err(e) = db.file_hash_table.delete_by_file_id(file_id).await
+        {
+            warn!("Failed to forget hash of deleted file {}: {}", file_id, e);
+        }
+        result
     }
 
     /// Gets the public URL for a file.
@@ -583,6 +911,74 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&temp_dir).await;
     }
 
+    #[tokio::test]
+    async fn test_local_storage_download_stream() {
+        let temp_dir = std::env::temp_dir().join(format!("test_storage_{}", Uuid::new_v4()));
+        let storage = LocalStorage::new(
+            temp_dir.clone(),
+            "http://localhost:3000/uploads".to_string(),
+        )
+        .unwrap();
+
+        // Larger than one ReaderStream chunk
+        let test_data: Vec<u8> = (0..20_000u32).map(|i| i as u8).collect();
+        let result = storage
+            .upload(
+                test_data.clone(),
+                "test.bin".to_string(),
+                "application/octet-stream".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let chunks: Vec<Bytes> = storage
+            .download_stream(&result.file_id)
+            .await
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.concat(), test_data);
+
+        let missing = storage.download_stream(&Uuid::new_v4().to_string()).await;
+        assert!(matches!(missing, Err(StorageError::NotFound(_))));
+
+        // Cleanup
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_head() {
+        let temp_dir = std::env::temp_dir().join(format!("test_storage_{}", Uuid::new_v4()));
+        let storage = LocalStorage::new(
+            temp_dir.clone(),
+            "http://localhost:3000/uploads".to_string(),
+        )
+        .unwrap();
+
+        let result = storage
+            .upload(
+                b"Test data".to_vec(),
+                "test.txt".to_string(),
+                "text/plain".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let meta = storage.head(&result.file_id).await.unwrap();
+        assert_eq!(meta.size, 9);
+        assert_eq!(storage.head(&result.file_id).await.unwrap(), meta);
+
+        assert!(matches!(
+            storage.head(&Uuid::new_v4().to_string()).await,
+            Err(StorageError::NotFound(_))
+        ));
+
+        // Cleanup
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+
     #[tokio::test]
     async fn test_local_storage_delete() {
         let temp_dir = std::env::temp_dir().join(format!("test_storage_{}", Uuid::new_v4()));
@@ -609,6 +1005,117 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&temp_dir).await;
     }
 
+    #[tokio::test]
+    async fn test_local_storage_finds_files_from_earlier_days() {
+        use std::sync::atomic::AtomicI64;
+        use std::sync::atomic::Ordering;
+
+        let temp_dir = std::env::temp_dir().join(format!("test_storage_{}", Uuid::new_v4()));
+        let start = DateTime::parse_from_rfc3339("2025-01-30T23:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let elapsed_hours = Arc::new(AtomicI64::new(0));
+        let clock_hours = elapsed_hours.clone();
+        let storage = LocalStorage::new(
+            temp_dir.clone(),
+            "http://localhost:3000/uploads".to_string(),
+        )
+        .unwrap()
+        .with_clock(Arc::new(move || {
+            start + chrono::Duration::hours(clock_hours.load(Ordering::SeqCst))
+        }));
+
+        let result = storage
+            .upload(
+                b"old".to_vec(),
+                "a.txt".to_string(),
+                "text/plain".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(result.file_id.starts_with("2025/01/30/"));
+        assert_eq!(
+            result.url,
+            format!("http://localhost:3000/uploads/{}", result.file_id)
+        );
+
+        // The next day's uploads land in a new partition
+        elapsed_hours.store(24, Ordering::SeqCst);
+        let next = storage
+            .upload(
+                b"new".to_vec(),
+                "b.txt".to_string(),
+                "text/plain".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(next.file_id.starts_with("2025/01/31/"));
+
+        // ...while yesterday's file is still reachable by its ID
+        assert_eq!(storage.download(&result.file_id).await.unwrap(), b"old");
+        storage.delete(&result.file_id).await.unwrap();
+        assert!(matches!(
+            storage.download(&result.file_id).await,
+            Err(StorageError::NotFound(_))
+        ));
+
+        // Cleanup
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+
+    #[test]
+    fn test_new_file_id() {
+        let now = DateTime::parse_from_rfc3339("2025-01-30T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let id = new_file_id(DEFAULT_PARTITION_FORMAT, now);
+        assert!(id.starts_with("2025/01/30/"));
+        assert!(validate_file_id(&id).is_ok());
+
+        let id = new_file_id("", now);
+        assert!(Uuid::try_parse(&id).is_ok());
+    }
+
+    #[test]
+    fn test_validate_file_id() {
+        let id = Uuid::new_v4().to_string();
+        assert!(validate_file_id(&id).is_ok());
+        assert!(validate_file_id(&format!("2025/01/30/{}", id)).is_ok());
+        assert!(validate_file_id(&format!("2025-w05/{}", id)).is_ok());
+
+        for invalid in [
+            String::new(),
+            "missing".to_string(),
+            format!("../{}", id),
+            format!("2025//{}", id),
+            format!("/2025/{}", id),
+            format!("2025/./{}", id),
+            format!("2025/{}", id.to_uppercase()),
+            format!("2025/{}", id.replace('-', "")),
+            format!("2025/{}/", id),
+        ] {
+            assert!(
+                matches!(
+                    validate_file_id(&invalid),
+                    Err(StorageError::InvalidPath(_))
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_partition_format() {
+        assert!(validate_partition_format(DEFAULT_PARTITION_FORMAT).is_ok());
+        assert!(validate_partition_format("%Y-%m").is_ok());
+        assert!(validate_partition_format("").is_ok());
+        assert!(validate_partition_format("%Q").is_err());
+        assert!(validate_partition_format("../%Y").is_err());
+        assert!(validate_partition_format("%Y/%m/").is_err());
+    }
+
     #[test]
     fn test_storage_type_from_str() {
         assert_eq!("local".parse::<StorageType>().unwrap(), StorageType::Local);
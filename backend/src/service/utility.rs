@@ -1,14 +1,34 @@
 //! Utility services including file uploads and health checks.
 
 use std::sync::Arc;
+use std::time::Instant;
 
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use log::error;
+use log::info;
+use log::warn;
 use serde::Serialize;
 
+use crate::auth::utils::Claims;
+use crate::config::RetentionConfig;
 use crate::database::Database;
-use crate::database::table::Table;
+use crate::database::model::UserRole;
 use crate::error::AppError;
+use crate::service::datetime::now_rfc3339;
+use crate::service::otp::OtpService;
+use crate::service::storage::ByteStream;
+use crate::service::storage::ObjectMeta;
 use crate::service::storage::StorageError;
 use crate::service::storage::StorageService;
+use crate::service::whatsapp::WhatsAppClient;
+
+/// The application version, from `Cargo.toml`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The short git commit the binary was built from, if known at build time.
+pub const GIT_SHA: Option<&str> = option_env!("GIT_SHA");
 
 /// Response structure for a single image upload.
 #[derive(Debug, Serialize)]
@@ -32,6 +52,8 @@ pub struct ImageUploadResponse {
     /// The timestamp when the file was uploaded.
     #[serde(rename = "uploadedAt")]
     pub uploaded_at: String,
+    /// Lowercase hex SHA-256 of the file contents, for integrity checks.
+    pub sha256: String,
 }
 
 /// Response structure for multiple image uploads.
@@ -50,13 +72,39 @@ pub struct MultiImageUploadResponse {
 /// Result structure for a single file in a batch upload.
 #[derive(Debug, Serialize)]
 pub struct ImageUploadResult {
-    /// The public URL where the file can be accessed.
+    /// The public URL where the file can be accessed, empty if it failed.
     pub url: String,
     /// The original file name.
     #[serde(rename = "fileName")]
     pub file_name: String,
     /// The size of the uploaded file in bytes.
     pub size: usize,
+    /// The MIME type the client declared for the file.
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    /// Why the file was not stored, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A file part received in an upload request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// The file name sent by the client.
+    pub file_name: String,
+    /// The MIME type sent by the client.
+    pub content_type: String,
+    /// The file contents.
+    pub data: Vec<u8>,
+}
+
+/// A file part refused before reaching storage, e.g. for being too large.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedFile {
+    /// The file name sent by the client.
+    pub file_name: String,
+    /// Why the file was refused.
+    pub reason: String,
 }
 
 /// Response structure for health check.
@@ -66,10 +114,81 @@ pub struct HealthCheckResponse {
     pub status: String,
     /// The application version.
     pub version: String,
+    /// The git commit the binary was built from, if known.
+    pub commit: Option<String>,
+    /// Seconds since the process started.
+    #[serde(rename = "uptimeSeconds")]
+    pub uptime_seconds: u64,
     /// The timestamp of the health check.
     pub timestamp: String,
     /// The health status of individual services.
     pub services: ServicesHealth,
+    /// Pool and migration state; only included by the detailed health check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<HealthDetails>,
+}
+
+/// Runtime details reported by [`UtilityService::detailed_health_check`].
+#[derive(Debug, Serialize)]
+pub struct HealthDetails {
+    /// Database connection pool usage.
+    #[serde(rename = "dbPool")]
+    pub db_pool: PoolStats,
+    /// Whether the database schema matches this build.
+    pub migrations: MigrationStatus,
+}
+
+/// Connection counts of the database pool.
+#[derive(Debug, Serialize)]
+pub struct PoolStats {
+    /// Open connections, idle or in use.
+    pub size: u32,
+    /// Open connections not currently in use.
+    pub idle: u32,
+    /// Connections currently checked out.
+    #[serde(rename = "inUse")]
+    pub in_use: u32,
+}
+
+/// Migration state of the database.
+#[derive(Debug, Serialize)]
+pub struct MigrationStatus {
+    /// Whether every embedded migration has been applied; `null` if the
+    /// applied migrations could not be read.
+    #[serde(rename = "upToDate")]
+    pub up_to_date: Option<bool>,
+    /// Versions of the migrations not applied yet.
+    pub pending: Vec<i64>,
+}
+
+/// Response of [`UtilityService::run_migrations`].
+#[derive(Debug, Serialize)]
+pub struct MigrateResponse {
+    /// Migrations applied by this run, in order; empty if the database was
+    /// already up to date.
+    pub applied: Vec<AppliedMigrationDto>,
+}
+
+/// A migration applied by [`UtilityService::run_migrations`].
+#[derive(Debug, Serialize)]
+pub struct AppliedMigrationDto {
+    pub version: i64,
+    pub description: String,
+}
+
+/// Response of [`UtilityService::cleanup`]: how many items were removed
+/// from each category.
+#[derive(Debug, Serialize)]
+pub struct CleanupResponse {
+    /// Expired OTPs dropped from memory.
+    #[serde(rename = "expiredOtps")]
+    pub expired_otps: usize,
+    /// Notifications viewed or resolved before the retention period.
+    #[serde(rename = "readNotifications")]
+    pub read_notifications: u64,
+    /// Audit log entries older than the retention period.
+    #[serde(rename = "auditLogs")]
+    pub audit_logs: u64,
 }
 
 /// Health status of individual services.
@@ -87,6 +206,10 @@ pub struct ServicesHealth {
 pub struct UtilityService {
     db: Arc<Database>,
     storage: Arc<StorageService>,
+    whatsapp: WhatsAppClient,
+    started_at: Instant,
+    otp_service: Option<Arc<OtpService>>,
+    retention: RetentionConfig,
 }
 
 impl UtilityService {
@@ -96,8 +219,40 @@ impl UtilityService {
     ///
     /// * `db` - The database instance.
     /// * `storage` - The storage service for file operations.
-    pub fn new(db: Arc<Database>, storage: Arc<StorageService>) -> Self {
-        Self { db, storage }
+    /// * `whatsapp` - The WhatsApp client whose reachability is reported.
+    pub fn new(db: Arc<Database>, storage: Arc<StorageService>, whatsapp: WhatsAppClient) -> Self {
+        Self::with_started_at(db, storage, whatsapp, Instant::now())
+    }
+
+    /// Creates a new `UtilityService` that reports uptime relative to
+    /// `started_at`, normally captured at the top of `main`.
+    pub fn with_started_at(
+        db: Arc<Database>,
+        storage: Arc<StorageService>,
+        whatsapp: WhatsAppClient,
+        started_at: Instant,
+    ) -> Self {
+        Self {
+            db,
+            storage,
+            whatsapp,
+            started_at,
+            otp_service: None,
+            retention: RetentionConfig::default(),
+        }
+    }
+
+    /// Lets [`UtilityService::cleanup`] purge the expired OTPs of
+    /// `otp_service`.
+    pub fn with_otp_service(mut self, otp_service: Arc<OtpService>) -> Self {
+        self.otp_service = Some(otp_service);
+        self
+    }
+
+    /// Sets how long [`UtilityService::cleanup`] keeps each kind of data.
+    pub fn with_retention(mut self, retention: RetentionConfig) -> Self {
+        self.retention = retention;
+        self
     }
 
     /// Uploads an image to the storage system.
@@ -142,6 +297,7 @@ impl UtilityService {
             width: 1920,
             height: 1080,
             uploaded_at: result.uploaded_at,
+            sha256: result.sha256,
         })
     }
 
@@ -149,26 +305,43 @@ impl UtilityService {
     ///
     /// # Arguments
     ///
-    /// * `files` - A vector of tuples containing (file_name, data, content_type).
+    /// * `files` - The received files in request order; files already refused
+    ///   by the caller are reported as failed without being stored.
     ///
     /// # Returns
     ///
-    /// Returns a [`MultiImageUploadResponse`] with results for each file.
+    /// Returns a [`MultiImageUploadResponse`] with results for each file, in
+    /// the same order.
     ///
     /// # Errors
     ///
     /// Returns an error if the operation fails catastrophically.
     pub async fn upload_multiple_images(
         &self,
-        files: Vec<(String, Vec<u8>, String)>,
+        files: Vec<Result<UploadedFile, RejectedFile>>,
     ) -> Result<MultiImageUploadResponse, AppError> {
         let mut results = Vec::new();
         let mut uploaded = 0;
         let mut failed = 0;
 
-        for (file_name, data, content_type) in files {
+        for file in files {
+            let file = match file {
+                Ok(file) => file,
+                Err(rejected) => {
+                    failed += 1;
+                    results.push(ImageUploadResult {
+                        url: String::new(),
+                        file_name: rejected.file_name,
+                        size: 0,
+                        mime_type: String::new(),
+                        error: Some(rejected.reason),
+                    });
+                    continue;
+                }
+            };
+
             match self
-                .upload_image(file_name.clone(), data, content_type)
+                .upload_image(file.file_name.clone(), file.data, file.content_type.clone())
                 .await
             {
                 Ok(response) => {
@@ -177,14 +350,25 @@ impl UtilityService {
                         url: response.url,
                         file_name: response.file_name,
                         size: response.size,
+                        mime_type: response.mime_type,
+                        error: None,
                     });
                 }
-                Err(_) => {
+                Err(e) => {
                     failed += 1;
+                    let reason = match e {
+                        AppError::BadRequest(msg) => msg,
+                        e => {
+                            warn!("Failed to upload {}: {}", file.file_name, e);
+                            "Upload failed".to_string()
+                        }
+                    };
                     results.push(ImageUploadResult {
                         url: String::new(),
-                        file_name,
+                        file_name: file.file_name,
                         size: 0,
+                        mime_type: file.content_type,
+                        error: Some(reason),
                     });
                 }
             }
@@ -198,8 +382,32 @@ impl UtilityService {
         })
     }
 
+    /// Streams a stored file without loading it into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if `file_id` is malformed and `NotFound` if no file
+    /// has this ID.
+    pub async fn download_file(&self, file_id: &str) -> Result<ByteStream, AppError> {
+        Ok(self.storage.download_stream(file_id).await?)
+    }
+
+    /// Reads a stored file's size, ETag and modification time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BadRequest` if `file_id` is malformed and `NotFound` if no file
+    /// has this ID.
+    pub async fn file_meta(&self, file_id: &str) -> Result<ObjectMeta, AppError> {
+        Ok(self.storage.head(file_id).await?)
+    }
+
     /// Performs a health check on all system services.
     ///
+    /// The database and storage are critical dependencies: if either is
+    /// unhealthy the overall status is `"unhealthy"`. WhatsApp is reported but
+    /// does not affect the overall status.
+    ///
     /// # Returns
     ///
     /// Returns a [`HealthCheckResponse`] with the status of each service.
@@ -208,9 +416,8 @@ impl UtilityService {
     ///
     /// Returns an error if the health check cannot be performed.
     pub async fn health_check(&self) -> Result<HealthCheckResponse, AppError> {
-        // Check DB connection by running a simple query through the database
-        let db_status = match self.db.user_table.select_all().await {
-            Ok(_) => "healthy",
+        let db_status = match self.db.ping().await {
+            Ok(()) => "healthy",
             Err(_) => "unhealthy",
         };
 
@@ -221,23 +428,187 @@ impl UtilityService {
             "unhealthy"
         };
 
+        let whatsapp_status = self.whatsapp.health_check().await;
+
+        let status = if db_status == "healthy" && storage_status == "healthy" {
+            "healthy"
+        } else {
+            "unhealthy"
+        };
+
         Ok(HealthCheckResponse {
-            status: "healthy".to_string(),
-            version: "1.0.0".to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            status: status.to_string(),
+            version: VERSION.to_string(),
+            commit: GIT_SHA.map(str::to_string),
+            uptime_seconds: self.started_at.elapsed().as_secs(),
+            timestamp: now_rfc3339(),
             services: ServicesHealth {
                 database: db_status.to_string(),
                 storage: storage_status.to_string(),
-                whatsapp: "healthy".to_string(), // Mock
+                whatsapp: whatsapp_status.to_string(),
             },
+            details: None,
+        })
+    }
+
+    /// Performs a [health check](Self::health_check) and adds the database
+    /// pool usage and migration state.
+    ///
+    /// Pending migrations are reported but do not affect the overall status.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins.
+    pub async fn detailed_health_check(
+        &self,
+        role: UserRole,
+    ) -> Result<HealthCheckResponse, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can view detailed health".into(),
+            ));
+        }
+
+        let mut response = self.health_check().await?;
+
+        let size = self.db.pool_size();
+        let idle = u32::try_from(self.db.pool_idle()).unwrap_or(size);
+        let migrations = match self.db.pending_migrations().await {
+            Ok(pending) => MigrationStatus {
+                up_to_date: Some(pending.is_empty()),
+                pending: pending.into_iter().map(|m| m.version).collect(),
+            },
+            Err(e) => {
+                warn!("Failed to read applied migrations: {}", e);
+                MigrationStatus {
+                    up_to_date: None,
+                    pending: Vec::new(),
+                }
+            }
+        };
+        response.details = Some(HealthDetails {
+            db_pool: PoolStats {
+                size,
+                idle,
+                in_use: size.saturating_sub(idle),
+            },
+            migrations,
+        });
+
+        Ok(response)
+    }
+
+    /// Applies the embedded migrations the database hasn't applied yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins and `InternalServerError` if a
+    /// migration fails.
+    pub async fn run_migrations(&self, claims: &Claims) -> Result<MigrateResponse, AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can run migrations".into(),
+            ));
+        }
+
+        info!("Running database migrations for user {}", claims.sub);
+        let applied = self.db.run_migrations().await.map_err(|e| {
+            error!("Manual migration failed: {}", e);
+            AppError::InternalServerError(format!("Migration failed: {}", e))
+        })?;
+        info!("Applied {} migrations", applied.len());
+
+        Ok(MigrateResponse {
+            applied: applied
+                .into_iter()
+                .map(|m| AppliedMigrationDto {
+                    version: m.version,
+                    description: m.description,
+                })
+                .collect(),
+        })
+    }
+
+    /// Removes data past its retention period: expired OTPs, notifications
+    /// viewed or resolved more than `notification_days` ago and audit log
+    /// entries older than `audit_log_days`. A period of 0 keeps that
+    /// category forever.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins.
+    pub async fn cleanup(&self, claims: &Claims) -> Result<CleanupResponse, AppError> {
+        if claims.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can purge data".into(),
+            ));
+        }
+
+        let expired_otps = match &self.otp_service {
+            Some(otp_service) => otp_service.cleanup_expired().await,
+            None => 0,
+        };
+
+        let now = Utc::now();
+        let read_notifications = match self.retention.notification_days {
+            0 => 0,
+            days => {
+                self.db
+                    .notification_table
+                    .delete_read_before(retention_cutoff(now, days)?)
+                    .await?
+            }
+        };
+        let audit_logs = match self.retention.audit_log_days {
+            0 => 0,
+            days => {
+                self.db
+                    .audit_log_table
+                    .delete_before(retention_cutoff(now, days)?)
+                    .await?
+            }
+        };
+
+        info!(
+            "User {} purged {} expired OTPs, {} read notifications and {} audit log entries",
+            claims.sub, expired_otps, read_notifications, audit_logs
+        );
+        Ok(CleanupResponse {
+            expired_otps,
+            read_notifications,
+            audit_logs,
         })
     }
 }
 
+/// The time `days` before `now`.
+///
+/// # Errors
+///
+/// Returns [`AppError::InternalServerError`] if that is out of range, which
+/// `Config::validate` should have prevented.
+fn retention_cutoff(now: DateTime<Utc>, days: u32) -> Result<DateTime<Utc>, AppError> {
+    Duration::try_days(days.into())
+        .and_then(|period| now.checked_sub_signed(period))
+        .ok_or_else(|| {
+            AppError::InternalServerError(format!("Retention of {} days is out of range", days))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_retention_cutoff() {
+        let now = Utc::now();
+        assert_eq!(retention_cutoff(now, 90).unwrap(), now - Duration::days(90));
+        assert!(matches!(
+            retention_cutoff(now, u32::MAX),
+            Err(AppError::InternalServerError(_))
+        ));
+    }
+
     // Note: These tests would need a mock database to run properly
     // For now, they serve as documentation of expected behavior
 
@@ -251,7 +622,8 @@ mod tests {
             mime_type: "image/jpeg".to_string(),
             width: 1920,
             height: 1080,
-            uploaded_at: chrono::Utc::now().to_rfc3339(),
+            uploaded_at: now_rfc3339(),
+            sha256: "0".repeat(64),
         };
 
         assert!(response.success);
@@ -269,11 +641,15 @@ mod tests {
                     url: "http://example.com/1.jpg".to_string(),
                     file_name: "1.jpg".to_string(),
                     size: 1024,
+                    mime_type: "image/jpeg".to_string(),
+                    error: None,
                 },
                 ImageUploadResult {
                     url: "http://example.com/2.jpg".to_string(),
                     file_name: "2.jpg".to_string(),
                     size: 2048,
+                    mime_type: "image/jpeg".to_string(),
+                    error: None,
                 },
             ],
         };
@@ -287,13 +663,16 @@ mod tests {
     fn test_health_check_response_structure() {
         let response = HealthCheckResponse {
             status: "healthy".to_string(),
-            version: "1.0.0".to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            version: VERSION.to_string(),
+            commit: None,
+            uptime_seconds: 0,
+            timestamp: now_rfc3339(),
             services: ServicesHealth {
                 database: "healthy".to_string(),
                 storage: "healthy".to_string(),
                 whatsapp: "healthy".to_string(),
             },
+            details: None,
         };
 
         assert_eq!(response.status, "healthy");
@@ -0,0 +1,191 @@
+//! Training video library service.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::database::model::UserRole;
+use crate::database::model::Video;
+use crate::database::table::Table;
+use crate::error::AppError;
+use crate::service::incident::require_non_empty;
+use crate::service::pagination::PaginatedResponse;
+use crate::service::pagination::Pagination;
+
+/// Request body for adding a video to the library.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateVideoRequest {
+    #[serde(rename = "youtubeId")]
+    pub youtube_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    /// Display duration, e.g. `"12:34"`.
+    pub duration: Option<String>,
+    #[serde(rename = "uploadDate")]
+    pub upload_date: Option<NaiveDate>,
+    /// Defaults to YouTube's own thumbnail for the video.
+    pub thumbnail: Option<String>,
+    #[serde(rename = "haccpRelevance")]
+    pub haccp_relevance: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoDto {
+    pub id: Uuid,
+    #[serde(rename = "youtubeId")]
+    pub youtube_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub duration: Option<String>,
+    #[serde(rename = "uploadDate")]
+    pub upload_date: Option<String>,
+    pub thumbnail: Option<String>,
+    #[serde(rename = "haccpRelevance")]
+    pub haccp_relevance: Option<String>,
+}
+
+/// Service for the training video library.
+pub struct VideoService {
+    db: Arc<Database>,
+}
+
+impl VideoService {
+    /// Creates a new `VideoService`.
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Lists videos, optionally filtered by category and HACCP relevance.
+    pub async fn list_videos(
+        &self,
+        limit: i64,
+        offset: i64,
+        category: Option<String>,
+        haccp_relevance: Option<String>,
+    ) -> Result<PaginatedResponse<VideoDto>, AppError> {
+        let (videos, total) = self
+            .db
+            .video_table
+            .list_filtered(
+                category.as_deref(),
+                haccp_relevance.as_deref(),
+                limit,
+                offset,
+            )
+            .await?;
+
+        Ok(PaginatedResponse {
+            data: videos.into_iter().map(map_to_dto).collect(),
+            pagination: Pagination::new(total, limit, offset),
+        })
+    }
+
+    /// Gets a single video.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the video doesn't exist.
+    pub async fn get_video(&self, id: Uuid) -> Result<VideoDto, AppError> {
+        let video = self
+            .db
+            .video_table
+            .select(&id)
+            .await?
+            .ok_or(AppError::NotFound("Video not found".into()))?;
+        Ok(map_to_dto(video))
+    }
+
+    /// Adds a video to the library.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` for non-admins, `BadRequest` if the title is empty or
+    /// `youtubeId` is not a valid YouTube video ID, and `Conflict` if the video
+    /// is already in the library.
+    pub async fn create_video(
+        &self,
+        role: UserRole,
+        req: CreateVideoRequest,
+    ) -> Result<Uuid, AppError> {
+        if role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only administrators can add videos".into(),
+            ));
+        }
+
+        let youtube_id = req.youtube_id.trim().to_string();
+        if !is_valid_youtube_id(&youtube_id) {
+            return Err(AppError::BadRequest(
+                "youtubeId must be an 11 character YouTube video ID".into(),
+            ));
+        }
+        let title = require_non_empty(&req.title, "title")?;
+
+        let video =
+            Video {
+                thumbnail: Some(req.thumbnail.unwrap_or_else(|| {
+                    format!("https://i.ytimg.com/vi/{}/hqdefault.jpg", youtube_id)
+                })),
+                youtube_id,
+                title,
+                description: req.description,
+                category: req.category.map(|c| c.trim().to_string()),
+                duration: req.duration,
+                upload_date: req.upload_date,
+                haccp_relevance: req.haccp_relevance,
+                ..Default::default()
+            };
+
+        self.db
+            .video_table
+            .insert_if_new(&video)
+            .await?
+            .ok_or(AppError::Conflict(
+                "This video is already in the library".into(),
+            ))
+    }
+}
+
+/// Whether `id` looks like a YouTube video ID: 11 characters from the URL-safe
+/// base64 alphabet.
+fn is_valid_youtube_id(id: &str) -> bool {
+    id.len() == 11
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn map_to_dto(v: Video) -> VideoDto {
+    VideoDto {
+        id: v.id,
+        youtube_id: v.youtube_id,
+        title: v.title,
+        description: v.description,
+        category: v.category,
+        duration: v.duration,
+        upload_date: v.upload_date.map(|d| d.to_string()),
+        thumbnail: v.thumbnail,
+        haccp_relevance: v.haccp_relevance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_youtube_id() {
+        assert!(is_valid_youtube_id("dQw4w9WgXcQ"));
+        assert!(is_valid_youtube_id("a-b_c-d_e-f"));
+        assert!(!is_valid_youtube_id("dQw4w9WgXc"));
+        assert!(!is_valid_youtube_id("dQw4w9WgXcQQ"));
+        assert!(!is_valid_youtube_id("dQw4w9WgX?Q"));
+        assert!(!is_valid_youtube_id("https://youtu.be/dQw4w9WgXcQ"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Outbound webhooks for regulators.
+//!
+//! Each event is POSTed as JSON to every configured URL with an
+//! `X-Signature: sha256=<hex>` header holding the HMAC-SHA256 of the body,
+//! keyed with the shared secret. Deliveries run in the background; ones that
+//! still fail after all retries are stored in `webhook_dead_letters`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::Hmac;
+use hmac::Mac;
+use log::error;
+use log::info;
+use log::warn;
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::task::JoinHandle;
+
+use crate::config::WebhookConfig;
+use crate::database::Database;
+use crate::database::model::WebhookDeadLetter;
+use crate::database::table::Table;
+use crate::service::datetime::now_rfc3339;
+
+/// Maximum time a single delivery attempt may take before it is retried.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Header carrying the payload signature.
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Header carrying the event name.
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+
+/// Body POSTed to webhook URLs.
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a, T: Serialize> {
+    event: &'a str,
+    #[serde(rename = "sentAt")]
+    sent_at: String,
+    data: &'a T,
+}
+
+/// Sends signed webhook events in the background.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    db: Arc<Database>,
+    config: WebhookConfig,
+    http_client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    /// Creates a dispatcher for the configured URLs.
+    pub fn new(db: Arc<Database>, config: WebhookConfig) -> Self {
+        Self {
+            db,
+            config,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Checks whether any URL is configured along with a signing secret.
+    pub fn is_enabled(&self) -> bool {
+        !self.config.urls.is_empty() && self.config.secret.is_some()
+    }
+
+    /// Sends `event` with `data` to every URL on a background task.
+    ///
+    /// Returns immediately; the handle resolves once every URL has either
+    /// accepted the event or been dead-lettered. Returns `None` when webhooks
+    /// are disabled.
+    pub fn dispatch<T: Serialize>(&self, event: &str, data: &T) -> Option<JoinHandle<()>> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let payload = WebhookPayload {
+            event,
+            sent_at: now_rfc3339(),
+            data,
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("Failed to serialize webhook event {}: {}", event, e);
+                return None;
+            }
+        };
+
+        let this = self.clone();
+        let event = event.to_string();
+        Some(tokio::spawn(async move {
+            let deliveries = this
+                .config
+                .urls
+                .iter()
+                .map(|url| this.deliver(url, &event, &body));
+            futures::future::join_all(deliveries).await;
+        }))
+    }
+
+    /// Delivers one event to one URL, dead-lettering it if every attempt fails.
+    async fn deliver(&self, url: &str, event: &str, body: &[u8]) {
+        let secret = self.config.secret.as_deref().unwrap_or_default();
+        let signature = sign(secret, body);
+
+        let attempts = self.config.send_attempts.max(1);
+        let mut delay = Duration::from_millis(self.config.retry_delay_ms);
+        let mut attempt = 1;
+        let reason = loop {
+            match self.post(url, event, &signature, body).await {
+                Ok(()) => {
+                    info!("Delivered webhook {} to {}", event, url);
+                    return;
+                }
+                Err(SendFailure::Transient(reason)) if attempt < attempts => {
+                    warn!(
+                        "Webhook {} to {} failed on attempt {}/{} ({}), retrying in {:?}",
+                        event, url, attempt, attempts, reason, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(SendFailure::Transient(reason)) | Err(SendFailure::Permanent(reason)) => {
+                    break reason;
+                }
+            }
+        };
+
+        error!(
+            "Giving up on webhook {} to {} after {} attempt(s): {}",
+            event, url, attempt, reason
+        );
+        let dead_letter = WebhookDeadLetter {
+            url: url.to_string(),
+            event: event.to_string(),
+            payload: serde_json::from_slice(body).unwrap_or_default(),
+            error: reason,
+            attempts: attempt as i32,
+            ..Default::default()
+        };
+        if let Err(e) = self.db.webhook_dead_letter_table.insert(&dead_letter).await {
+            error!("Failed to dead-letter webhook {} to {}: {}", event, url, e);
+        }
+    }
+
+    /// Posts the body once, classifying failures as transient (network
+    /// errors, timeouts, `429`, `5xx`) or permanent (any other status).
+    async fn post(
+        &self,
+        url: &str,
+        event: &str,
+        signature: &str,
+        body: &[u8],
+    ) -> Result<(), SendFailure> {
+        let response = self
+            .http_client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, event)
+            .timeout(SEND_TIMEOUT)
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(|e| SendFailure::Transient(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let reason = format!("status={}", status);
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(SendFailure::Transient(reason))
+        } else {
+            Err(SendFailure::Permanent(reason))
+        }
+    }
+}
+
+/// Why a single delivery attempt failed.
+enum SendFailure {
+    /// Worth retrying: network error, timeout, `429` or `5xx` response.
+    Transient(String),
+    /// Not worth retrying, e.g. a `4xx` caused by the request itself.
+    Permanent(String),
+}
+
+/// Signs `body` as `sha256=<hex HMAC-SHA256>` keyed with `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -1,10 +1,13 @@
 //! WhatsApp Business API integration for OTP delivery.
 //!
 //! This module provides functionality to send OTP codes via WhatsApp
-//! using the WhatsApp Business API (Meta/Facebook).
+//! using the WhatsApp Business API (Meta/Facebook), and to read the delivery
+//! statuses Meta reports back through webhook callbacks.
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_trait::async_trait;
 use log::error;
 use log::info;
 use log::warn;
@@ -13,6 +16,38 @@ use serde::Serialize;
 
 use crate::config::Config;
 use crate::error::AppError;
+use crate::service::channel;
+use crate::service::channel::MessageChannel;
+use crate::service::channel::Recipient;
+use crate::service::otp::DeliveryStatus;
+use crate::service::webhook::sign;
+
+/// Maximum time the reachability check may take before the API is considered down.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximum time a single send attempt may take before it is retried.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Header carrying the signature of a webhook callback.
+pub const SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+
+/// Placeholder replaced by the code in an OTP message template.
+pub const OTP_CODE_PLACEHOLDER: &str = "{code}";
+
+/// Placeholder replaced by the code's validity in whole minutes.
+pub const OTP_MINUTES_PLACEHOLDER: &str = "{minutes}";
+
+/// OTP message used when `WHATSAPP_OTP_MESSAGE_TEMPLATE` is not set.
+pub const DEFAULT_OTP_MESSAGE_TEMPLATE: &str = "Kode verifikasi MonitorMBG Anda: {code}. \
+    Kode berlaku selama {minutes} menit. Jangan berikan kode ini kepada siapa pun.";
+
+/// Fills in an OTP message template. `{minutes}` is `expiry_seconds` in whole
+/// minutes, rounded down.
+pub fn render_otp_message(template: &str, code: &str, expiry_seconds: u64) -> String {
+    template
+        .replace(OTP_CODE_PLACEHOLDER, code)
+        .replace(OTP_MINUTES_PLACEHOLDER, &(expiry_seconds / 60).to_string())
+}
 
 /// WhatsApp API client for sending messages.
 #[derive(Clone)]
@@ -43,8 +78,46 @@ impl WhatsAppClient {
             && whatsapp.phone_number_id.is_some()
     }
 
+    /// Checks whether the WhatsApp API is reachable.
+    ///
+    /// Issues a lightweight `HEAD` request against the configured API URL with a
+    /// short timeout. Any response that is not a server error counts as reachable,
+    /// since the API root usually answers `4xx` without a specific resource.
+    ///
+    /// # Returns
+    ///
+    /// `"disabled"` when the integration is not configured, otherwise
+    /// `"healthy"` or `"unhealthy"`.
+    pub async fn health_check(&self) -> &'static str {
+        if !self.is_enabled() {
+            return "disabled";
+        }
+
+        let api_url = self.config.whatsapp.api_url.as_ref().unwrap();
+        match self
+            .http_client
+            .head(api_url)
+            .timeout(HEALTH_CHECK_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(response) if !response.status().is_server_error() => "healthy",
+            Ok(response) => {
+                warn!("WhatsApp API health check returned {}", response.status());
+                "unhealthy"
+            }
+            Err(e) => {
+                warn!("WhatsApp API health check failed: {}", e);
+                "unhealthy"
+            }
+        }
+    }
+
     /// Sends an OTP code to a phone number via WhatsApp.
     ///
+    /// The message is a text message rendered from
+    /// `WhatsAppConfig::otp_message_template`.
+    ///
     /// # Arguments
     ///
     /// * `phone` - The phone number in international format (e.g., +628123456789)
@@ -68,19 +141,134 @@ impl WhatsAppClient {
         }
 
         let whatsapp = &self.config.whatsapp;
-        let api_url = whatsapp.api_url.as_ref().unwrap();
-        let api_token = whatsapp.api_token.as_ref().unwrap();
-        let phone_number_id = whatsapp.phone_number_id.as_ref().unwrap();
+        let template = whatsapp
+            .otp_message_template
+            .as_deref()
+            .unwrap_or(DEFAULT_OTP_MESSAGE_TEMPLATE);
+        let body = render_otp_message(template, otp_code, whatsapp.otp_expiry_seconds);
+
+        // Meta echoes the reference back in delivery status callbacks
+        self.send_message(phone, &body, Some(reference_id)).await?;
+        info!(
+            "OTP sent successfully to {} with reference {}",
+            Self::format_phone(phone),
+            reference_id
+        );
+        Ok(())
+    }
+
+    /// Sends a free-form text message to a phone number via WhatsApp.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ServiceUnavailable`] if WhatsApp is not configured
+    /// or the message could not be delivered.
+    pub async fn send_text(&self, phone: &str, body: &str) -> Result<(), AppError> {
+        self.send_message(phone, body, None).await
+    }
 
-        // Format phone number (ensure it starts with +)
-        let formatted_phone = if phone.starts_with('+') {
+    /// Sends a text message, attaching `callback_data` to the delivery status
+    /// callbacks Meta sends for it.
+    async fn send_message(
+        &self,
+        phone: &str,
+        body: &str,
+        callback_data: Option<&str>,
+    ) -> Result<(), AppError> {
+        if !self.is_enabled() {
+            return Err(AppError::ServiceUnavailable(
+                "WhatsApp service is not available".to_string(),
+            ));
+        }
+
+        let payload = WhatsAppTextRequest {
+            messaging_product: "whatsapp".to_string(),
+            recipient_type: "individual".to_string(),
+            to: Self::format_phone(phone),
+            type_: "text".to_string(),
+            text: TextBody {
+                body: body.to_string(),
+            },
+            biz_opaque_callback_data: callback_data.map(str::to_string),
+        };
+
+        self.send_with_retry(&payload).await
+    }
+
+    /// Checks the [`SIGNATURE_HEADER`] of a webhook callback against `body`.
+    ///
+    /// Meta signs callbacks like our own [outbound webhooks](crate::service::webhook),
+    /// as `sha256=<hex HMAC-SHA256>` keyed with the app secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ServiceUnavailable`] if no app secret is configured,
+    /// or [`AppError::Unauthorized`] if the signature is missing or wrong.
+    pub fn verify_signature(&self, body: &[u8], signature: Option<&str>) -> Result<(), AppError> {
+        let secret = self.config.whatsapp.app_secret.as_deref().ok_or_else(|| {
+            AppError::ServiceUnavailable("WhatsApp webhook is not configured".to_string())
+        })?;
+        let expected = sign(secret, body);
+        let valid = signature.is_some_and(|signature| {
+            constant_time_eq(
+                expected.as_bytes(),
+                signature.trim().to_ascii_lowercase().as_bytes(),
+            )
+        });
+        if !valid {
+            warn!("Rejected WhatsApp webhook callback with an invalid signature");
+            return Err(AppError::Unauthorized(
+                "Invalid webhook signature".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks a webhook subscription request, which Meta sends when the
+    /// callback URL is registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ServiceUnavailable`] if no verify token is
+    /// configured, or [`AppError::Forbidden`] if `mode` isn't `subscribe` or
+    /// `token` doesn't match.
+    pub fn verify_subscription(&self, mode: &str, token: &str) -> Result<(), AppError> {
+        let expected = self
+            .config
+            .whatsapp
+            .webhook_verify_token
+            .as_deref()
+            .ok_or_else(|| {
+                AppError::ServiceUnavailable("WhatsApp webhook is not configured".to_string())
+            })?;
+        if mode != "subscribe" || !constant_time_eq(expected.as_bytes(), token.as_bytes()) {
+            warn!("Rejected WhatsApp webhook subscription with an invalid verify token");
+            return Err(AppError::Forbidden(
+                "Invalid webhook verify token".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Formats a phone number for the API, ensuring it starts with `+`.
+    fn format_phone(phone: &str) -> String {
+        if phone.starts_with('+') {
             phone.to_string()
-        } else if phone.starts_with('0') {
+        } else if let Some(rest) = phone.strip_prefix('0') {
             // Convert Indonesian local format to international
-            format!("+62{}", &phone[1..])
+            format!("+62{}", rest)
         } else {
             format!("+{}", phone)
-        };
+        }
+    }
+
+    /// Posts `payload` to the messages endpoint, retrying transient failures
+    /// with exponential backoff.
+    async fn send_with_retry<P: Serialize + Sync>(&self, payload: &P) -> Result<(), AppError> {
+        let whatsapp = &self.config.whatsapp;
+        let api_url = whatsapp.api_url.as_ref().unwrap();
+        let api_token = whatsapp.api_token.as_ref().unwrap();
+        let phone_number_id = whatsapp.phone_number_id.as_ref().unwrap();
 
         // Build the WhatsApp API URL
         let url = format!(
@@ -89,71 +277,170 @@ impl WhatsAppClient {
             phone_number_id
         );
 
-        // Prepare the message payload
-        let payload = WhatsAppMessageRequest {
-            messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: formatted_phone.clone(),
-            type_: "template".to_string(),
-            template: Template {
-                name: "monitor_mbg_otp".to_string(),
-                language: Language {
-                    code: "id".to_string(),
-                },
-                components: vec![Component {
-                    type_: "body".to_string(),
-                    parameters: vec![
-                        Parameter {
-                            type_: "text".to_string(),
-                            text: otp_code.to_string(),
-                        },
-                        Parameter {
-                            type_: "text".to_string(),
-                            text: (whatsapp.otp_expiry_seconds / 60).to_string(),
-                        },
-                    ],
-                }],
-            },
-        };
+        let attempts = whatsapp.send_attempts.max(1);
+        let mut delay = Duration::from_millis(whatsapp.retry_delay_ms);
+        for attempt in 1..=attempts {
+            match self.post_message(&url, api_token, payload).await {
+                Ok(()) => return Ok(()),
+                Err(SendFailure::Transient(reason)) if attempt < attempts => {
+                    warn!(
+                        "WhatsApp send attempt {}/{} failed ({}), retrying in {:?}",
+                        attempt, attempts, reason, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(SendFailure::Transient(reason)) | Err(SendFailure::Permanent(reason)) => {
+                    error!(
+                        "Failed to send WhatsApp message after {} attempt(s): {}",
+                        attempt, reason
+                    );
+                    break;
+                }
+            }
+        }
+
+        Err(AppError::ServiceUnavailable(
+            "Failed to send WhatsApp message".to_string(),
+        ))
+    }
 
-        // Send the request
+    /// Posts a message payload once, classifying failures as transient
+    /// (network errors, timeouts, `5xx`) or permanent (any other status).
+    async fn post_message<P: Serialize + Sync>(
+        &self,
+        url: &str,
+        api_token: &str,
+        payload: &P,
+    ) -> Result<(), SendFailure> {
         let response = self
             .http_client
-            .post(&url)
+            .post(url)
             .header("Authorization", format!("Bearer {}", api_token))
             .header("Content-Type", "application/json")
-            .json(&payload)
+            .timeout(SEND_TIMEOUT)
+            .json(payload)
             .send()
             .await
-            .map_err(|e| {
-                error!("Failed to send WhatsApp message: {}", e);
-                AppError::ServiceUnavailable(format!("WhatsApp API error: {}", e))
-            })?;
+            .map_err(|e| SendFailure::Transient(e.to_string()))?;
 
-        if response.status().is_success() {
-            info!(
-                "OTP sent successfully to {} with reference {}",
-                formatted_phone, reference_id
-            );
-            Ok(())
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        let reason = format!("status={}, body={}", status, error_text);
+        if status.is_server_error() {
+            Err(SendFailure::Transient(reason))
         } else {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            error!("WhatsApp API error: status={}, body={}", status, error_text);
-            Err(AppError::ServiceUnavailable(
-                "Failed to send WhatsApp message".to_string(),
-            ))
+            Err(SendFailure::Permanent(reason))
         }
     }
 }
 
+#[async_trait]
+impl MessageChannel for WhatsAppClient {
+    fn name(&self) -> &'static str {
+        "whatsapp"
+    }
+
+    fn can_deliver(&self, recipient: &Recipient) -> bool {
+        self.is_enabled() && recipient.phone.is_some()
+    }
+
+    async fn send(
+        &self,
+        recipient: &Recipient,
+        message: &channel::Message,
+    ) -> Result<(), AppError> {
+        let phone = recipient
+            .phone
+            .as_deref()
+            .ok_or_else(|| AppError::BadRequest("Recipient has no phone number".to_string()))?;
+
+        match message {
+            channel::Message::Otp {
+                code, reference_id, ..
+            } => self.send_otp(phone, code, reference_id).await,
+            channel::Message::Text { body, .. } => self.send_text(phone, body).await,
+        }
+    }
+}
+
+/// Compares two byte strings in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// A message status reported by a webhook callback.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StatusUpdate {
+    /// WhatsApp message ID.
+    pub message_id: String,
+    /// OTP reference the message was sent for, `None` for other messages.
+    pub reference_id: Option<String>,
+    pub status: DeliveryStatus,
+}
+
+/// Extracts the message statuses from a webhook callback body.
+///
+/// Other changes, such as incoming messages, and statuses we don't track
+/// (e.g. `deleted`) are skipped.
+///
+/// # Errors
+///
+/// Returns [`AppError::BadRequest`] if the body is not a callback payload.
+pub fn parse_status_callback(body: &[u8]) -> Result<Vec<StatusUpdate>, AppError> {
+    let callback: WebhookCallback = serde_json::from_slice(body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid webhook payload: {}", e)))?;
+
+    let statuses = callback
+        .entry
+        .into_iter()
+        .flat_map(|entry| entry.changes)
+        .flat_map(|change| change.value.statuses)
+        .filter_map(|status| {
+            let delivery = match status.status.as_str() {
+                "sent" => DeliveryStatus::Sent,
+                "delivered" => DeliveryStatus::Delivered,
+                "read" => DeliveryStatus::Read,
+                "failed" => DeliveryStatus::Failed,
+                _ => return None,
+            };
+            if delivery == DeliveryStatus::Failed {
+                for error in &status.errors {
+                    warn!(
+                        "WhatsApp message {} failed: {} ({})",
+                        status.id, error.title, error.code
+                    );
+                }
+            }
+            Some(StatusUpdate {
+                message_id: status.id,
+                reference_id: status.biz_opaque_callback_data,
+                status: delivery,
+            })
+        })
+        .collect();
+    Ok(statuses)
+}
+
+/// Why a single WhatsApp API call failed.
+enum SendFailure {
+    /// Worth retrying: network error, timeout, or `5xx` response.
+    Transient(String),
+    /// Not worth retrying, e.g. a `4xx` caused by the request itself.
+    Permanent(String),
+}
+
 // WhatsApp API request/response structures
 
 #[derive(Serialize)]
-struct WhatsAppMessageRequest {
+struct WhatsAppTextRequest {
     #[serde(rename = "messaging_product")]
     messaging_product: String,
     #[serde(rename = "recipient_type")]
@@ -161,33 +448,56 @@ struct WhatsAppMessageRequest {
     to: String,
     #[serde(rename = "type")]
     type_: String,
-    template: Template,
+    text: TextBody,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biz_opaque_callback_data: Option<String>,
 }
 
 #[derive(Serialize)]
-struct Template {
-    name: String,
-    language: Language,
-    components: Vec<Component>,
+struct TextBody {
+    body: String,
 }
 
-#[derive(Serialize)]
-struct Language {
-    code: String,
+// WhatsApp webhook callback structures
+
+#[derive(Deserialize)]
+struct WebhookCallback {
+    #[serde(default)]
+    entry: Vec<WebhookEntry>,
 }
 
-#[derive(Serialize)]
-struct Component {
-    #[serde(rename = "type")]
-    type_: String,
-    parameters: Vec<Parameter>,
+#[derive(Deserialize)]
+struct WebhookEntry {
+    #[serde(default)]
+    changes: Vec<WebhookChange>,
 }
 
-#[derive(Serialize)]
-struct Parameter {
-    #[serde(rename = "type")]
-    type_: String,
-    text: String,
+#[derive(Deserialize)]
+struct WebhookChange {
+    #[serde(default)]
+    value: WebhookChangeValue,
+}
+
+#[derive(Deserialize, Default)]
+struct WebhookChangeValue {
+    #[serde(default)]
+    statuses: Vec<WebhookStatus>,
+}
+
+#[derive(Deserialize)]
+struct WebhookStatus {
+    id: String,
+    status: String,
+    biz_opaque_callback_data: Option<String>,
+    #[serde(default)]
+    errors: Vec<WebhookError>,
+}
+
+#[derive(Deserialize)]
+struct WebhookError {
+    code: i64,
+    #[serde(default)]
+    title: String,
 }
 
 #[derive(Deserialize)]
@@ -213,4 +523,225 @@ mod tests {
         let client = WhatsAppClient::new(config);
         assert!(!client.is_enabled());
     }
+
+    fn enabled_config(api_url: String) -> Arc<Config> {
+        let mut config = Config::default();
+        config.whatsapp.enabled = true;
+        config.whatsapp.api_url = Some(api_url);
+        config.whatsapp.api_token = Some("token".to_string());
+        config.whatsapp.phone_number_id = Some("123".to_string());
+        Arc::new(config)
+    }
+
+    #[test]
+    fn test_render_otp_message() {
+        assert_eq!(
+            render_otp_message("{code} / {minutes} menit / {code}", "123456", 300),
+            "123456 / 5 menit / 123456"
+        );
+        assert_eq!(
+            render_otp_message(DEFAULT_OTP_MESSAGE_TEMPLATE, "654321", 90),
+            "Kode verifikasi MonitorMBG Anda: 654321. Kode berlaku selama 1 menit. \
+             Jangan berikan kode ini kepada siapa pun."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_otp_sends_rendered_template() {
+        let server = httpmock::MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::POST)
+                    .path("/123/messages")
+                    .json_body(serde_json::json!({
+                        "messaging_product": "whatsapp",
+                        "recipient_type": "individual",
+                        "to": "+628123456789",
+                        "type": "text",
+                        "text": { "body": "[SPPG] 123456 berlaku 10 menit" },
+                        "biz_opaque_callback_data": "ref"
+                    }));
+                then.status(200);
+            })
+            .await;
+
+        let mut config = (*enabled_config(server.base_url())).clone();
+        config.whatsapp.otp_expiry_seconds = 600;
+        config.whatsapp.otp_message_template =
+            Some("[SPPG] {code} berlaku {minutes} menit".to_string());
+        let client = WhatsAppClient::new(Arc::new(config));
+
+        client
+            .send_otp("08123456789", "123456", "ref")
+            .await
+            .unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_otp_retries_server_errors() {
+        let server = httpmock::MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::POST).path("/123/messages");
+                then.status(503);
+            })
+            .await;
+
+        let mut config = (*enabled_config(server.base_url())).clone();
+        config.whatsapp.send_attempts = 3;
+        config.whatsapp.retry_delay_ms = 1;
+        let client = WhatsAppClient::new(Arc::new(config));
+
+        let result = client.send_otp("+628123456789", "123456", "ref").await;
+        assert!(matches!(result, Err(AppError::ServiceUnavailable(_))));
+        assert_eq!(mock.hits_async().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_otp_does_not_retry_client_errors() {
+        let server = httpmock::MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::POST).path("/123/messages");
+                then.status(400);
+            })
+            .await;
+
+        let mut config = (*enabled_config(server.base_url())).clone();
+        config.whatsapp.send_attempts = 3;
+        config.whatsapp.retry_delay_ms = 1;
+        let client = WhatsAppClient::new(Arc::new(config));
+
+        let result = client.send_otp("+628123456789", "123456", "ref").await;
+        assert!(matches!(result, Err(AppError::ServiceUnavailable(_))));
+        assert_eq!(mock.hits_async().await, 1);
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"entry":[]}"#;
+        let client = WhatsAppClient::new(Arc::new(Config::default()));
+        assert!(matches!(
+            client.verify_signature(body, None),
+            Err(AppError::ServiceUnavailable(_))
+        ));
+
+        let mut config = Config::default();
+        config.whatsapp.app_secret = Some("secret".to_string());
+        let client = WhatsAppClient::new(Arc::new(config));
+        let signature = sign("secret", body);
+        assert!(client.verify_signature(body, Some(&signature)).is_ok());
+        assert!(
+            client
+                .verify_signature(
+                    body,
+                    Some(&signature.to_uppercase().replace("SHA256", "sha256"))
+                )
+                .is_ok()
+        );
+        for wrong in [
+            None,
+            Some(""),
+            Some("sha256=00"),
+            Some(&*sign("other", body)),
+        ] {
+            assert!(matches!(
+                client.verify_signature(body, wrong),
+                Err(AppError::Unauthorized(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_status_callback() {
+        let body = serde_json::json!({
+            "object": "whatsapp_business_account",
+            "entry": [{
+                "id": "WABA_ID",
+                "changes": [{
+                    "field": "messages",
+                    "value": {
+                        "messaging_product": "whatsapp",
+                        "statuses": [
+                            {
+                                "id": "wamid.1",
+                                "status": "delivered",
+                                "timestamp": "1700000000",
+                                "recipient_id": "628123456789",
+                                "biz_opaque_callback_data": "otp_1"
+                            },
+                            {
+                                "id": "wamid.2",
+                                "status": "failed",
+                                "timestamp": "1700000000",
+                                "recipient_id": "628123456789",
+                                "errors": [{ "code": 131026, "title": "Message undeliverable" }]
+                            },
+                            { "id": "wamid.3", "status": "deleted" }
+                        ]
+                    }
+                }, {
+                    "field": "messages",
+                    "value": { "messages": [{ "id": "wamid.4", "type": "text" }] }
+                }]
+            }]
+        });
+
+        let statuses = parse_status_callback(body.to_string().as_bytes()).unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                StatusUpdate {
+                    message_id: "wamid.1".to_string(),
+                    reference_id: Some("otp_1".to_string()),
+                    status: DeliveryStatus::Delivered,
+                },
+                StatusUpdate {
+                    message_id: "wamid.2".to_string(),
+                    reference_id: None,
+                    status: DeliveryStatus::Failed,
+                },
+            ]
+        );
+        assert!(matches!(
+            parse_status_callback(b"not json"),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_disabled() {
+        let client = WhatsAppClient::new(Arc::new(Config::default()));
+        assert_eq!(client.health_check().await, "disabled");
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reachable() {
+        let server = httpmock::MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::HEAD);
+                then.status(404);
+            })
+            .await;
+
+        let client = WhatsAppClient::new(enabled_config(server.base_url()));
+        assert_eq!(client.health_check().await, "healthy");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_health_check_unreachable() {
+        let server = httpmock::MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::HEAD);
+                then.status(503);
+            })
+            .await;
+
+        let client = WhatsAppClient::new(enabled_config(server.base_url()));
+        assert_eq!(client.health_check().await, "unhealthy");
+    }
 }
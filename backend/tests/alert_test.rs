@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use backend::auth::middleware::AuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::database::model::Alert;
+use backend::database::model::Kitchen;
+use backend::database::model::User;
+use backend::database::model::UserRole;
+use backend::database::table::Table;
+use backend::routes::alert::AlertState;
+use backend::routes::alert::alert_routes;
+use backend::service::alert::AlertService;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+
+fn acknowledge(token: &str, ids: &[Uuid]) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/alerts/acknowledge")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(serde_json::json!({ "ids": ids }).to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_acknowledge_alerts() {
+    let (db, db_name) = common::setup_db().await;
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = AlertState {
+        service: Arc::new(AlertService::new(db.clone())),
+    };
+    let app = Router::new().nest(
+        "/alerts",
+        alert_routes(
+            state,
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    );
+
+    let owner_id = db
+        .user_table
+        .insert(&User {
+            name: "Kitchen Owner".to_string(),
+            role: UserRole::Kitchen,
+            unique_code: "KTCH-001".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let own_kitchen = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Own Kitchen".to_string(),
+            owner_id: Some(owner_id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let other_kitchen = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Other Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let mut own_alerts = Vec::new();
+    for title in ["Low hygiene score", "Missed inspection"] {
+        let id = db
+            .alert_table
+            .insert(&Alert {
+                kitchen_id: Some(own_kitchen),
+                title: title.to_string(),
+                message: "Check the kitchen".to_string(),
+                acknowledged: Some(false),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        own_alerts.push(id);
+    }
+    let other_alert = db
+        .alert_table
+        .insert(&Alert {
+            kitchen_id: Some(other_kitchen),
+            title: "Temperature violation".to_string(),
+            message: "Check the kitchen".to_string(),
+            acknowledged: Some(false),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let owner_token = generate_token(
+        owner_id,
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(acknowledge(&school_token, &own_alerts))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(acknowledge(&admin_token, &[]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Owners only acknowledge their own kitchens' alerts; duplicates count once
+    let unknown = Uuid::new_v4();
+    let response = app
+        .clone()
+        .oneshot(acknowledge(
+            &owner_token,
+            &[own_alerts[0], own_alerts[0], other_alert, unknown],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    assert_eq!(body["acknowledged"], 1);
+    assert_eq!(body["notFound"], 2);
+    assert_eq!(
+        body["notFoundIds"],
+        serde_json::json!([other_alert, unknown])
+    );
+
+    let acknowledged = |id: Uuid| {
+        let db = db.clone();
+        async move {
+            db.alert_table
+                .select(&id)
+                .await
+                .unwrap()
+                .unwrap()
+                .acknowledged
+        }
+    };
+    assert_eq!(acknowledged(own_alerts[0]).await, Some(true));
+    assert_eq!(acknowledged(own_alerts[1]).await, Some(false));
+    assert_eq!(acknowledged(other_alert).await, Some(false));
+
+    // Admins acknowledge any alert, including already acknowledged ones
+    let response = app
+        .clone()
+        .oneshot(acknowledge(
+            &admin_token,
+            &[own_alerts[0], own_alerts[1], other_alert],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    assert_eq!(body["acknowledged"], 3);
+    assert_eq!(body["notFound"], 0);
+    assert_eq!(acknowledged(other_alert).await, Some(true));
+
+    common::teardown_db(db, db_name).await;
+}
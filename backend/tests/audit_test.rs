@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use backend::auth::middleware::AuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::database::model::AuditLog;
+use backend::database::model::User;
+use backend::database::model::UserRole;
+use backend::database::table::Table;
+use backend::routes::audit::AuditLogState;
+use backend::routes::audit::audit_log_routes;
+use backend::service::audit::AuditService;
+use chrono::NaiveDate;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+
+fn entity_ids(body: &serde_json::Value) -> Vec<&str> {
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|log| log["entityId"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_list_audit_logs() {
+    let (db, db_name) = common::setup_db().await;
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = AuditLogState {
+        service: Arc::new(AuditService::new(db.clone())),
+    };
+    let app = Router::new().nest(
+        "/audit-logs",
+        audit_log_routes(
+            state,
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    );
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let kitchen_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let mut users = Vec::new();
+    for (name, unique_code) in [("Alice", "SCH-001"), ("Bob", "SCH-002")] {
+        let id = db
+            .user_table
+            .insert(&User {
+                name: name.to_string(),
+                role: UserRole::School,
+                unique_code: unique_code.to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        users.push(id);
+    }
+    let (alice, bob) = (users[0], users[1]);
+    let at = |day, hour| {
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .map(|t| t.and_utc())
+    };
+    for (user_id, action, entity_type, entity_id, timestamp) in [
+        (alice, "POST /reviews", "review", "r1", at(10, 8)),
+        (alice, "PATCH /reviews/r1", "review", "r1", at(12, 9)),
+        (bob, "POST /incidents", "incident", "i1", at(15, 10)),
+        (bob, "DELETE /reviews/r2", "review", "r2", at(20, 23)),
+    ] {
+        db.audit_log_table
+            .insert(&AuditLog {
+                user_id: Some(user_id),
+                action: action.to_string(),
+                entity_type: entity_type.to_string(),
+                entity_id: entity_id.to_string(),
+                timestamp,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    // Admins only
+    let response = app
+        .clone()
+        .oneshot(common::get_with_token("/audit-logs", &kitchen_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/audit-logs")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Newest first, paginated
+    let response = app
+        .clone()
+        .oneshot(common::get_with_token("/audit-logs?limit=2", &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    assert_eq!(entity_ids(&body), vec!["r2", "i1"]);
+    assert_eq!(body["pagination"]["total"], 4);
+    assert_eq!(body["pagination"]["hasMore"], true);
+
+    // Filters
+    let cases = [
+        (format!("userId={}", alice), vec!["r1", "r1"]),
+        ("entityType=incident".to_string(), vec!["i1"]),
+        ("action=post".to_string(), vec!["i1", "r1"]),
+        ("action=PATCH%20/reviews".to_string(), vec!["r1"]),
+        (
+            "from=2025-01-12&to=2025-01-15".to_string(),
+            vec!["i1", "r1"],
+        ),
+        ("to=2025-01-20".to_string(), vec!["r2", "i1", "r1", "r1"]),
+        (
+            format!("userId={}&entityType=review&from=2025-01-11", bob),
+            vec!["r2"],
+        ),
+    ];
+    for (query, expected) in cases {
+        let response = app
+            .clone()
+            .oneshot(common::get_with_token(
+                format!("/audit-logs?{}", query),
+                &admin_token,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", query);
+        let body = common::json_body(response).await;
+        assert_eq!(entity_ids(&body), expected, "{}", query);
+        assert_eq!(body["pagination"]["total"], expected.len(), "{}", query);
+    }
+
+    // Invalid date ranges
+    for query in ["from=last-week", "from=2025-01-20&to=2025-01-10"] {
+        let response = app
+            .clone()
+            .oneshot(common::get_with_token(
+                format!("/audit-logs?{}", query),
+                &admin_token,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", query);
+    }
+
+    common::teardown_db(db, db_name).await;
+}
@@ -5,6 +5,7 @@ use axum::body::Body;
 use axum::http::Request;
 use axum::http::StatusCode;
 use axum::routing::post;
+use backend::auth::utils::JwtKeys;
 use backend::config::Config;
 use backend::database::model::UserRole;
 use backend::routes::auth::AuthState;
@@ -27,7 +28,11 @@ async fn test_auth_validation_and_security() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
     let otp_service = Arc::new(OtpService::new(config.clone()));
 
     let state = AuthState {
@@ -4,19 +4,36 @@ use axum::Router;
 use axum::body::Body;
 use axum::http::Request;
 use axum::http::StatusCode;
+use axum::routing::get;
 use axum::routing::post;
+use backend::auth::middleware::AuthState as MiddlewareAuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::auth::utils::hash_password;
+use backend::auth::utils::needs_rehash;
+use backend::auth::utils::validate_token;
 use backend::config::Config;
+use backend::config::LoginLockoutConfig;
+use backend::config::PasswordHashConfig;
+use backend::database::model::User;
 use backend::database::model::UserRole;
+use backend::database::table::Table;
 use backend::routes::auth::AuthState;
 use backend::routes::auth::LoginRequest;
 use backend::routes::auth::RegisterRequest;
+use backend::routes::auth::auth_routes;
 use backend::routes::auth::login_handler;
+use backend::routes::auth::otp_status_handler;
 use backend::routes::auth::register_handler;
 use tower::util::ServiceExt;
+use uuid::Uuid;
 
 mod common;
 
 use backend::service::auth::AuthService;
+use backend::service::channel::ChannelDispatcher;
+use backend::service::channel::Recipient;
+use backend::service::otp::DeliveryStatus;
 use backend::service::otp::OtpService;
 
 #[tokio::test]
@@ -27,7 +44,11 @@ async fn test_register_and_login_success() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
 
     let otp_service = Arc::new(OtpService::new(config.clone()));
     let state = AuthState {
@@ -67,6 +88,17 @@ async fn test_register_and_login_success() {
 
     assert_eq!(response.status(), StatusCode::CREATED);
 
+    // The new user is signed in without calling /login
+    let registered = common::json_body(response).await;
+    let claims = validate_token(
+        registered["token"].as_str().unwrap(),
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    assert_eq!(registered["user"]["id"], serde_json::json!(claims.sub));
+    assert_eq!(claims.role, UserRole::Kitchen);
+    assert_eq!(registered["expiresIn"], 3600);
+
     // 2. Login
     let login_payload = LoginRequest {
         unique_code: "KITCHEN_TEST".to_string(),
@@ -122,7 +154,11 @@ async fn test_register_duplicate_unique_code() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
 
     let otp_service = Arc::new(OtpService::new(config.clone()));
     let state = AuthState {
@@ -186,7 +222,63 @@ async fn test_register_duplicate_unique_code() {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_concurrent_register_same_unique_code() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+
+    let state = AuthState {
+        service: Arc::new(AuthService::new(
+            db.clone(),
+            config.clone(),
+            Arc::new(JwtKeys::from_config(&config).unwrap()),
+        )),
+        otp_service: Arc::new(OtpService::new(config.clone())),
+    };
+    let app = Router::new()
+        .route("/register", post(register_handler))
+        .with_state(state);
+
+    let register = |name: &str| {
+        let payload = RegisterRequest {
+            name: name.to_string(),
+            role: UserRole::Kitchen,
+            unique_code: "RACE_CODE".to_string(),
+            password: "password123".to_string(),
+            phone: None,
+            institution_name: None,
+        };
+        app.clone().oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/register")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap(),
+        )
+    };
+
+    let (first, second) = tokio::join!(register("First User"), register("Second User"));
+    let mut statuses = [first.unwrap().status(), second.unwrap().status()];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::CREATED, StatusCode::CONFLICT]);
+
+    let users = db.user_table.select_all().await.unwrap();
+    assert_eq!(
+        users
+            .iter()
+            .filter(|u| u.unique_code == "RACE_CODE")
+            .count(),
+        1
+    );
 
     common::teardown_db(db, db_name).await;
 }
@@ -199,7 +291,11 @@ async fn test_register_validation_errors() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
 
     let otp_service = Arc::new(OtpService::new(config.clone()));
     let state = AuthState {
@@ -319,6 +415,95 @@ async fn test_register_validation_errors() {
 
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
+    // Test invalid phone number
+    let register_payload = RegisterRequest {
+        name: "Test User".to_string(),
+        role: UserRole::Kitchen,
+        unique_code: "TEST004".to_string(),
+        password: "password123".to_string(),
+        phone: Some("12345".to_string()),
+        institution_name: None,
+    };
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/register")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&register_payload).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_register_normalizes_phone() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+
+    let state = AuthState {
+        service: Arc::new(AuthService::new(
+            db.clone(),
+            config.clone(),
+            Arc::new(JwtKeys::from_config(&config).unwrap()),
+        )),
+        otp_service: Arc::new(OtpService::new(config.clone())),
+    };
+    let app = Router::new()
+        .route("/register", post(register_handler))
+        .with_state(state);
+
+    let cases = [
+        ("PHONE001", Some("08123456789"), Some("628123456789")),
+        ("PHONE002", Some("+628123456789"), Some("628123456789")),
+        ("PHONE003", Some(" 628123456789 "), Some("628123456789")),
+        ("PHONE004", Some(""), None),
+        ("PHONE005", None, None),
+    ];
+    for (unique_code, phone, expected) in cases {
+        let register_payload = RegisterRequest {
+            name: "Test User".to_string(),
+            role: UserRole::School,
+            unique_code: unique_code.to_string(),
+            password: "password123".to_string(),
+            phone: phone.map(str::to_string),
+            institution_name: None,
+        };
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/register")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(
+                        serde_json::to_string(&register_payload).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED, "{:?}", phone);
+
+        let body = common::json_body(response).await;
+        let user_id: Uuid = serde_json::from_value(body["user"]["id"].clone()).unwrap();
+        let user = db.user_table.select(&user_id).await.unwrap().unwrap();
+        assert_eq!(user.phone.as_deref(), expected, "{:?}", phone);
+    }
+
     common::teardown_db(db, db_name).await;
 }
 
@@ -330,7 +515,11 @@ async fn test_login_nonexistent_user() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
 
     let otp_service = Arc::new(OtpService::new(config.clone()));
     let state = AuthState {
@@ -373,7 +562,11 @@ async fn test_register_different_roles() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
 
     let otp_service = Arc::new(OtpService::new(config.clone()));
     let state = AuthState {
@@ -435,7 +628,11 @@ async fn test_register_input_sanitization() {
         ..Default::default()
     });
 
-    let auth_service = Arc::new(AuthService::new(db.clone(), config.clone()));
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
 
     let otp_service = Arc::new(OtpService::new(config.clone()));
     let state = AuthState {
@@ -498,3 +695,552 @@ async fn test_register_input_sanitization() {
 
     common::teardown_db(db, db_name).await;
 }
+
+#[tokio::test]
+async fn test_login_lockout_and_reset() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        login_lockout: LoginLockoutConfig {
+            max_failed_attempts: 3,
+            window_seconds: 900,
+        },
+        ..Default::default()
+    });
+
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
+
+    let otp_service = Arc::new(OtpService::new(config.clone()));
+    let state = AuthState {
+        service: auth_service,
+        otp_service,
+    };
+
+    let app = Router::new()
+        .route("/register", post(register_handler))
+        .route("/login", post(login_handler))
+        .with_state(state);
+
+    let register_payload = RegisterRequest {
+        name: "Lockout User".to_string(),
+        role: UserRole::Kitchen,
+        unique_code: "LOCKOUT_TEST".to_string(),
+        password: "password123".to_string(),
+        phone: None,
+        institution_name: None,
+    };
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/register")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&register_payload).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let login = |unique_code: &str, password: &str| {
+        let payload = LoginRequest {
+            unique_code: unique_code.to_string(),
+            password: password.to_string(),
+        };
+        let app = app.clone();
+        async move {
+            app.oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/login")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+        }
+    };
+
+    // A successful login resets the failure counter
+    for _ in 0..2 {
+        assert_eq!(
+            login("LOCKOUT_TEST", "wrongpassword").await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+    assert_eq!(login("LOCKOUT_TEST", "password123").await, StatusCode::OK);
+
+    // Three consecutive failures lock the account, even for the right password
+    for _ in 0..3 {
+        assert_eq!(
+            login("LOCKOUT_TEST", "wrongpassword").await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+    assert_eq!(
+        login("LOCKOUT_TEST", "password123").await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Unknown unique codes are locked the same way, so lockout doesn't reveal existence
+    for _ in 0..3 {
+        assert_eq!(
+            login("NONEXISTENT", "password123").await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+    assert_eq!(
+        login("NONEXISTENT", "password123").await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_login_upgrades_outdated_password_hash() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let auth_service = AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    );
+
+    // A hash made before the Argon2 parameters were raised
+    let old_params = PasswordHashConfig {
+        memory_cost_kib: 8192,
+        iterations: 1,
+        parallelism: 1,
+    };
+    let old_hash = hash_password("password123", &old_params).unwrap();
+    let user = User {
+        name: "Legacy User".to_string(),
+        role: UserRole::School,
+        unique_code: "REHASH_TEST".to_string(),
+        password_hash: Some(old_hash.clone()),
+        ..Default::default()
+    };
+    let user_id = db.user_table.insert(&user).await.unwrap();
+
+    auth_service
+        .login_user("REHASH_TEST".to_string(), "password123".to_string())
+        .await
+        .unwrap();
+
+    let new_hash = db
+        .user_table
+        .select(&user_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .password_hash
+        .unwrap();
+    assert_ne!(new_hash, old_hash);
+    assert!(!needs_rehash(&new_hash, &config.password_hash));
+
+    // The upgraded hash still accepts the same password
+    auth_service
+        .login_user("REHASH_TEST".to_string(), "password123".to_string())
+        .await
+        .unwrap();
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_login_records_last_login_and_me_returns_profile() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let auth_service = Arc::new(AuthService::new(
+        db.clone(),
+        config.clone(),
+        Arc::new(JwtKeys::from_config(&config).unwrap()),
+    ));
+    let state = AuthState {
+        service: auth_service.clone(),
+        otp_service: Arc::new(OtpService::new(config.clone())),
+    };
+    let app = Router::new().nest(
+        "/auth",
+        auth_routes(
+            state,
+            MiddlewareAuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    );
+
+    let (_, user) = auth_service
+        .register_user(
+            "Ibu Ani".to_string(),
+            UserRole::Kitchen,
+            "ME_CODE".to_string(),
+            "password123".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(user.last_login.is_none());
+
+    let login = LoginRequest {
+        unique_code: "ME_CODE".to_string(),
+        password: "password123".to_string(),
+    };
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/auth/login")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_string(&login).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    assert!(!body["user"]["last_login"].is_null());
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let stored = db.user_table.select(&user.id).await.unwrap().unwrap();
+    assert!(stored.last_login.is_some());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/auth/me")
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    assert_eq!(body["id"], user.id.to_string());
+    assert_eq!(body["unique_code"], "ME_CODE");
+    assert!(body["password_hash"].is_null());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/auth/me")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_admin_verifies_user() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = AuthState {
+        service: Arc::new(AuthService::new(
+            db.clone(),
+            config.clone(),
+            Arc::new(JwtKeys::from_config(&config).unwrap()),
+        )),
+        otp_service: Arc::new(OtpService::new(config.clone())),
+    };
+    let app = Router::new().nest(
+        "/auth",
+        auth_routes(
+            state,
+            MiddlewareAuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    );
+
+    let user_id = db
+        .user_table
+        .insert(&User {
+            name: "Pak Budi".to_string(),
+            role: UserRole::Supplier,
+            unique_code: "VERIFY_ME".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let kitchen_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let verify = |id: Uuid, token: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/auth/users/{}/verify", id))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(verify(user_id, &kitchen_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(verify(Uuid::new_v4(), &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.oneshot(verify(user_id, &admin_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(common::json_body(response).await["verified"], true);
+
+    let stored = db.user_table.select(&user_id).await.unwrap().unwrap();
+    assert_eq!(stored.verified, Some(true));
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_deactivated_user_cannot_log_in_or_use_tokens() {
+    let (db, db_name) = common::setup_db().await;
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = AuthState {
+        service: Arc::new(AuthService::new(
+            db.clone(),
+            config.clone(),
+            Arc::new(JwtKeys::from_config(&config).unwrap()),
+        )),
+        otp_service: Arc::new(OtpService::new(config.clone())),
+    };
+    let app = Router::new().nest(
+        "/auth",
+        auth_routes(
+            state,
+            MiddlewareAuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: Some(db.clone()),
+            },
+        ),
+    );
+
+    let user_id = db
+        .user_table
+        .insert(&User {
+            name: "Pak Inspektur".to_string(),
+            role: UserRole::Supplier,
+            unique_code: "INSPECTOR_1".to_string(),
+            password_hash: Some(hash_password("password123", &config.password_hash).unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_id = db
+        .user_table
+        .insert(&User {
+            name: "Admin".to_string(),
+            role: UserRole::Admin,
+            unique_code: "ADMIN_1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_token = generate_token(
+        admin_id,
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let login = |password: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/auth/login")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "unique_code": "INSPECTOR_1", "password": password })
+                    .to_string(),
+            ))
+            .unwrap()
+    };
+    let me = |token: &str| {
+        Request::builder()
+            .uri("/auth/me")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let deactivate = |id: Uuid, token: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/auth/users/{}/deactivate", id))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(login("password123")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let user_token = common::json_body(response).await["token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app.clone().oneshot(me(&user_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(common::json_body(response).await["active"], true);
+
+    // Only admins may deactivate, and not themselves
+    let response = app
+        .clone()
+        .oneshot(deactivate(admin_id, &user_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = app
+        .clone()
+        .oneshot(deactivate(admin_id, &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = app
+        .clone()
+        .oneshot(deactivate(Uuid::new_v4(), &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .clone()
+        .oneshot(deactivate(user_id, &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(common::json_body(response).await["active"], false);
+
+    // Tokens issued before deactivation stop working
+    let response = app.clone().oneshot(me(&user_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app.clone().oneshot(login("password123")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(
+        common::json_body(response).await["error"]
+            .as_str()
+            .unwrap()
+            .contains("account deactivated")
+    );
+
+    // A wrong password is still just invalid credentials
+    let response = app.clone().oneshot(login("wrong-password")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // The account itself is kept
+    let stored = db.user_table.select(&user_id).await.unwrap().unwrap();
+    assert_eq!(stored.active, Some(false));
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_otp_status_reports_delivery_status() {
+    let (db, db_name) = common::setup_db().await;
+    let mut config = Config::default();
+    config.whatsapp.otp_expiry_seconds = 300;
+    let config = Arc::new(config);
+
+    let otp_service = Arc::new(OtpService::with_channels(
+        config.clone(),
+        ChannelDispatcher::new(Vec::new()),
+    ));
+    let reference_id = otp_service
+        .send_otp(Recipient {
+            phone: Some("08123456789".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .reference_id;
+    let state = AuthState {
+        service: Arc::new(AuthService::new(
+            db.clone(),
+            config.clone(),
+            Arc::new(JwtKeys::from_config(&config).unwrap()),
+        )),
+        otp_service: otp_service.clone(),
+    };
+    let app = Router::new()
+        .route("/otp/{reference_id}/status", get(otp_status_handler))
+        .with_state(state);
+    let get_status = |reference_id: &str| {
+        app.clone().oneshot(
+            Request::builder()
+                .uri(format!("/otp/{}/status", reference_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = get_status(&reference_id).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = common::json_body(response).await;
+    assert_eq!(json["referenceId"], reference_id.as_str());
+    assert!(json["deliveryStatus"].is_null());
+    assert_eq!(json["verified"], false);
+
+    otp_service
+        .record_delivery_status(&reference_id, DeliveryStatus::Failed)
+        .await;
+    let json = common::json_body(get_status(&reference_id).await.unwrap()).await;
+    assert_eq!(json["deliveryStatus"], "failed");
+
+    let response = get_status("otp_unknown").await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    common::teardown_db(db, db_name).await;
+}
@@ -1,5 +1,14 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test binary compiles its own copy of this module and uses only some of
+//! it, hence the `dead_code` allowance.
+#![allow(dead_code)]
+
 use std::sync::Arc;
 
+use axum::body::Body;
+use axum::http::Request;
+use axum::response::Response;
 use backend::database::Database;
 use dotenv::dotenv;
 use sqlx::migrate::MigrateDatabase;
@@ -45,7 +54,7 @@ pub async fn teardown_db(db: Arc<Database>, db_name: String) {
     // Try to drop the database with retry logic
     let mut retries = 3;
     while retries > 0 {
-        match Postgres::drop_database(&db_url).await {
+        match Postgres::force_drop_database(&db_url).await {
             Ok(_) => break,
             Err(e) => {
                 retries -= 1;
@@ -58,3 +67,41 @@ pub async fn teardown_db(db: Arc<Database>, db_name: String) {
         }
     }
 }
+
+/// Reads a response body as JSON.
+pub async fn json_body(response: Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+/// Builds an unauthenticated `GET` request.
+pub fn get(uri: impl AsRef<str>) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(uri.as_ref())
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Builds a `GET` request carrying a bearer token.
+pub fn get_with_token(uri: impl AsRef<str>, token: &str) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(uri.as_ref())
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Builds a `POST` request with a JSON body and a bearer token.
+pub fn post_json(uri: impl AsRef<str>, token: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri.as_ref())
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
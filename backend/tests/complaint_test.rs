@@ -0,0 +1,677 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use backend::auth::middleware::AuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::database::model::Complaint;
+use backend::database::model::ComplaintCategory;
+use backend::database::model::ComplaintComment;
+use backend::database::model::ComplaintEvidence;
+use backend::database::model::ComplaintStatus;
+use backend::database::model::Kitchen;
+use backend::database::model::User;
+use backend::database::model::UserRole;
+use backend::database::table::AuditLogFilter;
+use backend::database::table::Table;
+use backend::routes::complaint::ComplaintState;
+use backend::routes::complaint::complaint_routes;
+use backend::service::complaint::ComplaintService;
+use chrono::Duration;
+use chrono::Utc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+
+fn request(method: &str, uri: String, token: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn app(db: Arc<backend::database::Database>) -> Router {
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = ComplaintState {
+        service: Arc::new(ComplaintService::new(db)),
+    };
+    Router::new().nest(
+        "/complaints",
+        complaint_routes(
+            state,
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    )
+}
+
+#[tokio::test]
+async fn test_complaint_lifecycle() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Complained Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let app = app(db.clone());
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let reporter_id = db
+        .user_table
+        .insert(&User {
+            name: "Ibu Sari".to_string(),
+            role: UserRole::School,
+            unique_code: "SCH-001".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let reporter_token = generate_token(
+        reporter_id,
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let other_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/complaints".into(),
+            &reporter_token,
+            serde_json::json!({
+                "kitchenId": kitchen_id,
+                "category": "Hygiene",
+                "description": "Hair found in rice"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/complaints".into(),
+            &reporter_token,
+            serde_json::json!({
+                "kitchenId": kitchen_id,
+                "category": "hygiene",
+                "description": "Hair found in rice"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let id: Uuid = common::json_body(response).await["id"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(location, format!("/api/complaints/{}", id));
+
+    // Hygiene complaints get the 24 hour default SLA
+    let complaint = db.complaint_table.select(&id).await.unwrap().unwrap();
+    assert_eq!(
+        complaint.sla_deadline.unwrap() - complaint.reported_at,
+        Duration::hours(24)
+    );
+
+    // Only the reporter and admins may add comments or evidence
+    let comment = serde_json::json!({ "message": "Photo attached" });
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            format!("/complaints/{}/comments", id),
+            &other_token,
+            comment.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            format!("/complaints/{}/comments", id),
+            &reporter_token,
+            comment,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let comment_id: Uuid = common::json_body(response).await["id"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let comment = db
+        .complaint_comment_table
+        .select(&comment_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(comment.author_id, Some(reporter_id));
+    assert_eq!(comment.author_name.as_deref(), Some("Ibu Sari"));
+    assert_eq!(comment.role.as_deref(), Some("school"));
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            format!("/complaints/{}/evidence", id),
+            &reporter_token,
+            serde_json::json!({
+                "url": "https://example.com/evidence.jpg",
+                "timestamp": "2026-03-01T12:00:00",
+                "captureMethod": "camera"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Status transitions are admin only and must follow pending -> in-progress -> resolved
+    let patch = |token: &str, body: serde_json::Value| {
+        request("PATCH", format!("/complaints/{}", id), token, body)
+    };
+    let response = app
+        .clone()
+        .oneshot(patch(
+            &reporter_token,
+            serde_json::json!({ "status": "in-progress" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(patch(
+            &admin_token,
+            serde_json::json!({ "status": "resolved", "resolution": "Staff retrained" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(patch(
+            &admin_token,
+            serde_json::json!({ "status": "in-progress" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(patch(
+            &admin_token,
+            serde_json::json!({ "status": "resolved", "resolution": "  " }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(patch(
+            &admin_token,
+            serde_json::json!({ "status": "resolved", "resolution": "Staff retrained" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let complaint = db.complaint_table.select(&id).await.unwrap().unwrap();
+    assert_eq!(complaint.status, Some(ComplaintStatus::Resolved));
+    assert_eq!(complaint.resolution.as_deref(), Some("Staff retrained"));
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_sla_breached_complaints() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Slow Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let now = Utc::now();
+    let complaint = |status: ComplaintStatus, deadline_hours: i64| Complaint {
+        kitchen_id,
+        category: ComplaintCategory::Taste,
+        description: "Cold food".to_string(),
+        status: Some(status),
+        reported_at: now - Duration::days(5),
+        sla_deadline: Some(now + Duration::hours(deadline_hours)),
+        ..Default::default()
+    };
+    let breached = db
+        .complaint_table
+        .insert(&complaint(ComplaintStatus::InProgress, -2))
+        .await
+        .unwrap();
+    db.complaint_table
+        .insert(&complaint(ComplaintStatus::Resolved, -2))
+        .await
+        .unwrap();
+    db.complaint_table
+        .insert(&complaint(ComplaintStatus::Pending, 2))
+        .await
+        .unwrap();
+
+    let app = app(db.clone());
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let get = |token: &str| {
+        Request::builder()
+            .method("GET")
+            .uri("/complaints/sla-breached")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(get(&school_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app.oneshot(get(&admin_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["id"], breached.to_string());
+    assert_eq!(data[0]["status"], "in-progress");
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_complaints_filters_and_orders_by_sla() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen = |name: &str| Kitchen {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&kitchen("Kitchen A"))
+        .await
+        .unwrap();
+    let other_kitchen_id = db
+        .kitchen_table
+        .insert(&kitchen("Kitchen B"))
+        .await
+        .unwrap();
+    let assignee_id = db
+        .user_table
+        .insert(&User {
+            name: "Support Agent".to_string(),
+            unique_code: "SUPPORT-1".to_string(),
+            role: UserRole::Admin,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let now = Utc::now();
+    let complaint = |kitchen_id, category, status, deadline_hours: Option<i64>| Complaint {
+        kitchen_id,
+        category,
+        description: "Complaint".to_string(),
+        status: Some(status),
+        reported_at: now - Duration::days(1),
+        sla_deadline: deadline_hours.map(|h| now + Duration::hours(h)),
+        ..Default::default()
+    };
+    let no_deadline = db
+        .complaint_table
+        .insert(&complaint(
+            kitchen_id,
+            ComplaintCategory::Taste,
+            ComplaintStatus::Pending,
+            None,
+        ))
+        .await
+        .unwrap();
+    let due_soon = db
+        .complaint_table
+        .insert(&Complaint {
+            assigned_to: Some(assignee_id),
+            ..complaint(
+                kitchen_id,
+                ComplaintCategory::Hygiene,
+                ComplaintStatus::InProgress,
+                Some(3),
+            )
+        })
+        .await
+        .unwrap();
+    let overdue = db
+        .complaint_table
+        .insert(&complaint(
+            kitchen_id,
+            ComplaintCategory::Hygiene,
+            ComplaintStatus::Pending,
+            Some(-3),
+        ))
+        .await
+        .unwrap();
+    let other_kitchen = db
+        .complaint_table
+        .insert(&complaint(
+            other_kitchen_id,
+            ComplaintCategory::Portion,
+            ComplaintStatus::Resolved,
+            Some(-10),
+        ))
+        .await
+        .unwrap();
+
+    let app = app(db.clone());
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let get = |query: &str, token: &str| {
+        Request::builder()
+            .method("GET")
+            .uri(format!("/complaints{}", query))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let ids = |body: &serde_json::Value| -> Vec<String> {
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let response = app.clone().oneshot(get("", &school_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Most urgent first, complaints without a deadline last
+    let response = app.clone().oneshot(get("", &admin_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = common::json_body(response).await;
+    assert_eq!(
+        ids(&body),
+        [other_kitchen, overdue, due_soon, no_deadline].map(|id| id.to_string())
+    );
+    assert_eq!(body["pagination"]["total"], 4);
+    let breached: Vec<bool> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["slaBreached"].as_bool().unwrap())
+        .collect();
+    assert_eq!(breached, [false, true, false, false]);
+
+    let response = app
+        .clone()
+        .oneshot(get(
+            &format!("?kitchenId={}&category=hygiene", kitchen_id),
+            &admin_token,
+        ))
+        .await
+        .unwrap();
+    let body = common::json_body(response).await;
+    assert_eq!(ids(&body), [overdue, due_soon].map(|id| id.to_string()));
+
+    let response = app
+        .clone()
+        .oneshot(get(&format!("?assignedTo={}", assignee_id), &admin_token))
+        .await
+        .unwrap();
+    let body = common::json_body(response).await;
+    assert_eq!(ids(&body), [due_soon.to_string()]);
+    assert_eq!(body["data"][0]["assignedTo"], assignee_id.to_string());
+
+    let response = app
+        .clone()
+        .oneshot(get("?status=pending&limit=1", &admin_token))
+        .await
+        .unwrap();
+    let body = common::json_body(response).await;
+    assert_eq!(ids(&body), [overdue.to_string()]);
+    assert_eq!(body["pagination"]["total"], 2);
+    assert_eq!(body["pagination"]["hasMore"], true);
+
+    // Everything was reported yesterday
+    let today = now.date_naive().to_string();
+    let response = app
+        .clone()
+        .oneshot(get(&format!("?from={}", today), &admin_token))
+        .await
+        .unwrap();
+    let body = common::json_body(response).await;
+    assert!(ids(&body).is_empty());
+
+    let response = app
+        .oneshot(get("?category=smell", &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_delete_complaint_records_audit_log() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Mistaken Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let complaint_id = db
+        .complaint_table
+        .insert(&Complaint {
+            kitchen_id,
+            category: ComplaintCategory::Taste,
+            description: "Filed against the wrong kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let comment_id = db
+        .complaint_comment_table
+        .insert(&ComplaintComment {
+            complaint_id,
+            message: "Wrong kitchen, sorry".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let evidence_id = db
+        .complaint_evidence_table
+        .insert(&ComplaintEvidence {
+            complaint_id,
+            url: "https://example.com/evidence.jpg".to_string(),
+            timestamp: Utc::now(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let app = app(db.clone());
+
+    let admin_id = db
+        .user_table
+        .insert(&User {
+            name: "Admin Dinkes".to_string(),
+            role: UserRole::Admin,
+            unique_code: "ADMIN-DEL".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_token = generate_token(
+        admin_id,
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let delete = |id: Uuid, token: &str| {
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/complaints/{}", id))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(delete(complaint_id, &school_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(delete(Uuid::new_v4(), &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .clone()
+        .oneshot(delete(complaint_id, &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Comments and evidence go with the complaint
+    assert!(
+        db.complaint_table
+            .select(&complaint_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        db.complaint_comment_table
+            .select(&comment_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        db.complaint_evidence_table
+            .select(&evidence_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let (logs, total) = db
+        .audit_log_table
+        .list(
+            &AuditLogFilter {
+                entity_type: Some("complaint".to_string()),
+                ..Default::default()
+            },
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(logs[0].entity_id, complaint_id.to_string());
+    assert_eq!(logs[0].user_id, Some(admin_id));
+    assert_eq!(logs[0].user_name.as_deref(), Some("Admin Dinkes"));
+    let deleted = &logs[0].metadata.as_ref().unwrap()["deleted"];
+    assert_eq!(deleted["id"], complaint_id.to_string());
+    assert_eq!(deleted["description"], "Filed against the wrong kitchen");
+
+    common::teardown_db(db, db_name).await;
+}
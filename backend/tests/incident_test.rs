@@ -4,18 +4,43 @@ use axum::Router;
 use axum::body::Body;
 use axum::http::Request;
 use axum::http::StatusCode;
+use axum::middleware;
 use axum::routing::get;
+use axum::routing::patch;
+use axum::routing::post;
+use backend::auth::middleware::AuthState;
+use backend::auth::middleware::auth_middleware;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::config::IncidentDedupConfig;
+use backend::config::WebhookConfig;
+use backend::database::model::AlertSeverity;
+use backend::database::model::AlertType;
 use backend::database::model::Incident;
 use backend::database::model::IncidentSeverity;
 use backend::database::model::IncidentSource;
 use backend::database::model::IncidentStatus;
+use backend::database::model::IncidentTimelineEvent;
 use backend::database::model::IncidentType;
 use backend::database::model::Kitchen;
+use backend::database::model::User;
+use backend::database::model::UserRole;
+use backend::database::table::AuditLogFilter;
 use backend::database::table::Table;
+use backend::error::AppError;
 use backend::routes::incident::IncidentState;
+use backend::routes::incident::add_corrective_action_handler;
+use backend::routes::incident::add_timeline_event_handler;
+use backend::routes::incident::create_incident_handler;
 use backend::routes::incident::get_incident_detail_handler;
+use backend::routes::incident::incident_routes;
 use backend::routes::incident::list_incidents_handler;
+use backend::routes::incident::update_incident_status_handler;
 use backend::service::incident::IncidentService;
+use backend::service::webhook::WebhookDispatcher;
+use backend::service::webhook::sign;
+use chrono::Utc;
 use tower::util::ServiceExt;
 use uuid::Uuid;
 
@@ -98,6 +123,7 @@ async fn test_list_incidents_with_data() {
         .with_state(state);
 
     let response = app
+        .clone()
         .oneshot(
             Request::builder()
                 .method("GET")
@@ -110,6 +136,142 @@ async fn test_list_incidents_with_data() {
 
     assert_eq!(response.status(), StatusCode::OK);
 
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/?status=resolved")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_search_incidents() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Test Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let incident = |description: &str| Incident {
+        kitchen_id,
+        description: Some(description.to_string()),
+        ..Default::default()
+    };
+    let rice = db
+        .incident_table
+        .insert(&incident("Students fell ill after eating stale rice"))
+        .await
+        .unwrap();
+    let chicken = db
+        .incident_table
+        .insert(&incident(
+            "Undercooked chicken served; students vomiting after lunch",
+        ))
+        .await
+        .unwrap();
+
+    let state = IncidentState {
+        service: Arc::new(IncidentService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/", get(list_incidents_handler))
+        .with_state(state);
+
+    let search = |query: &str| {
+        let app = app.clone();
+        let uri = format!("/?{}", query);
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            json["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["id"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(search("q=rice").await, vec![rice.to_string()]);
+    // Words need not be adjacent or in order
+    assert_eq!(
+        search("q=students%20chicken").await,
+        vec![chicken.to_string()]
+    );
+    assert_eq!(search("q=students%20ill").await, vec![rice.to_string()]);
+    // Keyset pagination applies the same filter
+    assert_eq!(
+        search("q=chicken%20lunch&cursor=").await,
+        vec![chicken.to_string()]
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_incidents_invalid_status() {
+    let (db, db_name) = common::setup_db().await;
+    let incident_service = Arc::new(IncidentService::new(db.clone()));
+
+    let state = IncidentState {
+        service: incident_service,
+    };
+
+    let app = Router::new()
+        .route("/", get(list_incidents_handler))
+        .with_state(state);
+
+    for uri in ["/?status=critical", "/?status=critical&cursor="] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("investigating, resolved, escalated"));
+    }
+
     common::teardown_db(db, db_name).await;
 }
 
@@ -131,6 +293,8 @@ async fn test_get_incident_detail_success() {
         description: Some("Critical incident".to_string()),
         affected_count: Some(15),
         deaths: Some(0),
+        hospitalized: Some(6),
+        source_url: Some("https://dinkes.example.go.id/laporan/1".to_string()),
         status: Some(IncidentStatus::Investigating),
         ..Default::default()
     };
@@ -159,6 +323,12 @@ async fn test_get_incident_detail_success() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["hospitalized"], 6);
+    assert_eq!(json["sourceUrl"], "https://dinkes.example.go.id/laporan/1");
 
     common::teardown_db(db, db_name).await;
 }
@@ -194,3 +364,1021 @@ async fn test_get_incident_detail_not_found() {
 
     common::teardown_db(db, db_name).await;
 }
+
+#[tokio::test]
+async fn test_add_incident_details_as_admin() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen = Kitchen {
+        name: "Test Kitchen".to_string(),
+        ..Default::default()
+    };
+    let kitchen_id = db.kitchen_table.insert(&kitchen).await.unwrap();
+
+    let incident = Incident {
+        kitchen_id,
+        r#type: IncidentType::Poisoning,
+        source: IncidentSource::Consumer,
+        severity: IncidentSeverity::Critical,
+        ..Default::default()
+    };
+    let incident_id = db.incident_table.insert(&incident).await.unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = IncidentState {
+        service: Arc::new(IncidentService::new(db.clone())),
+    };
+
+    let app = Router::new()
+        .route("/{id}/timeline", post(add_timeline_event_handler))
+        .route(
+            "/{id}/corrective-actions",
+            post(add_corrective_action_handler),
+        )
+        .layer(middleware::from_fn_with_state(
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+            auth_middleware,
+        ))
+        .route("/{id}", get(get_incident_detail_handler))
+        .with_state(state);
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let post_json = |uri: String, token: &str, body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(post_json(
+            format!("/{}/timeline", incident_id),
+            &admin_token,
+            serde_json::json!({
+                "date": "2026-01-10T08:00:00",
+                "event": "Incident reported",
+                "description": "Students reported nausea"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .clone()
+        .oneshot(post_json(
+            format!("/{}/corrective-actions", incident_id),
+            &admin_token,
+            serde_json::json!({ "action": "Kitchen suspended" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Non-admins cannot add details
+    let response = app
+        .clone()
+        .oneshot(post_json(
+            format!("/{}/corrective-actions", incident_id),
+            &school_token,
+            serde_json::json!({ "action": "Not allowed" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Unknown incidents are rejected
+    let response = app
+        .clone()
+        .oneshot(post_json(
+            format!("/{}/timeline", Uuid::new_v4()),
+            &admin_token,
+            serde_json::json!({ "date": "2026-01-10T08:00:00", "event": "Orphan" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}", incident_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let detail: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail["timeline"].as_array().unwrap().len(), 1);
+    assert_eq!(detail["timeline"][0]["event"], "Incident reported");
+    assert_eq!(
+        detail["correctiveActions"],
+        serde_json::json!(["Kitchen suspended"])
+    );
+    assert_eq!(detail["affectedInstitutions"], serde_json::json!([]));
+    assert!(detail["laboratoryResults"].is_null());
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_incident_status_transitions() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Test Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let incident_id = db
+        .incident_table
+        .insert(&Incident {
+            kitchen_id,
+            status: Some(IncidentStatus::Investigating),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_id = db
+        .user_table
+        .insert(&User {
+            name: "Admin".to_string(),
+            role: UserRole::Admin,
+            unique_code: "ADMIN-001".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = IncidentState {
+        service: Arc::new(IncidentService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/{id}/status", patch(update_incident_status_handler))
+        .layer(middleware::from_fn_with_state(
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+            auth_middleware,
+        ))
+        .route("/{id}", get(get_incident_detail_handler))
+        .with_state(state);
+
+    let admin_token = generate_token(
+        admin_id,
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let app_ref = &app;
+    let update = |token: String, body: serde_json::Value| async move {
+        let response = app_ref
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri(format!("/{}/status", incident_id))
+                    .header("Content-Type", "application/json")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        )
+    };
+
+    let (status, _) = update(
+        school_token.clone(),
+        serde_json::json!({ "status": "escalated" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = update(
+        admin_token.clone(),
+        serde_json::json!({ "status": "escalated", "note": "Second school affected" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "escalated");
+    assert!(body["resolvedAt"].is_null());
+
+    let (status, body) = update(
+        admin_token.clone(),
+        serde_json::json!({ "status": "resolved" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "resolved");
+    assert!(body["resolvedAt"].is_string());
+
+    // Resolved incidents stay resolved unless explicitly reopened
+    for body in [
+        serde_json::json!({ "status": "investigating" }),
+        serde_json::json!({ "status": "escalated", "reopen": true }),
+        serde_json::json!({ "status": "resolved" }),
+    ] {
+        let (status, _) = update(admin_token.clone(), body.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+    let (status, body) = update(
+        admin_token.clone(),
+        serde_json::json!({ "status": "investigating", "reopen": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "investigating");
+    assert!(body["resolvedAt"].is_null());
+
+    // Reopen only applies to resolved incidents; errors name the allowed states
+    let (status, body) = update(
+        admin_token.clone(),
+        serde_json::json!({ "status": "escalated", "reopen": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.to_string().contains("reopened"), "{}", body);
+    let (status, body) = update(
+        admin_token.clone(),
+        serde_json::json!({ "status": "investigating" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body.to_string()
+            .contains("allowed next states: 'escalated', 'resolved'"),
+        "{}",
+        body
+    );
+    let (status, _) = update(
+        admin_token.clone(),
+        serde_json::json!({ "status": "closed" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let history = db
+        .incident_table
+        .get_status_history(&incident_id)
+        .await
+        .unwrap();
+    let transitions: Vec<_> = history
+        .iter()
+        .map(|c| (c.from_status, c.to_status))
+        .collect();
+    assert_eq!(
+        transitions,
+        vec![
+            (IncidentStatus::Investigating, IncidentStatus::Escalated),
+            (IncidentStatus::Escalated, IncidentStatus::Resolved),
+            (IncidentStatus::Resolved, IncidentStatus::Investigating),
+        ]
+    );
+    assert!(history.iter().all(|c| c.changed_by == Some(admin_id)));
+    assert_eq!(history[0].note.as_deref(), Some("Second school affected"));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}", incident_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let detail: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail["statusHistory"].as_array().unwrap().len(), 3);
+    assert_eq!(detail["statusHistory"][1]["to"], "resolved");
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_incidents_cursor_pagination() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen = Kitchen {
+        name: "Test Kitchen".to_string(),
+        ..Default::default()
+    };
+    let kitchen_id = db.kitchen_table.insert(&kitchen).await.unwrap();
+
+    for i in 0..3 {
+        let incident = Incident {
+            kitchen_id,
+            r#type: IncidentType::Poisoning,
+            source: IncidentSource::Consumer,
+            severity: IncidentSeverity::Minor,
+            province: Some(if i == 1 { "Bali" } else { "DKI Jakarta" }.to_string()),
+            ..Default::default()
+        };
+        db.incident_table.insert(&incident).await.unwrap();
+    }
+
+    let state = IncidentState {
+        service: Arc::new(IncidentService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/", get(list_incidents_handler))
+        .with_state(state);
+
+    let get_page = |uri: String| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        }
+    };
+
+    let first = get_page("/?limit=1&cursor=&province=Jakarta".to_string()).await;
+    assert_eq!(first["pagination"]["total"], 2);
+    assert_eq!(first["data"].as_array().unwrap().len(), 1);
+    let next = first["nextCursor"].as_str().unwrap().to_string();
+
+    let second = get_page(format!("/?limit=1&cursor={}&province=Jakarta", next)).await;
+    assert_eq!(second["data"].as_array().unwrap().len(), 1);
+    assert_ne!(second["data"][0]["id"], first["data"][0]["id"]);
+    assert!(second["nextCursor"].is_null());
+
+    // Offset pagination still works and never returns a cursor.
+    let offset = get_page("/?limit=1&offset=1".to_string()).await;
+    assert_eq!(offset["pagination"]["total"], 3);
+    assert!(offset["nextCursor"].is_null());
+
+    common::teardown_db(db, db_name).await;
+}
+
+const WEBHOOK_SECRET: &str = "webhook-test-secret";
+
+fn has_valid_signature(req: &httpmock::prelude::HttpMockRequest) -> bool {
+    let body = req.body.as_deref().unwrap_or_default();
+    req.headers.iter().flatten().any(|(name, value)| {
+        name.eq_ignore_ascii_case("x-signature") && *value == sign(WEBHOOK_SECRET, body)
+    })
+}
+
+fn webhook_service(db: Arc<backend::database::Database>, url: String) -> IncidentService {
+    let config = WebhookConfig {
+        urls: vec![url],
+        secret: Some(WEBHOOK_SECRET.to_string()),
+        send_attempts: 2,
+        retry_delay_ms: 1,
+    };
+    IncidentService::new(db.clone()).with_webhooks(WebhookDispatcher::new(db, config))
+}
+
+/// Polls `check` until it returns true, failing after five seconds.
+async fn wait_for<F, Fut>(mut check: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    for _ in 0..100 {
+        if check().await {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("condition not met in time");
+}
+
+#[tokio::test]
+async fn test_create_critical_incident_sends_signed_webhook() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Webhook Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let server = httpmock::MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/hook")
+                .header("X-Webhook-Event", "incident.critical")
+                .body_contains("\"event\":\"incident.critical\"")
+                .matches(has_valid_signature);
+            then.status(204);
+        })
+        .await;
+
+    let service = webhook_service(db.clone(), server.url("/hook"));
+    for severity in [IncidentSeverity::Minor, IncidentSeverity::Critical] {
+        service
+            .create(Incident {
+                kitchen_id,
+                severity,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    wait_for(|| async { mock.hits_async().await == 1 }).await;
+    // Give a stray minor-incident delivery time to show up
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(mock.hits_async().await, 1);
+    assert!(
+        db.webhook_dead_letter_table
+            .select_all()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_failed_webhook_is_dead_lettered() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Webhook Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let server = httpmock::MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(httpmock::Method::POST).path("/hook");
+            then.status(503);
+        })
+        .await;
+
+    let service = webhook_service(db.clone(), server.url("/hook"));
+    let incident_id = service
+        .create(Incident {
+            kitchen_id,
+            severity: IncidentSeverity::Critical,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    wait_for(|| async {
+        !db.webhook_dead_letter_table
+            .select_all()
+            .await
+            .unwrap()
+            .is_empty()
+    })
+    .await;
+    assert_eq!(mock.hits_async().await, 2);
+
+    let dead_letters = db.webhook_dead_letter_table.select_all().await.unwrap();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].url, server.url("/hook"));
+    assert_eq!(dead_letters[0].event, "incident.critical");
+    assert_eq!(dead_letters[0].attempts, 2);
+    assert_eq!(
+        dead_letters[0].payload["data"]["id"],
+        incident_id.to_string()
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_create_incident_for_unknown_kitchen_is_bad_request() {
+    let (db, db_name) = common::setup_db().await;
+    let service = IncidentService::new(db.clone());
+
+    let err = service
+        .create(Incident {
+            kitchen_id: Uuid::new_v4(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    match err {
+        AppError::BadRequest(msg) => {
+            assert_eq!(msg, "referenced kitchen does not exist")
+        }
+        other => panic!("Expected BadRequest, got {:?}", other),
+    }
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_report_incident_endpoint() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Reported Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = IncidentState {
+        service: Arc::new(IncidentService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/", post(create_incident_handler))
+        .layer(middleware::from_fn_with_state(
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+            auth_middleware,
+        ))
+        .with_state(state);
+
+    let token = |role| {
+        generate_token(
+            Uuid::new_v4(),
+            role,
+            None,
+            None,
+            &JwtKeys::hmac("test_secret"),
+        )
+        .unwrap()
+    };
+    let admin_token = token(UserRole::Admin);
+    let report = |token: &str, body: serde_json::Value| {
+        app.clone().oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/")
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+    let body = |overrides: serde_json::Value| {
+        let mut body = serde_json::json!({
+            "kitchenId": kitchen_id,
+            "type": "poisoning",
+            "source": "inspector",
+            "severity": "critical",
+            "date": "2026-03-01T12:00:00",
+            "affectedCount": 12,
+            "deaths": 1,
+            "createAlert": true
+        });
+        for (key, value) in overrides.as_object().unwrap() {
+            body[key] = value.clone();
+        }
+        body
+    };
+
+    let response = report(&token(UserRole::School), body(serde_json::json!({})))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    for overrides in [
+        serde_json::json!({"kitchenId": Uuid::new_v4()}),
+        serde_json::json!({"type": "explosion"}),
+        serde_json::json!({"severity": "catastrophic"}),
+        serde_json::json!({"affectedCount": -1}),
+        serde_json::json!({"deaths": 13}),
+    ] {
+        let response = report(&admin_token, body(overrides.clone())).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{} should be rejected",
+            overrides
+        );
+    }
+    assert!(db.incident_table.select_all().await.unwrap().is_empty());
+
+    let response = report(&admin_token, body(serde_json::json!({})))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(
+        location,
+        format!("/api/incidents/{}", created["id"].as_str().unwrap())
+    );
+    assert_eq!(created["relatedKitchenName"], "Reported Kitchen");
+    assert_eq!(created["status"], "investigating");
+    assert_eq!(created["victims"], 12);
+    assert_eq!(created["deaths"], 1);
+    assert_eq!(created["date"], "2026-03-01T12:00:00Z");
+    assert!(created["createdAt"].as_str().unwrap().ends_with('Z'));
+
+    let alerts = db.alert_table.select_all().await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].kitchen_id, Some(kitchen_id));
+    assert_eq!(alerts[0].r#type, AlertType::Incident);
+    assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+
+    // Without createAlert no alert is raised
+    let response = report(
+        &admin_token,
+        body(serde_json::json!({"createAlert": false, "deaths": null})),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(db.alert_table.select_all().await.unwrap().len(), 1);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_public_incident_reports_are_deduplicated() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Reported Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = IncidentState {
+        service: Arc::new(
+            IncidentService::new(db.clone()).with_dedup(IncidentDedupConfig { window_hours: 24 }),
+        ),
+    };
+    let app = Router::new()
+        .route("/", post(create_incident_handler))
+        .layer(middleware::from_fn_with_state(
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+            auth_middleware,
+        ))
+        .with_state(state);
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let report = |overrides: serde_json::Value| {
+        let mut body = serde_json::json!({
+            "kitchenId": kitchen_id,
+            "type": "poisoning",
+            "source": "public",
+            "severity": "major",
+            "date": "2026-03-01T12:00:00Z",
+            "createAlert": true
+        });
+        for (key, value) in overrides.as_object().unwrap() {
+            body[key] = value.clone();
+        }
+        let app = app.clone();
+        let admin_token = admin_token.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/")
+                        .header("Authorization", format!("Bearer {}", admin_token))
+                        .header("Content-Type", "application/json")
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            (status, body["id"].as_str().unwrap().to_string())
+        }
+    };
+
+    let (status, first_id) = report(serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // A second article about the same event returns the stored incident
+    let (status, id) = report(serde_json::json!({"date": "2026-03-02T08:00:00Z"})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(id, first_id);
+    assert_eq!(db.incident_table.select_all().await.unwrap().len(), 1);
+    assert_eq!(db.alert_table.select_all().await.unwrap().len(), 1);
+
+    // Outside the window, of another type, or from another source is new
+    for overrides in [
+        serde_json::json!({"date": "2026-03-03T12:00:01Z"}),
+        serde_json::json!({"type": "sanitation"}),
+        serde_json::json!({"source": "inspector"}),
+    ] {
+        let (status, id) = report(overrides.clone()).await;
+        assert_eq!(
+            status,
+            StatusCode::CREATED,
+            "{} should be stored",
+            overrides
+        );
+        assert_ne!(id, first_id);
+    }
+    assert_eq!(db.incident_table.select_all().await.unwrap().len(), 4);
+
+    // A date whose window would overflow is rejected rather than panicking
+    let body = serde_json::json!({
+        "kitchenId": kitchen_id,
+        "type": "poisoning",
+        "source": "public",
+        "severity": "major",
+        "date": "+262142-12-31T12:00:00"
+    });
+    let response = app
+        .clone()
+        .oneshot(common::post_json("/", &admin_token, body))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        common::json_body(response).await["error"],
+        "date is out of range"
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_kitchen_with_incidents_cannot_be_deleted() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Restricted Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let incident_id = IncidentService::new(db.clone())
+        .create(Incident {
+            kitchen_id,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let err: AppError = db
+        .kitchen_table
+        .delete(&kitchen_id)
+        .await
+        .unwrap_err()
+        .into();
+    assert!(matches!(err, AppError::Conflict(_)));
+    assert!(
+        db.incident_table
+            .select(&incident_id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_delete_incident_records_audit_log() {
+    let (db, db_name) = common::setup_db().await;
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Mistaken Kitchen".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let incident_id = db
+        .incident_table
+        .insert(&Incident {
+            kitchen_id,
+            r#type: IncidentType::Poisoning,
+            source: IncidentSource::Consumer,
+            severity: IncidentSeverity::Minor,
+            location: Some("Bekasi".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let event_id = db
+        .incident_timeline_event_table
+        .insert(&IncidentTimelineEvent {
+            incident_id,
+            event_date: Utc::now(),
+            event_title: "Reported".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_id = db
+        .user_table
+        .insert(&User {
+            name: "Admin Dinkes".to_string(),
+            unique_code: "ADMIN-DEL".to_string(),
+            role: UserRole::Admin,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = IncidentState {
+        service: Arc::new(IncidentService::new(db.clone())),
+    };
+    let app = incident_routes(
+        state,
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+
+    let token =
+        |id, role| generate_token(id, role, None, None, &JwtKeys::hmac("test_secret")).unwrap();
+    let delete = |id: Uuid, token: &str| {
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/{}", id))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let admin_token = token(admin_id, UserRole::Admin);
+
+    let response = app
+        .clone()
+        .oneshot(delete(
+            incident_id,
+            &token(Uuid::new_v4(), UserRole::School),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(delete(Uuid::new_v4(), &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .clone()
+        .oneshot(delete(incident_id, &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The incident and its details are gone
+    assert!(
+        db.incident_table
+            .select(&incident_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        db.incident_timeline_event_table
+            .select(&event_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}", incident_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // ...but the audit log keeps the full row
+    let (logs, total) = db
+        .audit_log_table
+        .list(
+            &AuditLogFilter {
+                entity_type: Some("incident".to_string()),
+                ..Default::default()
+            },
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(logs[0].entity_id, incident_id.to_string());
+    assert_eq!(logs[0].user_id, Some(admin_id));
+    assert_eq!(logs[0].user_name.as_deref(), Some("Admin Dinkes"));
+    assert_eq!(logs[0].action, "DELETE incident");
+    let deleted = &logs[0].metadata.as_ref().unwrap()["deleted"];
+    assert_eq!(deleted["id"], incident_id.to_string());
+    assert_eq!(deleted["location"], "Bekasi");
+
+    let response = app
+        .clone()
+        .oneshot(delete(incident_id, &admin_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    common::teardown_db(db, db_name).await;
+}
@@ -0,0 +1,339 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use backend::auth::middleware::AuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::config::StorageConfig;
+use backend::database::model::Inspection;
+use backend::database::model::Kitchen;
+use backend::database::model::UserRole;
+use backend::database::table::Table;
+use backend::routes::inspection::InspectionState;
+use backend::routes::inspection::inspection_routes;
+use backend::routes::inspection::kitchen_inspection_routes;
+use backend::service::inspection::InspectionService;
+use backend::service::storage::StorageService;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+
+#[tokio::test]
+async fn test_create_and_view_inspection() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen = Kitchen {
+        name: "Inspected Kitchen".to_string(),
+        ..Default::default()
+    };
+    let kitchen_id = db.kitchen_table.insert(&kitchen).await.unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = InspectionState {
+        service: Arc::new(InspectionService::new(db.clone())),
+    };
+    let app = Router::new()
+        .nest(
+            "/inspections",
+            inspection_routes(
+                state.clone(),
+                AuthState {
+                    keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                    db: None,
+                },
+            ),
+        )
+        .nest("/kitchens", kitchen_inspection_routes(state));
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let school_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::School,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let payload = serde_json::json!({
+        "kitchenId": kitchen_id,
+        "inspectorName": "Dinas Kesehatan",
+        "date": "2026-03-01T09:00:00",
+        "overallScore": 82.5,
+        "followUpStatus": "in-progress",
+        "findings": [
+            {
+                "category": "major",
+                "description": "Raw chicken stored above vegetables",
+                "correctionRequired": true,
+                "deadline": "2026-03-08T00:00:00"
+            },
+            { "category": "observation", "description": "Handwashing sign faded" }
+        ]
+    });
+
+    // Non-admins cannot record inspections
+    let response = app
+        .clone()
+        .oneshot(common::post_json(
+            "/inspections",
+            &school_token,
+            payload.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Unknown finding categories are rejected before anything is written
+    let mut invalid = payload.clone();
+    invalid["findings"][1]["category"] = "cosmetic".into();
+    let response = app
+        .clone()
+        .oneshot(common::post_json("/inspections", &admin_token, invalid))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let mut invalid = payload.clone();
+    invalid["followUpStatus"] = "InProgress".into();
+    let response = app
+        .clone()
+        .oneshot(common::post_json("/inspections", &admin_token, invalid))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(
+        db.inspection_table
+            .list_by_kitchen(&kitchen_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let response = app
+        .clone()
+        .oneshot(common::post_json("/inspections", &admin_token, payload))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let inspection_id = common::json_body(response).await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = app
+        .clone()
+        .oneshot(common::get(format!("/inspections/{}", inspection_id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let detail = common::json_body(response).await;
+    assert_eq!(detail["kitchenId"], kitchen_id.to_string());
+    assert_eq!(detail["overallScore"], 82.5);
+    assert_eq!(detail["followUpStatus"], "in-progress");
+    let findings = detail["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 2);
+    assert!(findings.iter().any(|f| f["category"] == "major"
+        && f["correctionRequired"] == true
+        && f["description"] == "Raw chicken stored above vegetables"));
+
+    let response = app
+        .clone()
+        .oneshot(common::get(format!("/kitchens/{}/inspections", kitchen_id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let list = common::json_body(response).await;
+    assert_eq!(list["data"].as_array().unwrap().len(), 1);
+    assert_eq!(list["data"][0]["id"], inspection_id);
+
+    let response = app
+        .clone()
+        .oneshot(common::get(format!("/inspections/{}", Uuid::new_v4())))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(common::get(format!(
+            "/kitchens/{}/inspections",
+            Uuid::new_v4()
+        )))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    common::teardown_db(db, db_name).await;
+}
+
+/// Builds a `multipart/form-data` body with one `files` field per file.
+fn files_multipart(files: &[(&str, &[u8])]) -> (String, Vec<u8>) {
+    let boundary = "inspection-attachments-boundary";
+    let mut body = Vec::new();
+    for (file_name, data) in files {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"files\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                boundary, file_name
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+#[tokio::test]
+async fn test_upload_inspection_attachments() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "Kitchen with Signed Report".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let inspection_id = db
+        .inspection_table
+        .insert(&Inspection {
+            kitchen_id,
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap()
+                .and_utc(),
+            attachments: Some(serde_json::json!(["https://example.com/checklist.pdf"])),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let storage_dir =
+        std::env::temp_dir().join(format!("inspection_attachments_{}", Uuid::new_v4()));
+    let storage = Arc::new(
+        StorageService::new(&StorageConfig {
+            storage_type: "local".to_string(),
+            local_path: Some(storage_dir.to_string_lossy().into_owned()),
+            base_url: Some("http://localhost:3000/uploads".to_string()),
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = InspectionState {
+        service: Arc::new(InspectionService::new(db.clone()).with_attachment_storage(storage)),
+    };
+    let app = Router::new().nest(
+        "/inspections",
+        inspection_routes(
+            state,
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    );
+
+    let upload = |role: UserRole, id: Uuid, files: &[(&str, &[u8])]| {
+        let token = generate_token(
+            Uuid::new_v4(),
+            role,
+            None,
+            None,
+            &JwtKeys::hmac("test_secret"),
+        )
+        .unwrap();
+        let (content_type, body) = files_multipart(files);
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(format!("/inspections/{}/attachments", id))
+                        .header("Content-Type", content_type)
+                        .header("Authorization", format!("Bearer {}", token))
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            (response.status(), common::json_body(response).await)
+        }
+    };
+
+    let pdf: &[u8] = b"%PDF-1.7\n%signed report";
+    let png: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    // Only admins can attach files
+    let (status, _) = upload(UserRole::School, inspection_id, &[("report.pdf", pdf)]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Anything but PDFs and images is rejected before anything is stored
+    let (status, _) = upload(
+        UserRole::Admin,
+        inspection_id,
+        &[("report.pdf", pdf), ("notes.txt", b"not a report")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!storage_dir.exists() || std::fs::read_dir(&storage_dir).unwrap().next().is_none());
+
+    let (status, _) = upload(UserRole::Admin, Uuid::new_v4(), &[("report.pdf", pdf)]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, json) = upload(
+        UserRole::Admin,
+        inspection_id,
+        &[("report.pdf", pdf), ("kitchen.png", png)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let attachments = json["attachments"].as_array().unwrap();
+    assert_eq!(attachments.len(), 3);
+    assert_eq!(attachments[0], "https://example.com/checklist.pdf");
+    for attachment in &attachments[1..] {
+        let path = attachment
+            .as_str()
+            .unwrap()
+            .strip_prefix("http://localhost:3000/uploads/")
+            .unwrap();
+        assert!(storage_dir.join(path).is_file());
+    }
+
+    // The detail returns the appended attachments
+    let response = app
+        .oneshot(common::get(format!("/inspections/{}", inspection_id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        common::json_body(response).await["attachments"],
+        json["attachments"]
+    );
+
+    std::fs::remove_dir_all(&storage_dir).unwrap();
+    common::teardown_db(db, db_name).await;
+}
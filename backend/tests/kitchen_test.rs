@@ -1,19 +1,34 @@
 use std::sync::Arc;
 
+use axum::Extension;
 use axum::Router;
 use axum::body::Body;
 use axum::http::Request;
 use axum::http::StatusCode;
 use axum::routing::get;
+use backend::auth::middleware::AuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::config::KitchenRatingConfig;
+use backend::config::PaginationConfig;
+use backend::database::model::Incident;
+use backend::database::model::IncidentSeverity;
+use backend::database::model::IncidentType;
 use backend::database::model::Kitchen;
 use backend::database::model::KitchenType;
+use backend::database::model::Review;
+use backend::database::model::User;
+use backend::database::model::UserRole;
 use backend::database::table::Table;
 use backend::routes::kitchen::KitchenState;
 use backend::routes::kitchen::get_kitchen_detail_handler;
 use backend::routes::kitchen::get_kitchen_stats_handler;
 use backend::routes::kitchen::get_multiple_kitchens_handler;
+use backend::routes::kitchen::kitchen_routes;
 use backend::routes::kitchen::list_kitchens_handler;
 use backend::service::kitchen::KitchenService;
+use rust_decimal::Decimal;
 use tower::util::ServiceExt;
 use uuid::Uuid;
 
@@ -167,6 +182,26 @@ async fn test_list_kitchens_with_filters() {
     assert_eq!(response.status(), StatusCode::OK);
 
     // Test type filter
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/?type=Central%20Kitchen")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+
+    // Unknown type values are rejected instead of matching nothing
     let response = app
         .clone()
         .oneshot(
@@ -179,7 +214,412 @@ async fn test_list_kitchens_with_filters() {
         .await
         .unwrap();
 
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_kitchens_filter_combinations() {
+    let (db, db_name) = common::setup_db().await;
+
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 8".to_string(),
+            role: UserRole::School,
+            unique_code: "SCH-FILTER".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // Kitchen `i` matches filter `b` (q, loc, type, minRating) iff bit `b` of
+    // `i` is set, so every subset of filters selects a different set.
+    let mut kitchens = Vec::new();
+    for i in 0..16u32 {
+        let bit = |b: u32| i & (1 << b) != 0;
+        let id = db
+            .kitchen_table
+            .insert(&Kitchen {
+                name: format!("Dapur {} {}", if bit(0) { "Sehat" } else { "Ceria" }, i),
+                city: Some(if bit(1) { "Jakarta" } else { "Bandung" }.to_string()),
+                province: Some("Jawa Barat".to_string()),
+                r#type: Some(if bit(2) {
+                    KitchenType::CentralKitchen
+                } else {
+                    KitchenType::SatelliteKitchen
+                }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let rating = if bit(3) {
+            Decimal::new(45, 1)
+        } else {
+            Decimal::new(20, 1)
+        };
+        db.review_table
+            .insert(&Review {
+                kitchen_id: id,
+                reviewer_id,
+                reviewer_name: "SD Negeri 8".to_string(),
+                comment: "Cukup".to_string(),
+                taste_rating: rating,
+                hygiene_rating: rating,
+                freshness_rating: rating,
+                temperature_rating: rating,
+                packaging_rating: rating,
+                handling_rating: rating,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        kitchens.push(id);
+    }
+
+    let app = Router::new()
+        .route("/", get(list_kitchens_handler))
+        .with_state(KitchenState {
+            service: Arc::new(KitchenService::new(db.clone())),
+        });
+
+    let filters = [
+        "q=Sehat",
+        "loc=Jakarta",
+        "type=Central%20Kitchen",
+        "minRating=4",
+    ];
+    for subset in 0..16usize {
+        let mut query = vec!["limit=50".to_string()];
+        query.extend(
+            (0..4)
+                .filter(|b| subset & (1 << b) != 0)
+                .map(|b| filters[b].to_string()),
+        );
+        let query = query.join("&");
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/?{}", query))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", query);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let mut ids: Vec<Uuid> = json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|k| k["id"].as_str().unwrap().parse().unwrap())
+            .collect();
+        ids.sort();
+        let mut expected: Vec<Uuid> = kitchens
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i & subset == subset)
+            .map(|(_, id)| *id)
+            .collect();
+        expected.sort();
+        assert_eq!(ids, expected, "{}", query);
+        assert_eq!(
+            json["pagination"]["total"],
+            expected.len() as i64,
+            "{}",
+            query
+        );
+    }
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_search_kitchens() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen = |name: &str, address: &str, city: &str| Kitchen {
+        name: name.to_string(),
+        address: Some(address.to_string()),
+        city: Some(city.to_string()),
+        province: Some("Jawa Barat".to_string()),
+        ..Default::default()
+    };
+    let sehat = db
+        .kitchen_table
+        .insert(&kitchen("Dapur Sehat", "Jl. Merdeka 10", "Bogor"))
+        .await
+        .unwrap();
+    let bandung = db
+        .kitchen_table
+        .insert(&kitchen(
+            "Dapur Bandung",
+            "Jl. Asia Afrika, Bandung",
+            "Bandung",
+        ))
+        .await
+        .unwrap();
+    db.kitchen_table
+        .insert(&kitchen("Katering Bersih", "Jl. Braga 5", "Bandung"))
+        .await
+        .unwrap();
+
+    let state = KitchenState {
+        service: Arc::new(KitchenService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/", get(list_kitchens_handler))
+        .with_state(state);
+
+    let search = |q: &str| {
+        Request::builder()
+            .method("GET")
+            .uri(format!("/?q={}", q))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let ids = |json: &serde_json::Value| -> Vec<String> {
+        json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|k| k["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // A single word is a substring match on the name
+    let response = app.clone().oneshot(search("sehat")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(ids(&json), vec![sehat.to_string()]);
+
+    // Multiple words match across name, address, city and province, best first
+    let response = app
+        .clone()
+        .oneshot(search("dapur%20bandung"))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(ids(&json), vec![bandung.to_string()]);
+    assert_eq!(json["pagination"]["total"], 1);
+
+    let response = app.oneshot(search("merdeka%20bogor")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(ids(&json), vec![sehat.to_string()]);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_kitchens_near() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen = |name: &str, coords: Option<(f64, f64)>| Kitchen {
+        name: name.to_string(),
+        latitude: coords.map(|c| c.0),
+        longitude: coords.map(|c| c.1),
+        ..Default::default()
+    };
+    let jakarta = db
+        .kitchen_table
+        .insert(&kitchen("Jakarta Kitchen", Some((-6.1754, 106.8272))))
+        .await
+        .unwrap();
+    let bogor = db
+        .kitchen_table
+        .insert(&kitchen("Bogor Kitchen", Some((-6.5971, 106.8060))))
+        .await
+        .unwrap();
+    db.kitchen_table
+        .insert(&kitchen("Bandung Kitchen", Some((-6.9175, 107.6191))))
+        .await
+        .unwrap();
+    db.kitchen_table
+        .insert(&kitchen("Unmapped Kitchen", None))
+        .await
+        .unwrap();
+
+    let state = KitchenState {
+        service: Arc::new(KitchenService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/", get(list_kitchens_handler))
+        .with_state(state);
+    let list = |query: &str| {
+        Request::builder()
+            .method("GET")
+            .uri(format!("/?{}", query))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(list("lat=-6.2&lng=106.8&radius=60"))
+        .await
+        .unwrap();
     assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let data = json["data"].as_array().unwrap();
+    assert_eq!(json["pagination"]["total"], 2);
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0]["id"], jakarta.to_string());
+    assert_eq!(data[1]["id"], bogor.to_string());
+    let jakarta_km = data[0]["distanceKm"].as_f64().unwrap();
+    let bogor_km = data[1]["distanceKm"].as_f64().unwrap();
+    assert!((3.0..5.0).contains(&jakarta_km), "{}", jakarta_km);
+    assert!((40.0..50.0).contains(&bogor_km), "{}", bogor_km);
+
+    // Without a search point no distance is reported
+    let response = app.clone().oneshot(list("")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 4);
+    assert!(json["data"][0].get("distanceKm").is_none());
+
+    for invalid in [
+        "lat=-6.2&lng=106.8",
+        "lat=-91&lng=106.8&radius=10",
+        "lat=-6.2&lng=106.8&radius=0",
+    ] {
+        let response = app.clone().oneshot(list(invalid)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", invalid);
+    }
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_list_kitchens_sorting() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen = |name: &str, meals_served: Option<i32>| Kitchen {
+        name: name.to_string(),
+        meals_served,
+        ..Default::default()
+    };
+    let beta = db
+        .kitchen_table
+        .insert(&kitchen("beta Kitchen", Some(100)))
+        .await
+        .unwrap();
+    let alpha = db
+        .kitchen_table
+        .insert(&kitchen("Alpha Kitchen", None))
+        .await
+        .unwrap();
+    let gamma = db
+        .kitchen_table
+        .insert(&kitchen("Gamma Kitchen", Some(300)))
+        .await
+        .unwrap();
+
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 7".to_string(),
+            role: UserRole::School,
+            unique_code: "SCH-SORT".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        db.review_table
+            .insert(&Review {
+                kitchen_id: alpha,
+                reviewer_id,
+                reviewer_name: "SD Negeri 7".to_string(),
+                comment: "Enak".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    let state = KitchenState {
+        service: Arc::new(KitchenService::new(db.clone())),
+    };
+    let app = Router::new()
+        .route("/", get(list_kitchens_handler))
+        .with_state(state);
+    let ids = |query: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/?{}", query))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", query);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            json["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|k| k["id"].as_str().unwrap().parse::<Uuid>().unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Names sort case-insensitively, A-Z unless asked otherwise
+    assert_eq!(ids("sortBy=name").await, vec![alpha, beta, gamma]);
+    assert_eq!(
+        ids("sortBy=name&order=desc").await,
+        vec![gamma, beta, alpha]
+    );
+    // Unknown meal counts come last in both directions
+    assert_eq!(ids("sortBy=mealsServed").await, vec![gamma, beta, alpha]);
+    assert_eq!(
+        ids("sortBy=mealsServed&order=asc").await,
+        vec![beta, gamma, alpha]
+    );
+    assert_eq!(ids("sortBy=totalReviews").await[0], alpha);
+    assert_eq!(
+        ids("sortBy=createdAt&order=asc").await,
+        vec![beta, alpha, gamma]
+    );
+
+    for invalid in ["sortBy=rating;DROP", "sortBy=id", "order=up"] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/?{}", invalid))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", invalid);
+    }
 
     common::teardown_db(db, db_name).await;
 }
@@ -240,6 +680,13 @@ async fn test_list_kitchens_pagination() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["pagination"]["page"], 2);
+    assert_eq!(json["pagination"]["totalPages"], 3);
+    assert_eq!(json["pagination"]["hasMore"], true);
 
     // Test limit cap (should not exceed 100)
     let response = app
@@ -255,6 +702,51 @@ async fn test_list_kitchens_pagination() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["pagination"]["limit"], 100);
+    assert_eq!(json["data"].as_array().unwrap().len(), 25);
+
+    // Negative values are rejected
+    for uri in ["/?limit=-1", "/?offset=-10", "/?limit=abc"] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", uri);
+    }
+
+    // Without a limit the kitchen list uses its own default page size
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["pagination"]["limit"], 12);
+    assert_eq!(json["data"].as_array().unwrap().len(), 12);
+
+    // Operators can tune it, still under the hard cap
+    let response = app
+        .layer(Extension(PaginationConfig {
+            max_limit: 15,
+            kitchens_default_limit: Some(30),
+            ..Default::default()
+        }))
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["pagination"]["limit"], 15);
 
     common::teardown_db(db, db_name).await;
 }
@@ -444,11 +936,12 @@ async fn test_get_multiple_kitchens_success() {
         .route("/batch", get(get_multiple_kitchens_handler))
         .with_state(state);
 
+    let missing = Uuid::new_v4();
     let response = app
         .oneshot(
             Request::builder()
                 .method("GET")
-                .uri(format!("/batch?ids={},{}", id1, id2))
+                .uri(format!("/batch?ids={},{},{}", id2, missing, id1))
                 .body(Body::empty())
                 .unwrap(),
         )
@@ -457,6 +950,20 @@ async fn test_get_multiple_kitchens_success() {
 
     assert_eq!(response.status(), StatusCode::OK);
 
+    // Kitchens come back in the requested order, unknown ids are listed apart
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let kitchens: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let returned: Vec<_> = kitchens["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|k| k["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(returned, vec![id2.to_string(), id1.to_string()]);
+    assert_eq!(kitchens["notFound"], serde_json::json!([missing]));
+
     common::teardown_db(db, db_name).await;
 }
 
@@ -504,5 +1011,888 @@ async fn test_get_multiple_kitchens_invalid_ids() {
 
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
+    // Well-formed but unknown IDs aren't an error
+    let unknown = Uuid::new_v4();
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/batch?ids={},not-a-uuid", unknown))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"], serde_json::json!([]));
+    assert_eq!(json["notFound"], serde_json::json!([unknown]));
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_create_and_update_kitchen_contact_details() {
+    let (db, db_name) = common::setup_db().await;
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let app = kitchen_routes(
+        KitchenState {
+            service: Arc::new(KitchenService::new(db.clone())),
+        },
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+
+    let owner_id = db
+        .user_table
+        .insert(&User {
+            name: "Kitchen Manager".to_string(),
+            unique_code: "KITCHEN001".to_string(),
+            role: UserRole::Kitchen,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let owner_token = generate_token(
+        owner_id,
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let other_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+    let create_body = serde_json::json!({
+        "name": "SPPG Cakung",
+        "ownerId": owner_id,
+        "city": "Jakarta Timur",
+        "province": "DKI Jakarta",
+        "type": "Central Kitchen",
+        "contactPhone": "(021) 555-0123",
+        "contactEmail": "sppg.cakung@example.com",
+        "operatingHours": "Mon-Fri 05:00-14:00",
+        "capacity": 3000
+    });
+
+    // Only admins can register kitchens
+    let response = app
+        .clone()
+        .oneshot(send(
+            "POST",
+            "/".to_string(),
+            &owner_token,
+            create_body.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(send("POST", "/".to_string(), &admin_token, create_body))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let id = json["id"].as_str().unwrap().to_string();
+    assert_eq!(location, format!("/api/kitchens/{}", id));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["contactPhone"], "(021) 555-0123");
+    assert_eq!(json["contactEmail"], "sppg.cakung@example.com");
+    assert_eq!(json["operatingHours"], "Mon-Fri 05:00-14:00");
+    assert_eq!(json["capacity"], 3000);
+
+    // The owner can update their kitchen; other kitchen users cannot
+    let update = serde_json::json!({ "contactPhone": "081234567890", "capacity": 3500 });
+    let response = app
+        .clone()
+        .oneshot(send(
+            "PATCH",
+            format!("/{}", id),
+            &other_token,
+            update.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(send("PATCH", format!("/{}", id), &owner_token, update))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["contactPhone"], "081234567890");
+    assert_eq!(json["capacity"], 3500);
+    assert_eq!(json["contactEmail"], "sppg.cakung@example.com");
+
+    let response = app
+        .clone()
+        .oneshot(send(
+            "PATCH",
+            format!("/{}", id),
+            &owner_token,
+            serde_json::json!({ "contactEmail": "not-an-email" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Certifications must be a list of non-blank names
+    for certifications in [
+        serde_json::json!({ "Halal": true }),
+        serde_json::json!([1, 2]),
+        serde_json::json!("Halal"),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(send(
+                "PATCH",
+                format!("/{}", id),
+                &owner_token,
+                serde_json::json!({ "certifications": certifications }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(
+            json["error"]
+                .as_str()
+                .unwrap()
+                .contains("certifications must be an array of strings"),
+            "{json}"
+        );
+    }
+
+    let response = app
+        .clone()
+        .oneshot(send(
+            "PATCH",
+            format!("/{}", id),
+            &owner_token,
+            serde_json::json!({ "certifications": ["Halal", "  "] }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(send(
+            "PATCH",
+            format!("/{}", id),
+            &owner_token,
+            serde_json::json!({ "certifications": [" Halal MUI ", "HACCP"] }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json["certifications"],
+        serde_json::json!(["Halal MUI", "HACCP"])
+    );
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_stale_kitchen_update_conflicts() {
+    let (db, db_name) = common::setup_db().await;
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let app = kitchen_routes(
+        KitchenState {
+            service: Arc::new(KitchenService::new(db.clone())),
+        },
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "SPPG Shared".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    // Both clients load the same copy of the kitchen
+    let response = app
+        .clone()
+        .oneshot(common::get(format!("/{}", kitchen_id)))
+        .await
+        .unwrap();
+    let snapshot = common::json_body(response).await["updatedAt"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let patch = |if_match: &str, body: serde_json::Value| {
+        Request::builder()
+            .method("PATCH")
+            .uri(format!("/{}", kitchen_id))
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", admin_token))
+            .header("If-Match", format!("\"{}\"", if_match))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(patch(&snapshot, serde_json::json!({ "capacity": 3000 })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let current = common::json_body(response).await["updatedAt"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(current, snapshot);
+
+    // The second client's edit is based on the copy the first one replaced
+    let response = app
+        .clone()
+        .oneshot(patch(&snapshot, serde_json::json!({ "capacity": 1500 })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let kitchen = db.kitchen_table.select(&kitchen_id).await.unwrap().unwrap();
+    assert_eq!(kitchen.capacity, Some(3000));
+
+    // After refetching, it goes through
+    let response = app
+        .clone()
+        .oneshot(patch(&current, serde_json::json!({ "capacity": 1500 })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(patch("yesterday", serde_json::json!({ "capacity": 1 })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_kitchen_report_pdf() {
+    let (db, db_name) = common::setup_db().await;
+
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "SPPG Report Test".to_string(),
+            city: Some("Bandung".to_string()),
+            province: Some("Jawa Barat".to_string()),
+            contact_phone: Some("022-555-0100".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 5".to_string(),
+            unique_code: "SCHOOL005".to_string(),
+            role: UserRole::School,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    db.review_table
+        .insert(&Review {
+            kitchen_id,
+            reviewer_id,
+            reviewer_name: "SD Negeri 5".to_string(),
+            comment: "Nasi masih hangat (tepat waktu)".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    db.incident_table
+        .insert(&Incident {
+            kitchen_id,
+            r#type: IncidentType::Sanitation,
+            severity: IncidentSeverity::Minor,
+            description: Some("Wadah makanan bocor".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let app = kitchen_routes(
+        KitchenState {
+            service: Arc::new(KitchenService::new(db.clone())),
+        },
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}/report.pdf", kitchen_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/pdf");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        format!("attachment; filename=\"kitchen-{}-report.pdf\"", kitchen_id).as_str()
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let pdf = String::from_utf8_lossy(&body);
+    assert!(pdf.starts_with("%PDF-"));
+    assert!(pdf.contains("(Kitchen Report: SPPG Report Test)"));
+    assert!(pdf.contains("(Phone: 022-555-0100)"));
+    assert!(pdf.contains("Nasi masih hangat \\(tepat waktu\\)"));
+    assert!(pdf.contains("Wadah makanan bocor"));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}/report.pdf", Uuid::new_v4()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_export_kitchen_reviews() {
+    let (db, db_name) = common::setup_db().await;
+
+    let owner_id = db
+        .user_table
+        .insert(&User {
+            name: "Kitchen Manager".to_string(),
+            unique_code: "KITCHEN001".to_string(),
+            role: UserRole::Kitchen,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 7".to_string(),
+            unique_code: "SCHOOL007".to_string(),
+            role: UserRole::School,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "SPPG Export Test".to_string(),
+            owner_id: Some(owner_id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    for (comment, rating, is_draft) in [
+        ("Lauk kurang, \"nasi\" dingin", 3, false),
+        ("Tepat waktu", 5, false),
+        ("Belum selesai", 1, true),
+    ] {
+        let rating = Decimal::from(rating);
+        db.review_table
+            .insert(&Review {
+                kitchen_id,
+                reviewer_id,
+                reviewer_name: "SD Negeri 7".to_string(),
+                taste_rating: rating,
+                hygiene_rating: rating,
+                freshness_rating: rating,
+                temperature_rating: rating,
+                packaging_rating: rating,
+                handling_rating: rating,
+                comment: comment.to_string(),
+                is_draft: Some(is_draft),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let app = kitchen_routes(
+        KitchenState {
+            service: Arc::new(KitchenService::new(db.clone())),
+        },
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+    let token = |id: Uuid, role: UserRole| {
+        generate_token(id, role, None, None, &JwtKeys::hmac("test_secret")).unwrap()
+    };
+    let export = |uri: String, token: String| {
+        Request::builder()
+            .uri(uri)
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let owner_token = token(owner_id, UserRole::Kitchen);
+
+    let response = app
+        .clone()
+        .oneshot(export(
+            format!("/{}/reviews/export", kitchen_id),
+            owner_token.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers()["content-disposition"],
+        format!(
+            "attachment; filename=\"kitchen-{}-reviews.csv\"",
+            kitchen_id
+        )
+        .as_str()
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let mut reader = csv::Reader::from_reader(body.as_ref());
+    let headers = reader.headers().unwrap().clone();
+    assert_eq!(&headers[0], "id");
+    assert!(headers.iter().any(|h| h == "averageRating"));
+    let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    assert_eq!(rows.len(), 2);
+    let comment = headers.iter().position(|h| h == "comment").unwrap();
+    let comments: Vec<&str> = rows.iter().map(|r| &r[comment]).collect();
+    assert!(comments.contains(&"Lauk kurang, \"nasi\" dingin"));
+    assert!(!comments.contains(&"Belum selesai"));
+
+    let response = app
+        .clone()
+        .oneshot(export(
+            format!("/{}/reviews/export?format=json", kitchen_id),
+            token(Uuid::new_v4(), UserRole::Admin),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let reviews = json.as_array().unwrap();
+    assert_eq!(reviews.len(), 2);
+    let on_time = reviews
+        .iter()
+        .find(|r| r["comment"] == "Tepat waktu")
+        .unwrap();
+    assert_eq!(on_time["averageRating"], 5.0);
+    assert_eq!(on_time["hygieneRating"], 5.0);
+    assert_eq!(on_time["verificationStatus"], "unverified");
+
+    let response = app
+        .clone()
+        .oneshot(export(
+            format!("/{}/reviews/export?format=xml", kitchen_id),
+            owner_token.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(export(
+            format!("/{}/reviews/export", kitchen_id),
+            token(Uuid::new_v4(), UserRole::Kitchen),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .oneshot(export(
+            format!("/{}/reviews/export", Uuid::new_v4()),
+            owner_token,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_import_kitchens() {
+    let (db, db_name) = common::setup_db().await;
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let app = kitchen_routes(
+        KitchenState {
+            service: Arc::new(KitchenService::new(db.clone())),
+        },
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+
+    let existing_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "SPPG Cakung".to_string(),
+            city: Some("Jakarta Timur".to_string()),
+            contact_phone: Some("(021) 555-0123".to_string()),
+            capacity: Some(3000),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let kitchen_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let import = |token: &str, content_type: &str, body: String| {
+        Request::builder()
+            .method("POST")
+            .uri("/import")
+            .header("Content-Type", content_type)
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(body))
+            .unwrap()
+    };
+    let rows = serde_json::json!([
+        { "name": "sppg cakung", "city": "JAKARTA TIMUR", "capacity": 3500 },
+        { "name": "SPPG Depok", "city": "Depok", "type": "Central Kitchen" },
+        { "name": "SPPG Bogor", "contactEmail": "not-an-email" },
+        { "name": "SPPG Depok", "city": "depok" },
+        { "city": "Bekasi" }
+    ]);
+
+    // Only admins can import kitchens
+    let response = app
+        .clone()
+        .oneshot(import(&kitchen_token, "application/json", rows.to_string()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(import(&admin_token, "application/json", rows.to_string()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["created"], 1);
+    assert_eq!(json["updated"], 1);
+    assert_eq!(json["failed"], 3);
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results[0]["row"], 1);
+    assert_eq!(results[0]["status"], "updated");
+    assert_eq!(results[0]["kitchenId"], existing_id.to_string());
+    assert_eq!(results[1]["status"], "created");
+    assert!(results[1]["kitchenId"].is_string());
+    assert_eq!(results[2]["status"], "failed");
+    assert!(
+        results[2]["error"]
+            .as_str()
+            .unwrap()
+            .contains("contactEmail")
+    );
+    assert_eq!(results[3]["status"], "failed");
+    assert!(results[3]["error"].as_str().unwrap().contains("row 2"));
+    assert_eq!(results[4]["status"], "failed");
+    assert!(results[4]["kitchenId"].is_null());
+
+    // Updates keep fields the row leaves empty
+    let existing = db
+        .kitchen_table
+        .select(&existing_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(existing.capacity, Some(3500));
+    assert_eq!(existing.contact_phone.as_deref(), Some("(021) 555-0123"));
+
+    let csv = "name,city,province,certifications,capacity\n\
+               SPPG Depok,Depok,Jawa Barat,Halal;HACCP,1200\n\
+               SPPG Bandung,Bandung,Jawa Barat,,\n\
+               SPPG Garut,Garut,Jawa Barat,,lots\n";
+    let response = app
+        .clone()
+        .oneshot(import(
+            &admin_token,
+            "text/csv; charset=utf-8",
+            csv.to_string(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["created"], 1);
+    assert_eq!(json["updated"], 1);
+    assert_eq!(json["failed"], 1);
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results[0]["status"], "updated");
+    assert_eq!(results[1]["status"], "created");
+    assert_eq!(results[2]["status"], "failed");
+
+    let depok_id: Uuid = results[0]["kitchenId"].as_str().unwrap().parse().unwrap();
+    let depok = db.kitchen_table.select(&depok_id).await.unwrap().unwrap();
+    assert_eq!(depok.province.as_deref(), Some("Jawa Barat"));
+    assert_eq!(
+        depok.certifications.map(|c| c.0),
+        Some(vec!["Halal".to_string(), "HACCP".to_string()])
+    );
+    assert_eq!(depok.r#type, Some(KitchenType::CentralKitchen));
+
+    // The format comes from the Content-Type header
+    let response = app
+        .clone()
+        .oneshot(import(&admin_token, "text/plain", csv.to_string()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .oneshot(import(&admin_token, "application/json", "[]".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_rating_is_rounded_and_provisional_below_min_reviews() {
+    let (db, db_name) = common::setup_db().await;
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 8".to_string(),
+            unique_code: "SCHOOL008".to_string(),
+            role: UserRole::School,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let kitchen_id = db
+        .kitchen_table
+        .insert(&Kitchen {
+            name: "SPPG Rating Test".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let add_review = |rating: i64| {
+        let db = db.clone();
+        async move {
+            let rating = Decimal::from(rating);
+            db.review_table
+                .insert(&Review {
+                    kitchen_id,
+                    reviewer_id,
+                    reviewer_name: "SD Negeri 8".to_string(),
+                    taste_rating: rating,
+                    hygiene_rating: rating,
+                    freshness_rating: rating,
+                    temperature_rating: rating,
+                    packaging_rating: rating,
+                    handling_rating: rating,
+                    comment: "Baik".to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+    };
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let app = kitchen_routes(
+        KitchenState {
+            service: Arc::new(
+                KitchenService::new(db.clone())
+                    .with_rating_config(KitchenRatingConfig { min_reviews: 3 }),
+            ),
+        },
+        AuthState {
+            keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+            db: None,
+        },
+    );
+    let get = |uri: String| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        }
+    };
+
+    let detail = get(format!("/{}", kitchen_id)).await;
+    assert_eq!(detail["rating"], 0.0);
+    assert_eq!(detail["ratingProvisional"], true);
+
+    // One review short of the minimum
+    add_review(5).await;
+    add_review(4).await;
+    let detail = get(format!("/{}", kitchen_id)).await;
+    assert_eq!(detail["rating"], 4.5);
+    assert_eq!(detail["totalReviews"], 2);
+    assert_eq!(detail["ratingProvisional"], true);
+
+    // At the minimum, and 13/3 is rounded to one decimal
+    add_review(4).await;
+    let detail = get(format!("/{}", kitchen_id)).await;
+    assert_eq!(detail["rating"], 4.3);
+    assert_eq!(detail["totalReviews"], 3);
+    assert_eq!(detail["ratingProvisional"], false);
+
+    let list = get("/".to_string()).await;
+    assert_eq!(list["data"][0]["rating"], 4.3);
+    assert_eq!(list["data"][0]["ratingProvisional"], false);
+
     common::teardown_db(db, db_name).await;
 }
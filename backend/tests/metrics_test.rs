@@ -0,0 +1,61 @@
+use axum::Router;
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use axum::routing::get;
+use backend::middleware::metrics::install_recorder;
+use backend::middleware::metrics::track_metrics;
+use backend::routes::metrics::MetricsState;
+use backend::routes::metrics::metrics_routes;
+use tower::util::ServiceExt;
+
+mod common;
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    let (db, db_name) = common::setup_db().await;
+    let handle = install_recorder().unwrap();
+
+    let api = Router::new().route("/items/{id}", get(|| async { "item" }));
+    let app = Router::new()
+        .nest("/api", api)
+        .layer(axum::middleware::from_fn(track_metrics))
+        .merge(metrics_routes(MetricsState {
+            handle,
+            db: db.clone(),
+        }));
+
+    for id in ["1", "2"] {
+        let response = app
+            .clone()
+            .oneshot(get_request(&format!("/api/items/{}", id)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = app.oneshot(get_request("/metrics")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    // Requests are grouped by route template, not raw path
+    assert!(
+        body.contains(r#"http_requests_total{method="GET",path="/api/items/{id}",status="200"} 2"#)
+    );
+    assert!(body.contains("http_request_duration_seconds_bucket"));
+    assert!(body.contains("db_pool_connections"));
+    // The scrape itself isn't tracked
+    assert!(!body.contains(r#"path="/metrics""#));
+
+    common::teardown_db(db, db_name).await;
+}
@@ -0,0 +1,76 @@
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use axum::http::header;
+use backend::routes::openapi::openapi_routes;
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+#[tokio::test]
+async fn test_openapi_document_lists_documented_endpoints() {
+    let response = openapi_routes()
+        .oneshot(
+            Request::builder()
+                .uri("/api/openapi.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let doc: Value = serde_json::from_slice(&body).unwrap();
+
+    let paths = doc["paths"].as_object().unwrap();
+    for path in [
+        "/api/auth/login",
+        "/api/auth/me",
+        "/api/kitchens",
+        "/api/kitchens/{id}",
+        "/api/reviews",
+        "/api/reviews/{id}/photos",
+    ] {
+        assert!(paths.contains_key(path), "missing path {path}");
+    }
+
+    assert_eq!(
+        doc["components"]["securitySchemes"]["bearer_auth"]["scheme"],
+        "bearer"
+    );
+    assert_eq!(
+        doc["paths"]["/api/auth/me"]["get"]["security"][0]["bearer_auth"],
+        serde_json::json!([])
+    );
+
+    // Kitchen and review lists share one pagination schema
+    let schemas = doc["components"]["schemas"].as_object().unwrap();
+    assert!(!schemas.contains_key("ReviewPagination"));
+    let properties = schemas["Pagination"]["properties"].as_object().unwrap();
+    for field in ["total", "limit", "offset", "hasMore", "page", "totalPages"] {
+        assert!(properties.contains_key(field), "missing {field}");
+    }
+}
+
+#[tokio::test]
+async fn test_swagger_ui_is_served() {
+    let response = openapi_routes()
+        .oneshot(
+            Request::builder()
+                .uri("/api/docs/")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+}
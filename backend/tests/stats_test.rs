@@ -0,0 +1,323 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use axum::http::header;
+use backend::config::HaccpWeights;
+use backend::config::LeaderboardConfig;
+use backend::database::model::Incident;
+use backend::database::model::Kitchen;
+use backend::database::model::Review;
+use backend::database::model::User;
+use backend::database::model::UserRole;
+use backend::database::table::Table;
+use backend::routes::stats::StatsState;
+use backend::routes::stats::stats_routes;
+use backend::service::stats::StatsService;
+use chrono::Duration;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use tower::util::ServiceExt;
+use tower_http::compression::CompressionLayer;
+
+mod common;
+
+#[tokio::test]
+async fn test_stats_response_is_compressed_when_requested() {
+    let (db, db_name) = common::setup_db().await;
+    let state = StatsState {
+        service: Arc::new(StatsService::new(db.clone())),
+    };
+
+    let app = Router::new()
+        .nest("/stats", stats_routes(state))
+        .layer(CompressionLayer::new());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/stats/national")
+                .header(header::ACCEPT_ENCODING, "gzip")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+    // Clients that don't ask for compression get plain JSON
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/stats/national")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(serde_json::from_slice::<serde_json::Value>(&body).is_ok());
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_leaderboard_ranks_by_weighted_score() {
+    let (db, db_name) = common::setup_db().await;
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 3".to_string(),
+            role: UserRole::School,
+            unique_code: "SCH-LEADER".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // One perfect review against many slightly worse ones, plus two kitchens
+    // tied on everything but their names
+    for (name, reviews, rating) in [
+        ("Solo Kitchen", 1, Decimal::new(50, 1)),
+        ("Steady Kitchen", 20, Decimal::new(48, 1)),
+        ("Bravo Kitchen", 1, Decimal::new(40, 1)),
+        ("Alpha Kitchen", 1, Decimal::new(40, 1)),
+        ("Empty Kitchen", 0, Decimal::ZERO),
+    ] {
+        let kitchen_id = db
+            .kitchen_table
+            .insert(&Kitchen {
+                name: name.to_string(),
+                province: Some("Jawa Barat".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        for _ in 0..reviews {
+            db.review_table
+                .insert(&Review {
+                    kitchen_id,
+                    reviewer_id,
+                    reviewer_name: "SD Negeri 3".to_string(),
+                    taste_rating: rating,
+                    hygiene_rating: rating,
+                    freshness_rating: rating,
+                    temperature_rating: rating,
+                    packaging_rating: rating,
+                    handling_rating: rating,
+                    comment: "Baik".to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    let leaderboard = |config: LeaderboardConfig, query: &'static str| {
+        let state = StatsState {
+            service: Arc::new(
+                StatsService::with_rating_weights(db.clone(), HaccpWeights::default())
+                    .with_leaderboard(config),
+            ),
+        };
+        async move {
+            let response = Router::new()
+                .nest("/stats", stats_routes(state))
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/stats/leaderboard{}", query))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<Value>(&body).unwrap()
+        }
+    };
+    let names = |body: &Value| -> Vec<String> {
+        body["kitchens"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|k| k["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Pulled towards the mean of all reviews, the single 5.0 drops below 20 4.8s
+    let body = leaderboard(LeaderboardConfig::default(), "").await;
+    assert_eq!(
+        names(&body),
+        [
+            "Steady Kitchen",
+            "Solo Kitchen",
+            "Alpha Kitchen",
+            "Bravo Kitchen"
+        ]
+    );
+    assert_eq!(body["minReviews"], 10);
+    assert_eq!(body["kitchens"][0]["rank"], 1);
+    assert_eq!(body["kitchens"][0]["reviewCount"], 20);
+    let score = body["kitchens"][0]["weightedScore"].as_f64().unwrap();
+    let mean = (5.0 + 20.0 * 4.8 + 2.0 * 4.0) / 23.0;
+    assert!((score - (20.0 * 4.8 + 10.0 * mean) / 30.0).abs() < 1e-6);
+
+    // With no minimum the raw average decides
+    let raw = LeaderboardConfig {
+        min_reviews: 0,
+        prior_rating: None,
+    };
+    assert_eq!(
+        names(&leaderboard(raw, "").await)[..2],
+        ["Solo Kitchen", "Steady Kitchen"]
+    );
+
+    // An explicit prior is used instead of the mean
+    let strict = LeaderboardConfig {
+        min_reviews: 10,
+        prior_rating: Some(1.0),
+    };
+    let body = leaderboard(strict, "?limit=2&offset=1").await;
+    assert_eq!(names(&body), ["Solo Kitchen", "Alpha Kitchen"]);
+    assert_eq!(body["kitchens"][0]["rank"], 2);
+    let score = body["kitchens"][0]["weightedScore"].as_f64().unwrap();
+    assert!((score - 15.0 / 11.0).abs() < 1e-6);
+    // Kitchens without reviews aren't counted
+    assert_eq!(body["pagination"]["total"], 4);
+    assert_eq!(body["pagination"]["totalPages"], 2);
+    assert_eq!(body["pagination"]["hasMore"], true);
+
+    let body = leaderboard(LeaderboardConfig::default(), "?province=Bali").await;
+    assert!(names(&body).is_empty());
+    assert_eq!(body["region"]["province"], "Bali");
+    assert_eq!(body["pagination"]["total"], 0);
+
+    common::teardown_db(db, db_name).await;
+}
+
+#[tokio::test]
+async fn test_national_timeseries() {
+    let (db, db_name) = common::setup_db().await;
+    let reviewer_id = db
+        .user_table
+        .insert(&User {
+            name: "SD Negeri 4".to_string(),
+            role: UserRole::School,
+            unique_code: "SCH-SERIES".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    for (province, rating) in [("Jawa Barat", 4), ("Bali", 2)] {
+        let kitchen_id = db
+            .kitchen_table
+            .insert(&Kitchen {
+                name: format!("{} Kitchen", province),
+                province: Some(province.to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let rating = Decimal::from(rating);
+        db.review_table
+            .insert(&Review {
+                kitchen_id,
+                reviewer_id,
+                reviewer_name: "SD Negeri 4".to_string(),
+                taste_rating: rating,
+                hygiene_rating: rating,
+                freshness_rating: rating,
+                temperature_rating: rating,
+                packaging_rating: rating,
+                handling_rating: rating,
+                comment: "Baik".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        for days_ago in [0, 95] {
+            db.incident_table
+                .insert(&Incident {
+                    kitchen_id,
+                    date: Utc::now() - Duration::days(days_ago),
+                    affected_count: Some(3),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    let app = stats_routes(StatsState {
+        service: Arc::new(StatsService::new(db.clone())),
+    });
+    let get = |uri: &str| {
+        app.clone().oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = get("/national/timeseries?months=6").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let series: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(series["region"]["province"], "National");
+    let data = series["data"].as_array().unwrap();
+    assert_eq!(data.len(), 6);
+    let current = data.last().unwrap();
+    assert_eq!(current["totalReviews"], 2);
+    assert_eq!(current["averageRating"], 3.0);
+    assert_eq!(current["totalIncidents"], 2);
+    assert_eq!(current["totalVictims"], 6);
+    let total = |key: &str| data.iter().map(|m| m[key].as_i64().unwrap()).sum::<i64>();
+    assert_eq!(total("totalReviews"), 2);
+    assert_eq!(total("totalIncidents"), 4);
+    // Quiet months are still listed
+    assert!(data.iter().any(|m| m["averageRating"].is_null()));
+
+    let response = get("/national/timeseries?months=2&province=Bali")
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let series: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(series["region"]["province"], "Bali");
+    let data = series["data"].as_array().unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[1]["totalReviews"], 1);
+    assert_eq!(data[1]["averageRating"], 2.0);
+    assert_eq!(data[1]["totalIncidents"], 1);
+
+    for months in [0, 61] {
+        let response = get(&format!("/national/timeseries?months={}", months))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    common::teardown_db(db, db_name).await;
+}
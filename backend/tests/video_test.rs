@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::http::StatusCode;
+use backend::auth::middleware::AuthState;
+use backend::auth::utils::JwtKeys;
+use backend::auth::utils::generate_token;
+use backend::config::Config;
+use backend::database::model::UserRole;
+use backend::routes::video::VideoState;
+use backend::routes::video::video_routes;
+use backend::service::video::VideoService;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+
+#[tokio::test]
+async fn test_video_library() {
+    let (db, db_name) = common::setup_db().await;
+
+    let config = Arc::new(Config {
+        jwt_secret: "test_secret".to_string(),
+        ..Default::default()
+    });
+    let state = VideoState {
+        service: Arc::new(VideoService::new(db.clone())),
+    };
+    let app = Router::new().nest(
+        "/videos",
+        video_routes(
+            state,
+            AuthState {
+                keys: Arc::new(JwtKeys::from_config(&config).unwrap()),
+                db: None,
+            },
+        ),
+    );
+
+    let admin_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Admin,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+    let kitchen_token = generate_token(
+        Uuid::new_v4(),
+        UserRole::Kitchen,
+        None,
+        None,
+        &JwtKeys::hmac("test_secret"),
+    )
+    .unwrap();
+
+    let video = |youtube_id: &str, category: &str, relevance: &str| {
+        serde_json::json!({
+            "youtubeId": youtube_id,
+            "title": "Safe food storage",
+            "category": category,
+            "haccpRelevance": relevance,
+            "uploadDate": "2026-01-15"
+        })
+    };
+
+    let response = app
+        .clone()
+        .oneshot(common::post_json(
+            "/videos",
+            &kitchen_token,
+            video("dQw4w9WgXcQ", "Storage", "Temperature control"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(common::post_json(
+            "/videos",
+            &admin_token,
+            video(
+                "https://youtu.be/dQw4w9WgXcQ",
+                "Storage",
+                "Temperature control",
+            ),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(common::post_json(
+            "/videos",
+            &admin_token,
+            video(
+                "dQw4w9WgXcQ",
+                "Storage",
+                "Temperature control, cross-contamination",
+            ),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let id = common::json_body(response).await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // The same YouTube video can only be added once
+    let response = app
+        .clone()
+        .oneshot(common::post_json(
+            "/videos",
+            &admin_token,
+            video("dQw4w9WgXcQ", "Hygiene", "Handwashing"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = app
+        .clone()
+        .oneshot(common::post_json(
+            "/videos",
+            &admin_token,
+            video("9bZkp7q19f0", "Hygiene", "Handwashing"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .clone()
+        .oneshot(common::get(format!("/videos/{}", id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let detail = common::json_body(response).await;
+    assert_eq!(detail["youtubeId"], "dQw4w9WgXcQ");
+    assert_eq!(
+        detail["thumbnail"],
+        "https://i.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg"
+    );
+
+    let list = |query: &str| common::get(format!("/videos{}", query));
+
+    let body = common::json_body(app.clone().oneshot(list("")).await.unwrap()).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+    assert_eq!(body["pagination"]["total"], 2);
+
+    let body = common::json_body(
+        app.clone()
+            .oneshot(list("?category=storage"))
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["id"], id);
+
+    let body = common::json_body(
+        app.clone()
+            .oneshot(list("?haccpRelevance=contamination"))
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["id"], id);
+
+    let body = common::json_body(app.clone().oneshot(list("?limit=1")).await.unwrap()).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["pagination"]["hasMore"], true);
+
+    let response = app
+        .oneshot(common::get(format!("/videos/{}", Uuid::new_v4())))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    common::teardown_db(db, db_name).await;
+}
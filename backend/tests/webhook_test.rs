@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use backend::config::Config;
+use backend::routes::webhook::WebhookState;
+use backend::routes::webhook::webhook_routes;
+use backend::service::channel::ChannelDispatcher;
+use backend::service::channel::Recipient;
+use backend::service::otp::DeliveryStatus;
+use backend::service::otp::OtpService;
+use backend::service::webhook::sign;
+use backend::service::whatsapp::WhatsAppClient;
+use serde_json::Value;
+use serde_json::json;
+use tower::util::ServiceExt;
+
+#[tokio::test]
+async fn test_whatsapp_webhook_updates_otp_delivery_status() {
+    let mut config = Config::default();
+    config.whatsapp.otp_expiry_seconds = 300;
+    config.whatsapp.app_secret = Some("app_secret".to_string());
+    let config = Arc::new(config);
+
+    let otp_service = Arc::new(OtpService::with_channels(
+        config.clone(),
+        ChannelDispatcher::new(Vec::new()),
+    ));
+    let reference_id = otp_service
+        .send_otp(Recipient {
+            phone: Some("08123456789".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .reference_id;
+
+    let app = webhook_routes(WebhookState {
+        whatsapp: WhatsAppClient::new(config),
+        otp_service: otp_service.clone(),
+    });
+    let callback = |status: &str, signature: Option<String>| {
+        let body = json!({
+            "object": "whatsapp_business_account",
+            "entry": [{
+                "changes": [{
+                    "field": "messages",
+                    "value": {
+                        "statuses": [
+                            {
+                                "id": "wamid.1",
+                                "status": status,
+                                "biz_opaque_callback_data": reference_id,
+                            },
+                            { "id": "wamid.2", "status": status },
+                        ]
+                    }
+                }]
+            }]
+        })
+        .to_string();
+        let signature = signature.unwrap_or_else(|| sign("app_secret", body.as_bytes()));
+        app.clone().oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/whatsapp")
+                .header("Content-Type", "application/json")
+                .header("X-Hub-Signature-256", signature)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+    };
+
+    let response = callback("read", Some(sign("wrong_secret", b"{}")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(otp_service.delivery_status(&reference_id).await, None);
+
+    let response = callback("read", None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["received"], 2);
+    assert_eq!(json["updated"], 1);
+    assert_eq!(
+        otp_service.delivery_status(&reference_id).await,
+        Some(DeliveryStatus::Read)
+    );
+
+    // Callbacks arriving out of order don't move the status back
+    let response = callback("delivered", None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        otp_service.delivery_status(&reference_id).await,
+        Some(DeliveryStatus::Read)
+    );
+}
+
+#[tokio::test]
+async fn test_whatsapp_webhook_disabled_without_app_secret() {
+    let config = Arc::new(Config::default());
+    let app = webhook_routes(WebhookState {
+        whatsapp: WhatsAppClient::new(config.clone()),
+        otp_service: Arc::new(OtpService::new(config)),
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/whatsapp")
+                .header("X-Hub-Signature-256", sign("", b"{}"))
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_whatsapp_webhook_subscription_echoes_challenge() {
+    let subscribe = |config: Config, query: &'static str| {
+        let config = Arc::new(config);
+        webhook_routes(WebhookState {
+            whatsapp: WhatsAppClient::new(config.clone()),
+            otp_service: Arc::new(OtpService::new(config)),
+        })
+        .oneshot(
+            Request::builder()
+                .uri(format!("/whatsapp?{}", query))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let mut config = Config::default();
+    config.whatsapp.webhook_verify_token = Some("verify_me".to_string());
+
+    let response = subscribe(
+        config.clone(),
+        "hub.mode=subscribe&hub.verify_token=verify_me&hub.challenge=1158201444",
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"1158201444");
+
+    for query in [
+        "hub.mode=subscribe&hub.verify_token=wrong&hub.challenge=1",
+        "hub.mode=unsubscribe&hub.verify_token=verify_me&hub.challenge=1",
+        "hub.challenge=1",
+    ] {
+        let response = subscribe(config.clone(), query).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    let response = subscribe(
+        Config::default(),
+        "hub.mode=subscribe&hub.verify_token=&hub.challenge=1",
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}